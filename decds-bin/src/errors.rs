@@ -1,12 +1,121 @@
+use crate::{interrupt::INTERRUPTED_EXIT_CODE, utils::format_bytes};
+use decds_lib::DecdsError;
+use std::path::PathBuf;
+
 #[derive(Debug, PartialEq)]
 pub enum DecdsCLIError {
-    FailedToReadProofCarryingChunk(String),
+    /// Contains the path of the erasure-coded chunk file and why it couldn't be read.
+    FailedToReadProofCarryingChunk(PathBuf, String),
+    /// Contains the path, which was expected to be a directory.
+    NotADirectory(PathBuf),
+    /// Contains the path, which was expected to be a file.
+    NotAFile(PathBuf),
+    /// Contains the path of the file, which couldn't be read or parsed, and why.
+    FailedToReadFile(PathBuf, String),
+    /// Contains the path of the file or directory, which couldn't be written or created, and why.
+    FailedToWriteFile(PathBuf, String),
+    /// Contains the path of the target directory, which exists, but isn't asked to be, or can't be, overwritten, as it isn't
+    /// output of an earlier run.
+    TargetAlreadyExists(PathBuf),
+    /// Contains the path of the target directory, which is asked to be overwritten, and the path of the input it holds.
+    TargetHoldsInput(PathBuf, PathBuf),
+    /// Contains the path of the target directory, and required and available free space on its filesystem, in bytes.
+    NotEnoughFreeSpace(PathBuf, usize, usize),
+    /// Neither a header registry path is given, nor can a default one be found.
+    UnknownRegistryPath,
+    /// Contains the key, which resolves to no blob, and the path of the header registry.
+    BlobNotInRegistry(String, PathBuf),
+    /// Contains the path of the audit log file, whose hash chain is broken, and why.
+    AuditLogNotIntact(PathBuf, String),
+    /// Contains the path of the inventory file, and ID of the blob it was expected to be of.
+    ForeignInventory(PathBuf, blake3::Hash),
+    /// Contains the path of the inventory file, which couldn't be merged, and why.
+    FailedToMergeInventory(PathBuf, String),
+    /// Contains the number of chunksets, chunks offered by the inventory don't suffice to repair.
+    InsufficientRepairPlan(usize),
+    /// Contains the chunkset ID, the short ID of its blob, and the directory of its chunks, too few of which are valid.
+    FailedToRepairChunkset(usize, String, PathBuf),
+    /// Contains the path of the erasure-coded chunk file, the short ID of its blob, and the unexpected error it caused.
+    UnexpectedChunkError(PathBuf, String, DecdsError),
+    /// Contains the path of the blob metadata file, which stores blob data inline, so there are no chunks.
+    InlineBlob(PathBuf),
+    /// Contains the path of the blob metadata file, of a blob not marked as encrypted, though only ciphertext is accepted.
+    PlaintextBlob(PathBuf),
+    /// Contains the address, which couldn't be connected to or listened on, and why.
+    NetworkFailure(String, String),
+    /// Contains the command line argument, which couldn't be parsed, and its expected form.
+    InvalidArgument(String, String),
+    /// Contains the error reported by `decds-lib`.
+    Decds(DecdsError),
+    /// Ctrl-C was pressed while writing output, which is removed.
+    Interrupted,
+}
+
+impl DecdsCLIError {
+    /// Returns the exit code, the process terminates with, reporting this error.
+    pub fn get_exit_code(&self) -> i32 {
+        match self {
+            DecdsCLIError::Interrupted => INTERRUPTED_EXIT_CODE,
+            _ => 1,
+        }
+    }
+}
+
+impl From<DecdsError> for DecdsCLIError {
+    fn from(err: DecdsError) -> Self {
+        DecdsCLIError::Decds(err)
+    }
 }
 
 impl std::fmt::Display for DecdsCLIError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            DecdsCLIError::FailedToReadProofCarryingChunk(err) => write!(f, "{}", err),
+            DecdsCLIError::FailedToReadProofCarryingChunk(chunk_path, err) => write!(f, "failed to read erasure-coded chunk file {:?}: {}", chunk_path, err),
+            DecdsCLIError::NotADirectory(path) => write!(f, "{:?} is not a directory", path),
+            DecdsCLIError::NotAFile(path) => write!(f, "{:?} is not a file", path),
+            DecdsCLIError::FailedToReadFile(path, err) => write!(f, "failed to read {:?}: {}", path, err),
+            DecdsCLIError::FailedToWriteFile(path, err) => write!(f, "failed to write {:?}: {}", path, err),
+            DecdsCLIError::TargetAlreadyExists(path) => write!(f, "{:?} already exists, pass `-f` to overwrite it, if it's decds output", path),
+            DecdsCLIError::TargetHoldsInput(path, input_path) => write!(f, "refusing to overwrite {:?}, as it holds input {:?}", path, input_path),
+            DecdsCLIError::NotEnoughFreeSpace(path, required_bytes, available_bytes) => write!(
+                f,
+                "not enough free space for {:?}, requires ~{}, but only {} is available",
+                path,
+                format_bytes(*required_bytes),
+                format_bytes(*available_bytes)
+            ),
+            DecdsCLIError::UnknownRegistryPath => write!(f, "neither registry path, nor $DECDS_REGISTRY or $HOME is set"),
+            DecdsCLIError::BlobNotInRegistry(key, registry_path) => {
+                write!(f, "no blob named {} or with such blob ID or digest in header registry {:?}", key, registry_path)
+            }
+            DecdsCLIError::AuditLogNotIntact(audit_log_path, err) => write!(f, "audit log {:?} is not intact: {}", audit_log_path, err),
+            DecdsCLIError::ForeignInventory(inventory_path, blob_id) => write!(f, "inventory {:?} is not of blob {}", inventory_path, blob_id),
+            DecdsCLIError::FailedToMergeInventory(inventory_path, err) => write!(f, "failed to merge inventory {:?}: {}", inventory_path, err),
+            DecdsCLIError::InsufficientRepairPlan(num_chunksets) => write!(f, "inventory offers too few chunks to repair {} chunkset(s)", num_chunksets),
+            DecdsCLIError::FailedToRepairChunkset(chunkset_id, blob_id, chunkset_dir_path) => {
+                write!(f, "failed to repair chunkset {} of blob {}, from {:?}", chunkset_id, blob_id, chunkset_dir_path)
+            }
+            DecdsCLIError::UnexpectedChunkError(chunk_path, blob_id, err) => {
+                write!(f, "encountered unexpected error with {:?}, of blob {}: {}", chunk_path, blob_id, err)
+            }
+            DecdsCLIError::InlineBlob(blob_metadata_path) => {
+                write!(
+                    f,
+                    "blob data is stored inline in metadata {:?}, there are no erasure-coded chunks",
+                    blob_metadata_path
+                )
+            }
+            DecdsCLIError::PlaintextBlob(blob_metadata_path) => {
+                write!(
+                    f,
+                    "blob of metadata {:?} is not marked as encrypted, but only ciphertext is accepted",
+                    blob_metadata_path
+                )
+            }
+            DecdsCLIError::NetworkFailure(addr, err) => write!(f, "network failure with {}: {}", addr, err),
+            DecdsCLIError::InvalidArgument(arg, expected) => write!(f, "argument {:?} is not {}", arg, expected),
+            DecdsCLIError::Decds(err) => write!(f, "{}", err),
+            DecdsCLIError::Interrupted => write!(f, "interrupted"),
         }
     }
 }
@@ -0,0 +1,29 @@
+use crate::{errors::DecdsCLIError, utils::read_audit_log};
+use std::path::PathBuf;
+
+/// Checks integrity of the hash chain of an audit log file, optionally listing its records, as
+/// `<sequence> <timestamp_ms> <blob_id> <operation>` lines.
+pub fn handle_audit_command(audit_log_path: &PathBuf, list: bool) -> Result<(), DecdsCLIError> {
+    if !audit_log_path.is_file() {
+        return Err(DecdsCLIError::NotAFile(audit_log_path.clone()));
+    }
+
+    let (log, records) = read_audit_log(audit_log_path)?;
+
+    if list {
+        records.iter().for_each(|record| {
+            println!(
+                "{}\t{}\t{}\t{:?}",
+                record.get_sequence(),
+                record.get_timestamp_ms(),
+                record.get_blob_id(),
+                record.get_operation()
+            );
+        });
+    }
+
+    println!("Audit log {:?} is intact, holding {} record(s)\t✅", audit_log_path, log.get_num_records());
+    println!("Head digest: {}", log.get_head());
+
+    Ok(())
+}
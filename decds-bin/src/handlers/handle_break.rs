@@ -1,106 +1,148 @@
-use crate::utils::{format_bytes, get_target_directory_path};
-use decds_lib::{Blob, BlobHeader, DECDS_NUM_ERASURE_CODED_SHARES, ProofCarryingChunk};
-use std::{path::PathBuf, process::exit};
-
-pub fn handle_break_command(blob_path: &PathBuf, opt_target_dir: &Option<PathBuf>) {
-    match std::fs::read(blob_path) {
-        Ok(blob_bytes) => {
-            println!("Read {:?}", blob_path);
-            println!("Size {}", format_bytes(blob_bytes.len()));
-
-            match Blob::new(blob_bytes) {
-                Ok(erasure_coded) => {
-                    let metadata = erasure_coded.get_blob_header();
-                    println!("BLAKE3 Digest: {}", metadata.get_blob_digest());
-                    println!("Blob root commitment: {}", metadata.get_root_commitment());
-                    println!("Number of chunksets: {}", metadata.get_num_chunksets());
-                    println!("Number of chunks: {}", metadata.get_num_chunks());
-
-                    let mut rng = rand::rng();
-                    let target_dir_path = get_target_directory_path(blob_path, opt_target_dir, &mut rng);
-
-                    if let Err(e) = std::fs::DirBuilder::new().recursive(true).create(&target_dir_path) {
-                        eprintln!("Error: {}", e);
-                        exit(1);
-                    }
+use crate::{
+    errors::DecdsCLIError,
+    interrupt::PartialOutputGuard,
+    tasks::run_scoped,
+    utils::{create_dir_all, ensure_enough_free_space, format_bytes, get_short_blob_id, get_target_directory_path, write_file},
+};
+use decds_lib::{Blob, BlobDirLayout, BlobHeader, ContentFlags, DECDS_NUM_ERASURE_CODED_SHARES, DecdsBuilder, ProofCarryingChunk};
+use std::{
+    io::{BufWriter, Write},
+    path::PathBuf,
+    sync::mpsc::{SyncSender, sync_channel},
+};
 
-                    println!("Writing blob metadata and erasure-coded chunks...");
+/// Erasure-coded chunk files are written in pieces of this many bytes.
+const CHUNK_FILE_WRITE_BUFFER_BYTE_LEN: usize = 1usize << 16;
+/// Number of chunks, each share writer can have queued up, before the producer blocks.
+const SHARE_WRITER_QUEUE_LEN: usize = 4;
 
-                    write_blob_metadata(&target_dir_path, metadata);
-                    (0..DECDS_NUM_ERASURE_CODED_SHARES).for_each(|share_id| {
-                        write_blob_share(&target_dir_path, share_id, erasure_coded.get_share(share_id).unwrap());
-                    });
+pub fn handle_break_command(
+    blob_path: &PathBuf,
+    opt_target_dir: &Option<PathBuf>,
+    inline_threshold: usize,
+    force: bool,
+    opt_num_writers: Option<usize>,
+    content_flags: ContentFlags,
+) -> Result<(), DecdsCLIError> {
+    // Blob is read and erasure-coded one chunkset at a time, so that it's never held in memory as a whole. It isn't
+    // memory-mapped, as nothing keeps other processes from modifying the file meanwhile.
+    let blob_file = std::fs::File::open(blob_path).map_err(|e| DecdsCLIError::FailedToReadFile(blob_path.clone(), e.to_string()))?;
+    let erasure_coded = DecdsBuilder::new()
+        .inline_threshold(inline_threshold)
+        .content_flags(content_flags)
+        .build_from_reader(blob_file)?;
 
-                    println!("Erasure-coded chunks placed in {:?}", &target_dir_path);
-                }
-                Err(e) => {
-                    eprintln!("Error: {}", e);
-                    exit(1);
-                }
-            }
-        }
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            exit(1);
-        }
+    let metadata = erasure_coded.get_blob_header();
+    println!("Read {:?}", blob_path);
+    println!("Size {}", format_bytes(metadata.get_blob_size()));
+    println!("Blob ID: {}", metadata.get_blob_id());
+    println!("BLAKE3 Digest: {}", metadata.get_blob_digest());
+    println!("Blob root commitment: {}", metadata.get_root_commitment());
+    println!("Number of chunksets: {}", metadata.get_num_chunksets());
+    println!("Number of chunks: {}", metadata.get_num_chunks());
+    if metadata.is_inline() {
+        println!("Blob data is stored inline in metadata");
     }
+
+    let blob_file_name = blob_path.file_name().map(|file_name| file_name.to_string_lossy()).unwrap_or_default();
+    let default_target_dir_name = format!("{}-{}", blob_file_name, get_short_blob_id(metadata));
+    let target_dir_path = get_target_directory_path(&default_target_dir_name, opt_target_dir, blob_path, force)?;
+    ensure_enough_free_space(&target_dir_path, estimate_erasure_coded_blob_size(&erasure_coded))?;
+    create_dir_all(&target_dir_path)?;
+
+    println!("Writing blob metadata and erasure-coded chunks...");
+
+    let layout = BlobDirLayout::new(&target_dir_path);
+    let guard = PartialOutputGuard::new(&target_dir_path);
+
+    write_blob_metadata(&layout, metadata)?;
+    write_blob_shares(&layout, &erasure_coded, get_num_writers(opt_num_writers), &guard)?;
+
+    println!("Erasure-coded chunks placed in {:?}", &target_dir_path);
+    Ok(())
 }
 
-fn write_blob_metadata(target_dir: &PathBuf, metadata: &BlobHeader) {
-    let mut blob_metadata_path = target_dir.clone();
-    blob_metadata_path.push("metadata.commit");
+/// Estimates how many bytes blob metadata and all erasure-coded chunks take, once written to disk.
+fn estimate_erasure_coded_blob_size(erasure_coded: &Blob) -> usize {
+    let metadata = erasure_coded.get_blob_header();
+    let metadata_size = metadata.to_bytes().map(|bytes| bytes.len()).unwrap_or_default();
 
-    match metadata.to_bytes() {
-        Ok(bytes) => {
-            if let Err(e) = std::fs::write(blob_metadata_path, bytes) {
-                eprintln!("Error: {}", e);
-                exit(1);
-            }
-        }
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            exit(1);
-        }
-    }
+    let chunk_size = erasure_coded
+        .get_chunk(0, 0)
+        .ok()
+        .and_then(|chunk| chunk.to_bytes().ok())
+        .map(|bytes| bytes.len())
+        .unwrap_or_default();
+
+    metadata.get_num_chunks().saturating_mul(chunk_size).saturating_add(metadata_size)
+}
+
+fn write_blob_metadata(layout: &BlobDirLayout, metadata: &BlobHeader) -> Result<(), DecdsCLIError> {
+    write_file(&layout.get_metadata_path(), metadata.to_bytes()?)
+}
+
+/// Returns the number of concurrent chunk file writers to use, defaulting to the available parallelism, but never more than
+/// the number of shares, as each writer owns at least one share directory.
+fn get_num_writers(opt_num_writers: Option<usize>) -> usize {
+    opt_num_writers
+        .unwrap_or_else(|| std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1))
+        .clamp(1, DECDS_NUM_ERASURE_CODED_SHARES)
 }
 
-fn write_blob_share(target_dir: &PathBuf, share_id: usize, share: Vec<ProofCarryingChunk>) {
-    let mut blob_share_path = target_dir.clone();
+/// Writes all erasure-coded chunks, using `num_writers` concurrent writers. Share `i` is written by writer `i % num_writers`,
+/// which is fed chunks through a bounded channel, so that slow disks or network filesystems apply backpressure, instead of
+/// queueing up the whole blob. Chunkset directories are created up front, before any of their chunks are handed out. A
+/// failing writer hangs up, which stops handing out chunks, and its failure is returned, once all writers finished.
+fn write_blob_shares(layout: &BlobDirLayout, erasure_coded: &Blob, num_writers: usize, guard: &PartialOutputGuard) -> Result<(), DecdsCLIError> {
+    run_scoped(|tasks| {
+        let writers = (0..num_writers)
+            .map(|_| {
+                let (sender, receiver) = sync_channel::<(usize, usize, &ProofCarryingChunk)>(SHARE_WRITER_QUEUE_LEN);
+                tasks.spawn(move || {
+                    receiver
+                        .iter()
+                        .try_for_each(|(chunkset_id, share_id, chunk)| write_chunk_file(layout, chunkset_id, share_id, chunk))
+                });
+
+                sender
+            })
+            .collect::<Vec<SyncSender<(usize, usize, &ProofCarryingChunk)>>>();
+
+        let mut shares = (0..DECDS_NUM_ERASURE_CODED_SHARES)
+            .map(|share_id| unsafe { erasure_coded.iter_share(share_id).unwrap_unchecked() })
+            .collect::<Vec<_>>();
+
+        let metadata = erasure_coded.get_blob_header();
+        let num_chunksets = if metadata.is_inline() { 0 } else { metadata.get_num_chunksets() };
 
-    for (chunkset_id, chunk) in share.iter().enumerate() {
-        blob_share_path.push(format!("chunkset.{}", chunkset_id));
+        for chunkset_id in 0..num_chunksets {
+            guard.check_interrupted()?;
+            create_dir_all(&layout.get_chunkset_dir_path(chunkset_id))?;
 
-        match blob_share_path.try_exists() {
-            Ok(ok) => {
-                if !ok {
-                    if let Err(e) = std::fs::create_dir(&blob_share_path) {
-                        eprintln!("Error: {}", e);
-                        exit(1);
+            for (share_id, share) in shares.iter_mut().enumerate() {
+                if let Some(chunk) = share.next() {
+                    // A writer only hangs up on failure, which is returned by `run_scoped`.
+                    if writers[share_id % num_writers].send((chunkset_id, share_id, chunk)).is_err() {
+                        return Ok(());
                     }
                 }
             }
-            Err(e) => {
-                eprintln!("Error: {}", e);
-                exit(1);
-            }
-        };
+        }
 
-        blob_share_path.push(format!("share{:02}.data", share_id));
+        Ok(())
+    })
+}
 
-        match chunk.to_bytes() {
-            Ok(bytes) => {
-                if let Err(e) = std::fs::write(&blob_share_path, bytes) {
-                    eprintln!("Error: {}", e);
-                    exit(1);
-                }
-            }
-            Err(e) => {
-                eprintln!("Error: {}", e);
-                exit(1);
-            }
-        };
+fn write_chunk_file(layout: &BlobDirLayout, chunkset_id: usize, share_id: usize, chunk: &ProofCarryingChunk) -> Result<(), DecdsCLIError> {
+    let chunk_path = layout.get_chunk_path(chunkset_id, share_id);
 
-        blob_share_path.pop();
-        blob_share_path.pop();
-    }
+    // Chunk is serialized straight into its file, instead of into an intermediate buffer first.
+    std::fs::File::create(&chunk_path)
+        .map_err(|e| e.to_string())
+        .and_then(|fd| {
+            let mut buffered_fd = BufWriter::with_capacity(CHUNK_FILE_WRITE_BUFFER_BYTE_LEN, fd);
+            chunk.write_to(&mut buffered_fd).map_err(|e| e.to_string())?;
+            buffered_fd.flush().map_err(|e| e.to_string())
+        })
+        .map_err(|e| DecdsCLIError::FailedToWriteFile(chunk_path, e))
 }
@@ -0,0 +1,40 @@
+use crate::{
+    errors::DecdsCLIError,
+    utils::{ChunkFileStatus, ensure_is_dir, read_blob_metadata, verify_chunk_file, write_file},
+};
+use decds_lib::BlobDirLayout;
+use std::{fmt::Write, path::PathBuf};
+
+pub fn handle_digests_command(chunk_dir_path: &PathBuf, opt_target_file: &Option<PathBuf>) -> Result<(), DecdsCLIError> {
+    ensure_is_dir(chunk_dir_path)?;
+
+    let layout = BlobDirLayout::new(chunk_dir_path);
+    let blob_metadata = read_blob_metadata(&layout.get_metadata_path())?;
+
+    // Chunk digests depend on the random coding vectors picked while breaking the blob, so they are read off the chunk
+    // files, rather than recomputed from the original blob.
+    let mut digests = String::new();
+    if !blob_metadata.is_inline() {
+        (0..blob_metadata.get_num_chunksets()).for_each(|chunkset_id| {
+            (0..blob_metadata.get_config().get_num_shares()).for_each(|share_id| match verify_chunk_file(&layout, &blob_metadata, chunkset_id, share_id) {
+                ChunkFileStatus::Valid(chunk) => {
+                    let _ = writeln!(digests, "{}\t{}\t{}", chunkset_id, share_id, chunk.get_chunk_digest());
+                }
+                ChunkFileStatus::Invalid(reason) => {
+                    eprintln!("Skipping {:?}: {}\t🚫", layout.get_chunk_path(chunkset_id, share_id), reason);
+                }
+                ChunkFileStatus::Missing => {}
+            });
+        });
+    }
+
+    match opt_target_file {
+        Some(target_file) => {
+            write_file(target_file, &digests)?;
+            println!("Digests of {} chunk(s) written to {:?}", digests.lines().count(), target_file);
+        }
+        None => print!("{}", digests),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use crate::{errors::DecdsCLIError, utils::write_file};
+use decds_lib::TestVectors;
+use std::path::PathBuf;
+
+pub fn handle_gen_vectors_command(opt_seed: &Option<String>, blob_size: usize, opt_target_file: &Option<PathBuf>) -> Result<(), DecdsCLIError> {
+    let seed = match opt_seed {
+        Some(seed_hex) => const_hex::decode_to_array::<_, 32>(seed_hex)
+            .map_err(|e| DecdsCLIError::InvalidArgument(seed_hex.clone(), format!("a 32-bytes hex encoded seed: {}", e)))?,
+        None => [0u8; 32],
+    };
+
+    let test_vectors = TestVectors::generate(seed, blob_size)?;
+
+    match opt_target_file {
+        Some(target_file) => {
+            write_file(target_file, test_vectors.to_string())?;
+            println!("Test vectors written to {:?}", target_file);
+        }
+        None => print!("{}", test_vectors),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use crate::{
+    errors::DecdsCLIError,
+    utils::{ChunkFileStatus, ensure_is_dir, read_blob_metadata, verify_chunk_file, write_file},
+};
+use decds_lib::{BlobDirLayout, ChunkInventory};
+use std::path::PathBuf;
+
+pub fn handle_inventory_command(chunk_dir_path: &PathBuf, node: &str, merge_paths: &[PathBuf], target_file: &PathBuf) -> Result<(), DecdsCLIError> {
+    ensure_is_dir(chunk_dir_path)?;
+
+    let layout = BlobDirLayout::new(chunk_dir_path);
+    let blob_metadata = read_blob_metadata(&layout.get_metadata_path())?;
+
+    let mut inventory = ChunkInventory::new(blob_metadata.get_blob_id(), blob_metadata.get_num_chunksets());
+    let mut num_local_chunks = 0;
+
+    (0..blob_metadata.get_num_chunksets()).for_each(|chunkset_id| {
+        (0..blob_metadata.get_config().get_num_shares()).for_each(|share_id| {
+            if let ChunkFileStatus::Valid(_) = verify_chunk_file(&layout, &blob_metadata, chunkset_id, share_id) {
+                let _ = inventory.record(node, chunkset_id, share_id);
+                num_local_chunks += 1;
+            }
+        });
+    });
+
+    merge_paths.iter().try_for_each(|merge_path| {
+        std::fs::read(merge_path)
+            .map_err(|err| err.to_string())
+            .and_then(|bytes| ChunkInventory::from_bytes(&bytes).map_err(|err| err.to_string()))
+            .and_then(|other| inventory.merge(&other).map_err(|err| err.to_string()))
+            .map_err(|err| DecdsCLIError::FailedToMergeInventory(merge_path.clone(), err))
+    })?;
+
+    let inventory_bytes = inventory.to_bytes()?;
+    write_file(target_file, &inventory_bytes)?;
+
+    println!(
+        "Inventory of {} node(s), with {} local chunk(s), written to {:?} ({}B)",
+        inventory.get_nodes().count(),
+        num_local_chunks,
+        target_file,
+        inventory_bytes.len()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use crate::{
+    errors::DecdsCLIError,
+    utils::{ChunkFileStatus, ensure_is_dir, format_bytes, read_blob_metadata, verify_chunk_file},
+};
+use decds_lib::{BlobDirLayout, RepairingBlob};
+use std::path::{Path, PathBuf};
+
+pub fn handle_ls_command(dir_path: &PathBuf) -> Result<(), DecdsCLIError> {
+    ensure_is_dir(dir_path)?;
+
+    if BlobDirLayout::new(dir_path).get_metadata_path().is_file() {
+        list_blob(dir_path);
+        return Ok(());
+    }
+
+    let mut blob_dir_paths = std::fs::read_dir(dir_path)
+        .map_err(|e| DecdsCLIError::FailedToReadFile(dir_path.clone(), e.to_string()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| BlobDirLayout::new(path).get_metadata_path().is_file())
+        .collect::<Vec<PathBuf>>();
+
+    if blob_dir_paths.is_empty() {
+        return Err(DecdsCLIError::FailedToReadFile(dir_path.clone(), "no erasure-coded blob found".to_string()));
+    }
+
+    blob_dir_paths.sort();
+    blob_dir_paths.iter().for_each(|blob_dir_path| list_blob(blob_dir_path));
+
+    Ok(())
+}
+
+fn list_blob(blob_dir_path: &Path) {
+    let layout = BlobDirLayout::new(blob_dir_path);
+    println!("{}", layout.get_root().display());
+
+    // One broken blob doesn't stop listing the others.
+    let blob_metadata = match read_blob_metadata(&layout.get_metadata_path()) {
+        Ok(blob_metadata) => blob_metadata,
+        Err(e) => {
+            println!("\tError: {}\t🚫\n", e);
+            return;
+        }
+    };
+
+    println!("\tBlob ID: {}", blob_metadata.get_blob_id());
+    println!("\tBlob size: {}", format_bytes(blob_metadata.get_blob_size()));
+
+    if blob_metadata.is_inline() {
+        println!("\tBlob data is stored inline in metadata\t✅\n");
+        return;
+    }
+
+    println!("\n\tCHUNKSET\tPRESENT\tVALID\tSTATUS");
+
+    let num_shares = blob_metadata.get_config().get_num_shares();
+    let mut repairer = RepairingBlob::new(blob_metadata.clone());
+    let num_repairable_chunksets = (0..blob_metadata.get_num_chunksets()).fold(0, |num_repairable_chunksets, chunkset_id| {
+        let (num_present_shares, num_valid_shares) = (0..num_shares).fold((0, 0), |(num_present_shares, num_valid_shares), share_id| {
+            match verify_chunk_file(&layout, &blob_metadata, chunkset_id, share_id) {
+                ChunkFileStatus::Valid(chunk) => {
+                    let _ = repairer.add_chunk(&chunk);
+                    (num_present_shares + 1, num_valid_shares + 1)
+                }
+                ChunkFileStatus::Invalid(_) => (num_present_shares + 1, num_valid_shares),
+                ChunkFileStatus::Missing => (num_present_shares, num_valid_shares),
+            }
+        });
+
+        let is_repairable = unsafe { repairer.is_chunkset_ready_to_repair(chunkset_id).unwrap_unchecked() };
+        println!(
+            "\t{}\t{}/{}\t{}/{}\t{}",
+            BlobDirLayout::get_chunkset_dir_name(chunkset_id),
+            num_present_shares,
+            num_shares,
+            num_valid_shares,
+            num_shares,
+            if is_repairable { "✅ repairable" } else { "🚫 not repairable" }
+        );
+
+        num_repairable_chunksets + is_repairable as usize
+    });
+
+    println!(
+        "\n\t{}/{} chunksets repairable\t{}\n",
+        num_repairable_chunksets,
+        blob_metadata.get_num_chunksets(),
+        if num_repairable_chunksets == blob_metadata.get_num_chunksets() {
+            "✅"
+        } else {
+            "🚫"
+        }
+    );
+}
@@ -0,0 +1,132 @@
+use crate::{
+    errors::DecdsCLIError,
+    utils::{ChunkFileStatus, ensure_is_dir, read_blob_metadata, verify_chunk_file, write_file},
+};
+use decds_lib::{BlobDirLayout, BlobHeader, ChunkInventory, RepairingBlob, ShareAvailability};
+use std::{fmt::Write, path::PathBuf};
+
+/// A chunk offered by a storage node, as listed in an inventory.
+struct InventoryEntry {
+    chunkset_id: usize,
+    share_id: usize,
+    node: String,
+}
+
+pub fn handle_plan_command(chunk_dir_path: &PathBuf, inventory_path: &PathBuf, opt_target_file: &Option<PathBuf>) -> Result<(), DecdsCLIError> {
+    ensure_is_dir(chunk_dir_path)?;
+
+    let layout = BlobDirLayout::new(chunk_dir_path);
+    let blob_metadata = read_blob_metadata(&layout.get_metadata_path())?;
+    let inventory = read_inventory(inventory_path, &blob_metadata)?;
+
+    let mut repairer = RepairingBlob::new(blob_metadata.clone());
+    let mut plan = String::new();
+    let mut num_unplanned_chunksets = 0;
+
+    let mut local_availability = ShareAvailability::new(blob_metadata.get_num_chunksets());
+    (0..blob_metadata.get_num_chunksets()).for_each(|chunkset_id| {
+        (0..blob_metadata.get_config().get_num_shares()).for_each(|share_id| {
+            if let ChunkFileStatus::Valid(chunk) = verify_chunk_file(&layout, &blob_metadata, chunkset_id, share_id) {
+                let _ = local_availability.insert(chunkset_id, share_id);
+                let _ = repairer.add_chunk(&chunk);
+            }
+        });
+    });
+
+    let mut entries_per_chunkset = vec![Vec::new(); blob_metadata.get_num_chunksets()];
+    inventory.iter().for_each(|entry| entries_per_chunkset[entry.chunkset_id].push(entry));
+
+    repairer.missing().for_each(|(chunkset_id, num_remaining_chunks)| {
+        let mut planned_shares = local_availability.get_bitmap(chunkset_id).unwrap_or_default();
+
+        let planned_entries = entries_per_chunkset[chunkset_id]
+            .iter()
+            .filter(|entry| {
+                planned_shares & (1 << entry.share_id) == 0 && {
+                    planned_shares |= 1 << entry.share_id;
+                    true
+                }
+            })
+            .take(num_remaining_chunks)
+            .collect::<Vec<&&InventoryEntry>>();
+
+        planned_entries.iter().for_each(|entry| {
+            let _ = writeln!(plan, "{}\t{}\t{}", entry.chunkset_id, entry.share_id, entry.node);
+        });
+
+        if planned_entries.len() < num_remaining_chunks {
+            eprintln!(
+                "Chunkset {} requires {} more chunk(s), but inventory offers only {}\t🚫",
+                chunkset_id,
+                num_remaining_chunks,
+                planned_entries.len()
+            );
+            num_unplanned_chunksets += 1;
+        }
+    });
+
+    match opt_target_file {
+        Some(target_file) => {
+            write_file(target_file, &plan)?;
+            println!("Repair plan of {} chunk(s) written to {:?}", plan.lines().count(), target_file);
+        }
+        None => print!("{}", plan),
+    }
+
+    if num_unplanned_chunksets > 0 {
+        return Err(DecdsCLIError::InsufficientRepairPlan(num_unplanned_chunksets));
+    }
+
+    Ok(())
+}
+
+/// Reads an inventory file, either a serialized `ChunkInventory`, as written by `decds inventory`, where nodes are
+/// preferred in order of appearance, or a text file, where each line is `<chunkset_id> <share_id> <node>`, separated by
+/// whitespaces. Empty lines and lines starting with `#` are skipped.
+fn read_inventory(inventory_path: &PathBuf, blob_metadata: &BlobHeader) -> Result<Vec<InventoryEntry>, DecdsCLIError> {
+    let to_cli_error = |err: String| DecdsCLIError::FailedToReadFile(inventory_path.clone(), err);
+    let inventory = std::fs::read(inventory_path).map_err(|e| to_cli_error(e.to_string()))?;
+
+    if let Ok(inventory) = ChunkInventory::from_bytes(&inventory) {
+        if inventory.get_blob_id() != blob_metadata.get_blob_id() || inventory.get_num_chunksets() != blob_metadata.get_num_chunksets() {
+            return Err(DecdsCLIError::ForeignInventory(inventory_path.clone(), blob_metadata.get_blob_id()));
+        }
+
+        return Ok(inventory
+            .entries()
+            .map(|(chunkset_id, share_id, node)| InventoryEntry {
+                chunkset_id,
+                share_id,
+                node: node.to_string(),
+            })
+            .collect());
+    }
+
+    let inventory = String::from_utf8(inventory).map_err(|_| to_cli_error("neither a serialized chunk inventory, nor a text file".to_string()))?;
+    inventory
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(line_idx, line)| {
+            let mut fields = line.split_whitespace();
+
+            let parsed = (|| {
+                let chunkset_id = fields.next()?.parse::<usize>().ok()?;
+                let share_id = fields.next()?.parse::<usize>().ok()?;
+                let node = fields.next()?.to_string();
+
+                fields.next().is_none().then_some(InventoryEntry { chunkset_id, share_id, node })
+            })();
+
+            match parsed {
+                Some(entry) if entry.chunkset_id < blob_metadata.get_num_chunksets() && entry.share_id < blob_metadata.get_config().get_num_shares() => {
+                    Ok(entry)
+                }
+                _ => Err(to_cli_error(format!(
+                    "line {} is not of form `<chunkset_id> <share_id> <node>`, with valid IDs",
+                    line_idx + 1
+                ))),
+            }
+        })
+        .collect()
+}
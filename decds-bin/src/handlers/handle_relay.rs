@@ -0,0 +1,204 @@
+use crate::{
+    errors::DecdsCLIError,
+    tasks::run_scoped,
+    utils::{AuditLogFile, ensure_is_dir, read_blob_metadata},
+};
+use decds_lib::{AuditOperation, BlobDirLayout, BlobHeader, DecdsError, ProofCarryingChunk};
+use std::{
+    collections::HashMap,
+    io::{BufReader, ErrorKind, Read, Write},
+    net::{Shutdown, TcpListener, TcpStream},
+    path::PathBuf,
+    sync::{Mutex, MutexGuard},
+    time::Duration,
+};
+
+/// Frames longer than this can't hold a single proof-carrying chunk, so the connection sending them is dropped.
+const MAX_FRAME_BYTE_LEN: usize = 1usize << 21;
+/// Listener is polled for new connections this often, so that it notices failed relays in time.
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Relays erasure-coded chunks of a known blob. Chunks arrive on `listen_addr` as frames, each being a 4-bytes little-endian
+/// length, followed by that many bytes of a serialized `ProofCarryingChunk`. Chunks, which verify against the blob metadata
+/// in `chunk_dir_path`, are persisted into it and forwarded, as is, to every downstream node. Others are dropped. Outcome
+/// of verifying each chunk, and persisting it, are recorded in the audit log, if one is given. Once a chunk can't be
+/// persisted, or recorded, no more connections are accepted, open ones are shut down, and the failure is returned.
+///
+/// With `require_encrypted`, only a blob marked as encrypted is relayed, and chunks not marked as encrypted in their
+/// envelope are rejected right away, so that plaintext uploaded by mistake never reaches the disk.
+pub fn handle_relay_command(
+    chunk_dir_path: &PathBuf,
+    listen_addr: &str,
+    downstream_addrs: &[String],
+    opt_audit_log_path: &Option<PathBuf>,
+    require_encrypted: bool,
+) -> Result<(), DecdsCLIError> {
+    ensure_is_dir(chunk_dir_path)?;
+
+    let layout = BlobDirLayout::new(chunk_dir_path);
+    let blob_metadata = read_blob_metadata(&layout.get_metadata_path())?;
+    if blob_metadata.is_inline() {
+        return Err(DecdsCLIError::InlineBlob(layout.get_metadata_path()));
+    }
+    if require_encrypted && !blob_metadata.get_content_flags().is_encrypted() {
+        return Err(DecdsCLIError::PlaintextBlob(layout.get_metadata_path()));
+    }
+
+    let downstreams = downstream_addrs
+        .iter()
+        .map(|downstream_addr| {
+            TcpStream::connect(downstream_addr)
+                .map(|stream| (downstream_addr.as_str(), Mutex::new(Some(stream))))
+                .map_err(|e| DecdsCLIError::NetworkFailure(downstream_addr.clone(), e.to_string()))
+        })
+        .collect::<Result<Vec<(&str, Mutex<Option<TcpStream>>)>, DecdsCLIError>>()?;
+
+    let to_cli_error = |e: std::io::Error| DecdsCLIError::NetworkFailure(listen_addr.to_string(), e.to_string());
+    let listener = TcpListener::bind(listen_addr).map_err(to_cli_error)?;
+    listener.set_nonblocking(true).map_err(to_cli_error)?;
+
+    let audit_log = AuditLogFile::open(opt_audit_log_path)?;
+
+    println!("Relaying chunks of blob {} on {}...", blob_metadata.get_blob_id(), listen_addr);
+
+    // Open connections, so that they can be shut down, unblocking their relays, once one of them fails.
+    let connections = Mutex::new(HashMap::<usize, TcpStream>::new());
+
+    run_scoped(|tasks| {
+        let connections = &connections;
+        let mut next_connection_id = 0;
+
+        while !tasks.has_failed() {
+            let stream = match listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                    std::thread::sleep(ACCEPT_POLL_INTERVAL);
+                    continue;
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    continue;
+                }
+            };
+
+            // Accepted streams inherit non-blocking mode of the listener on some platforms.
+            let registered = stream
+                .set_nonblocking(false)
+                .and_then(|_| stream.try_clone())
+                .map(|cloned| lock_connections(connections).insert(next_connection_id, cloned));
+            if let Err(e) = registered {
+                eprintln!("Error: {}", e);
+                continue;
+            }
+
+            let connection_id = next_connection_id;
+            let (layout, blob_metadata, downstreams, audit_log) = (&layout, &blob_metadata, &downstreams, &audit_log);
+            tasks.spawn(move || {
+                let result = relay_chunks(stream, layout, blob_metadata, downstreams, audit_log, require_encrypted);
+                lock_connections(connections).remove(&connection_id);
+                result
+            });
+
+            next_connection_id += 1;
+        }
+
+        lock_connections(connections).values().for_each(|stream| {
+            let _ = stream.shutdown(Shutdown::Both);
+        });
+        Ok(())
+    })
+}
+
+fn lock_connections(connections: &Mutex<HashMap<usize, TcpStream>>) -> MutexGuard<'_, HashMap<usize, TcpStream>> {
+    connections.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn relay_chunks(
+    stream: TcpStream,
+    layout: &BlobDirLayout,
+    blob_metadata: &BlobHeader,
+    downstreams: &[(&str, Mutex<Option<TcpStream>>)],
+    audit_log: &AuditLogFile,
+    require_encrypted: bool,
+) -> Result<(), DecdsCLIError> {
+    let peer = stream.peer_addr().map(|addr| addr.to_string()).unwrap_or_default();
+    let mut reader = BufReader::new(stream);
+    let mut frame = Vec::new();
+
+    loop {
+        let mut frame_len_bytes = [0u8; 4];
+        match reader.read_exact(&mut frame_len_bytes) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(()),
+            Err(e) => {
+                eprintln!("Dropping connection from {}: {}", peer, e);
+                return Ok(());
+            }
+        }
+
+        let frame_len = u32::from_le_bytes(frame_len_bytes) as usize;
+        if frame_len > MAX_FRAME_BYTE_LEN {
+            eprintln!("Dropping connection from {}: frame of {}B is too large", peer, frame_len);
+            return Ok(());
+        }
+
+        frame.resize(frame_len, 0);
+        if let Err(e) = reader.read_exact(&mut frame) {
+            eprintln!("Dropping connection from {}: {}", peer, e);
+            return Ok(());
+        }
+
+        let chunk = match ProofCarryingChunk::from_bytes(&frame) {
+            Ok((chunk, n)) if n == frame_len => chunk,
+            Ok((_, n)) => {
+                eprintln!("Rejected chunk from {}: {} trailing bytes\t🚫", peer, frame_len - n);
+                continue;
+            }
+            Err(e) => {
+                eprintln!("Rejected chunk from {}: {}\t🚫", peer, e);
+                continue;
+            }
+        };
+
+        if require_encrypted && !chunk.get_content_flags().is_encrypted() {
+            eprintln!("Rejected chunk from {}: not marked as encrypted\t🚫", peer);
+            continue;
+        }
+
+        let (chunkset_id, share_id) = (chunk.get_chunkset_id(), chunk.get_local_chunk_id());
+        let is_valid = blob_metadata.validate_chunk(&chunk);
+        audit_log.record(
+            blob_metadata.get_blob_id(),
+            AuditOperation::ChunkVerified {
+                chunkset_id,
+                share_id,
+                is_valid,
+            },
+        )?;
+
+        if !is_valid {
+            eprintln!("Rejected chunk from {}: {}\t🚫", peer, DecdsError::InvalidProofInChunk(chunkset_id, share_id));
+            continue;
+        }
+
+        let chunk_path = layout.get_chunk_path(chunkset_id, share_id);
+        std::fs::DirBuilder::new()
+            .recursive(true)
+            .create(layout.get_chunkset_dir_path(chunkset_id))
+            .and_then(|_| std::fs::write(&chunk_path, &frame))
+            .map_err(|e| DecdsCLIError::FailedToWriteFile(chunk_path, e.to_string()))?;
+        audit_log.record(blob_metadata.get_blob_id(), AuditOperation::ChunkPut { chunkset_id, share_id })?;
+
+        downstreams.iter().for_each(|(downstream_addr, downstream)| {
+            let mut downstream = downstream.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            if let Some(stream) = downstream.as_mut() {
+                if let Err(e) = stream.write_all(&frame_len_bytes).and_then(|_| stream.write_all(&frame)) {
+                    eprintln!("Disconnecting downstream {}: {}", downstream_addr, e);
+                    *downstream = None;
+                }
+            }
+        });
+
+        println!("Relayed chunk of share {} for chunkset {} from {}\t✅", share_id, chunkset_id, peer);
+    }
+}
@@ -1,146 +1,374 @@
-use crate::utils::{format_bytes, get_target_directory_path, read_blob_metadata, read_proof_carrying_chunk};
-use decds_lib::{BlobHeader, DECDS_NUM_ERASURE_CODED_SHARES, DecdsError, RepairingBlob};
-use std::{io::Write, path::PathBuf, process::exit};
-
-pub fn handle_repair_command(chunk_dir_path: &PathBuf, opt_target_dir: &Option<PathBuf>) {
-    if !chunk_dir_path.is_dir() {
-        eprintln!("{:?} is not a directory", chunk_dir_path);
-        exit(1);
-    }
+use crate::{
+    errors::DecdsCLIError,
+    interrupt::PartialOutputGuard,
+    tasks::run_scoped,
+    utils::{
+        AuditLogFile, REPAIRED_BLOB_FILE_NAME, create_dir_all, ensure_enough_free_space, ensure_is_dir, format_bytes, get_repaired_chunkset_file_name,
+        get_short_blob_id, get_target_directory_path, quarantine_chunk_file, read_blob_metadata, write_file,
+    },
+};
+use decds_lib::{AuditOperation, BlobDirLayout, BlobHeader, DecdsError, RepairingBlob, VerifiedChunk};
+use std::{
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::mpsc::SyncSender,
+};
+
+/// Erasure-coded chunk files are read in pieces of this many bytes.
+const CHUNK_FILE_READ_BUFFER_BYTE_LEN: usize = 1usize << 16;
+
+/// Default number of verified chunks, which may wait for decoding, i.e. about as many MBs.
+const DEFAULT_VERIFIED_CHUNK_QUEUE_LEN: usize = 16;
 
-    let mut blob_metadata_path = chunk_dir_path.clone();
-    blob_metadata_path.push("metadata.commit");
+pub fn handle_repair_command(
+    chunk_dir_path: &PathBuf,
+    opt_target_dir: &Option<PathBuf>,
+    force: bool,
+    quarantine: bool,
+    share_weights: &[String],
+    opt_queue_len: Option<usize>,
+    opt_audit_log_path: &Option<PathBuf>,
+) -> Result<(), DecdsCLIError> {
+    ensure_is_dir(chunk_dir_path)?;
+
+    let layout = BlobDirLayout::new(chunk_dir_path);
+    let blob_metadata_path = layout.get_metadata_path();
 
     println!("Looking for erasure-coded blob metadata file {:?}...", blob_metadata_path);
-    let blob_metadata = read_blob_metadata(&blob_metadata_path);
+    let blob_metadata = read_blob_metadata(&blob_metadata_path)?;
 
     println!("Original blob size: {}", format_bytes(blob_metadata.get_blob_size()));
+    println!("Original blob ID: {}", blob_metadata.get_blob_id());
     println!("Original blob BLAKE3 Digest: {}", blob_metadata.get_blob_digest());
     println!("Original blob root commitment: {}", blob_metadata.get_root_commitment());
     println!("Original blob number of chunksets: {}", blob_metadata.get_num_chunksets());
     println!("Original blob number of chunks: {}", blob_metadata.get_num_chunks());
 
-    reconstruct_original_blob_from_erasure_coded_chunks(chunk_dir_path, opt_target_dir, &blob_metadata);
+    let mut repairer = RepairingBlob::new(blob_metadata.clone());
+    share_weights.iter().try_for_each(|share_weight| {
+        let (share_id, weight) = share_weight
+            .split_once('=')
+            .and_then(|(share_id, weight)| Some((share_id.trim().parse::<usize>().ok()?, weight.trim().parse::<u32>().ok()?)))
+            .ok_or_else(|| DecdsCLIError::InvalidArgument(share_weight.clone(), "of form `share_id=weight`".to_string()))?;
+
+        repairer.set_share_weight(share_id, weight).map_err(DecdsCLIError::from)
+    })?;
+
+    let queue_len = opt_queue_len.unwrap_or(DEFAULT_VERIFIED_CHUNK_QUEUE_LEN).max(1);
+    let audit_log = AuditLogFile::open(opt_audit_log_path)?;
+    let handling = ChunkFileHandling {
+        quarantine,
+        audit_log: &audit_log,
+    };
+    reconstruct_original_blob_from_erasure_coded_chunks(&layout, opt_target_dir, force, &blob_metadata, repairer, handling, queue_len)
 }
 
-fn reconstruct_original_blob_from_erasure_coded_chunks(chunk_dir_path: &PathBuf, opt_target_dir: &Option<PathBuf>, blob_metadata: &BlobHeader) {
-    let mut rng = rand::rng();
-    let target_dir_path = get_target_directory_path(chunk_dir_path, opt_target_dir, &mut rng);
+fn reconstruct_original_blob_from_erasure_coded_chunks(
+    layout: &BlobDirLayout,
+    opt_target_dir: &Option<PathBuf>,
+    force: bool,
+    blob_metadata: &BlobHeader,
+    repairer: RepairingBlob,
+    handling: ChunkFileHandling,
+    queue_len: usize,
+) -> Result<(), DecdsCLIError> {
+    let default_target_dir_name = format!("repaired-{}", get_short_blob_id(blob_metadata));
+    let target_dir_path = get_target_directory_path(&default_target_dir_name, opt_target_dir, layout.get_root(), force)?;
 
-    if let Err(e) = std::fs::DirBuilder::new().recursive(true).create(&target_dir_path) {
-        eprintln!("Error: {}", e);
-        exit(1);
-    }
+    // Repaired chunksets are merged into the repaired blob one at a time, deleting each once merged, so at peak, the whole
+    // blob and one more chunkset are on disk.
+    let required_bytes = blob_metadata
+        .get_blob_size()
+        .saturating_add(blob_metadata.get_chunkset_size(0).unwrap_or_default());
+    ensure_enough_free_space(&target_dir_path, required_bytes)?;
+    create_dir_all(&target_dir_path)?;
 
     println!("Repairing chunksets and blob in {:?}...", target_dir_path);
 
-    reconstruct_chunksets(chunk_dir_path, &target_dir_path, blob_metadata);
-    reconstruct_original_blob_from_chunksets(&target_dir_path, blob_metadata);
+    let guard = PartialOutputGuard::new(&target_dir_path);
+    reconstruct_chunksets(layout, &target_dir_path, blob_metadata, repairer, handling, queue_len, &guard)?;
+    reconstruct_original_blob_from_chunksets(&target_dir_path, blob_metadata, &guard)
 }
 
-fn reconstruct_chunksets(chunk_dir_path: &PathBuf, target_dir_path: &PathBuf, blob_metadata: &BlobHeader) {
-    let mut blob_share_dir_path = chunk_dir_path.clone();
-    let mut repaired_chunkset_dir_path = target_dir_path.clone();
+/// Sent from the verification stage of repair to its decoding stage.
+enum VerificationStageMessage {
+    /// A chunk, which verified against blob metadata.
+    Chunk(VerifiedChunk),
+    /// A chunk file of this source couldn't be read, or failed verification.
+    Suspect(String),
+    /// All chunks, the verification stage read for a chunkset, are sent. Contains the chunkset ID and the index, in
+    /// preferred share order, of the first share it didn't read, from where the decoding stage continues, if needed.
+    EndOfChunkset(usize, usize),
+}
 
-    let mut repairer = RepairingBlob::new(blob_metadata.clone());
-    let mut chunkset_id = 0;
+/// How chunk files are handled, as they're read: whether ones failing verification are quarantined, and where reads,
+/// verification outcomes and quarantines are recorded.
+#[derive(Clone, Copy)]
+struct ChunkFileHandling<'a> {
+    quarantine: bool,
+    audit_log: &'a AuditLogFile,
+}
 
-    while chunkset_id < blob_metadata.get_num_chunksets() {
-        blob_share_dir_path.push(format!("chunkset.{}", chunkset_id));
-        repaired_chunkset_dir_path.push(format!("chunkset.{}.data", chunkset_id));
-
-        let mut share_id = 0;
-        while (share_id < DECDS_NUM_ERASURE_CODED_SHARES) && unsafe { !repairer.is_chunkset_ready_to_repair(chunkset_id).unwrap_unchecked() } {
-            blob_share_dir_path.push(format!("share{:02}.data", share_id));
-
-            if blob_share_dir_path.is_file() {
-                if let Ok(chunk) = read_proof_carrying_chunk(&blob_share_dir_path) {
-                    match repairer.add_chunk(&chunk) {
-                        Ok(()) => {}
-                        Err(e) => match e {
-                            DecdsError::InvalidProofInChunk(_) => {}
-                            DecdsError::InvalidChunkMetadata(_) => {}
-                            DecdsError::ChunkDecodingFailed(_, _) => {}
-                            _ => {
-                                eprintln!("Encountered unexpected error: {}", e);
-                                exit(1);
-                            }
-                        },
-                    };
+/// Outcome of reading a chunk file.
+enum ChunkFile {
+    Missing,
+    Invalid,
+    Verified(VerifiedChunk),
+}
+
+fn reconstruct_chunksets(
+    layout: &BlobDirLayout,
+    target_dir_path: &Path,
+    blob_metadata: &BlobHeader,
+    mut repairer: RepairingBlob,
+    handling: ChunkFileHandling,
+    queue_len: usize,
+    guard: &PartialOutputGuard,
+) -> Result<(), DecdsCLIError> {
+    let preferred_share_order = repairer.get_preferred_share_order();
+    let num_needed_chunks = (0..blob_metadata.get_num_chunksets())
+        .map(|chunkset_id| unsafe { repairer.get_num_remaining_chunks(chunkset_id).unwrap_unchecked() })
+        .collect::<Vec<usize>>();
+
+    // Chunks are read and verified on a separate thread, which runs ahead of RLNC decoding by at most `queue_len` chunks,
+    // so that neither of them stalls the other. If either stage fails, the other one stops, as the channel is hung up.
+    run_scoped(|tasks| {
+        let (tx, rx) = std::sync::mpsc::sync_channel(queue_len);
+        let preferred_share_order = &preferred_share_order;
+        let num_needed_chunks = &num_needed_chunks;
+
+        tasks.spawn(move || verify_chunks(layout, blob_metadata, preferred_share_order, num_needed_chunks, handling, tx));
+
+        for message in rx {
+            match message {
+                VerificationStageMessage::Chunk(chunk) => add_verified_chunk(&mut repairer, &chunk, layout, blob_metadata)?,
+                VerificationStageMessage::Suspect(source) => repairer.record_suspect_source(&source),
+                VerificationStageMessage::EndOfChunkset(chunkset_id, mut share_idx) => {
+                    guard.check_interrupted()?;
+
+                    // Some chunks turned out to be useless for decoding, so more are read, this time without running ahead.
+                    while (share_idx < preferred_share_order.len()) && unsafe { !repairer.is_chunkset_ready_to_repair(chunkset_id).unwrap_unchecked() } {
+                        let share_id = preferred_share_order[share_idx];
+
+                        match read_chunk_file(layout, blob_metadata, chunkset_id, share_id, handling)? {
+                            ChunkFile::Verified(chunk) => add_verified_chunk(&mut repairer, &chunk, layout, blob_metadata)?,
+                            ChunkFile::Invalid => repairer.record_suspect_source(&format!("share{:02}", share_id)),
+                            ChunkFile::Missing => {}
+                        }
+
+                        share_idx += 1;
+                    }
+
+                    if unsafe { !repairer.is_chunkset_ready_to_repair(chunkset_id).unwrap_unchecked() } {
+                        print_suspect_sources_report(&repairer);
+                        return Err(DecdsCLIError::FailedToRepairChunkset(
+                            chunkset_id,
+                            get_short_blob_id(blob_metadata),
+                            layout.get_chunkset_dir_path(chunkset_id),
+                        ));
+                    }
+
+                    let repaired_chunkset = unsafe { repairer.get_repaired_chunkset(chunkset_id).unwrap_unchecked() };
+                    write_file(&target_dir_path.join(get_repaired_chunkset_file_name(chunkset_id)), repaired_chunkset)?;
+                    handling
+                        .audit_log
+                        .record(blob_metadata.get_blob_id(), AuditOperation::ChunksetRepaired { chunkset_id })?;
+                }
+            }
+        }
+
+        Ok(())
+    })?;
+
+    print_suspect_sources_report(&repairer);
+    Ok(())
+}
+
+/// Verification stage of repair: reads chunk files of each chunkset, in preferred share order, until as many chunks as
+/// needed for decoding it verified, sending them to the decoding stage.
+fn verify_chunks(
+    layout: &BlobDirLayout,
+    blob_metadata: &BlobHeader,
+    preferred_share_order: &[usize],
+    num_needed_chunks: &[usize],
+    handling: ChunkFileHandling,
+    tx: SyncSender<VerificationStageMessage>,
+) -> Result<(), DecdsCLIError> {
+    for (chunkset_id, &num_needed) in num_needed_chunks.iter().enumerate() {
+        let mut num_verified = 0;
+        let mut share_idx = 0;
+
+        while (share_idx < preferred_share_order.len()) && (num_verified < num_needed) {
+            let share_id = preferred_share_order[share_idx];
+
+            let message = match read_chunk_file(layout, blob_metadata, chunkset_id, share_id, handling)? {
+                ChunkFile::Verified(chunk) => {
+                    num_verified += 1;
+                    Some(VerificationStageMessage::Chunk(chunk))
                 }
+                ChunkFile::Invalid => Some(VerificationStageMessage::Suspect(format!("share{:02}", share_id))),
+                ChunkFile::Missing => None,
+            };
+
+            // Decoding stage is gone only if it failed, so there is no one left to verify chunks for.
+            if message.is_some_and(|message| tx.send(message).is_err()) {
+                return Ok(());
             }
 
-            blob_share_dir_path.pop();
-            share_id += 1;
+            share_idx += 1;
         }
 
-        if unsafe { !repairer.is_chunkset_ready_to_repair(chunkset_id).unwrap_unchecked() } {
-            eprintln!("Failed to repair chunkset {:?}", blob_share_dir_path);
-            exit(1);
+        if tx.send(VerificationStageMessage::EndOfChunkset(chunkset_id, share_idx)).is_err() {
+            return Ok(());
         }
+    }
 
-        let repaired_chunkset = unsafe { repairer.get_repaired_chunkset(chunkset_id).unwrap_unchecked() };
-        if let Err(e) = std::fs::write(&repaired_chunkset_dir_path, repaired_chunkset) {
-            eprintln!("Error: {}", e);
-            exit(1);
+    Ok(())
+}
+
+fn read_chunk_file(
+    layout: &BlobDirLayout,
+    blob_metadata: &BlobHeader,
+    chunkset_id: usize,
+    share_id: usize,
+    handling: ChunkFileHandling,
+) -> Result<ChunkFile, DecdsCLIError> {
+    let chunk_path = layout.get_chunk_path(chunkset_id, share_id);
+    if !chunk_path.is_file() {
+        return Ok(ChunkFile::Missing);
+    }
+
+    let fd = match std::fs::File::open(&chunk_path) {
+        Ok(fd) => fd,
+        Err(e) => {
+            quarantine_if_asked(layout, blob_metadata, chunkset_id, share_id, &e.to_string(), handling)?;
+            return Ok(ChunkFile::Invalid);
         }
+    };
+    handling
+        .audit_log
+        .record(blob_metadata.get_blob_id(), AuditOperation::ChunkGet { chunkset_id, share_id })?;
 
-        repaired_chunkset_dir_path.pop();
-        blob_share_dir_path.pop();
-        chunkset_id += 1;
+    // Chunk file is hashed while being read, so it is never buffered in full and never hashed twice.
+    let mut reader = BufReader::with_capacity(CHUNK_FILE_READ_BUFFER_BYTE_LEN, fd);
+
+    match blob_metadata.read_verified_chunk(&mut reader) {
+        Ok(chunk) => {
+            if reader.fill_buf().is_ok_and(|trailing_bytes| !trailing_bytes.is_empty()) {
+                eprintln!("Warning: erasure-coded chunk file {:?} has trailing bytes, ignoring them", chunk_path);
+            }
+
+            record_verification(handling.audit_log, blob_metadata, chunkset_id, share_id, true)?;
+            Ok(ChunkFile::Verified(chunk))
+        }
+        Err(
+            e @ (DecdsError::InvalidProofInChunk(_, _)
+            | DecdsError::InvalidChunkMetadata(_, _)
+            | DecdsError::InvalidChunksetId(_, _)
+            | DecdsError::ProofCarryingChunkDeserializationFailed(_)),
+        ) => {
+            record_verification(handling.audit_log, blob_metadata, chunkset_id, share_id, false)?;
+            quarantine_if_asked(layout, blob_metadata, chunkset_id, share_id, &e.to_string(), handling)?;
+            Ok(ChunkFile::Invalid)
+        }
+        Err(e) => Err(DecdsCLIError::UnexpectedChunkError(chunk_path, get_short_blob_id(blob_metadata), e)),
     }
 }
 
-fn reconstruct_original_blob_from_chunksets(target_dir_path: &PathBuf, blob_metadata: &BlobHeader) {
-    let mut repaired_blob_path = target_dir_path.clone();
-    repaired_blob_path.push("repaired.data");
+fn add_verified_chunk(repairer: &mut RepairingBlob, chunk: &VerifiedChunk, layout: &BlobDirLayout, blob_metadata: &BlobHeader) -> Result<(), DecdsCLIError> {
+    match repairer.add_verified_chunk(chunk) {
+        Ok(()) | Err(DecdsError::NonInnovativeChunk(_, _)) => Ok(()),
+        Err(e) => {
+            let (chunkset_id, share_id) = (chunk.get_chunk().get_chunkset_id(), chunk.get_chunk().get_local_chunk_id());
+            Err(DecdsCLIError::UnexpectedChunkError(
+                layout.get_chunk_path(chunkset_id, share_id),
+                get_short_blob_id(blob_metadata),
+                e,
+            ))
+        }
+    }
+}
 
-    let mut repaired_chunkset_path = target_dir_path.clone();
+fn record_verification(audit_log: &AuditLogFile, blob_metadata: &BlobHeader, chunkset_id: usize, share_id: usize, is_valid: bool) -> Result<(), DecdsCLIError> {
+    audit_log.record(
+        blob_metadata.get_blob_id(),
+        AuditOperation::ChunkVerified {
+            chunkset_id,
+            share_id,
+            is_valid,
+        },
+    )
+}
 
-    let repaired_blob_digest = match std::fs::OpenOptions::new().create_new(true).write(true).open(&repaired_blob_path) {
-        Ok(fd) => {
-            let mut buffered_fd = std::io::BufWriter::new(fd);
-            let mut blake3_hasher = blake3::Hasher::new();
+fn quarantine_if_asked(
+    layout: &BlobDirLayout,
+    blob_metadata: &BlobHeader,
+    chunkset_id: usize,
+    share_id: usize,
+    reason: &str,
+    handling: ChunkFileHandling,
+) -> Result<(), DecdsCLIError> {
+    if !handling.quarantine {
+        return Ok(());
+    }
 
-            let mut chunkset_id = 0;
-            while chunkset_id < blob_metadata.get_num_chunksets() {
-                repaired_chunkset_path.push(format!("chunkset.{}.data", chunkset_id));
+    let chunk_path = layout.get_chunk_path(chunkset_id, share_id);
+    match quarantine_chunk_file(layout, chunkset_id, share_id, reason) {
+        Ok(quarantined_chunk_path) => {
+            handling
+                .audit_log
+                .record(blob_metadata.get_blob_id(), AuditOperation::ChunkDelete { chunkset_id, share_id })?;
+            println!("Quarantined {:?} to {:?}: {}", chunk_path, quarantined_chunk_path, reason)
+        }
+        Err(e) => eprintln!("Failed to quarantine {:?}: {}", chunk_path, e),
+    }
 
-                match std::fs::read(&repaired_chunkset_path) {
-                    Ok(bytes) => {
-                        if let Err(e) = buffered_fd.write_all(&bytes) {
-                            eprintln!("Error: {}", e);
-                            exit(1);
-                        }
+    Ok(())
+}
 
-                        blake3_hasher.update(&bytes);
-                    }
-                    Err(e) => {
-                        eprintln!("Error: {}", e);
-                        exit(1);
-                    }
-                }
+fn print_suspect_sources_report(repairer: &RepairingBlob) {
+    let suspect_sources = repairer.get_suspect_sources();
+    if suspect_sources.is_empty() {
+        return;
+    }
 
-                if let Err(e) = std::fs::remove_file(&repaired_chunkset_path) {
-                    eprintln!("Error: {}", e);
-                }
+    println!("Suspect sources, serving chunks which failed verification:");
+    suspect_sources.iter().for_each(|(source, num_invalid_chunks)| {
+        println!("\t- {}\t{} invalid chunk(s)\t🚫", source, num_invalid_chunks);
+    });
+}
 
-                repaired_chunkset_path.pop();
-                chunkset_id += 1;
-            }
+fn reconstruct_original_blob_from_chunksets(target_dir_path: &Path, blob_metadata: &BlobHeader, guard: &PartialOutputGuard) -> Result<(), DecdsCLIError> {
+    let repaired_blob_path = target_dir_path.join(REPAIRED_BLOB_FILE_NAME);
+    let to_cli_error = |e: std::io::Error| DecdsCLIError::FailedToWriteFile(repaired_blob_path.clone(), e.to_string());
 
-            if let Err(e) = buffered_fd.flush() {
-                eprintln!("Error: {}", e);
-                exit(1);
-            }
+    let fd = std::fs::OpenOptions::new()
+        .create_new(true)
+        .write(true)
+        .open(&repaired_blob_path)
+        .map_err(to_cli_error)?;
+    let mut buffered_fd = std::io::BufWriter::new(fd);
+    let mut blake3_hasher = blake3::Hasher::new();
 
-            blake3_hasher.finalize()
-        }
-        Err(e) => {
+    let mut chunkset_id = 0;
+    while chunkset_id < blob_metadata.get_num_chunksets() {
+        guard.check_interrupted()?;
+
+        let repaired_chunkset_path = target_dir_path.join(get_repaired_chunkset_file_name(chunkset_id));
+        let bytes = std::fs::read(&repaired_chunkset_path).map_err(|e| DecdsCLIError::FailedToReadFile(repaired_chunkset_path.clone(), e.to_string()))?;
+
+        buffered_fd.write_all(&bytes).map_err(to_cli_error)?;
+        blake3_hasher.update(&bytes);
+
+        if let Err(e) = std::fs::remove_file(&repaired_chunkset_path) {
             eprintln!("Error: {}", e);
-            exit(1);
         }
-    };
+
+        chunkset_id += 1;
+    }
+
+    buffered_fd.flush().map_err(to_cli_error)?;
+    let repaired_blob_digest = blake3_hasher.finalize();
 
     println!("Repaired blob @ {:?}", repaired_blob_path);
     println!(
@@ -152,4 +380,6 @@ fn reconstruct_original_blob_from_chunksets(target_dir_path: &PathBuf, blob_meta
             "🚫"
         }
     );
+
+    Ok(())
 }
@@ -0,0 +1,35 @@
+use crate::{
+    errors::DecdsCLIError,
+    utils::{format_bytes, get_registry_path, read_registry, write_file},
+};
+use std::path::PathBuf;
+
+pub fn handle_resolve_command(key: &str, opt_registry_path: &Option<PathBuf>, opt_target_file: &Option<PathBuf>) -> Result<(), DecdsCLIError> {
+    let registry_path = get_registry_path(opt_registry_path)?;
+    let registry = read_registry(&registry_path)?;
+
+    let blob_metadata = registry
+        .resolve(key)
+        .ok_or_else(|| DecdsCLIError::BlobNotInRegistry(key.to_string(), registry_path.clone()))?;
+
+    println!("Blob ID: {}", blob_metadata.get_blob_id());
+    println!("Blob size: {}", format_bytes(blob_metadata.get_blob_size()));
+    println!("Blob digest: {}", blob_metadata.get_blob_digest());
+    println!("Number of chunksets: {}", blob_metadata.get_num_chunksets());
+
+    let names = registry
+        .names()
+        .filter(|(_, blob_id)| *blob_id == blob_metadata.get_blob_id())
+        .map(|(name, _)| name)
+        .collect::<Vec<&str>>();
+    if !names.is_empty() {
+        println!("Tags: {}", names.join(", "));
+    }
+
+    if let Some(target_file) = opt_target_file {
+        write_file(target_file, blob_metadata.to_bytes()?)?;
+        println!("Blob metadata written to {:?}", target_file);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use crate::{
+    errors::DecdsCLIError,
+    utils::{ensure_is_dir, get_registry_path, read_blob_metadata, read_registry, write_registry},
+};
+use decds_lib::BlobDirLayout;
+use std::path::PathBuf;
+
+pub fn handle_tag_command(blob_dir_path: &PathBuf, name: &str, opt_registry_path: &Option<PathBuf>) -> Result<(), DecdsCLIError> {
+    ensure_is_dir(blob_dir_path)?;
+
+    let blob_metadata = read_blob_metadata(&BlobDirLayout::new(blob_dir_path).get_metadata_path())?;
+    let blob_id = blob_metadata.get_blob_id();
+
+    let registry_path = get_registry_path(opt_registry_path)?;
+    let mut registry = read_registry(&registry_path)?;
+
+    match registry.tag(name, blob_metadata)? {
+        Some(previous_blob_id) if previous_blob_id != blob_id => println!("Moved tag {} from blob {} to blob {}", name, previous_blob_id, blob_id),
+        _ => println!("Tagged blob {} as {}", blob_id, name),
+    }
+
+    write_registry(&registry_path, &registry)
+}
@@ -1,101 +1,136 @@
-use crate::utils::{format_bytes, read_blob_metadata, read_proof_carrying_chunk};
-use decds_lib::{BlobHeader, DECDS_NUM_ERASURE_CODED_SHARES};
-use std::{path::PathBuf, process::exit};
-
-pub fn handle_verify_command(blob_dir_path: &PathBuf) {
-    if !blob_dir_path.is_dir() {
-        eprintln!("{:?} is not a directory", blob_dir_path);
-        exit(1);
-    }
+use crate::{
+    errors::DecdsCLIError,
+    utils::{AuditLogFile, ChunkFileStatus, ensure_is_dir, format_bytes, quarantine_chunk_file, read_blob_metadata, verify_chunk_file},
+};
+use decds_lib::{AuditOperation, BlobDirLayout, BlobHeader};
+use std::path::PathBuf;
+
+pub fn handle_verify_command(blob_dir_path: &PathBuf, quarantine: bool, opt_audit_log_path: &Option<PathBuf>) -> Result<(), DecdsCLIError> {
+    ensure_is_dir(blob_dir_path)?;
 
-    let mut blob_metadata_path = blob_dir_path.clone();
-    blob_metadata_path.push("metadata.commit");
+    let layout = BlobDirLayout::new(blob_dir_path);
+    let blob_metadata_path = layout.get_metadata_path();
 
     println!("Looking for erasure-coded blob metadata file {:?}...", blob_metadata_path);
-    let blob_metadata = read_blob_metadata(&blob_metadata_path);
+    let blob_metadata = read_blob_metadata(&blob_metadata_path)?;
 
     println!("Original blob size: {}", format_bytes(blob_metadata.get_blob_size()));
+    println!("Original blob ID: {}", blob_metadata.get_blob_id());
     println!("Original blob BLAKE3 Digest: {}", blob_metadata.get_blob_digest());
     println!("Original blob root commitment: {}", blob_metadata.get_root_commitment());
     println!("Original blob number of chunksets: {}", blob_metadata.get_num_chunksets());
     println!("Original blob number of chunks: {}", blob_metadata.get_num_chunks());
 
-    verify_erasure_coded_chunks_and_report(blob_dir_path, &blob_metadata);
+    if blob_metadata.is_inline() {
+        println!("Blob data is stored inline in metadata, there are no erasure-coded chunks to verify.");
+        return Ok(());
+    }
+
+    let audit_log = AuditLogFile::open(opt_audit_log_path)?;
+    verify_erasure_coded_chunks_and_report(&layout, &blob_metadata, quarantine, &audit_log)
 }
 
-fn verify_erasure_coded_chunks_and_report(target_dir: &PathBuf, blob_metadata: &BlobHeader) {
-    let mut blob_share_path = target_dir.clone();
+fn verify_erasure_coded_chunks_and_report(
+    layout: &BlobDirLayout,
+    blob_metadata: &BlobHeader,
+    quarantine: bool,
+    audit_log: &AuditLogFile,
+) -> Result<(), DecdsCLIError> {
     let mut indent = String::new();
     let mut total_num_valid_chunks = 0;
 
     println!("Verifying erasure-coded proof-carrying chunks...\n");
-    println!("{}", blob_share_path.to_str().unwrap());
+    println!("{}", layout.get_root().display());
 
-    (0..blob_metadata.get_num_chunksets()).for_each(|chunkset_id| {
-        blob_share_path.push(format!("chunkset.{}", chunkset_id));
+    (0..blob_metadata.get_num_chunksets()).try_for_each(|chunkset_id| {
         indent.push('\t');
 
         let (console_log, num_valid_shares) =
-            (0..DECDS_NUM_ERASURE_CODED_SHARES).fold((String::new(), 0usize), |(mut console_log, mut num_valid_shares), share_id| {
-                blob_share_path.push(format!("share{:02}.data", share_id));
+            (0..blob_metadata.get_config().get_num_shares()).try_fold((String::new(), 0usize), |(mut console_log, mut num_valid_shares), share_id| {
+                let chunk_file_name = BlobDirLayout::get_chunk_file_name(share_id);
                 indent.push('\t');
 
-                let share_stat_log = if let Ok(ok) = blob_share_path.try_exists()
-                    && ok
-                {
-                    match read_proof_carrying_chunk(&blob_share_path) {
-                        Ok(chunk) => {
-                            if blob_metadata.validate_chunk(&chunk) {
-                                num_valid_shares += 1;
-                                format!("{}- {}\t✅", indent, blob_share_path.file_name().unwrap().to_str().unwrap())
-                            } else {
-                                format!(
-                                    "{}- {}\t🚫\tError: proof verification failed",
-                                    indent,
-                                    blob_share_path.file_name().unwrap().to_str().unwrap()
-                                )
-                            }
-                        }
-                        Err(e) => {
-                            format!("{}- {}\t🚫\tError: {}", indent, blob_share_path.file_name().unwrap().to_str().unwrap(), e)
-                        }
+                let share_stat_log = match verify_chunk_file(layout, blob_metadata, chunkset_id, share_id) {
+                    ChunkFileStatus::Valid(_) => {
+                        record_verification(audit_log, blob_metadata, chunkset_id, share_id, true)?;
+                        num_valid_shares += 1;
+                        format!("{}- {}\t✅", indent, chunk_file_name)
                     }
-                } else {
-                    format!(
-                        "{}- {}\t🚫\tError: chunk not present",
-                        indent,
-                        blob_share_path.file_name().unwrap().to_str().unwrap()
-                    )
+                    ChunkFileStatus::Invalid(reason) => {
+                        record_verification(audit_log, blob_metadata, chunkset_id, share_id, false)?;
+                        format!(
+                            "{}- {}\t🚫\tError: {}{}",
+                            indent,
+                            chunk_file_name,
+                            reason,
+                            quarantine_if_asked(layout, blob_metadata, chunkset_id, share_id, &reason, quarantine, audit_log)?
+                        )
+                    }
+                    ChunkFileStatus::Missing => format!("{}- {}\t🚫\tError: chunk not present", indent, chunk_file_name),
                 };
 
-                blob_share_path.pop();
                 indent.pop();
 
                 console_log.push_str(&share_stat_log);
                 console_log.push('\n');
 
-                (console_log, num_valid_shares)
-            });
+                Ok::<_, DecdsCLIError>((console_log, num_valid_shares))
+            })?;
 
         println!(
             "{}- {}\t({}/{})",
             indent,
-            blob_share_path.file_name().unwrap().to_str().unwrap(),
+            BlobDirLayout::get_chunkset_dir_name(chunkset_id),
             num_valid_shares,
-            DECDS_NUM_ERASURE_CODED_SHARES
+            blob_metadata.get_config().get_num_shares()
         );
         println!("{}", console_log);
 
         total_num_valid_chunks += num_valid_shares;
 
-        blob_share_path.pop();
         indent.pop();
-    });
+        Ok::<_, DecdsCLIError>(())
+    })?;
 
     println!(
         "Found {}/{} valid chunks in {:?}.",
         total_num_valid_chunks,
         blob_metadata.get_num_chunks(),
-        target_dir
+        layout.get_root()
     );
+
+    Ok(())
+}
+
+fn record_verification(audit_log: &AuditLogFile, blob_metadata: &BlobHeader, chunkset_id: usize, share_id: usize, is_valid: bool) -> Result<(), DecdsCLIError> {
+    audit_log.record(
+        blob_metadata.get_blob_id(),
+        AuditOperation::ChunkVerified {
+            chunkset_id,
+            share_id,
+            is_valid,
+        },
+    )
+}
+
+fn quarantine_if_asked(
+    layout: &BlobDirLayout,
+    blob_metadata: &BlobHeader,
+    chunkset_id: usize,
+    share_id: usize,
+    reason: &str,
+    quarantine: bool,
+    audit_log: &AuditLogFile,
+) -> Result<String, DecdsCLIError> {
+    if !quarantine {
+        return Ok(String::new());
+    }
+
+    match quarantine_chunk_file(layout, chunkset_id, share_id, reason) {
+        Ok(quarantined_chunk_path) => {
+            audit_log.record(blob_metadata.get_blob_id(), AuditOperation::ChunkDelete { chunkset_id, share_id })?;
+            Ok(format!("\t(quarantined to {:?})", quarantined_chunk_path))
+        }
+        Err(e) => Ok(format!("\t(failed to quarantine: {})", e)),
+    }
 }
@@ -1,7 +1,25 @@
+mod handle_audit;
 mod handle_break;
+mod handle_digests;
+mod handle_gen_vectors;
+mod handle_inventory;
+mod handle_ls;
+mod handle_plan;
+mod handle_relay;
 mod handle_repair;
+mod handle_resolve;
+mod handle_tag;
 mod handle_verify;
 
+pub use handle_audit::handle_audit_command;
 pub use handle_break::handle_break_command;
+pub use handle_digests::handle_digests_command;
+pub use handle_gen_vectors::handle_gen_vectors_command;
+pub use handle_inventory::handle_inventory_command;
+pub use handle_ls::handle_ls_command;
+pub use handle_plan::handle_plan_command;
+pub use handle_relay::handle_relay_command;
 pub use handle_repair::handle_repair_command;
+pub use handle_resolve::handle_resolve_command;
+pub use handle_tag::handle_tag_command;
 pub use handle_verify::handle_verify_command;
@@ -0,0 +1,67 @@
+use crate::errors::DecdsCLIError;
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// Exit code of a process terminated by SIGINT, as reported by shells.
+pub const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+static WRITING_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// Installs a Ctrl-C handler. While no output is being written, it terminates the process right away. Otherwise it only
+/// records the interruption, so that the writer can remove its partial output at the next `PartialOutputGuard` check.
+#[cfg(unix)]
+pub fn install_interrupt_handler() {
+    extern "C" fn on_sigint(_signum: libc::c_int) {
+        if !WRITING_OUTPUT.load(Ordering::SeqCst) {
+            unsafe { libc::_exit(INTERRUPTED_EXIT_CODE) };
+        }
+
+        INTERRUPTED.store(true, Ordering::SeqCst);
+    }
+
+    unsafe { libc::signal(libc::SIGINT, on_sigint as extern "C" fn(libc::c_int) as libc::sighandler_t) };
+}
+
+/// Ctrl-C can't be intercepted on this platform, so the default behavior is kept.
+#[cfg(not(unix))]
+pub fn install_interrupt_handler() {}
+
+/// Owns an output directory while it is being written to. If Ctrl-C is pressed meanwhile, the directory is removed at the
+/// next `check_interrupted` check, so that no half-written chunk tree is left behind to confuse later runs.
+/// The directory must be freshly created by the command, see `get_target_directory_path`, as it's removed as a whole.
+pub struct PartialOutputGuard {
+    output_dir_path: PathBuf,
+}
+
+impl PartialOutputGuard {
+    pub fn new(output_dir_path: &Path) -> Self {
+        WRITING_OUTPUT.store(true, Ordering::SeqCst);
+        PartialOutputGuard {
+            output_dir_path: output_dir_path.to_path_buf(),
+        }
+    }
+
+    /// Removes the partial output directory and fails with `DecdsCLIError::Interrupted`, if Ctrl-C was pressed since the
+    /// guard was created, so that the command stops and the process exits with `INTERRUPTED_EXIT_CODE`.
+    pub fn check_interrupted(&self) -> Result<(), DecdsCLIError> {
+        if !INTERRUPTED.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        eprintln!("Interrupted, removing partial output {:?}...", self.output_dir_path);
+        if let Err(e) = std::fs::remove_dir_all(&self.output_dir_path) {
+            eprintln!("Error: {}", e);
+        }
+
+        Err(DecdsCLIError::Interrupted)
+    }
+}
+
+impl Drop for PartialOutputGuard {
+    fn drop(&mut self) {
+        WRITING_OUTPUT.store(false, Ordering::SeqCst);
+    }
+}
@@ -1,9 +1,12 @@
 mod errors;
 mod handlers;
+mod interrupt;
+mod tasks;
 mod utils;
 
 use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use decds_lib::ContentFlags;
+use std::{path::PathBuf, process::exit};
 
 #[derive(Parser)]
 #[command(name = "decds", version, about, long_about = None)]
@@ -19,34 +22,243 @@ enum DecdsCommand {
         /// Path of source data blob
         #[arg(short)]
         blob_path: PathBuf,
-        /// Optional target directory to put erasure-coded chunks
+        /// Optional target directory to put erasure-coded chunks, defaults to `<blob file name>-<blob ID prefix>`
         #[arg(short)]
         opt_target_dir: Option<PathBuf>,
+        /// Overwrite target directory, if it already exists
+        #[arg(short)]
+        force: bool,
+        /// Optional byte length threshold, blobs not larger than this are stored inline in metadata
+        #[arg(short)]
+        inline_threshold: Option<usize>,
+        /// Optional number of concurrent chunk file writers, defaults to available parallelism, at most one per share
+        #[arg(short = 'w')]
+        num_writers: Option<usize>,
+        /// Mark the blob as encrypted, in its metadata and in each chunk, so that ciphertext-only nodes accept it
+        #[arg(short)]
+        encrypted: bool,
+        /// Mark the blob as compressed, in its metadata and in each chunk
+        #[arg(short = 'z')]
+        compressed: bool,
     },
     /// Validate proof of inclusion for erasure-coded chunks
     Verify {
         /// Directory path to erasure-coded proof-carrying chunks
         blob_dir_path: PathBuf,
+        /// Move chunk files failing verification into `quarantine` directory, with a reason log
+        #[arg(short)]
+        quarantine: bool,
+        /// Optional append-only audit log file, hash-chained records of chunk operations are appended to
+        #[arg(short)]
+        audit_log_path: Option<PathBuf>,
+    },
+    /// Lists blobs, with available and valid erasure-coded chunks per chunkset, and whether each chunkset is repairable
+    Ls {
+        /// Directory path to erasure-coded chunks of a blob, or to a directory holding many of them
+        dir_path: PathBuf,
+    },
+    /// Lists the fewest chunks, as `<chunkset_id> <share_id> <node>` lines, to fetch from storage nodes for repairing a blob
+    Plan {
+        /// Directory path to erasure-coded chunks, only blob metadata file is required, locally valid chunks are not fetched
+        #[arg(short)]
+        chunk_dir_path: PathBuf,
+        /// Inventory file listing which node holds which chunk, as written by `inventory` command or as `<chunkset_id> <share_id> <node>` lines, earlier ones are preferred
+        #[arg(short)]
+        inventory: PathBuf,
+        /// Optional target file to write repair plan to, defaults to stdout
+        #[arg(short)]
+        opt_target_file: Option<PathBuf>,
+    },
+    /// Writes a compact inventory of locally valid chunks, held by given node, optionally merged with other nodes' inventories
+    Inventory {
+        /// Directory path to erasure-coded chunks
+        #[arg(short)]
+        chunk_dir_path: PathBuf,
+        /// Identifier of the node holding the chunks, e.g. its address
+        #[arg(short)]
+        node: String,
+        /// Optional comma separated inventory files of other nodes, to merge in, after the local one
+        #[arg(short, value_delimiter = ',')]
+        merge: Vec<PathBuf>,
+        /// Target file to write the inventory to
+        #[arg(short)]
+        target_file: PathBuf,
+    },
+    /// Lists BLAKE3 digest of every valid erasure-coded chunk, as `<chunkset_id> <share_id> <digest>` lines
+    Digests {
+        /// Directory path to erasure-coded chunks
+        #[arg(short)]
+        chunk_dir_path: PathBuf,
+        /// Optional target file to write chunk digests to, defaults to stdout
+        #[arg(short)]
+        opt_target_file: Option<PathBuf>,
     },
     /// Reconstructs original data blob using erasure-coded proof-carrying chunks
     Repair {
         /// Directory path to erasure-coded chunks
         #[arg(short)]
         chunk_dir_path: PathBuf,
-        /// Optional target directory to put repaired chunksets and blob
+        /// Optional target directory to put repaired chunksets and blob, defaults to `repaired-<blob ID prefix>`
         #[arg(short)]
         opt_target_dir: Option<PathBuf>,
+        /// Overwrite target directory, if it already exists
+        #[arg(short)]
+        force: bool,
+        /// Move chunk files failing verification into `quarantine` directory, with a reason log
+        #[arg(short)]
+        quarantine: bool,
+        /// Optional comma separated share weights, as `share_id=weight`, cheaper (lower weight) shares are consumed first
+        #[arg(short, value_delimiter = ',')]
+        weights: Vec<String>,
+        /// Optional number of verified chunks, which may wait for decoding, while more are read and verified, defaults to 16
+        #[arg(short = 'l')]
+        queue_len: Option<usize>,
+        /// Optional append-only audit log file, hash-chained records of chunk operations are appended to
+        #[arg(short)]
+        audit_log_path: Option<PathBuf>,
+    },
+    /// Receives framed erasure-coded chunks over TCP, persisting and forwarding ones which verify against blob metadata
+    Relay {
+        /// Directory path to erasure-coded chunks, holding blob metadata, where received chunks are persisted
+        #[arg(short)]
+        chunk_dir_path: PathBuf,
+        /// Address to listen on for chunk streams, as `host:port`
+        #[arg(short)]
+        listen_addr: String,
+        /// Optional comma separated addresses of downstream nodes to forward verified chunks to, as `host:port`
+        #[arg(short, value_delimiter = ',')]
+        downstreams: Vec<String>,
+        /// Optional append-only audit log file, hash-chained records of chunk operations are appended to
+        #[arg(short)]
+        audit_log_path: Option<PathBuf>,
+        /// Accept chunks of encrypted blobs only, rejecting those, which aren't marked as encrypted
+        #[arg(short = 'e')]
+        require_encrypted: bool,
+    },
+    /// Checks integrity of the hash chain of an audit log file, written by `verify`, `repair` or `relay`
+    Audit {
+        /// Path of the audit log file
+        audit_log_path: PathBuf,
+        /// List records of the audit log, as `<sequence> <timestamp_ms> <blob_id> <operation>` lines
+        #[arg(short)]
+        list: bool,
+    },
+    /// Registers blob metadata in the header registry, under a human-readable name
+    Tag {
+        /// Directory path to erasure-coded chunks, only blob metadata file is required
+        #[arg(short)]
+        chunk_dir_path: PathBuf,
+        /// Name of the blob, free of whitespaces, moved if it names another blob already
+        #[arg(short)]
+        name: String,
+        /// Optional header registry file, defaults to `$DECDS_REGISTRY`, or else `$HOME/.decds/registry`
+        #[arg(short)]
+        registry_path: Option<PathBuf>,
+    },
+    /// Looks blob metadata up in the header registry, by name, blob ID or blob digest
+    Resolve {
+        /// Name of the blob, or hex encoded blob ID or blob digest
+        key: String,
+        /// Optional header registry file, defaults to `$DECDS_REGISTRY`, or else `$HOME/.decds/registry`
+        #[arg(short)]
+        registry_path: Option<PathBuf>,
+        /// Optional target file to write blob metadata to, e.g. `metadata.commit` of a blob directory
+        #[arg(short)]
+        opt_target_file: Option<PathBuf>,
+    },
+    /// Emits canonical test vectors, for checking compatibility of alternative implementations
+    GenVectors {
+        /// Optional 32-bytes hex encoded seed, defaults to all zeros
+        #[arg(short)]
+        seed: Option<String>,
+        /// Optional byte length of the test vector blob, defaults to 1024
+        #[arg(short)]
+        blob_size: Option<usize>,
+        /// Optional target file to write test vectors to, defaults to stdout
+        #[arg(short)]
+        opt_target_file: Option<PathBuf>,
     },
 }
 
 fn main() {
     let cli = DecdsCLI::parse();
-    match &cli.command {
-        DecdsCommand::Break { blob_path, opt_target_dir } => handlers::handle_break_command(blob_path, opt_target_dir),
-        DecdsCommand::Verify { blob_dir_path } => handlers::handle_verify_command(blob_dir_path),
+    interrupt::install_interrupt_handler();
+
+    // Handlers return failures, instead of exiting, so that they are reported here, and only here.
+    let result = match &cli.command {
+        DecdsCommand::Break {
+            blob_path,
+            opt_target_dir,
+            force,
+            inline_threshold,
+            num_writers,
+            encrypted,
+            compressed,
+        } => handlers::handle_break_command(
+            blob_path,
+            opt_target_dir,
+            inline_threshold.unwrap_or(0),
+            *force,
+            *num_writers,
+            ContentFlags::new().encrypted(*encrypted).compressed(*compressed),
+        ),
+        DecdsCommand::Verify {
+            blob_dir_path,
+            quarantine,
+            audit_log_path,
+        } => handlers::handle_verify_command(blob_dir_path, *quarantine, audit_log_path),
+        DecdsCommand::Ls { dir_path } => handlers::handle_ls_command(dir_path),
+        DecdsCommand::Plan {
+            chunk_dir_path,
+            inventory,
+            opt_target_file,
+        } => handlers::handle_plan_command(chunk_dir_path, inventory, opt_target_file),
+        DecdsCommand::Inventory {
+            chunk_dir_path,
+            node,
+            merge,
+            target_file,
+        } => handlers::handle_inventory_command(chunk_dir_path, node, merge, target_file),
+        DecdsCommand::Digests {
+            chunk_dir_path,
+            opt_target_file,
+        } => handlers::handle_digests_command(chunk_dir_path, opt_target_file),
         DecdsCommand::Repair {
             chunk_dir_path,
             opt_target_dir,
-        } => handlers::handle_repair_command(chunk_dir_path, opt_target_dir),
+            force,
+            quarantine,
+            weights,
+            queue_len,
+            audit_log_path,
+        } => handlers::handle_repair_command(chunk_dir_path, opt_target_dir, *force, *quarantine, weights, *queue_len, audit_log_path),
+        DecdsCommand::Relay {
+            chunk_dir_path,
+            listen_addr,
+            downstreams,
+            audit_log_path,
+            require_encrypted,
+        } => handlers::handle_relay_command(chunk_dir_path, listen_addr, downstreams, audit_log_path, *require_encrypted),
+        DecdsCommand::Audit { audit_log_path, list } => handlers::handle_audit_command(audit_log_path, *list),
+        DecdsCommand::Tag {
+            chunk_dir_path,
+            name,
+            registry_path,
+        } => handlers::handle_tag_command(chunk_dir_path, name, registry_path),
+        DecdsCommand::Resolve {
+            key,
+            registry_path,
+            opt_target_file,
+        } => handlers::handle_resolve_command(key, registry_path, opt_target_file),
+        DecdsCommand::GenVectors {
+            seed,
+            blob_size,
+            opt_target_file,
+        } => handlers::handle_gen_vectors_command(seed, blob_size.unwrap_or(1024), opt_target_file),
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        exit(e.get_exit_code());
     }
 }
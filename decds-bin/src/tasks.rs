@@ -0,0 +1,67 @@
+use crate::errors::DecdsCLIError;
+use std::{
+    sync::{Arc, Mutex, MutexGuard},
+    thread::Scope,
+};
+
+/// Scope of tasks, each running on its own thread, spawned by a long operation of a command, see `run_scoped`. Tasks report
+/// failures by returning them, instead of terminating the process, and the first one is kept for the operation to return.
+pub struct TaskScope<'scope, 'env: 'scope> {
+    scope: &'scope Scope<'scope, 'env>,
+    first_error: Arc<Mutex<Option<DecdsCLIError>>>,
+}
+
+impl<'scope, 'env> TaskScope<'scope, 'env> {
+    /// Spawns a task on a new scoped thread. If it fails, its error is kept, unless another task failed before.
+    pub fn spawn<F>(&self, task: F)
+    where
+        F: FnOnce() -> Result<(), DecdsCLIError> + Send + 'scope,
+    {
+        let first_error = self.first_error.clone();
+
+        self.scope.spawn(move || {
+            if let Err(e) = task() {
+                let mut first_error = lock(&first_error);
+                if first_error.is_none() {
+                    *first_error = Some(e);
+                }
+            }
+        });
+    }
+
+    /// Returns `true` if any task of this scope failed so far, so that the others can stop early.
+    pub fn has_failed(&self) -> bool {
+        lock(&self.first_error).is_some()
+    }
+}
+
+/// Runs `operation`, which may spawn tasks on the given `TaskScope`, waiting for all of them to finish before returning.
+/// This is the single point, where failures of an operation and its tasks are collected.
+///
+/// # Returns
+///
+/// Returns a `Result` which is:
+/// - `Ok(T)` containing what `operation` returned, if neither it nor any of its tasks failed.
+/// - `Err(DecdsCLIError)` of `operation`, if it failed, or else the first error of a task.
+pub fn run_scoped<'env, T, F>(operation: F) -> Result<T, DecdsCLIError>
+where
+    F: for<'scope> FnOnce(&TaskScope<'scope, 'env>) -> Result<T, DecdsCLIError>,
+{
+    let first_error = Arc::new(Mutex::new(None));
+
+    let result = std::thread::scope(|scope| {
+        operation(&TaskScope {
+            scope,
+            first_error: first_error.clone(),
+        })
+    });
+
+    match (result, lock(&first_error).take()) {
+        (Err(e), _) | (Ok(_), Some(e)) => Err(e),
+        (Ok(value), None) => Ok(value),
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
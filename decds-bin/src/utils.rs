@@ -1,6 +1,9 @@
-use decds_lib::{BlobHeader, ProofCarryingChunk};
-use rand::Rng;
-use std::{path::PathBuf, process::exit, str::FromStr};
+use decds_lib::{AuditLog, AuditOperation, AuditRecord, BlobDirLayout, BlobHeader, DecdsError, HeaderRegistry, ProofCarryingChunk};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
 
 use crate::errors::DecdsCLIError;
 
@@ -17,80 +20,347 @@ pub fn format_bytes(bytes: usize) -> String {
     format!("{:.1}{}", size, suffixes[index])
 }
 
-pub fn read_blob_metadata(blob_metadata_path: &PathBuf) -> BlobHeader {
-    match std::fs::read(blob_metadata_path) {
-        Ok(bytes) => match BlobHeader::from_bytes(&bytes) {
-            Ok((blob_header, n)) => {
-                if n != bytes.len() {
-                    eprintln!(
-                        "Erasure-coded blob metadata file {:?} is {} bytes longer than it should be",
-                        blob_metadata_path,
-                        bytes.len() - n
-                    );
-                    exit(1);
-                }
+pub fn read_blob_metadata(blob_metadata_path: &Path) -> Result<BlobHeader, DecdsCLIError> {
+    let to_cli_error = |err: String| DecdsCLIError::FailedToReadFile(blob_metadata_path.to_path_buf(), err);
 
-                blob_header
-            }
-            Err(e) => {
-                eprintln!("Error: {}", e);
-                exit(1);
-            }
-        },
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            exit(1);
-        }
+    let bytes = std::fs::read(blob_metadata_path).map_err(|e| to_cli_error(e.to_string()))?;
+    let (blob_header, n) = BlobHeader::from_bytes(&bytes).map_err(|e| to_cli_error(e.to_string()))?;
+
+    if n != bytes.len() {
+        return Err(to_cli_error(format!(
+            "erasure-coded blob metadata file is {} bytes longer than it should be",
+            bytes.len() - n
+        )));
+    }
+
+    Ok(blob_header)
+}
+
+/// Returns the path of the header registry file, i.e. given one, or `$DECDS_REGISTRY`, or `$HOME/.decds/registry`.
+pub fn get_registry_path(opt_registry_path: &Option<PathBuf>) -> Result<PathBuf, DecdsCLIError> {
+    if let Some(registry_path) = opt_registry_path {
+        return Ok(registry_path.clone());
+    }
+    if let Some(registry_path) = std::env::var_os("DECDS_REGISTRY") {
+        return Ok(PathBuf::from(registry_path));
+    }
+
+    std::env::var_os("HOME")
+        .map(|home_dir| PathBuf::from(home_dir).join(".decds").join("registry"))
+        .ok_or(DecdsCLIError::UnknownRegistryPath)
+}
+
+/// Reads the header registry file, starting with an empty registry, if the file doesn't exist yet.
+pub fn read_registry(registry_path: &Path) -> Result<HeaderRegistry, DecdsCLIError> {
+    let to_cli_error = |err: String| DecdsCLIError::FailedToReadFile(registry_path.to_path_buf(), err);
+
+    let bytes = match std::fs::read(registry_path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HeaderRegistry::new()),
+        Err(e) => return Err(to_cli_error(e.to_string())),
+    };
+
+    match HeaderRegistry::from_bytes(&bytes) {
+        Ok((registry, n)) if n == bytes.len() => Ok(registry),
+        Ok((_, n)) => Err(to_cli_error(format!(
+            "header registry file is {} bytes longer than it should be",
+            bytes.len() - n
+        ))),
+        Err(e) => Err(to_cli_error(e.to_string())),
     }
 }
 
-pub fn read_proof_carrying_chunk(chunk_path: &PathBuf) -> Result<ProofCarryingChunk, DecdsCLIError> {
+/// Writes the header registry file, by replacing it with a fully written temporary file, so that an interrupted write
+/// doesn't leave a truncated registry behind.
+pub fn write_registry(registry_path: &Path, registry: &HeaderRegistry) -> Result<(), DecdsCLIError> {
+    registry
+        .to_bytes()
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| {
+            if let Some(parent_dir) = registry_path.parent().filter(|parent_dir| !parent_dir.as_os_str().is_empty()) {
+                std::fs::DirBuilder::new().recursive(true).create(parent_dir).map_err(|e| e.to_string())?;
+            }
+
+            let mut temp_registry_path = registry_path.as_os_str().to_owned();
+            temp_registry_path.push(".tmp");
+
+            std::fs::write(&temp_registry_path, bytes).map_err(|e| e.to_string())?;
+            std::fs::rename(&temp_registry_path, registry_path).map_err(|e| e.to_string())
+        })
+        .map_err(|e| DecdsCLIError::FailedToWriteFile(registry_path.to_path_buf(), e))
+}
+
+pub fn read_proof_carrying_chunk(chunk_path: &Path) -> Result<ProofCarryingChunk, DecdsCLIError> {
+    let to_cli_error = |err: String| DecdsCLIError::FailedToReadProofCarryingChunk(chunk_path.to_path_buf(), err);
+
     match std::fs::read(chunk_path) {
         Ok(bytes) => match ProofCarryingChunk::from_bytes(&bytes) {
             Ok((chunk, n)) => {
                 if n != bytes.len() {
-                    Err(DecdsCLIError::FailedToReadProofCarryingChunk(format!(
-                        "Erasure-coded chunk file {:?} is {} bytes longer than it should be",
-                        chunk_path,
-                        bytes.len() - n
-                    )))
+                    Err(to_cli_error(format!("file is {} bytes longer than it should be", bytes.len() - n)))
                 } else {
                     Ok(chunk)
                 }
             }
-            Err(e) => Err(DecdsCLIError::FailedToReadProofCarryingChunk(e.to_string())),
+            Err(e) => Err(to_cli_error(e.to_string())),
         },
-        Err(e) => Err(DecdsCLIError::FailedToReadProofCarryingChunk(e.to_string())),
+        Err(e) => Err(to_cli_error(e.to_string())),
     }
 }
 
-pub fn get_target_directory_path<R: Rng + ?Sized>(blob_path: &PathBuf, opt_target_dir: &Option<PathBuf>, rng: &mut R) -> PathBuf {
-    match opt_target_dir {
-        Some(path) => match path.try_exists() {
-            Ok(ok) => {
-                if ok {
-                    prepare_random_target_directory_name(unsafe { path.to_str().unwrap_unchecked() }, rng)
-                } else {
-                    path.clone()
-                }
+/// Outcome of verifying one erasure-coded chunk file of a blob directory.
+pub enum ChunkFileStatus {
+    Missing,
+    Valid(ProofCarryingChunk),
+    Invalid(String),
+}
+
+/// Reads and verifies the chunk file of a share in a chunkset against blob metadata.
+pub fn verify_chunk_file(layout: &BlobDirLayout, blob_metadata: &BlobHeader, chunkset_id: usize, share_id: usize) -> ChunkFileStatus {
+    let chunk_path = layout.get_chunk_path(chunkset_id, share_id);
+    if !chunk_path.try_exists().unwrap_or(false) {
+        return ChunkFileStatus::Missing;
+    }
+
+    match read_proof_carrying_chunk(&chunk_path) {
+        Ok(chunk) => {
+            if blob_metadata.validate_chunk(&chunk) {
+                ChunkFileStatus::Valid(chunk)
+            } else {
+                ChunkFileStatus::Invalid(format!(
+                    "{}, in blob {}",
+                    DecdsError::InvalidProofInChunk(chunk.get_chunkset_id(), chunk.get_local_chunk_id()),
+                    get_short_blob_id(blob_metadata)
+                ))
             }
-            Err(e) => {
-                eprintln!("Error: {}", e);
-                exit(1);
+        }
+        Err(e) => ChunkFileStatus::Invalid(e.to_string()),
+    }
+}
+
+/// Name of the file, `decds repair` writes the repaired blob into, inside its target directory.
+pub const REPAIRED_BLOB_FILE_NAME: &str = "repaired.data";
+
+/// Returns the name of the file, `decds repair` writes a repaired chunkset into, before merging it into the repaired blob.
+pub fn get_repaired_chunkset_file_name(chunkset_id: usize) -> String {
+    format!("chunkset.{}.data", chunkset_id)
+}
+
+/// Returns the path of the target directory, given with `-o`, or `default_dir_name` otherwise, which must not exist. With
+/// `force`, an existing target is removed, but only if it doesn't hold `input_path`, which the command reads from, and it
+/// looks like output of an earlier run, see `is_decds_output_dir`, so that a mistyped `-o` can't wipe unrelated data.
+pub fn get_target_directory_path(default_dir_name: &str, opt_target_dir: &Option<PathBuf>, input_path: &Path, force: bool) -> Result<PathBuf, DecdsCLIError> {
+    let target_dir_path = opt_target_dir.clone().unwrap_or_else(|| PathBuf::from(default_dir_name));
+    let to_cli_error = |e: std::io::Error| DecdsCLIError::FailedToWriteFile(target_dir_path.clone(), e.to_string());
+
+    if target_dir_path.try_exists().map_err(to_cli_error)? {
+        if !force {
+            return Err(DecdsCLIError::TargetAlreadyExists(target_dir_path));
+        }
+
+        let canonical_target_dir_path = target_dir_path.canonicalize().map_err(to_cli_error)?;
+        let canonical_input_path = input_path
+            .canonicalize()
+            .map_err(|e| DecdsCLIError::FailedToReadFile(input_path.to_path_buf(), e.to_string()))?;
+        if canonical_input_path.starts_with(&canonical_target_dir_path) {
+            return Err(DecdsCLIError::TargetHoldsInput(target_dir_path, input_path.to_path_buf()));
+        }
+
+        if !is_decds_output_dir(&target_dir_path) {
+            return Err(DecdsCLIError::TargetAlreadyExists(target_dir_path));
+        }
+
+        std::fs::remove_dir_all(&target_dir_path).map_err(to_cli_error)?;
+    }
+
+    Ok(target_dir_path)
+}
+
+/// Tells whether `dir_path` is a directory, which looks like output of `decds break`, i.e. holds blob metadata, or of
+/// `decds repair`, i.e. holds nothing but the repaired blob and repaired chunkset files.
+fn is_decds_output_dir(dir_path: &Path) -> bool {
+    let is_repaired_blob_file_name = |file_name: &str| {
+        file_name == REPAIRED_BLOB_FILE_NAME
+            || file_name
+                .strip_prefix("chunkset.")
+                .and_then(|file_name| file_name.strip_suffix(".data"))
+                .is_some_and(|chunkset_id| chunkset_id.parse::<usize>().is_ok())
+    };
+
+    if !dir_path.is_dir() {
+        return false;
+    }
+    if BlobDirLayout::new(dir_path).get_metadata_path().is_file() {
+        return true;
+    }
+
+    std::fs::read_dir(dir_path).is_ok_and(|mut entries| {
+        entries.all(|entry| {
+            entry.is_ok_and(|entry| {
+                entry.file_type().is_ok_and(|file_type| file_type.is_file()) && entry.file_name().to_str().is_some_and(is_repaired_blob_file_name)
+            })
+        })
+    })
+}
+
+/// Creates the directory at `dir_path`, along with its missing ancestors.
+pub fn create_dir_all(dir_path: &Path) -> Result<(), DecdsCLIError> {
+    std::fs::DirBuilder::new()
+        .recursive(true)
+        .create(dir_path)
+        .map_err(|e| DecdsCLIError::FailedToWriteFile(dir_path.to_path_buf(), e.to_string()))
+}
+
+/// Writes `contents` into the file at `file_path`, replacing it, if it exists.
+pub fn write_file(file_path: &Path, contents: impl AsRef<[u8]>) -> Result<(), DecdsCLIError> {
+    std::fs::write(file_path, contents).map_err(|e| DecdsCLIError::FailedToWriteFile(file_path.to_path_buf(), e.to_string()))
+}
+
+/// Fails, unless `dir_path` is an existing directory.
+pub fn ensure_is_dir(dir_path: &Path) -> Result<(), DecdsCLIError> {
+    if !dir_path.is_dir() {
+        return Err(DecdsCLIError::NotADirectory(dir_path.to_path_buf()));
+    }
+
+    Ok(())
+}
+
+/// Fails early, if the filesystem holding `target_dir_path` doesn't have at least `required_bytes` of free space, rather
+/// than dying mid-write. The check is skipped, with a warning, if available space can't be queried.
+pub fn ensure_enough_free_space(target_dir_path: &Path, required_bytes: usize) -> Result<(), DecdsCLIError> {
+    match get_available_space(target_dir_path) {
+        Ok(Some(available_bytes)) => {
+            let available_bytes = usize::try_from(available_bytes).unwrap_or(usize::MAX);
+
+            if available_bytes < required_bytes {
+                return Err(DecdsCLIError::NotEnoughFreeSpace(
+                    target_dir_path.to_path_buf(),
+                    required_bytes,
+                    available_bytes,
+                ));
             }
-        },
-        None => prepare_random_target_directory_name(unsafe { blob_path.file_name().unwrap_unchecked().to_str().unwrap_unchecked() }, rng),
+        }
+        Ok(None) => {}
+        Err(e) => eprintln!("Warning: failed to query free space for {:?}: {}", target_dir_path, e),
     }
+
+    Ok(())
 }
 
-fn prepare_random_target_directory_name<R: Rng + ?Sized>(prefix: &str, rng: &mut R) -> PathBuf {
-    let mut rand_suffix = [0u8; 4];
-    rng.fill_bytes(&mut rand_suffix);
+/// Returns free space, in bytes, available to unprivileged users on the filesystem holding `path`. As `path` may not exist
+/// yet, its nearest existing ancestor is queried.
+#[cfg(unix)]
+fn get_available_space(path: &Path) -> std::io::Result<Option<u64>> {
+    use std::{ffi::CString, os::unix::ffi::OsStrExt};
+
+    let existing_path = path
+        .ancestors()
+        .map(|ancestor| if ancestor.as_os_str().is_empty() { Path::new(".") } else { ancestor })
+        .find(|ancestor| ancestor.exists())
+        .unwrap_or(Path::new("."));
+
+    let c_path = CString::new(existing_path.as_os_str().as_bytes()).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    let mut stat = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+
+    if unsafe { libc::statvfs(c_path.as_ptr(), stat.as_mut_ptr()) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    let stat = unsafe { stat.assume_init() };
+
+    // Widths of these fields vary across platforms.
+    #[allow(clippy::unnecessary_cast)]
+    Ok(Some((stat.f_bavail as u64).saturating_mul(stat.f_frsize as u64)))
+}
+
+/// Free space can't be queried on this platform, so the check is skipped.
+#[cfg(not(unix))]
+fn get_available_space(_path: &Path) -> std::io::Result<Option<u64>> {
+    Ok(None)
+}
+
+pub fn get_short_blob_id(blob_metadata: &BlobHeader) -> String {
+    blob_metadata.get_blob_id().to_hex()[..16].to_string()
+}
+
+/// Moves a chunk file, which failed verification, into `quarantine/` directory of the blob, logging why in `quarantine/reasons.log`.
+pub fn quarantine_chunk_file(layout: &BlobDirLayout, chunkset_id: usize, share_id: usize, reason: &str) -> std::io::Result<PathBuf> {
+    std::fs::DirBuilder::new().recursive(true).create(layout.get_quarantine_dir_path())?;
+
+    let quarantined_chunk_path = layout.get_quarantined_chunk_path(chunkset_id, share_id);
+    std::fs::rename(layout.get_chunk_path(chunkset_id, share_id), &quarantined_chunk_path)?;
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+
+    let mut reasons_log = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(layout.get_quarantine_reasons_log_path())?;
+    writeln!(
+        reasons_log,
+        "{}\t{}/{}\t{}",
+        timestamp,
+        BlobDirLayout::get_chunkset_dir_name(chunkset_id),
+        BlobDirLayout::get_chunk_file_name(share_id),
+        reason
+    )?;
+
+    Ok(quarantined_chunk_path)
+}
+
+/// Append-only audit log file, along with the head of its hash chain, shared by all threads of a command. Recording is a
+/// no-op, if no audit log file is given.
+pub struct AuditLogFile {
+    opt_log: Option<(PathBuf, Mutex<(AuditLog, std::fs::File)>)>,
+}
+
+impl AuditLogFile {
+    /// Opens the audit log file, if one is given, creating it if it doesn't exist yet, after checking integrity of records
+    /// already in it, so that new records extend an intact hash chain.
+    pub fn open(opt_audit_log_path: &Option<PathBuf>) -> Result<Self, DecdsCLIError> {
+        let Some(audit_log_path) = opt_audit_log_path else {
+            return Ok(AuditLogFile { opt_log: None });
+        };
+
+        let (log, _) = read_audit_log(audit_log_path)?;
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(audit_log_path)
+            .map_err(|e| DecdsCLIError::FailedToWriteFile(audit_log_path.clone(), e.to_string()))?;
+
+        Ok(AuditLogFile {
+            opt_log: Some((audit_log_path.clone(), Mutex::new((log, file)))),
+        })
+    }
+
+    /// Appends a record of `operation` on chunks of the blob with `blob_id`, failing if it can't be persisted.
+    pub fn record(&self, blob_id: blake3::Hash, operation: AuditOperation) -> Result<(), DecdsCLIError> {
+        let Some((audit_log_path, log)) = &self.opt_log else {
+            return Ok(());
+        };
+
+        let mut log = log.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let (log, file) = &mut *log;
+
+        log.append(blob_id, operation)
+            .and_then(|record| record.to_bytes())
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| file.write_all(&bytes).and_then(|_| file.sync_data()).map_err(|e| e.to_string()))
+            .map_err(|e| DecdsCLIError::FailedToWriteFile(audit_log_path.clone(), e))
+    }
+}
 
-    let mut res = String::new();
-    res.push_str(prefix);
-    res.push('-');
-    res.push_str(&const_hex::encode(rand_suffix));
+/// Reads the audit log file, checking integrity of its hash chain, starting with an empty log, if the file doesn't exist yet.
+pub fn read_audit_log(audit_log_path: &Path) -> Result<(AuditLog, Vec<AuditRecord>), DecdsCLIError> {
+    let bytes = match std::fs::read(audit_log_path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(DecdsCLIError::FailedToReadFile(audit_log_path.to_path_buf(), e.to_string())),
+    };
 
-    unsafe { PathBuf::from_str(&res).unwrap_unchecked() }
+    AuditLog::from_bytes(&bytes).map_err(|e| DecdsCLIError::AuditLogNotIntact(audit_log_path.to_path_buf(), e.to_string()))
 }
@@ -0,0 +1,322 @@
+//! Mini storage cluster, built on `decds-lib` APIs only.
+//!
+//! `NUM_NODES` store nodes are spawned, each being a separate process of this very example, run with `node` as its only
+//! argument. A client then
+//!
+//! 1. puts a blob on them, placing share `share_id` of chunkset `chunkset_id` on node `place(chunkset_id, share_id)`, and
+//!    recording where each chunk went in a `ChunkInventory`,
+//! 2. kills `NUM_KILLED_NODES` of them,
+//! 3. repairs the blob, from verified chunks held by surviving nodes, re-disperses lost shares to replacement nodes,
+//! 4. and reads the blob back, fetching verified chunks from the healed cluster.
+//!
+//! Nodes speak a tiny request/response protocol over TCP, each message being a 4-bytes little-endian length, followed by
+//! that many bytes, of which the first one of a request is its opcode. Nodes validate chunks they are given against the
+//! blob header, before storing them, so a client can't fill them with garbage.
+//!
+//! ```bash
+//! cargo run --release --example mini_cluster
+//! ```
+
+use decds_lib::{Blob, BlobConfig, BlobHeader, ChunkInventory, DECDS_NUM_ERASURE_CODED_SHARES, DurabilitySimulation, ProofCarryingChunk, RepairingBlob};
+use rand::Rng;
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    process::{Child, Command, Stdio},
+};
+
+const NUM_NODES: usize = 8;
+const NUM_KILLED_NODES: usize = 2;
+
+/// Request carrying a serialized `BlobHeader`, which the node validates chunks against.
+const OP_PUT_HEADER: u8 = 0;
+/// Request carrying a serialized `ProofCarryingChunk` to store.
+const OP_PUT_CHUNK: u8 = 1;
+/// Request carrying 8-bytes little-endian chunkset ID and share ID of a chunk to serve, replied to with the serialized chunk,
+/// or nothing, if the node doesn't hold it.
+const OP_GET_CHUNK: u8 = 2;
+
+/// Places share `share_id` of chunkset `chunkset_id` on a node, rotating shares of consecutive chunksets, so that every node
+/// holds two shares of each chunkset, but not the same ones.
+fn place(chunkset_id: usize, share_id: usize) -> usize {
+    (chunkset_id + share_id) % NUM_NODES
+}
+
+fn main() {
+    match std::env::args().nth(1).as_deref() {
+        Some("node") => run_node(),
+        _ => run_client(),
+    }
+}
+
+/// A store node process, killed when dropped.
+struct Node {
+    addr: String,
+    process: Child,
+}
+
+impl Node {
+    /// Spawns a store node, waiting for it to report the address it listens on.
+    fn spawn() -> Node {
+        let exe = std::env::current_exe().expect("Must be able to find example executable");
+        let mut process = Command::new(exe)
+            .arg("node")
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("Must be able to spawn store node");
+
+        let mut addr = String::new();
+        BufReader::new(process.stdout.take().expect("Must be able to read store node output"))
+            .read_line(&mut addr)
+            .expect("Store node must report its address");
+
+        Node {
+            addr: addr.trim().to_string(),
+            process,
+        }
+    }
+
+    fn kill(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+    }
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        self.kill();
+    }
+}
+
+fn run_client() {
+    let mut rng = rand::rng();
+
+    let blob_data = (0..(BlobConfig::default().get_chunkset_byte_length() * 2 + (1 << 20)))
+        .map(|_| rng.random())
+        .collect::<Vec<u8>>();
+    let seed = rng.random::<[u8; 32]>();
+
+    let blob = Blob::new_with_seed(blob_data.clone(), &seed).expect("Must be able to prepare blob");
+    let header = blob.get_blob_header().clone();
+    let num_chunksets = header.get_num_chunksets();
+
+    let estimate = DurabilitySimulation::from_header(&header, vec![0.01; NUM_NODES])
+        .placement(Box::new(place))
+        .simulate(30, 1000, 42)
+        .expect("Must be able to simulate durability");
+    println!(
+        "Blob {} of {} chunksets, loss probability over 30 days, with 1% daily node failures: {:.4}",
+        header.get_blob_id(),
+        num_chunksets,
+        estimate.get_loss_probability(29)
+    );
+
+    // 1. Put the blob on the cluster.
+    let mut nodes = (0..NUM_NODES).map(|_| Node::spawn()).collect::<Vec<Node>>();
+    let mut connections = nodes.iter().map(|node| connect(&node.addr, &header)).collect::<Vec<TcpStream>>();
+    let mut inventory = ChunkInventory::new(header.get_blob_id(), num_chunksets);
+
+    for share_id in 0..DECDS_NUM_ERASURE_CODED_SHARES {
+        for chunk in blob.get_share(share_id).expect("Must be able to get share") {
+            let node_id = place(chunk.get_chunkset_id(), share_id);
+            put_chunk(&mut connections[node_id], &chunk);
+            inventory
+                .record(&nodes[node_id].addr, chunk.get_chunkset_id(), share_id)
+                .expect("Must be able to record chunk");
+        }
+    }
+    println!("Put {} chunks on {} nodes", num_chunksets * DECDS_NUM_ERASURE_CODED_SHARES, NUM_NODES);
+
+    // 2. Kill some nodes.
+    drop(connections);
+    nodes.iter_mut().take(NUM_KILLED_NODES).for_each(Node::kill);
+    println!("Killed {} nodes", NUM_KILLED_NODES);
+
+    // 3. Find dead nodes, repair the blob from survivors, and re-disperse lost shares to replacement nodes.
+    let (live_nodes, dead_nodes): (Vec<&Node>, Vec<&Node>) = nodes.iter().partition(|node| TcpStream::connect(&node.addr).is_ok());
+    let live_addrs = live_nodes.iter().map(|node| node.addr.as_str()).collect::<Vec<&str>>();
+    let dead_addrs = dead_nodes.iter().map(|node| node.addr.as_str()).collect::<Vec<&str>>();
+
+    let repaired_data = repair_from_surviving_chunks(&header, &inventory, &live_addrs);
+    assert_eq!(blake3::hash(&repaired_data), header.get_blob_digest());
+    println!("Repaired blob from chunks held by {} surviving nodes", live_addrs.len());
+
+    // Same data and seed give the very same chunks, so lost ones are regenerated, as committed to by the header.
+    let regenerated = Blob::new_with_seed(repaired_data, &seed).expect("Must be able to prepare blob");
+    assert_eq!(regenerated.get_blob_header().get_root_commitment(), header.get_root_commitment());
+
+    let replacements = dead_addrs.iter().map(|_| Node::spawn()).collect::<Vec<Node>>();
+    let mut healed_inventory = ChunkInventory::new(header.get_blob_id(), num_chunksets);
+
+    for (chunkset_id, share_id, addr) in inventory.entries() {
+        let dead_node_id = dead_addrs.iter().position(|dead_addr| *dead_addr == addr);
+        let addr = match dead_node_id {
+            Some(dead_node_id) => {
+                let replacement = &replacements[dead_node_id];
+                let chunk = regenerated.get_chunk(chunkset_id, share_id).expect("Must be able to get chunk");
+                put_chunk(&mut connect(&replacement.addr, &header), &chunk);
+                replacement.addr.as_str()
+            }
+            None => addr,
+        };
+
+        healed_inventory.record(addr, chunkset_id, share_id).expect("Must be able to record chunk");
+    }
+    println!("Re-dispersed lost shares to {} replacement nodes", replacements.len());
+
+    // 4. Read the blob back, from verified chunks of the healed cluster.
+    let mut repairer = RepairingBlob::new(header.clone());
+    let mut connections = HashMap::<String, TcpStream>::new();
+
+    for (chunkset_id, share_id, addr) in healed_inventory.entries() {
+        if repairer.is_chunkset_ready_to_repair(chunkset_id).unwrap_or(true) {
+            continue;
+        }
+
+        let stream = connections.entry(addr.to_string()).or_insert_with(|| connect(addr, &header));
+        let mut request = vec![OP_GET_CHUNK];
+        request.extend_from_slice(&(chunkset_id as u64).to_le_bytes());
+        request.extend_from_slice(&(share_id as u64).to_le_bytes());
+
+        let response = request_response(stream, &request);
+        if let Ok((chunk, _)) = ProofCarryingChunk::from_bytes(&response) {
+            let _ = repairer.add_chunk_from_source(&chunk, addr);
+        }
+    }
+
+    let progress = repairer.get_progress();
+    assert!(progress.is_ready());
+    println!(
+        "Fetched {} useful chunks for reading back {} chunksets",
+        progress.get_num_useful_chunks(),
+        progress.get_num_chunksets()
+    );
+
+    let read_back_data = (0..num_chunksets)
+        .flat_map(|chunkset_id| repairer.get_repaired_chunkset(chunkset_id).expect("Must be able to repair chunkset"))
+        .collect::<Vec<u8>>();
+    assert_eq!(read_back_data, blob_data);
+    println!("Read back blob matches what was put");
+}
+
+/// Repairs the blob from verified chunks held by live nodes, asking for chunks of a chunkset only until it's ready to
+/// repair.
+fn repair_from_surviving_chunks(header: &BlobHeader, inventory: &ChunkInventory, live_addrs: &[&str]) -> Vec<u8> {
+    let mut repairer = RepairingBlob::new(header.clone());
+    let mut connections = live_addrs.iter().map(|addr| connect(addr, header)).collect::<Vec<TcpStream>>();
+
+    for (chunkset_id, share_id, addr) in inventory.entries() {
+        let Some(node_id) = live_addrs.iter().position(|live_addr| *live_addr == addr) else {
+            continue;
+        };
+        if repairer.is_chunkset_ready_to_repair(chunkset_id).unwrap_or(true) {
+            continue;
+        }
+
+        let mut request = vec![OP_GET_CHUNK];
+        request.extend_from_slice(&(chunkset_id as u64).to_le_bytes());
+        request.extend_from_slice(&(share_id as u64).to_le_bytes());
+
+        let response = request_response(&mut connections[node_id], &request);
+        if let Ok((chunk, _)) = ProofCarryingChunk::from_bytes(&response) {
+            let _ = repairer.add_chunk(&chunk);
+        }
+    }
+
+    (0..header.get_num_chunksets())
+        .flat_map(|chunkset_id| repairer.get_repaired_chunkset(chunkset_id).expect("Must be able to repair chunkset"))
+        .collect::<Vec<u8>>()
+}
+
+/// Connects to a store node, handing it the blob header.
+fn connect(addr: &str, header: &BlobHeader) -> TcpStream {
+    let mut stream = TcpStream::connect(addr).expect("Must be able to connect to store node");
+
+    let mut request = vec![OP_PUT_HEADER];
+    request.extend_from_slice(&header.to_bytes().expect("Must be able to serialize header"));
+    assert_eq!(request_response(&mut stream, &request), [1], "Store node must accept header");
+
+    stream
+}
+
+fn put_chunk(stream: &mut TcpStream, chunk: &ProofCarryingChunk) {
+    let mut request = vec![OP_PUT_CHUNK];
+    request.extend_from_slice(&chunk.to_bytes().expect("Must be able to serialize chunk"));
+    assert_eq!(request_response(stream, &request), [1], "Store node must accept chunk");
+}
+
+fn request_response(stream: &mut TcpStream, request: &[u8]) -> Vec<u8> {
+    write_message(stream, request).expect("Must be able to send request");
+    read_message(stream)
+        .expect("Must be able to receive response")
+        .expect("Store node must respond")
+}
+
+fn run_node() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Must be able to listen");
+    println!("{}", listener.local_addr().expect("Must be able to get listen address"));
+    std::io::stdout().flush().expect("Must be able to report listen address");
+
+    let mut header = None;
+    let mut chunks = HashMap::<(usize, usize), ProofCarryingChunk>::new();
+
+    for stream in listener.incoming() {
+        let Ok(mut stream) = stream else {
+            continue;
+        };
+
+        while let Ok(Some(request)) = read_message(&mut stream) {
+            let response = handle_request(&request, &mut header, &mut chunks);
+            if write_message(&mut stream, &response).is_err() {
+                break;
+            }
+        }
+    }
+}
+
+fn handle_request(request: &[u8], header: &mut Option<BlobHeader>, chunks: &mut HashMap<(usize, usize), ProofCarryingChunk>) -> Vec<u8> {
+    let read_id = |at: usize| {
+        request
+            .get(at..at + 8)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap_or_default()) as usize)
+    };
+
+    match (request.first().copied(), header.as_ref()) {
+        (Some(OP_PUT_HEADER), _) => {
+            *header = BlobHeader::from_bytes(&request[1..]).ok().map(|(header, _)| header);
+            vec![header.is_some() as u8]
+        }
+        (Some(OP_PUT_CHUNK), Some(header)) => match ProofCarryingChunk::from_bytes(&request[1..]) {
+            Ok((chunk, _)) if header.validate_chunk(&chunk) => {
+                chunks.insert((chunk.get_chunkset_id(), chunk.get_local_chunk_id()), chunk);
+                vec![1]
+            }
+            _ => vec![0],
+        },
+        (Some(OP_GET_CHUNK), Some(_)) => match (read_id(1), read_id(9)) {
+            (Some(chunkset_id), Some(share_id)) => chunks.get(&(chunkset_id, share_id)).and_then(|chunk| chunk.to_bytes().ok()).unwrap_or_default(),
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    }
+}
+
+fn write_message(stream: &mut TcpStream, message: &[u8]) -> std::io::Result<()> {
+    stream.write_all(&(message.len() as u32).to_le_bytes())?;
+    stream.write_all(message)
+}
+
+/// Reads a message, returning `None` once the peer closes the connection.
+fn read_message(stream: &mut TcpStream) -> std::io::Result<Option<Vec<u8>>> {
+    let mut len_bytes = [0u8; 4];
+    match stream.read_exact(&mut len_bytes) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+
+    let mut message = vec![0u8; u32::from_le_bytes(len_bytes) as usize];
+    stream.read_exact(&mut message)?;
+    Ok(Some(message))
+}
@@ -0,0 +1,217 @@
+use crate::{blob::BlobHeader, errors::DecdsError};
+
+/// Compact record binding a blob to an external anchor, such as a blockchain or a transparency log. It commits to the
+/// blob ID, the Merkle root commitment over all chunksets, the blob size, the BLAKE3 digest of the blob and its records
+/// digest, see `BlobHeader::get_records_digest`, as record offsets aren't covered by any of the others.
+///
+/// It is serialized as a fixed-width record of `BlobAnchor::BYTE_LENGTH` bytes, laid out as
+/// `blob_id || root_commitment || blob_size || blob_digest || records_digest`, where `blob_size` is encoded as 8-bytes
+/// little-endian unsigned integer, so that it is cheap to store and simple to parse on-chain.
+///
+/// ```rust
+/// use decds_lib::{Blob, BlobAnchor, BlobHeader};
+///
+/// let blob = Blob::new(b"hello, world".to_vec()).expect("Failed to create blob");
+/// let anchor_bytes = BlobAnchor::new(blob.get_blob_header()).to_bytes();
+///
+/// // ... publish `anchor_bytes`, later fetch them back, along with a header from an untrusted source ...
+/// let header_bytes = blob.get_blob_header().to_bytes().expect("Failed to serialize blob header");
+///
+/// let anchor = BlobAnchor::from_bytes(&anchor_bytes).expect("Failed to parse blob anchor");
+/// let (header, _) = BlobHeader::from_bytes(&header_bytes).expect("Failed to deserialize blob header");
+/// assert!(anchor.verify_header(&header).is_ok());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlobAnchor {
+    blob_id: blake3::Hash,
+    root_commitment: blake3::Hash,
+    blob_size: u64,
+    blob_digest: blake3::Hash,
+    records_digest: blake3::Hash,
+}
+
+impl BlobAnchor {
+    /// Byte length of a serialized `BlobAnchor`.
+    pub const BYTE_LENGTH: usize = 4 * blake3::OUT_LEN + std::mem::size_of::<u64>();
+
+    /// Creates the anchor record of the blob described by `header`.
+    pub fn new(header: &BlobHeader) -> Self {
+        BlobAnchor {
+            blob_id: header.get_blob_id(),
+            root_commitment: header.get_root_commitment(),
+            blob_size: header.get_blob_size() as u64,
+            blob_digest: header.get_blob_digest(),
+            records_digest: header.get_records_digest(),
+        }
+    }
+
+    /// Returns the anchored blob ID.
+    pub fn get_blob_id(&self) -> blake3::Hash {
+        self.blob_id
+    }
+
+    /// Returns the anchored Merkle root commitment of the blob.
+    pub fn get_root_commitment(&self) -> blake3::Hash {
+        self.root_commitment
+    }
+
+    /// Returns the anchored size of the blob, in bytes.
+    pub fn get_blob_size(&self) -> u64 {
+        self.blob_size
+    }
+
+    /// Returns the anchored BLAKE3 digest of the blob.
+    pub fn get_blob_digest(&self) -> blake3::Hash {
+        self.blob_digest
+    }
+
+    /// Returns the anchored records digest of the blob, committing to its record offsets.
+    pub fn get_records_digest(&self) -> blake3::Hash {
+        self.records_digest
+    }
+
+    /// Serializes the anchor into its fixed-width form, of `BlobAnchor::BYTE_LENGTH` bytes.
+    pub fn to_bytes(&self) -> [u8; Self::BYTE_LENGTH] {
+        let mut bytes = [0u8; Self::BYTE_LENGTH];
+
+        bytes[..32].copy_from_slice(self.blob_id.as_bytes());
+        bytes[32..64].copy_from_slice(self.root_commitment.as_bytes());
+        bytes[64..72].copy_from_slice(&self.blob_size.to_le_bytes());
+        bytes[72..104].copy_from_slice(self.blob_digest.as_bytes());
+        bytes[104..].copy_from_slice(self.records_digest.as_bytes());
+
+        bytes
+    }
+
+    /// Parses an anchor from its fixed-width form, as returned by `Self::to_bytes`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Exactly `BlobAnchor::BYTE_LENGTH` bytes of a serialized anchor.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(BlobAnchor)` if successful.
+    /// - `Err(DecdsError::InvalidBlobAnchor)` if `bytes` is of unexpected length, or if its blob ID isn't the one derived
+    ///   from its blob size and digest.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecdsError> {
+        if bytes.len() != Self::BYTE_LENGTH {
+            return Err(DecdsError::InvalidBlobAnchor(format!(
+                "expected {} bytes, got {}",
+                Self::BYTE_LENGTH,
+                bytes.len()
+            )));
+        }
+
+        let hash_at = |offset: usize| unsafe { blake3::Hash::from_bytes(bytes[offset..offset + 32].try_into().unwrap_unchecked()) };
+        let anchor = BlobAnchor {
+            blob_id: hash_at(0),
+            root_commitment: hash_at(32),
+            blob_size: u64::from_le_bytes(unsafe { bytes[64..72].try_into().unwrap_unchecked() }),
+            blob_digest: hash_at(72),
+            records_digest: hash_at(104),
+        };
+
+        if anchor.blob_id != BlobHeader::compute_blob_id(anchor.blob_size, anchor.blob_digest) {
+            return Err(DecdsError::InvalidBlobAnchor("blob id doesn't match blob size and digest".to_string()));
+        }
+
+        Ok(anchor)
+    }
+
+    /// Checks that a blob header, usually fetched from an untrusted source, is the one this anchor was made of. The
+    /// header's root commitment is recomputed from its chunkset commitments, so that all chunks validated against a
+    /// matching header are bound to the anchor, and so are its record offsets.
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - The blob header to check.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(())` if the header matches the anchor.
+    /// - `Err(DecdsError::BlobAnchorMismatch)` naming the first field, which differs. A header whose chunkset commitments
+    ///   don't add up to its root commitment is reported as a `root_commitment` mismatch.
+    pub fn verify_header(&self, header: &BlobHeader) -> Result<(), DecdsError> {
+        if header.get_blob_id() != self.blob_id {
+            return Err(DecdsError::BlobAnchorMismatch("blob_id"));
+        }
+        if header.get_blob_size() as u64 != self.blob_size {
+            return Err(DecdsError::BlobAnchorMismatch("blob_size"));
+        }
+        if header.get_blob_digest() != self.blob_digest {
+            return Err(DecdsError::BlobAnchorMismatch("blob_digest"));
+        }
+        if header.get_root_commitment() != self.root_commitment || !header.verify_root_commitment() {
+            return Err(DecdsError::BlobAnchorMismatch("root_commitment"));
+        }
+        if header.get_records_digest() != self.records_digest {
+            return Err(DecdsError::BlobAnchorMismatch("records_digest"));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "coding"))]
+mod tests {
+    use crate::{BlobAnchor, DecdsBuilder, blob::Blob, errors::DecdsError};
+
+    #[test]
+    fn test_blob_anchor() {
+        let blob = Blob::new(vec![0xcd; 1024]).expect("Failed to create blob");
+        let header = blob.get_blob_header();
+
+        let anchor = BlobAnchor::new(header);
+        assert_eq!(anchor.get_blob_id(), header.get_blob_id());
+        assert_eq!(anchor.get_blob_size(), 1024);
+
+        let anchor_bytes = anchor.to_bytes();
+        assert_eq!(BlobAnchor::from_bytes(&anchor_bytes), Ok(anchor));
+        assert!(anchor.verify_header(header).is_ok());
+
+        assert!(matches!(BlobAnchor::from_bytes(&anchor_bytes[1..]), Err(DecdsError::InvalidBlobAnchor(_))));
+
+        let mut tampered_anchor_bytes = anchor_bytes;
+        tampered_anchor_bytes[64] ^= 1;
+        assert!(matches!(BlobAnchor::from_bytes(&tampered_anchor_bytes), Err(DecdsError::InvalidBlobAnchor(_))));
+
+        // Root commitment isn't bound by the blob ID, so a tampered one parses, but doesn't match the header
+        let mut tampered_anchor_bytes = anchor_bytes;
+        tampered_anchor_bytes[32] ^= 1;
+        let tampered_anchor = BlobAnchor::from_bytes(&tampered_anchor_bytes).unwrap();
+        assert_eq!(tampered_anchor.verify_header(header), Err(DecdsError::BlobAnchorMismatch("root_commitment")));
+
+        let other_blob = Blob::new(vec![0xcd; 1025]).expect("Failed to create blob");
+        assert_eq!(
+            anchor.verify_header(other_blob.get_blob_header()),
+            Err(DecdsError::BlobAnchorMismatch("blob_id"))
+        );
+
+        // Same content, encoded differently, has the same blob ID, but a different root commitment
+        let reencoded_blob = DecdsBuilder::new().seed([7u8; 32]).build(vec![0xcd; 1024]).expect("Failed to create blob");
+        assert_eq!(
+            anchor.verify_header(reencoded_blob.get_blob_header()),
+            Err(DecdsError::BlobAnchorMismatch("root_commitment"))
+        );
+
+        // Record offsets are neither in the blob ID nor in the root commitment, but they are anchored
+        let blob = DecdsBuilder::new().seed([7u8; 32]).build(vec![0xcd; 1024]).expect("Failed to create blob");
+        let anchor = BlobAnchor::new(blob.get_blob_header());
+        let blob_with_records = DecdsBuilder::new()
+            .seed([7u8; 32])
+            .record_offsets(&[0, 100])
+            .build(vec![0xcd; 1024])
+            .expect("Failed to create blob");
+        assert_eq!(
+            blob_with_records.get_blob_header().get_root_commitment(),
+            blob.get_blob_header().get_root_commitment()
+        );
+        assert_eq!(
+            anchor.verify_header(blob_with_records.get_blob_header()),
+            Err(DecdsError::BlobAnchorMismatch("records_digest"))
+        );
+    }
+}
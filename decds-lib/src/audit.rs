@@ -0,0 +1,284 @@
+use crate::{consts::DECDS_BINCODE_CONFIG, errors::DecdsError};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// An operation on erasure-coded chunks of a blob, as recorded in an audit log.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+pub enum AuditOperation {
+    /// A chunk was stored.
+    ChunkPut { chunkset_id: usize, share_id: usize },
+    /// A chunk was read, e.g. for serving or repairing.
+    ChunkGet { chunkset_id: usize, share_id: usize },
+    /// A chunk was deleted, or moved out of the blob directory, e.g. quarantined.
+    ChunkDelete { chunkset_id: usize, share_id: usize },
+    /// A chunk was checked against the blob header, and either passed or failed verification.
+    ChunkVerified { chunkset_id: usize, share_id: usize, is_valid: bool },
+    /// A chunkset was repaired, i.e. its original data was reconstructed.
+    ChunksetRepaired { chunkset_id: usize },
+}
+
+/// A single record of an audit log. Each record commits to the digest of the one before it, so records can't be altered,
+/// removed or reordered without breaking the hash chain, which `AuditLog::from_bytes` checks.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct AuditRecord {
+    sequence: u64,
+    timestamp_ms: u64,
+    blob_id: [u8; blake3::OUT_LEN],
+    operation: AuditOperation,
+    prev_digest: [u8; blake3::OUT_LEN],
+}
+
+impl AuditRecord {
+    /// BLAKE3 key derivation context, used for hashing audit records.
+    const AUDIT_RECORD_KDF_CONTEXT: &str = "decds 2025-07-01 audit record";
+
+    /// Returns the position of this record in its audit log, starting from `0`.
+    pub fn get_sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Returns when the operation was recorded, as milliseconds since the Unix epoch.
+    pub fn get_timestamp_ms(&self) -> u64 {
+        self.timestamp_ms
+    }
+
+    /// Returns the ID of the blob, whose chunks the operation touched.
+    pub fn get_blob_id(&self) -> blake3::Hash {
+        blake3::Hash::from_bytes(self.blob_id)
+    }
+
+    /// Returns the recorded operation.
+    pub fn get_operation(&self) -> AuditOperation {
+        self.operation
+    }
+
+    /// Returns digest of the previous record, or zero hash for the first record of an audit log.
+    pub fn get_prev_digest(&self) -> blake3::Hash {
+        blake3::Hash::from_bytes(self.prev_digest)
+    }
+
+    /// Returns digest of this record, i.e. BLAKE3 hash of its serialized form, in key derivation mode with
+    /// `AUDIT_RECORD_KDF_CONTEXT`, which the next record commits to.
+    pub fn get_digest(&self) -> Result<blake3::Hash, DecdsError> {
+        Ok(blake3::Hasher::new_derive_key(Self::AUDIT_RECORD_KDF_CONTEXT)
+            .update(&self.to_bytes()?)
+            .finalize())
+    }
+
+    /// Serializes the record into a vector of bytes using `bincode`. Serialized records are self-delimiting, so an audit
+    /// log is simply all of its records, appended one after another.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Vec<u8>)` containing the serialized bytes if successful.
+    /// - `Err(DecdsError::AuditRecordSerializationFailed)` if serialization fails, containing the error message.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, DecdsError> {
+        bincode::serde::encode_to_vec(self, DECDS_BINCODE_CONFIG).map_err(|err| DecdsError::AuditRecordSerializationFailed(err.to_string()))
+    }
+
+    /// Deserializes a record from a byte slice using `bincode`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The byte slice from which to deserialize the record.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok((Self, usize))` containing the deserialized record and the number of bytes read if successful.
+    /// - `Err(DecdsError::AuditRecordDeserializationFailed)` if `bincode` deserialization fails, containing the error message.
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Self, usize), DecdsError> {
+        bincode::serde::decode_from_slice::<AuditRecord, bincode::config::Configuration>(bytes, DECDS_BINCODE_CONFIG)
+            .map_err(|err| DecdsError::AuditRecordDeserializationFailed(err.to_string()))
+    }
+}
+
+/// Append-only, hash-chained audit log of operations on erasure-coded chunks, e.g. for stores and daemons backing
+/// compliance-sensitive archives. The log itself only keeps the head of the hash chain, handing out records, which the
+/// caller appends wherever it persists the log, e.g. to a file opened in append mode.
+///
+/// Publishing, or otherwise anchoring, the head digest from time to time lets one detect truncation of the log, which the
+/// hash chain alone can't reveal.
+///
+/// ```rust
+/// use decds_lib::{AuditLog, AuditOperation};
+///
+/// let blob_id = blake3::hash(b"blob");
+/// let mut log = AuditLog::new();
+/// let mut persisted = Vec::new();
+///
+/// let record = log.append(blob_id, AuditOperation::ChunkPut { chunkset_id: 0, share_id: 3 }).expect("Failed to append record");
+/// persisted.extend(record.to_bytes().expect("Failed to serialize record"));
+///
+/// let (reopened, records) = AuditLog::from_bytes(&persisted).expect("Audit log must be intact");
+/// assert_eq!(records, vec![record]);
+/// assert_eq!(reopened.get_head(), log.get_head());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuditLog {
+    num_records: u64,
+    head: blake3::Hash,
+}
+
+impl Default for AuditLog {
+    fn default() -> Self {
+        AuditLog {
+            num_records: 0,
+            head: blake3::Hash::from_bytes([0u8; blake3::OUT_LEN]),
+        }
+    }
+}
+
+impl AuditLog {
+    /// Creates an empty audit log, whose head is the zero hash.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of records in the audit log.
+    pub fn get_num_records(&self) -> u64 {
+        self.num_records
+    }
+
+    /// Returns digest of the last record, or zero hash if the audit log is empty.
+    pub fn get_head(&self) -> blake3::Hash {
+        self.head
+    }
+
+    /// Records an operation, timestamped with the current system time, extending the hash chain.
+    ///
+    /// # Arguments
+    ///
+    /// * `blob_id` - The ID of the blob, whose chunks the operation touched.
+    /// * `operation` - The operation to record.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(AuditRecord)` containing the new record, to be appended to the persisted log, if successful.
+    /// - `Err(DecdsError::AuditRecordSerializationFailed)` if the record can't be serialized for hashing.
+    pub fn append(&mut self, blob_id: blake3::Hash, operation: AuditOperation) -> Result<AuditRecord, DecdsError> {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or_default();
+
+        self.append_at(timestamp_ms, blob_id, operation)
+    }
+
+    /// Same as `Self::append`, but with an explicitly given timestamp, as milliseconds since the Unix epoch.
+    pub fn append_at(&mut self, timestamp_ms: u64, blob_id: blake3::Hash, operation: AuditOperation) -> Result<AuditRecord, DecdsError> {
+        let record = AuditRecord {
+            sequence: self.num_records,
+            timestamp_ms,
+            blob_id: *blob_id.as_bytes(),
+            operation,
+            prev_digest: *self.head.as_bytes(),
+        };
+
+        self.head = record.get_digest()?;
+        self.num_records += 1;
+
+        Ok(record)
+    }
+
+    /// Deserializes all records of a persisted audit log, checking integrity of the hash chain, i.e. that records are
+    /// numbered consecutively from `0` and each one commits to the digest of the one before it. Returns the audit log,
+    /// positioned after its last record, so that more records can be appended.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - All records of the audit log, as serialized by `AuditRecord::to_bytes`, appended one after another.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok((Self, Vec<AuditRecord>))` containing the audit log and its records, in order, if the log is intact.
+    /// - `Err(DecdsError::AuditRecordDeserializationFailed)` if a record, e.g. a partially written last one, can't be deserialized.
+    /// - `Err(DecdsError::AuditLogChainBroken)` if a record is out of sequence or doesn't commit to its previous record.
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<(Self, Vec<AuditRecord>), DecdsError> {
+        let mut log = AuditLog::new();
+        let mut records = Vec::new();
+
+        while !bytes.is_empty() {
+            let (record, n) = AuditRecord::from_bytes(bytes)?;
+            if record.sequence != log.num_records || record.get_prev_digest() != log.head {
+                return Err(DecdsError::AuditLogChainBroken(log.num_records));
+            }
+
+            log.head = record.get_digest()?;
+            log.num_records += 1;
+
+            records.push(record);
+            bytes = &bytes[n..];
+        }
+
+        Ok((log, records))
+    }
+}
+
+#[cfg(all(test, feature = "coding"))]
+mod tests {
+    use crate::{
+        audit::{AuditLog, AuditOperation, AuditRecord},
+        errors::DecdsError,
+    };
+
+    #[test]
+    fn test_audit_log_hash_chain() {
+        let blob_id = blake3::hash(b"blob");
+        let operations = [
+            AuditOperation::ChunkPut { chunkset_id: 0, share_id: 1 },
+            AuditOperation::ChunkVerified {
+                chunkset_id: 0,
+                share_id: 1,
+                is_valid: true,
+            },
+            AuditOperation::ChunkGet { chunkset_id: 0, share_id: 1 },
+            AuditOperation::ChunksetRepaired { chunkset_id: 0 },
+            AuditOperation::ChunkDelete { chunkset_id: 0, share_id: 1 },
+        ];
+
+        let mut log = AuditLog::new();
+        let records = operations
+            .iter()
+            .enumerate()
+            .map(|(i, operation)| log.append_at(1_000 + i as u64, blob_id, *operation).unwrap())
+            .collect::<Vec<AuditRecord>>();
+        let serialized = records.iter().map(|record| record.to_bytes().unwrap()).collect::<Vec<Vec<u8>>>();
+        let persisted = serialized.concat();
+
+        assert_eq!(log.get_num_records(), operations.len() as u64);
+        assert_eq!(log.get_head(), records.last().unwrap().get_digest().unwrap());
+        assert_eq!(records[0].get_prev_digest(), AuditLog::new().get_head());
+        assert_eq!(records[3].get_operation(), operations[3]);
+        assert_eq!(records[3].get_blob_id(), blob_id);
+        assert_eq!(records[3].get_timestamp_ms(), 1_003);
+
+        // Reopening the log resumes its hash chain
+        let (mut reopened, read_records) = AuditLog::from_bytes(&persisted).expect("Audit log must be intact");
+        assert_eq!(read_records, records);
+        assert_eq!(reopened, log);
+        assert_eq!(reopened.append_at(2_000, blob_id, operations[0]), log.append_at(2_000, blob_id, operations[0]));
+        assert_eq!(AuditLog::from_bytes(&[]), Ok((AuditLog::new(), Vec::new())));
+
+        // Removing, reordering or altering a record breaks the chain
+        let removed = [serialized[0].as_slice(), &serialized[2..].concat()].concat();
+        assert_eq!(AuditLog::from_bytes(&removed), Err(DecdsError::AuditLogChainBroken(1)));
+
+        let reordered = [serialized[1].as_slice(), &serialized[0], &serialized[2..].concat()].concat();
+        assert_eq!(AuditLog::from_bytes(&reordered), Err(DecdsError::AuditLogChainBroken(0)));
+
+        let mut altered_record = records[2].clone();
+        altered_record.operation = AuditOperation::ChunkGet { chunkset_id: 0, share_id: 2 };
+        let altered = [serialized[..2].concat(), altered_record.to_bytes().unwrap(), serialized[3..].concat()].concat();
+        assert_eq!(AuditLog::from_bytes(&altered), Err(DecdsError::AuditLogChainBroken(3)));
+
+        // A partially written last record isn't mistaken for an intact log
+        assert!(matches!(
+            AuditLog::from_bytes(&persisted[..persisted.len() - 1]),
+            Err(DecdsError::AuditRecordDeserializationFailed(_))
+        ));
+    }
+}
@@ -0,0 +1,189 @@
+use crate::{chunkset::ChunkSet, consts::DECDS_NUM_ERASURE_CODED_SHARES, errors::DecdsError};
+
+/// Bitset of available share IDs of a chunkset, where bit `share_id` is set if the share is available.
+pub type ShareBitmap = u16;
+
+const _: () = assert!(DECDS_NUM_ERASURE_CODED_SHARES <= ShareBitmap::BITS as usize);
+
+/// Availability of erasure-coded shares of every chunkset of a blob, kept as one fixed-size `ShareBitmap` per chunkset, so
+/// that counting available shares is a popcount and uniting availability reported by many nodes is a bitwise OR, even for
+/// blobs of millions of chunksets.
+///
+/// A chunkset is deemed repairable when at least `ChunkSet::NUM_ORIGINAL_CHUNKS` of its shares are available. Coding
+/// vectors of distinct shares are linearly independent with overwhelming probability, but only `RepairingBlob` can tell
+/// for sure, once chunks are fetched.
+///
+/// ```rust
+/// use decds_lib::ShareAvailability;
+///
+/// let mut availability = ShareAvailability::new(2);
+/// (0..10).for_each(|share_id| availability.insert(0, share_id).expect("Failed to mark share available"));
+/// availability.insert(1, 3).expect("Failed to mark share available");
+///
+/// assert!(availability.is_repairable(0));
+/// assert_eq!(availability.unrepairable().collect::<Vec<_>>(), vec![(1, 9)]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShareAvailability {
+    bitmaps: Vec<ShareBitmap>,
+}
+
+impl ShareAvailability {
+    /// Bitmap of a chunkset, whose all shares are available.
+    pub const ALL_SHARES: ShareBitmap = ShareBitmap::MAX >> (ShareBitmap::BITS as usize - DECDS_NUM_ERASURE_CODED_SHARES);
+
+    /// Creates availability of a blob of `num_chunksets` chunksets, none of whose shares are available.
+    pub fn new(num_chunksets: usize) -> Self {
+        ShareAvailability {
+            bitmaps: vec![0; num_chunksets],
+        }
+    }
+
+    /// Creates availability from one bitmap per chunkset, indexed by chunkset ID, ignoring bits of non-existent shares.
+    pub fn from_bitmaps(bitmaps: Vec<ShareBitmap>) -> Self {
+        ShareAvailability {
+            bitmaps: bitmaps.into_iter().map(|bitmap| bitmap & Self::ALL_SHARES).collect(),
+        }
+    }
+
+    /// Returns the number of chunksets.
+    pub fn get_num_chunksets(&self) -> usize {
+        self.bitmaps.len()
+    }
+
+    /// Returns the bitmap of available shares of a chunkset, or `None` if `chunkset_id` is out of bounds.
+    pub fn get_bitmap(&self, chunkset_id: usize) -> Option<ShareBitmap> {
+        self.bitmaps.get(chunkset_id).copied()
+    }
+
+    /// Marks share `share_id` of chunkset `chunkset_id` available.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset.
+    /// * `share_id` - The ID of the erasure-coded share.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(())` if successful.
+    /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_id` is out of bounds.
+    /// - `Err(DecdsError::InvalidErasureCodedShareId)` if `share_id` is not less than `DECDS_NUM_ERASURE_CODED_SHARES`.
+    pub fn insert(&mut self, chunkset_id: usize, share_id: usize) -> Result<(), DecdsError> {
+        if share_id >= DECDS_NUM_ERASURE_CODED_SHARES {
+            return Err(DecdsError::InvalidErasureCodedShareId(share_id));
+        }
+
+        let num_chunksets = self.get_num_chunksets();
+        let bitmap = self
+            .bitmaps
+            .get_mut(chunkset_id)
+            .ok_or(DecdsError::InvalidChunksetId(chunkset_id, num_chunksets))?;
+        *bitmap |= 1 << share_id;
+
+        Ok(())
+    }
+
+    /// Returns `true` if share `share_id` of chunkset `chunkset_id` is available.
+    pub fn contains(&self, chunkset_id: usize, share_id: usize) -> bool {
+        share_id < DECDS_NUM_ERASURE_CODED_SHARES && self.bitmaps.get(chunkset_id).is_some_and(|bitmap| (bitmap >> share_id) & 1 == 1)
+    }
+
+    /// Returns the number of available shares of a chunkset, `0` if `chunkset_id` is out of bounds.
+    pub fn count(&self, chunkset_id: usize) -> usize {
+        self.bitmaps.get(chunkset_id).map_or(0, |bitmap| bitmap.count_ones() as usize)
+    }
+
+    /// Returns `true` if at least `ChunkSet::NUM_ORIGINAL_CHUNKS` shares of a chunkset are available.
+    pub fn is_repairable(&self, chunkset_id: usize) -> bool {
+        self.count(chunkset_id) >= ChunkSet::NUM_ORIGINAL_CHUNKS
+    }
+
+    /// Returns the number of repairable chunksets.
+    pub fn num_repairable(&self) -> usize {
+        self.bitmaps
+            .iter()
+            .filter(|bitmap| bitmap.count_ones() as usize >= ChunkSet::NUM_ORIGINAL_CHUNKS)
+            .count()
+    }
+
+    /// Returns chunksets, which aren't repairable, along with the number of shares they still lack, in order of chunkset ID.
+    pub fn unrepairable(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.bitmaps.iter().enumerate().filter_map(|(chunkset_id, bitmap)| {
+            let num_available_shares = bitmap.count_ones() as usize;
+            (num_available_shares < ChunkSet::NUM_ORIGINAL_CHUNKS).then_some((chunkset_id, ChunkSet::NUM_ORIGINAL_CHUNKS - num_available_shares))
+        })
+    }
+
+    /// Marks all shares available in `other` available in this one too.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Availability of the same blob, e.g. as reported by another node.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(())` if successful.
+    /// - `Err(DecdsError::MismatchingNumberOfChunksets)` if `other` has a different number of chunksets.
+    pub fn union(&mut self, other: &ShareAvailability) -> Result<(), DecdsError> {
+        if other.get_num_chunksets() != self.get_num_chunksets() {
+            return Err(DecdsError::MismatchingNumberOfChunksets(other.get_num_chunksets(), self.get_num_chunksets()));
+        }
+
+        self.bitmaps
+            .iter_mut()
+            .zip(&other.bitmaps)
+            .for_each(|(bitmap, other_bitmap)| *bitmap |= other_bitmap);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DECDS_NUM_ERASURE_CODED_SHARES, ShareAvailability, chunkset::ChunkSet, errors::DecdsError};
+
+    #[test]
+    fn test_share_availability() {
+        const NUM_CHUNKSETS: usize = 1_000_000;
+
+        let mut availability = ShareAvailability::new(NUM_CHUNKSETS);
+        (0..ChunkSet::NUM_ORIGINAL_CHUNKS - 1).for_each(|share_id| {
+            (0..NUM_CHUNKSETS).for_each(|chunkset_id| availability.insert(chunkset_id, share_id).unwrap());
+        });
+
+        assert_eq!(availability.num_repairable(), 0);
+        assert_eq!(availability.count(7), ChunkSet::NUM_ORIGINAL_CHUNKS - 1);
+        assert!(availability.contains(7, 0));
+        assert!(!availability.contains(7, DECDS_NUM_ERASURE_CODED_SHARES - 1));
+
+        let mut other = ShareAvailability::new(NUM_CHUNKSETS);
+        (1..NUM_CHUNKSETS).for_each(|chunkset_id| other.insert(chunkset_id, DECDS_NUM_ERASURE_CODED_SHARES - 1).unwrap());
+        // Already available share doesn't count twice
+        other.insert(0, 0).unwrap();
+
+        availability.union(&other).unwrap();
+        assert_eq!(availability.num_repairable(), NUM_CHUNKSETS - 1);
+        assert_eq!(availability.unrepairable().collect::<Vec<(usize, usize)>>(), vec![(0, 1)]);
+        assert!(availability.is_repairable(NUM_CHUNKSETS - 1));
+        assert!(!availability.is_repairable(NUM_CHUNKSETS));
+
+        assert_eq!(
+            availability.insert(NUM_CHUNKSETS, 0),
+            Err(DecdsError::InvalidChunksetId(NUM_CHUNKSETS, NUM_CHUNKSETS))
+        );
+        assert_eq!(
+            availability.insert(0, DECDS_NUM_ERASURE_CODED_SHARES),
+            Err(DecdsError::InvalidErasureCodedShareId(DECDS_NUM_ERASURE_CODED_SHARES))
+        );
+        assert_eq!(
+            availability.union(&ShareAvailability::new(1)),
+            Err(DecdsError::MismatchingNumberOfChunksets(1, NUM_CHUNKSETS))
+        );
+
+        assert_eq!(
+            ShareAvailability::from_bitmaps(vec![ShareAvailability::ALL_SHARES]).count(0),
+            DECDS_NUM_ERASURE_CODED_SHARES
+        );
+    }
+}
@@ -1,29 +1,93 @@
+#[cfg(feature = "coding")]
 use crate::{
     RepairingChunkSet,
-    chunk::{self, ProofCarryingChunk},
-    chunkset::{self, ChunkSet},
-    consts::{DECDS_BINCODE_CONFIG, DECDS_NUM_ERASURE_CODED_SHARES},
-    errors::DecdsError,
+    availability::ShareBitmap,
+    cache::RepairedChunksetCache,
+    chunk::ProofCarryingChunk,
+    chunkset,
+    consts::DECDS_NUM_ERASURE_CODED_SHARES,
+    events::{EventHandler, NoopEventHandler},
+    extension::{ExtraChunk, ShareExtension},
+    lazy::LazyShares,
+    mmap::MappedFile,
+    pipeline::BlobPipeline,
+    progress::RepairProgress,
+    recoder::{RecodedChunk, Recoder},
+    seal::SealedBlobHeader,
+    verify::VerifiedChunk,
+};
+use crate::{
+    cache::ChunkValidationCache,
+    chunk, commitment,
+    config::BlobConfig,
+    consts::DECDS_BINCODE_CONFIG,
+    errors::{ChunksetValidationError, DecdsError},
+    flags::ContentFlags,
     merkle_tree::MerkleTree,
 };
-use blake3;
+#[cfg(feature = "coding")]
+use rand::Rng;
+#[cfg(feature = "coding")]
 use rayon::prelude::*;
-use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, ops::RangeBounds, usize};
+use serde::{Deserialize, Deserializer, Serialize, de};
+#[cfg(feature = "coding")]
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    ops::Range,
+    path::Path,
+    sync::atomic::{AtomicU16, Ordering},
+};
+use std::{
+    io::{Read, Write},
+    ops::RangeBounds,
+};
 
 /// Represents the header of a `Blob`, containing essential metadata about the blob's
 /// structure and cryptographic commitments. This is essentially what is used during
 /// validity checking and repairing of erasure-coded chunks.
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub struct BlobHeader {
+    #[serde(deserialize_with = "deserialize_addressable_usize")]
     byte_length: usize,
+    #[serde(deserialize_with = "deserialize_addressable_usize")]
     num_chunksets: usize,
+    config: BlobConfig,
+    content_flags: ContentFlags,
     digest: blake3::Hash,
     root_commitment: blake3::Hash,
     chunkset_root_commitments: Vec<blake3::Hash>,
+    inline_data: Option<Vec<u8>>,
+    record_offsets: Vec<usize>,
 }
 
 impl BlobHeader {
+    /// BLAKE3 key derivation context, used for deriving the blob ID.
+    const BLOB_ID_KDF_CONTEXT: &str = "decds 2025-07-01 blob id";
+    /// BLAKE3 key derivation context, used for deriving the records digest.
+    const RECORDS_DIGEST_KDF_CONTEXT: &str = "decds 2025-07-01 blob records digest";
+
+    /// Creates a new `BlobHeader` of a blob, erasure-coded, i.e. not stored inline, with the geometry `config`.
+    pub(crate) fn new(
+        byte_length: usize,
+        config: BlobConfig,
+        digest: blake3::Hash,
+        root_commitment: blake3::Hash,
+        chunkset_root_commitments: Vec<blake3::Hash>,
+    ) -> Self {
+        BlobHeader {
+            byte_length,
+            num_chunksets: chunkset_root_commitments.len(),
+            config,
+            content_flags: ContentFlags::default(),
+            digest,
+            root_commitment,
+            chunkset_root_commitments,
+            inline_data: None,
+            record_offsets: Vec::new(),
+        }
+    }
+
     /// Returns the original byte length of the blob data before padding.
     pub fn get_blob_size(&self) -> usize {
         self.byte_length
@@ -34,9 +98,39 @@ impl BlobHeader {
         self.num_chunksets
     }
 
-    /// Returns the total number of erasure-coded chunks across all chunksets in the blob.
+    /// Returns the erasure-coding geometry, the blob is split and encoded with.
+    pub fn get_config(&self) -> BlobConfig {
+        self.config
+    }
+
+    /// Returns content flags of the blob, which its chunks carry as well. See `ContentFlags`.
+    pub fn get_content_flags(&self) -> ContentFlags {
+        self.content_flags
+    }
+
+    /// Sets content flags of the blob. Its chunks must carry the same ones, for being valid.
+    pub(crate) fn set_content_flags(&mut self, content_flags: ContentFlags) {
+        self.content_flags = content_flags;
+    }
+
+    /// Returns the total number of erasure-coded chunks across all chunksets in the blob, saturating at `usize::MAX`.
+    /// For an inline blob this is `0`, because its data is carried in the header itself.
     pub fn get_num_chunks(&self) -> usize {
-        self.get_num_chunksets() * chunkset::ChunkSet::NUM_ERASURE_CODED_CHUNKS
+        if self.is_inline() {
+            return 0;
+        }
+
+        self.get_num_chunksets().saturating_mul(self.config.get_num_shares())
+    }
+
+    /// Returns `true` if the blob data is stored inline in this header, instead of being erasure-coded into chunks.
+    pub fn is_inline(&self) -> bool {
+        self.inline_data.is_some()
+    }
+
+    /// Returns the raw blob data, if it is stored inline in this header.
+    pub fn get_inline_data(&self) -> Option<&[u8]> {
+        self.inline_data.as_deref()
     }
 
     /// Returns the BLAKE3 digest of the original, unpadded blob data.
@@ -44,6 +138,23 @@ impl BlobHeader {
         self.digest
     }
 
+    /// Returns the blob ID, which identifies the blob by its content, i.e. it is the same for all erasure-coded
+    /// versions of the same data, no matter which coding vectors are used.
+    ///
+    /// It is the BLAKE3 digest of `byte_length || digest`, hashed in key derivation mode with `BLOB_ID_KDF_CONTEXT`,
+    /// where `byte_length` is encoded as 8-bytes little-endian unsigned integer.
+    pub fn get_blob_id(&self) -> blake3::Hash {
+        Self::compute_blob_id(self.byte_length as u64, self.digest)
+    }
+
+    /// Computes the blob ID of a blob of `byte_length` bytes, with BLAKE3 digest `digest`. See `Self::get_blob_id`.
+    pub(crate) fn compute_blob_id(byte_length: u64, digest: blake3::Hash) -> blake3::Hash {
+        blake3::Hasher::new_derive_key(Self::BLOB_ID_KDF_CONTEXT)
+            .update(&byte_length.to_le_bytes())
+            .update(digest.as_bytes())
+            .finalize()
+    }
+
     /// Returns the Merkle root commitment of the entire blob.
     ///
     /// This commitment is derived from the Merkle tree of all chunksets in the blob.
@@ -65,12 +176,114 @@ impl BlobHeader {
     pub fn get_chunkset_commitment(&self, chunkset_id: usize) -> Result<blake3::Hash, DecdsError> {
         self.chunkset_root_commitments
             .get(chunkset_id)
-            .and_then(|&v| Some(v))
+            .copied()
             .ok_or(DecdsError::InvalidChunksetId(chunkset_id, self.get_num_chunksets()))
     }
 
+    /// Returns root commitments of all chunksets, ordered by chunkset ID.
+    pub(crate) fn get_chunkset_commitments(&self) -> &[blake3::Hash] {
+        &self.chunkset_root_commitments
+    }
+
+    /// Generates the Merkle proof of inclusion of a chunkset commitment in the root commitment of the blob, so that it can be
+    /// handed out on its own, without the rest of the chunkset commitments, and checked with `MerkleTree::verify_proof`,
+    /// using the chunkset ID as the leaf index. See `Self::subset` for proofs of many chunksets at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset whose commitment is to be proven.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Vec<blake3::Hash>)` containing the Merkle proof if successful.
+    /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_id` is out of bounds.
+    pub fn get_chunkset_commitment_proof(&self, chunkset_id: usize) -> Result<Vec<blake3::Hash>, DecdsError> {
+        self.get_chunkset_commitment(chunkset_id)?;
+        Ok(unsafe { self.build_commitment_tree().generate_proof(chunkset_id).unwrap_unchecked() })
+    }
+
+    /// Builds the Merkle tree over chunkset commitments, whose root is the root commitment of the blob, as long as the
+    /// header is consistent.
+    pub(crate) fn build_commitment_tree(&self) -> MerkleTree {
+        // A header always has at least one chunkset commitment.
+        unsafe { MerkleTree::new(self.chunkset_root_commitments.clone()).unwrap_unchecked() }
+    }
+
+    /// Reports which chunksets of `other`, e.g. a newer version of the same object, differ from those of this header, so that
+    /// sync tooling needs to transfer only shares of those chunksets. A chunkset differs if its commitment differs, or if it
+    /// doesn't exist in this header at all.
+    ///
+    /// Commitments depend on RLNC coding vectors, which are sampled randomly by default, so only headers of blobs, encoded
+    /// with the same seed (see `DecdsBuilder::seed`), can be meaningfully compared. Otherwise every chunkset differs.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The `BlobHeader` to compare this one against.
+    ///
+    /// # Returns
+    ///
+    /// Returns IDs of differing chunksets of `other`, in ascending order. It is empty if both headers commit to the same blob.
+    pub fn diff(&self, other: &BlobHeader) -> Vec<usize> {
+        other
+            .chunkset_root_commitments
+            .iter()
+            .enumerate()
+            .filter(|&(chunkset_id, commitment)| self.chunkset_root_commitments.get(chunkset_id) != Some(commitment))
+            .map(|(chunkset_id, _)| chunkset_id)
+            .collect()
+    }
+
+    /// Generates a Merkle consistency proof, showing that the tree over chunkset commitments of this blob extends the one of
+    /// an older blob with `old_num_chunksets` chunksets, i.e. that the older blob's chunkset commitments are unchanged. This
+    /// lets auditors check that a blob was only appended to, using just both root commitments and chunkset counts, without
+    /// downloading any data. See `MerkleTree::verify_consistency_proof`.
+    ///
+    /// Chunkset commitments depend on RLNC coding vectors, so appended blobs must be encoded with the same seed as the older
+    /// one (see `DecdsBuilder::seed`), and the older blob's last chunkset must be full, for its commitments to be unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `old_num_chunksets` - Number of chunksets of the older blob.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Vec<blake3::Hash>)` containing the consistency proof if successful.
+    /// - `Err(DecdsError::InvalidNumberOfOldLeafNodes)` if `old_num_chunksets` is zero or more than the number of chunksets of this blob.
+    pub fn generate_consistency_proof(&self, old_num_chunksets: usize) -> Result<Vec<blake3::Hash>, DecdsError> {
+        MerkleTree::new(self.chunkset_root_commitments.clone())?.generate_consistency_proof(old_num_chunksets)
+    }
+
+    /// Checks whether the blob root commitment is consistent with the list of chunkset root commitments in this header,
+    /// by recomputing the root of the Merkle tree built over the chunkset commitments.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the recomputed blob root commitment matches the one in this header, `false` otherwise.
+    pub fn verify_root_commitment(&self) -> bool {
+        commitment::compute_blob_root_commitment(&self.chunkset_root_commitments).is_ok_and(|root_commitment| root_commitment == self.root_commitment)
+    }
+
+    /// Returns the exact, unpadded byte length of the last chunkset, i.e. how many of its bytes belong to the blob. The
+    /// rest of it is zero padding, which is erasure-coded along with the data, but dropped when repairing.
+    pub fn get_last_chunkset_size(&self) -> usize {
+        let last_chunkset_offset = self.num_chunksets.saturating_sub(1).saturating_mul(self.config.get_chunkset_byte_length());
+        self.byte_length.saturating_sub(last_chunkset_offset)
+    }
+
+    /// Returns the number of zero bytes, the last chunkset is padded with before erasure-coding. Inline blobs, not being
+    /// erasure-coded, aren't padded.
+    pub fn get_padding_byte_length(&self) -> usize {
+        if self.is_inline() {
+            0
+        } else {
+            self.config.get_chunkset_byte_length().saturating_sub(self.get_last_chunkset_size())
+        }
+    }
+
     /// Calculates the effective byte length of a specific chunkset within the blob.
-    /// This accounts for the last chunkset potentially being smaller than `ChunkSet::BYTE_LENGTH`.
+    /// This accounts for the last chunkset potentially being smaller than `BlobConfig::get_chunkset_byte_length`.
     ///
     /// # Arguments
     ///
@@ -81,16 +294,10 @@ impl BlobHeader {
     /// Returns a `Result` which is:
     /// - `Ok(usize)` containing the effective byte length of the chunkset if successful.
     /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_id` is out of bounds.
+    /// - `Err(DecdsError::ArithmeticOverflow)` if the byte offset of the chunkset doesn't fit in a `usize`.
     pub fn get_chunkset_size(&self, chunkset_id: usize) -> Result<usize, DecdsError> {
-        if chunkset_id < self.get_num_chunksets() {
-            let from = chunkset_id * ChunkSet::BYTE_LENGTH;
-            let to = (from + ChunkSet::BYTE_LENGTH).min(self.get_blob_size());
-            let effective_len = to - from;
-
-            Ok(effective_len)
-        } else {
-            Err(DecdsError::InvalidChunksetId(chunkset_id, self.get_num_chunksets()))
-        }
+        let (from, to) = self.get_byte_range_for_chunkset(chunkset_id)?;
+        Ok(to - from)
     }
 
     /// Returns the full byte range `[start, end)` of a specific chunkset as it would appear
@@ -105,15 +312,20 @@ impl BlobHeader {
     /// Returns a `Result` which is:
     /// - `Ok((usize, usize))` containing a tuple `[start_byte_idx, end_byte_idx)` if successful.
     /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_id` is out of bounds.
+    /// - `Err(DecdsError::ArithmeticOverflow)` if the byte offset of the chunkset doesn't fit in a `usize`.
     pub fn get_byte_range_for_chunkset(&self, chunkset_id: usize) -> Result<(usize, usize), DecdsError> {
-        if chunkset_id < self.get_num_chunksets() {
-            let from = chunkset_id * ChunkSet::BYTE_LENGTH;
-            let to = (from + ChunkSet::BYTE_LENGTH).min(self.get_blob_size());
-
-            Ok((from, to))
-        } else {
-            Err(DecdsError::InvalidChunksetId(chunkset_id, self.get_num_chunksets()))
+        if chunkset_id >= self.get_num_chunksets() {
+            return Err(DecdsError::InvalidChunksetId(chunkset_id, self.get_num_chunksets()));
         }
+
+        // Can't overflow for a consistent header, as the chunkset starts within the blob, but headers may be crafted.
+        let chunkset_byte_length = self.config.get_chunkset_byte_length();
+        let from = chunkset_id
+            .checked_mul(chunkset_byte_length)
+            .ok_or_else(|| DecdsError::ArithmeticOverflow(format!("byte offset of chunkset {}", chunkset_id)))?;
+        let to = from.saturating_add(chunkset_byte_length).min(self.get_blob_size()).max(from);
+
+        Ok((from, to))
     }
 
     /// Determines the IDs of all chunksets that overlap with a given byte range within the blob.
@@ -148,8 +360,8 @@ impl BlobHeader {
             _ => return Err(DecdsError::InvalidEndBound(usize::MAX)),
         };
 
-        let start_chunkset_id = start / ChunkSet::BYTE_LENGTH;
-        let end_chunkset_id = end / ChunkSet::BYTE_LENGTH;
+        let start_chunkset_id = start / self.config.get_chunkset_byte_length();
+        let end_chunkset_id = end / self.config.get_chunkset_byte_length();
 
         if end_chunkset_id >= self.get_num_chunksets() {
             return Err(DecdsError::InvalidChunksetId(end_chunkset_id, self.get_num_chunksets()));
@@ -158,6 +370,82 @@ impl BlobHeader {
         Ok((start_chunkset_id..=end_chunkset_id).collect())
     }
 
+    /// Returns start offsets of application records, registered at blob creation, in ascending order, see
+    /// `DecdsBuilder::record_offsets`. It's empty, unless record boundaries were registered.
+    ///
+    /// Record offsets are covered by neither the blob ID nor the root commitment, so chunks validating against a header
+    /// say nothing about its record offsets. They are only as trustworthy as the header, unless it's checked against a
+    /// `BlobAnchor` or a signed `SealedBlobHeader`, both committing to `Self::get_records_digest`.
+    pub fn get_record_offsets(&self) -> &[usize] {
+        &self.record_offsets
+    }
+
+    /// Returns the number of application records, registered at blob creation.
+    pub fn get_num_records(&self) -> usize {
+        self.record_offsets.len()
+    }
+
+    /// Returns the records digest, committing to start offsets of application records, registered at blob creation.
+    ///
+    /// It is the BLAKE3 digest of `num_records || record_offsets`, hashed in key derivation mode with
+    /// `RECORDS_DIGEST_KDF_CONTEXT`, where the count and each offset are encoded as 8-bytes little-endian unsigned integers.
+    pub fn get_records_digest(&self) -> blake3::Hash {
+        let mut hasher = blake3::Hasher::new_derive_key(Self::RECORDS_DIGEST_KDF_CONTEXT);
+        hasher.update(&(self.record_offsets.len() as u64).to_le_bytes());
+        self.record_offsets.iter().for_each(|&record_offset| {
+            hasher.update(&(record_offset as u64).to_le_bytes());
+        });
+
+        hasher.finalize()
+    }
+
+    /// Returns the byte range `[start, end)` of an application record within the blob, i.e. from its start offset up to
+    /// the start offset of the next record, or up to the end of the blob, for the last record.
+    ///
+    /// # Arguments
+    ///
+    /// * `record_id` - Index of the record, among those registered at blob creation.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok((usize, usize))` containing a tuple `[start_byte_idx, end_byte_idx)` if successful.
+    /// - `Err(DecdsError::InvalidRecordId)` if `record_id` is out of bounds.
+    pub fn get_byte_range_for_record(&self, record_id: usize) -> Result<(usize, usize), DecdsError> {
+        let from = *self
+            .record_offsets
+            .get(record_id)
+            .ok_or(DecdsError::InvalidRecordId(record_id, self.get_num_records()))?;
+        let to = self.record_offsets.get(record_id + 1).copied().unwrap_or(self.byte_length);
+
+        Ok((from, to))
+    }
+
+    /// Determines the IDs of all chunksets holding some bytes of an application record, so that exactly those can be
+    /// fetched and repaired for reading the record, without an external offset index.
+    ///
+    /// # Arguments
+    ///
+    /// * `record_id` - Index of the record, among those registered at blob creation.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Vec<usize>)` containing a vector of chunkset IDs, in ascending order, if successful.
+    /// - `Err(DecdsError::InvalidRecordId)` if `record_id` is out of bounds.
+    pub fn get_chunkset_ids_for_record(&self, record_id: usize) -> Result<Vec<usize>, DecdsError> {
+        let (from, to) = self.get_byte_range_for_record(record_id)?;
+        self.get_chunkset_ids_for_byte_range(from..to)
+    }
+
+    /// Registers start offsets of application records, after checking that they are ascending and within the blob.
+    pub(crate) fn set_record_offsets(&mut self, record_offsets: Vec<usize>) -> Result<(), DecdsError> {
+        validate_record_offsets(&record_offsets, self.byte_length)?;
+
+        self.record_offsets = record_offsets;
+        Ok(())
+    }
+
     /// Serializes the `BlobHeader` into a vector of bytes using `bincode`.
     ///
     /// # Returns
@@ -179,501 +467,3317 @@ impl BlobHeader {
     ///
     /// Returns a `Result` which is:
     /// - `Ok((Self, usize))` containing the deserialized `BlobHeader` and the number of bytes read if successful.
-    /// - `Err(DecdsError::BlobHeaderDeserializationFailed)` if `bincode` deserialization fails, or if the number
-    ///   of chunksets in the header does not match the number of root commitments.
+    /// - `Err(DecdsError::BlobHeaderDeserializationFailed)` if `bincode` deserialization fails, if the number
+    ///   of chunksets in the header does not match the number of root commitments or the blob size, if the blob
+    ///   size or number of chunksets can't be addressed on this platform, if the inline data (when present)
+    ///   does not match the blob size and digest, if the recorded `BlobConfig` isn't valid, if record offsets aren't
+    ///   ascending or within the blob, or if the chunkset commitments don't hash up to the root commitment, see
+    ///   `Self::verify_root_commitment`.
     pub fn from_bytes(bytes: &[u8]) -> Result<(Self, usize), DecdsError> {
         match bincode::serde::decode_from_slice::<BlobHeader, bincode::config::Configuration>(bytes, DECDS_BINCODE_CONFIG) {
             Ok((header, n)) => {
-                if header.num_chunksets != header.chunkset_root_commitments.len() {
-                    return Err(DecdsError::BlobHeaderDeserializationFailed(
-                        "number of chunksets and root commitments do not match".to_string(),
-                    ));
-                }
-
+                header.validate_consistency()?;
                 Ok((header, n))
             }
             Err(err) => Err(DecdsError::BlobHeaderDeserializationFailed(err.to_string())),
         }
     }
 
-    /// Validates a `ProofCarryingChunk` against the `BlobHeader`'s commitments.
-    ///
-    /// This checks if the chunk is correctly included in the blob (via blob root commitment)
-    /// and its respective chunkset (via chunkset root commitment).
+    /// Serializes the `BlobHeader` straight into a writer, producing exactly the bytes `Self::to_bytes` returns, without
+    /// encoding the whole header, which is multi-megabytes for a blob of hundreds of thousands of chunksets, into an
+    /// intermediate buffer. Pass the writer wrapped in a `BufWriter`, if it is unbuffered.
     ///
     /// # Arguments
     ///
-    /// * `chunk` - A reference to the `ProofCarryingChunk` to validate.
+    /// * `writer` - The writer to serialize the header into.
     ///
     /// # Returns
     ///
-    /// Returns `true` if the chunk is valid and its proofs are consistent with the blob header, `false` otherwise.
-    pub fn validate_chunk(&self, chunk: &chunk::ProofCarryingChunk) -> bool {
-        chunk.validate_inclusion_in_blob(self.root_commitment)
-            && (chunk.get_chunkset_id() < self.num_chunksets)
-            && chunk.validate_inclusion_in_chunkset(self.chunkset_root_commitments[chunk.get_chunkset_id()])
+    /// Returns a `Result` which is:
+    /// - `Ok(usize)` containing the number of bytes written if successful.
+    /// - `Err(DecdsError::BlobHeaderSerializationFailed)` if serialization or writing fails.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<usize, DecdsError> {
+        let map_encode_err = |err: bincode::error::EncodeError| DecdsError::BlobHeaderSerializationFailed(err.to_string());
+        let map_write_err = |err: std::io::Error| DecdsError::BlobHeaderSerializationFailed(err.to_string());
+
+        // Fields are written one by one, in the order `serde` writes them, as `Self::from_reader` and `BlobHeaderView`
+        // read them back. Each hash is a fixed-width run of `blake3::OUT_LEN` bytes on the wire.
+        let mut num_written_bytes = 0;
+        for field in [self.byte_length, self.num_chunksets] {
+            num_written_bytes += bincode::encode_into_std_write(field, writer, DECDS_BINCODE_CONFIG).map_err(map_encode_err)?;
+        }
+        num_written_bytes += bincode::serde::encode_into_std_write(self.config, writer, DECDS_BINCODE_CONFIG).map_err(map_encode_err)?;
+        num_written_bytes += bincode::encode_into_std_write(self.content_flags.bits(), writer, DECDS_BINCODE_CONFIG).map_err(map_encode_err)?;
+
+        for hash in [self.digest, self.root_commitment] {
+            writer.write_all(hash.as_bytes()).map_err(map_write_err)?;
+            num_written_bytes += blake3::OUT_LEN;
+        }
+
+        num_written_bytes += bincode::encode_into_std_write(self.chunkset_root_commitments.len(), writer, DECDS_BINCODE_CONFIG).map_err(map_encode_err)?;
+        for commitment in &self.chunkset_root_commitments {
+            writer.write_all(commitment.as_bytes()).map_err(map_write_err)?;
+            num_written_bytes += blake3::OUT_LEN;
+        }
+
+        match &self.inline_data {
+            Some(inline_data) => {
+                num_written_bytes += bincode::encode_into_std_write(1u8, writer, DECDS_BINCODE_CONFIG).map_err(map_encode_err)?;
+                num_written_bytes += bincode::encode_into_std_write(inline_data.len(), writer, DECDS_BINCODE_CONFIG).map_err(map_encode_err)?;
+                writer.write_all(inline_data).map_err(map_write_err)?;
+                num_written_bytes += inline_data.len();
+            }
+            None => {
+                num_written_bytes += bincode::encode_into_std_write(0u8, writer, DECDS_BINCODE_CONFIG).map_err(map_encode_err)?;
+            }
+        }
+
+        num_written_bytes += bincode::encode_into_std_write(self.record_offsets.len(), writer, DECDS_BINCODE_CONFIG).map_err(map_encode_err)?;
+        for &record_offset in &self.record_offsets {
+            num_written_bytes += bincode::encode_into_std_write(record_offset, writer, DECDS_BINCODE_CONFIG).map_err(map_encode_err)?;
+        }
+
+        Ok(num_written_bytes)
     }
-}
 
-/// Represents a complete, erasure-coded blob of data, consisting of a `BlobHeader` and a collection of `ChunkSet`s,
-/// each of which are holding 16 erasure-coded proof-of-inclusion carrying chunks.
-pub struct Blob {
-    header: BlobHeader,
-    body: Vec<chunkset::ChunkSet>,
-}
+    /// Maximum number of chunkset commitments, `Self::from_reader` allocates room for, before reading any of them.
+    const MAX_PREALLOCATED_COMMITMENTS: usize = 1usize << 12;
 
-impl Blob {
-    /// Creates a new `Blob` from raw byte data.
+    /// Deserializes a `BlobHeader` from a reader, consuming exactly its serialized bytes, as written by `Self::to_bytes`
+    /// or `Self::write_to`. Chunkset commitments are read one by one, so a bogus commitment count can't make us allocate a
+    /// lot upfront. Pass the reader wrapped in a `BufReader`, if it is unbuffered.
     ///
-    /// This involves:
-    /// 1. Calculating the blob's digest and padding its length to a multiple of `ChunkSet::BYTE_LENGTH`.
-    /// 2. Dividing the data into `ChunkSet`s and erasure-coding them individually.
-    /// 3. Building a Merkle tree over the chunksets' root commitments to create the blob's root commitment.
-    /// 4. Appending blob-level Merkle proofs to each chunk within the chunksets.
+    /// To look up only a few chunkset commitments of a huge header, without reading all of them, see `BlobHeaderView`.
     ///
     /// # Arguments
     ///
-    /// * `data` - The raw `Vec<u8>` representing the blob's content.
+    /// * `reader` - The reader from which to deserialize the header.
     ///
     /// # Returns
     ///
     /// Returns a `Result` which is:
-    /// - `Ok(Self)` containing the newly created `Blob` if successful.
-    /// - `Err(DecdsError::EmptyDataForBlob)` if the input `data` is empty.
-    /// - Other `DecdsError` types may be returned from underlying `ChunkSet::new` or `MerkleTree::new` calls.
-    pub fn new(mut data: Vec<u8>) -> Result<Self, DecdsError> {
-        if data.is_empty() {
-            return Err(DecdsError::EmptyDataForBlob);
+    /// - `Ok(Self)` containing the deserialized `BlobHeader` if successful.
+    /// - `Err(DecdsError::BlobHeaderDeserializationFailed)` if reading fails, or for any reason `Self::from_bytes` fails.
+    pub fn from_reader<R: Read>(reader: &mut R) -> Result<Self, DecdsError> {
+        let mut header = Self::read_leading_fields(reader)?;
+
+        // The vector grows as commitments arrive, up to the count already checked against the number of chunksets.
+        let mut chunkset_root_commitments = Vec::with_capacity(header.num_chunksets.min(Self::MAX_PREALLOCATED_COMMITMENTS));
+        for _ in 0..header.num_chunksets {
+            chunkset_root_commitments.push(read_hash(reader)?);
         }
 
-        let blob_digest = blake3::hash(&data);
-        let blob_length = data.len();
+        header.chunkset_root_commitments = chunkset_root_commitments;
+        header.read_trailing_fields(reader)?;
+        header.validate_consistency()?;
 
-        let num_chunksets = blob_length.div_ceil(chunkset::ChunkSet::BYTE_LENGTH);
-        let zero_padded_blob_len = num_chunksets * chunkset::ChunkSet::BYTE_LENGTH;
-        data.resize(zero_padded_blob_len, 0);
+        Ok(header)
+    }
 
-        let mut chunksets = (0..num_chunksets)
-            .into_par_iter()
-            .map(|chunkset_id| {
-                let offset = chunkset_id * chunkset::ChunkSet::BYTE_LENGTH;
-                let till = offset + chunkset::ChunkSet::BYTE_LENGTH;
+    /// Reads all fields of a serialized header, which precede its chunkset commitments, and their count, leaving the reader
+    /// at the first commitment. The count must match the number of chunksets. Returns a header without chunkset commitments
+    /// or inline data, to be filled in by the caller.
+    pub(crate) fn read_leading_fields<R: Read>(reader: &mut R) -> Result<Self, DecdsError> {
+        let byte_length = read_addressable_usize(reader)?;
+        let num_chunksets = read_addressable_usize(reader)?;
+        let config = bincode::serde::decode_from_std_read::<BlobConfig, _, _>(reader, DECDS_BINCODE_CONFIG)
+            .map_err(|err| DecdsError::BlobHeaderDeserializationFailed(err.to_string()))?;
+        let content_flags = bincode::decode_from_std_read::<u8, _, _>(reader, DECDS_BINCODE_CONFIG)
+            .map_err(|err| DecdsError::BlobHeaderDeserializationFailed(err.to_string()))
+            .and_then(|bits| ContentFlags::from_bits(bits).map_err(|err| DecdsError::BlobHeaderDeserializationFailed(err.to_string())))?;
+        let digest = read_hash(reader)?;
+        let root_commitment = read_hash(reader)?;
 
-                unsafe { chunkset::ChunkSet::new(chunkset_id, data[offset..till].to_vec()).unwrap_unchecked() }
-            })
-            .collect::<Vec<chunkset::ChunkSet>>();
+        if read_addressable_usize(reader)? != num_chunksets {
+            return Err(DecdsError::BlobHeaderDeserializationFailed(
+                "number of chunksets and root commitments do not match".to_string(),
+            ));
+        }
 
-        let merkle_leaves = chunksets.iter().map(|chunkset| chunkset.get_root_commitment()).collect::<Vec<blake3::Hash>>();
-        let merkle_tree = MerkleTree::new(merkle_leaves)?;
-        let commitment = merkle_tree.get_root_commitment();
+        Ok(BlobHeader {
+            byte_length,
+            num_chunksets,
+            config,
+            content_flags,
+            digest,
+            root_commitment,
+            chunkset_root_commitments: Vec::new(),
+            inline_data: None,
+            record_offsets: Vec::new(),
+        })
+    }
 
-        chunksets.par_iter_mut().enumerate().for_each(|(chunkset_idx, chunkset)| {
-            let blob_proof = unsafe { merkle_tree.generate_proof(chunkset_idx).unwrap_unchecked() };
-            chunkset.append_blob_inclusion_proof(&blob_proof);
-        });
+    /// Reads the last fields of a serialized header, i.e. its optional inline data and record offsets, following its
+    /// chunkset commitments.
+    pub(crate) fn read_trailing_fields<R: Read>(&mut self, reader: &mut R) -> Result<(), DecdsError> {
+        let map_decode_err = |err: bincode::error::DecodeError| DecdsError::BlobHeaderDeserializationFailed(err.to_string());
 
-        Ok(Blob {
-            header: BlobHeader {
-                byte_length: blob_length,
-                num_chunksets,
-                digest: blob_digest,
-                root_commitment: commitment,
-                chunkset_root_commitments: chunksets.iter().map(|chunkset| chunkset.get_root_commitment()).collect(),
-            },
-            body: chunksets,
-        })
+        self.inline_data = match bincode::decode_from_std_read::<u8, _, _>(reader, DECDS_BINCODE_CONFIG).map_err(map_decode_err)? {
+            0 => None,
+            1 => {
+                let inline_data_byte_len = read_addressable_usize(reader)?;
+
+                // Inline data can't be larger than the blob, so a bogus length prefix is caught right away.
+                if inline_data_byte_len != self.byte_length {
+                    return Err(DecdsError::BlobHeaderDeserializationFailed(
+                        "inline data does not match blob size or digest".to_string(),
+                    ));
+                }
+
+                let mut inline_data = Vec::new();
+                reader
+                    .take(inline_data_byte_len as u64)
+                    .read_to_end(&mut inline_data)
+                    .map_err(|err| DecdsError::BlobHeaderDeserializationFailed(err.to_string()))?;
+                if inline_data.len() != inline_data_byte_len {
+                    return Err(DecdsError::BlobHeaderDeserializationFailed("unexpected end of inline data".to_string()));
+                }
+
+                Some(inline_data)
+            }
+            tag => {
+                return Err(DecdsError::BlobHeaderDeserializationFailed(format!("invalid inline data tag {}", tag)));
+            }
+        };
+
+        // Offsets are read one by one, so a bogus count fails at the end of input, instead of allocating a lot upfront.
+        let num_records = read_addressable_usize(reader)?;
+        let mut record_offsets = Vec::new();
+        for _ in 0..num_records {
+            record_offsets.push(read_addressable_usize(reader)?);
+        }
+
+        self.record_offsets = record_offsets;
+        Ok(())
     }
 
-    /// Returns a reference to the `BlobHeader` of this blob.
-    pub fn get_blob_header(&self) -> &BlobHeader {
-        &self.header
+    /// Checks that fields of a deserialized header agree with each other, see `Self::from_bytes`. Headers embedded in other
+    /// serialized types are deserialized without `Self::from_bytes`, so those need to call this explicitly.
+    pub(crate) fn validate_consistency(&self) -> Result<(), DecdsError> {
+        self.validate_consistency_with(self.chunkset_root_commitments.len())?;
+
+        // A tampered list of chunkset commitments passes count checks, but doesn't hash up to the root commitment.
+        if !self.verify_root_commitment() {
+            return Err(DecdsError::BlobHeaderDeserializationFailed(
+                "chunkset commitments do not match root commitment".to_string(),
+            ));
+        }
+
+        Ok(())
     }
 
-    /// Retrieves a specific "share" (a collection of erasure-coded chunks, one from each chunkset)
-    /// based on the `share_id`.
+    /// Same as `Self::validate_consistency`, but for a header, whose `num_commitments` chunkset commitments aren't held
+    /// in memory, see `BlobHeaderView`, so they aren't checked against the root commitment.
+    pub(crate) fn validate_consistency_with(&self, num_commitments: usize) -> Result<(), DecdsError> {
+        self.config
+            .validate()
+            .map_err(|err| DecdsError::BlobHeaderDeserializationFailed(err.to_string()))?;
+        self.content_flags
+            .validate()
+            .map_err(|err| DecdsError::BlobHeaderDeserializationFailed(err.to_string()))?;
+        validate_record_offsets(&self.record_offsets, self.byte_length).map_err(|err| DecdsError::BlobHeaderDeserializationFailed(err.to_string()))?;
+
+        if self.num_chunksets != num_commitments {
+            return Err(DecdsError::BlobHeaderDeserializationFailed(
+                "number of chunksets and root commitments do not match".to_string(),
+            ));
+        }
+
+        if let Some(inline_data) = self.get_inline_data() {
+            if self.num_chunksets != 1 || inline_data.len() != self.byte_length || blake3::hash(inline_data) != self.digest {
+                return Err(DecdsError::BlobHeaderDeserializationFailed(
+                    "inline data does not match blob size or digest".to_string(),
+                ));
+            }
+        } else if self.byte_length == 0 || self.num_chunksets != self.byte_length.div_ceil(self.config.get_chunkset_byte_length()) {
+            return Err(DecdsError::BlobHeaderDeserializationFailed(
+                "number of chunksets does not match blob size".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Validates a `ProofCarryingChunk` against the `BlobHeader`'s commitments.
     ///
-    /// Each share represents a vertical slice through the blob's chunksets.
+    /// This checks if the chunk is correctly included in the blob (via blob root commitment)
+    /// and its respective chunkset (via chunkset root commitment).
     ///
     /// # Arguments
     ///
-    /// * `share_id` - The ID of the share to retrieve (`0` to `DECDS_NUM_ERASURE_CODED_SHARES - 1`).
+    /// * `chunk` - A reference to the `ProofCarryingChunk` to validate.
     ///
     /// # Returns
     ///
-    /// Returns a `Result` which is:
-    /// - `Ok(Vec<ProofCarryingChunk>)` containing a vector of proof-carrying chunks for the requested share.
-    /// - `Err(DecdsError::InvalidErasureCodedShareId)` if `share_id` is out of bounds.
-    pub fn get_share(&self, share_id: usize) -> Result<Vec<ProofCarryingChunk>, DecdsError> {
-        if share_id >= DECDS_NUM_ERASURE_CODED_SHARES {
-            return Err(DecdsError::InvalidErasureCodedShareId(share_id));
-        }
-
-        Ok((0..self.header.num_chunksets)
-            .map(|chunkset_id| unsafe {
-                let chunkset = &self.body[chunkset_id];
-                chunkset.get_chunk(share_id).unwrap_unchecked().clone()
-            })
-            .collect::<Vec<ProofCarryingChunk>>())
+    /// Returns `true` if the chunk is valid and its proofs are consistent with the blob header, `false` otherwise.
+    /// No chunk is valid for an inline blob, nor one carrying content flags other than those of the header.
+    pub fn validate_chunk(&self, chunk: &chunk::ProofCarryingChunk) -> bool {
+        self.validate_chunk_with_digest(chunk, chunk.get_chunk_digest())
     }
-}
-
-/// Represents a blob that is in the process of being incrementally repaired or reconstructed
-/// from received `ProofCarryingChunk`s.
-pub struct RepairingBlob {
-    header: BlobHeader,
-    body: HashMap<usize, Option<chunkset::RepairingChunkSet>>,
-}
 
-impl RepairingBlob {
-    /// Creates a new `RepairingBlob` instance from a `BlobHeader`.
-    ///
-    /// This initializes an empty `RepairingChunkSet` for each chunkset indicated in the header,
-    /// ready to receive chunks for repair.
+    /// Validates all chunks of a chunkset, e.g. as uploaded together, against the `BlobHeader`'s commitments, in parallel,
+    /// stopping at the first rejected chunk. Meant for ingest pipelines, which reject an entire upload on any corruption.
+    /// All chunks must be of the same chunkset as the first one. Without the `coding` feature, chunks are validated one
+    /// after another.
     ///
     /// # Arguments
     ///
-    /// * `header` - The `BlobHeader` of the blob to be repaired. This header provides the necessary
-    ///   metadata, including chunkset commitments, for the repair process.
+    /// * `chunks` - Chunks of a single chunkset to validate.
     ///
     /// # Returns
     ///
-    /// A new `RepairingBlob` instance, prepared to accept chunks for reconstruction.
-    pub fn new(header: BlobHeader) -> Self {
-        RepairingBlob {
-            body: HashMap::from_iter((0..header.get_num_chunksets()).map(|chunkset_id| {
-                (
-                    chunkset_id,
-                    Some(RepairingChunkSet::new(chunkset_id, unsafe {
-                        header.get_chunkset_commitment(chunkset_id).unwrap_unchecked()
-                    })),
-                )
-            })),
-            header: header,
+    /// Returns a `Result` which is:
+    /// - `Ok(())` if all chunks are valid, which is trivially the case if there are none.
+    /// - `Err(ChunksetValidationError)` pointing at the rejected chunk of the lowest index, otherwise.
+    pub fn validate_chunkset(&self, chunks: &[chunk::ProofCarryingChunk]) -> Result<(), ChunksetValidationError> {
+        let Some(chunkset_id) = chunks.first().map(|chunk| chunk.get_chunkset_id()) else {
+            return Ok(());
+        };
+        let is_rejected = |chunk: &chunk::ProofCarryingChunk| chunk.get_chunkset_id() != chunkset_id || !self.validate_chunk(chunk);
+
+        #[cfg(feature = "coding")]
+        let rejected_chunk_index = chunks.par_iter().position_first(is_rejected);
+        #[cfg(not(feature = "coding"))]
+        let rejected_chunk_index = chunks.iter().position(is_rejected);
+
+        match rejected_chunk_index {
+            Some(chunk_index) => {
+                let chunk = &chunks[chunk_index];
+                let error = if chunk.get_chunkset_id() != chunkset_id {
+                    DecdsError::InvalidChunkMetadata(chunk.get_chunkset_id(), chunk.get_local_chunk_id())
+                } else {
+                    DecdsError::InvalidProofInChunk(chunk.get_chunkset_id(), chunk.get_local_chunk_id())
+                };
+
+                Err(ChunksetValidationError::new(chunk_index, error))
+            }
+            None => Ok(()),
         }
     }
 
-    /// Adds a `ProofCarryingChunk` to the appropriate `RepairingChunkSet` within the blob.
+    /// Validates a `ProofCarryingChunk` against the `BlobHeader`'s commitments, consulting a cache of
+    /// already validated chunk digests first.
     ///
-    /// This method first validates the chunk's inclusion using the blob header, then attempts
-    /// to add it to the relevant chunkset's decoder.
+    /// The chunk is hashed only once, whether or not its digest is found in the cache, as digests are what the cache is
+    /// keyed by. A hit skips only verification of the Merkle proofs, i.e. hashing their few 32-bytes nodes, which is cheap
+    /// next to hashing the chunk itself, otherwise the chunk is validated as in `Self::validate_chunk` and its digest is
+    /// remembered on success.
+    /// The cache must only be used with chunks of this blob.
     ///
     /// # Arguments
     ///
-    /// * `chunk` - A reference to the `ProofCarryingChunk` to add.
+    /// * `chunk` - A reference to the `ProofCarryingChunk` to validate.
+    /// * `cache` - Cache of digests of already validated chunks of this blob.
     ///
     /// # Returns
     ///
-    /// Returns a `Result` which is:
-    /// - `Ok(())` if the chunk is successfully added.
-    /// - `Err(DecdsError::InvalidChunksetId)` if the chunk's `chunkset_id` does not exist in this blob.
-    /// - `Err(DecdsError::ChunksetAlreadyRepaired)` if the target chunkset has already been repaired.
-    /// - `Err(DecdsError::InvalidProofInChunk)` if the chunk's proof of inclusion in the blob or chunkset is invalid.
-    /// - `Err(DecdsError::ChunksetReadyToRepair)` if the chunkset is already ready to repair (and thus cannot accept more chunks).
-    /// - Other `DecdsError` types may be returned from `RepairingChunkSet::add_chunk_unvalidated`.
-    pub fn add_chunk(&mut self, chunk: &chunk::ProofCarryingChunk) -> Result<(), DecdsError> {
-        let chunkset_id = chunk.get_chunkset_id();
+    /// Returns `true` if the chunk is valid and its proofs are consistent with the blob header, `false` otherwise.
+    pub fn validate_chunk_cached(&self, chunk: &chunk::ProofCarryingChunk, cache: &mut ChunkValidationCache) -> bool {
+        self.validate_chunk_cached_with_digest(chunk, chunk.get_chunk_digest(), cache)
+    }
+
+    /// Same as `Self::validate_chunk_cached`, but uses already computed digest of the chunk.
+    fn validate_chunk_cached_with_digest(&self, chunk: &chunk::ProofCarryingChunk, chunk_digest: blake3::Hash, cache: &mut ChunkValidationCache) -> bool {
+        // A cache hit only vouches for the chunk digest, which doesn't cover content flags, hence fields are checked first.
+        if !self.validate_chunk_fields(chunk) {
+            return false;
+        }
+        if cache.contains(&chunk_digest) {
+            return true;
+        }
+
+        let is_valid = self.validate_chunk_with_digest(chunk, chunk_digest);
+        if is_valid {
+            cache.insert(chunk_digest);
+        }
+
+        is_valid
+    }
+
+    /// Same as `Self::validate_chunk`, but uses already computed digest of the chunk.
+    pub(crate) fn validate_chunk_with_digest(&self, chunk: &chunk::ProofCarryingChunk, chunk_digest: blake3::Hash) -> bool {
+        self.validate_chunk_fields(chunk)
+            && chunk.validate_inclusion_in_blob_with_digest(chunk_digest, self.root_commitment)
+            && chunk.validate_inclusion_in_chunkset_with_digest(chunk_digest, self.chunkset_root_commitments[chunk.get_chunkset_id()])
+    }
+
+    /// Checks fields of a chunk, which are checked without its digest, i.e. that it claims an existing chunkset and share of
+    /// a blob, which isn't inline, and carries the content flags of the blob.
+    fn validate_chunk_fields(&self, chunk: &chunk::ProofCarryingChunk) -> bool {
+        !self.is_inline()
+            && (chunk.get_local_chunk_id() < self.config.get_num_shares())
+            && (chunk.get_content_flags() == self.content_flags)
+            && (chunk.get_chunkset_id() < self.num_chunksets)
+    }
+}
+
+/// Deserializes a `usize`, which is always encoded as a 64-bit unsigned integer on the wire, failing explicitly, instead
+/// of truncating, if it can't be represented on this platform, e.g. size of a multi-terabyte blob on a 32-bit target.
+pub(crate) fn deserialize_addressable_usize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<usize, D::Error> {
+    let value = u64::deserialize(deserializer)?;
+    usize::try_from(value).map_err(|_| de::Error::custom(format!("{} can't be addressed on this {}-bit platform", value, usize::BITS)))
+}
+
+/// Reads a `usize` of a serialized header, which is always encoded as a 64-bit unsigned integer, in the same way as
+/// `deserialize_addressable_usize` does.
+fn read_addressable_usize<R: Read>(reader: &mut R) -> Result<usize, DecdsError> {
+    let value =
+        bincode::decode_from_std_read::<u64, _, _>(reader, DECDS_BINCODE_CONFIG).map_err(|err| DecdsError::BlobHeaderDeserializationFailed(err.to_string()))?;
+    usize::try_from(value)
+        .map_err(|_| DecdsError::BlobHeaderDeserializationFailed(format!("{} can't be addressed on this {}-bit platform", value, usize::BITS)))
+}
+
+/// Reads a hash of a serialized header, which is `blake3::OUT_LEN` raw bytes on the wire.
+pub(crate) fn read_hash<R: Read>(reader: &mut R) -> Result<blake3::Hash, DecdsError> {
+    let mut bytes = [0u8; blake3::OUT_LEN];
+    reader
+        .read_exact(&mut bytes)
+        .map_err(|err| DecdsError::BlobHeaderDeserializationFailed(err.to_string()))?;
+
+    Ok(blake3::Hash::from_bytes(bytes))
+}
+
+/// Resolves bounds of `byte_range` within a blob of `blob_size` bytes, into a half-open range of byte offsets.
+///
+/// Returns a `Result` which is:
+/// - `Ok(Range<usize>)` containing the resolved range, which may be empty.
+/// - `Err(DecdsError::InvalidStartBound)` if the range starts after it ends.
+/// - `Err(DecdsError::InvalidEndBound)` if the range ends beyond the blob.
+#[cfg(feature = "coding")]
+fn resolve_byte_range(blob_size: usize, byte_range: impl RangeBounds<usize>) -> Result<Range<usize>, DecdsError> {
+    let start = match byte_range.start_bound() {
+        std::ops::Bound::Unbounded => 0,
+        std::ops::Bound::Included(&x) => x,
+        std::ops::Bound::Excluded(&x) => x.checked_add(1).ok_or(DecdsError::InvalidStartBound)?,
+    };
+    let end = match byte_range.end_bound() {
+        std::ops::Bound::Unbounded => blob_size,
+        std::ops::Bound::Included(&x) => x.checked_add(1).ok_or(DecdsError::InvalidEndBound(x))?,
+        std::ops::Bound::Excluded(&x) => x,
+    };
+
+    if end > blob_size {
+        return Err(DecdsError::InvalidEndBound(end));
+    }
+    if start > end {
+        return Err(DecdsError::InvalidStartBound);
+    }
+
+    Ok(start..end)
+}
+
+/// Checks that start offsets of application records are strictly ascending, so that no record is empty, and within a blob
+/// of `byte_length` bytes.
+fn validate_record_offsets(record_offsets: &[usize], byte_length: usize) -> Result<(), DecdsError> {
+    record_offsets.iter().enumerate().try_for_each(|(record_id, &record_offset)| {
+        let is_ascending = record_id == 0 || record_offsets[record_id - 1] < record_offset;
+        if !is_ascending || record_offset >= byte_length {
+            return Err(DecdsError::InvalidRecordOffset(record_id, record_offset));
+        }
+
+        Ok(())
+    })
+}
+
+/// Error for a chunkset, which a `RepairingBlob` doesn't track: either it doesn't exist, or it's outside the byte range the
+/// repairer is restricted to.
+#[cfg(feature = "coding")]
+fn untracked_chunkset_error(header: &BlobHeader, chunkset_id: usize) -> DecdsError {
+    if chunkset_id < header.get_num_chunksets() {
+        DecdsError::ChunksetOutsideTargetRange(chunkset_id)
+    } else {
+        DecdsError::InvalidChunksetId(chunkset_id, header.get_num_chunksets())
+    }
+}
+
+/// Reads at most `chunkset_byte_length` bytes of blob data from `reader`, returning fewer only at its end.
+#[cfg(feature = "coding")]
+fn read_chunkset_data<R: Read>(reader: &mut R, chunkset_byte_length: usize) -> Result<Vec<u8>, DecdsError> {
+    let mut data = Vec::new();
+    reader
+        .take(chunkset_byte_length as u64)
+        .read_to_end(&mut data)
+        .map_err(|err| DecdsError::BlobDataReadFailed(err.to_string()))?;
+
+    Ok(data)
+}
+
+/// Represents a complete, erasure-coded blob of data, consisting of a `BlobHeader` and a collection of `ChunkSet`s,
+/// each of which are holding 16 (or as many as `BlobConfig` asks for) erasure-coded proof-of-inclusion carrying chunks.
+///
+/// The blob keeps track of which shares it has handed out, and once sealed, with `Blob::seal`, it refuses to hand out
+/// any other share.
+#[cfg(feature = "coding")]
+pub struct Blob {
+    header: BlobHeader,
+    body: BlobBody,
+    issued_shares: AtomicU16,
+    sealed: bool,
+    num_extra_shares: usize,
+}
+
+/// Erasure-coded chunks of a `Blob`, either all held in memory, or coded on demand. See `DecdsBuilder::lazy_shares`.
+#[cfg(feature = "coding")]
+enum BlobBody {
+    Materialized(Vec<chunkset::ChunkSet>),
+    Lazy(LazyShares),
+}
+
+#[cfg(feature = "coding")]
+impl BlobBody {
+    /// Returns the number of erasure-coded chunksets, which is zero for an inline blob.
+    fn get_num_chunksets(&self) -> usize {
+        match self {
+            BlobBody::Materialized(chunksets) => chunksets.len(),
+            BlobBody::Lazy(lazy_shares) => lazy_shares.get_num_chunksets(),
+        }
+    }
+}
+
+#[cfg(feature = "coding")]
+impl Blob {
+    /// Creates a new `Blob` from raw byte data.
+    ///
+    /// This involves:
+    /// 1. Calculating the blob's digest and padding its length to a multiple of `ChunkSet::BYTE_LENGTH`.
+    /// 2. Dividing the data into `ChunkSet`s and erasure-coding them individually.
+    /// 3. Building a Merkle tree over the chunksets' root commitments to create the blob's root commitment.
+    /// 4. Appending blob-level Merkle proofs to each chunk within the chunksets.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The raw `Vec<u8>` representing the blob's content.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Self)` containing the newly created `Blob` if successful.
+    /// - `Err(DecdsError::EmptyDataForBlob)` if the input `data` is empty.
+    /// - Other `DecdsError` types may be returned from underlying `ChunkSet::new` or `MerkleTree::new` calls.
+    pub fn new(data: Vec<u8>) -> Result<Self, DecdsError> {
+        Self::new_with_inline_threshold(data, 0)
+    }
+
+    /// Creates a new `Blob` from raw byte data, storing it inline in the `BlobHeader` if it is small enough.
+    ///
+    /// Blobs of size at most `inline_threshold` bytes are not erasure-coded at all. Instead the raw data is kept
+    /// in the header, which has a single chunkset and no chunks. `RepairingBlob` recognizes such a header and
+    /// returns the inline data as the repaired chunkset `0`, without needing any chunks. The threshold is capped
+    /// at `ChunkSet::BYTE_LENGTH`, so an inline blob always fits in a single chunkset. Larger blobs are processed
+    /// exactly as in `Blob::new`.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The raw `Vec<u8>` representing the blob's content.
+    /// * `inline_threshold` - Maximum byte length of a blob, which is stored inline. Pass `0` to never inline.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Self)` containing the newly created `Blob` if successful.
+    /// - `Err(DecdsError::EmptyDataForBlob)` if the input `data` is empty.
+    /// - Other `DecdsError` types may be returned from underlying `ChunkSet::new` or `MerkleTree::new` calls.
+    pub fn new_with_inline_threshold(data: Vec<u8>, inline_threshold: usize) -> Result<Self, DecdsError> {
+        Self::build(data, inline_threshold, &BlobConfig::default(), None, &NoopEventHandler)
+    }
+
+    /// Same as `Blob::new`, but invokes `EventHandler::on_chunkset_encoded` of `handler`, as each chunkset is encoded.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The raw `Vec<u8>` representing the blob's content.
+    /// * `handler` - The `EventHandler` to notify about build progress.
+    ///
+    /// # Returns
+    ///
+    /// Returns exactly what `Blob::new` returns.
+    pub fn new_with_event_handler(data: Vec<u8>, handler: &dyn EventHandler) -> Result<Self, DecdsError> {
+        Self::build(data, 0, &BlobConfig::default(), None, handler)
+    }
+
+    /// Same as `Blob::new`, but coding vectors of all erasure-coded chunks are deterministically derived from `seed`,
+    /// instead of being randomly sampled. See `ChunkSet::derive_coding_vector` for the derivation.
+    ///
+    /// Two parties encoding the same data with the same seed get bit-identical shares and commitments, so that encoding
+    /// can be audited by reproducing it, and identical uploads can be deduplicated across nodes by their header alone.
+    /// Use `DecdsBuilder::seed` for doing the same with a custom geometry.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The raw `Vec<u8>` representing the blob's content.
+    /// * `seed` - 32-bytes seed, used for deriving coding vectors.
+    ///
+    /// # Returns
+    ///
+    /// Returns exactly what `Blob::new` returns.
+    pub fn new_with_seed(data: Vec<u8>, seed: &[u8; 32]) -> Result<Self, DecdsError> {
+        Self::build(data, 0, &BlobConfig::default(), Some(seed), &NoopEventHandler)
+    }
+
+    pub(crate) fn build(
+        data: Vec<u8>,
+        inline_threshold: usize,
+        config: &BlobConfig,
+        seed: Option<&[u8; 32]>,
+        handler: &dyn EventHandler,
+    ) -> Result<Self, DecdsError> {
+        if data.is_empty() {
+            return Err(DecdsError::EmptyDataForBlob);
+        }
+        config.validate()?;
+
+        let blob_digest = blake3::hash(&data);
+        let blob_length = data.len();
+
+        if blob_length <= inline_threshold.min(config.get_chunkset_byte_length()) {
+            return Ok(Self::new_inline(data, config, blob_digest));
+        }
+
+        let split = BlobPipeline::new_with_config(data, *config)?;
+        let encoded = match seed {
+            Some(seed) => split.encode_with_seed(seed, handler),
+            None => split.encode_with_event_handler(handler),
+        };
+
+        Ok(encoded.commit()?.attach_proofs())
+    }
+
+    /// Same as `Blob::build`, but instead of holding all erasure-coded chunks, the blob keeps `data` around and codes a
+    /// chunk again, whenever it is asked for. See `DecdsBuilder::lazy_shares`.
+    pub(crate) fn build_lazy(
+        data: Vec<u8>,
+        inline_threshold: usize,
+        config: &BlobConfig,
+        seed: Option<&[u8; 32]>,
+        handler: &dyn EventHandler,
+    ) -> Result<Self, DecdsError> {
+        if data.is_empty() {
+            return Err(DecdsError::EmptyDataForBlob);
+        }
+        config.validate()?;
+
+        let blob_digest = blake3::hash(&data);
+        if data.len() <= inline_threshold.min(config.get_chunkset_byte_length()) {
+            return Ok(Self::new_inline(data, config, blob_digest));
+        }
+
+        // Chunks must be coded with the same coding vectors every time, hence a seed is sampled, if none is given.
+        let seed = seed.copied().unwrap_or_else(|| rand::rng().random::<[u8; 32]>());
+        let (header, lazy_shares) = LazyShares::new(data, blob_digest, config, seed, handler)?;
+
+        Ok(Blob {
+            header,
+            body: BlobBody::Lazy(lazy_shares),
+            issued_shares: AtomicU16::new(0),
+            sealed: false,
+            num_extra_shares: 0,
+        })
+    }
+
+    /// Same as `Blob::new`, but consumes blob data from `reader`, one chunkset at a time, erasure-coding each chunkset as
+    /// soon as it's read, while the blob digest is computed incrementally. So the raw blob, e.g. a huge file, is never held
+    /// in memory as a whole, only a chunkset or two of it, along with erasure-coded chunksets.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Source of the blob's content, read until its end.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Self)` containing the newly created `Blob` if successful.
+    /// - `Err(DecdsError::EmptyDataForBlob)` if `reader` yields no data.
+    /// - `Err(DecdsError::BlobDataReadFailed)` if reading from `reader` fails.
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, DecdsError> {
+        Self::build_from_reader(reader, 0, &BlobConfig::default(), None, &NoopEventHandler)
+    }
+
+    pub(crate) fn build_from_reader<R: Read>(
+        mut reader: R,
+        inline_threshold: usize,
+        config: &BlobConfig,
+        seed: Option<&[u8; 32]>,
+        handler: &dyn EventHandler,
+    ) -> Result<Self, DecdsError> {
+        config.validate()?;
+
+        let chunkset_byte_length = config.get_chunkset_byte_length();
+        let mut hasher = blake3::Hasher::new();
+        let mut byte_length = 0;
+        let mut chunksets = Vec::new();
+
+        // One chunkset is read ahead, so that the end of the blob is known before the last chunkset is encoded.
+        let mut next_data = read_chunkset_data(&mut reader, chunkset_byte_length)?;
+        if next_data.is_empty() {
+            return Err(DecdsError::EmptyDataForBlob);
+        }
+
+        while !next_data.is_empty() {
+            let mut data = next_data;
+            hasher.update(&data);
+            byte_length += data.len();
+
+            next_data = if data.len() < chunkset_byte_length {
+                Vec::new()
+            } else {
+                read_chunkset_data(&mut reader, chunkset_byte_length)?
+            };
+
+            if next_data.is_empty() && chunksets.is_empty() && byte_length <= inline_threshold.min(chunkset_byte_length) {
+                return Ok(Self::new_inline(data, config, hasher.finalize()));
+            }
+
+            let chunkset_id = chunksets.len();
+            data.resize(chunkset_byte_length, 0);
+
+            let chunkset = chunkset::ChunkSet::new_with_config(chunkset_id, data, config, seed)?;
+            handler.on_chunkset_encoded(chunkset_id, chunkset.get_root_commitment());
+            chunksets.push(chunkset);
+        }
+
+        Ok(BlobPipeline::from_chunksets(byte_length, *config, hasher.finalize(), chunksets)
+            .commit()?
+            .attach_proofs())
+    }
+
+    /// Same as `Blob::new`, but erasure-codes the file at `path`, memory-mapping it on unix, so that chunksets are encoded in
+    /// parallel, straight from the mapping. The file is never copied into memory as a whole, only a chunkset at a time
+    /// per encoding thread. Elsewhere, the file is read into memory first. Use `Self::from_reader` for files, which may be
+    /// modified meanwhile.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be truncated or modified, by this or any other process, until this function returns. Truncating it
+    /// makes reads off the mapping fail with SIGBUS, while modifying it makes the blob digest and chunksets, which are read
+    /// off the mapping separately, disagree.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the file holding the blob's content.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Self)` containing the newly created `Blob` if successful.
+    /// - `Err(DecdsError::EmptyDataForBlob)` if the file is empty.
+    /// - `Err(DecdsError::BlobDataReadFailed)` if the file can't be opened or mapped.
+    /// - `Err(DecdsError::CodecNotCompiledIn)` if the codec of `BlobConfig` is disabled by a feature flag.
+    pub unsafe fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, DecdsError> {
+        unsafe { Self::build_from_file(path.as_ref(), 0, &BlobConfig::default(), None, &NoopEventHandler) }
+    }
+
+    /// # Safety
+    ///
+    /// Same contract as `Self::from_file`.
+    pub(crate) unsafe fn build_from_file(
+        path: &Path,
+        inline_threshold: usize,
+        config: &BlobConfig,
+        seed: Option<&[u8; 32]>,
+        handler: &dyn EventHandler,
+    ) -> Result<Self, DecdsError> {
+        let data = unsafe { MappedFile::open(path) }.map_err(|err| DecdsError::BlobDataReadFailed(err.to_string()))?;
+        if data.is_empty() {
+            return Err(DecdsError::EmptyDataForBlob);
+        }
+        config.validate()?;
+        config.get_codec().check_compiled_in()?;
+
+        let blob_digest = blake3::hash(&data);
+        let chunkset_byte_length = config.get_chunkset_byte_length();
+
+        if data.len() <= inline_threshold.min(chunkset_byte_length) {
+            return Ok(Self::new_inline(data.to_vec(), config, blob_digest));
+        }
+
+        let chunksets = data
+            .par_chunks(chunkset_byte_length)
+            .enumerate()
+            .map(|(chunkset_id, chunkset_data)| {
+                let mut chunkset_data = chunkset_data.to_vec();
+                chunkset_data.resize(chunkset_byte_length, 0);
+
+                let chunkset = unsafe { chunkset::ChunkSet::new_with_config(chunkset_id, chunkset_data, config, seed).unwrap_unchecked() };
+                handler.on_chunkset_encoded(chunkset_id, chunkset.get_root_commitment());
+
+                chunkset
+            })
+            .collect::<Vec<chunkset::ChunkSet>>();
+
+        Ok(BlobPipeline::from_chunksets(data.len(), *config, blob_digest, chunksets)
+            .commit()?
+            .attach_proofs())
+    }
+
+    /// Reassembles a `Blob` from its header and a complete set of its stored erasure-coded chunks, without erasure-coding
+    /// anything again, e.g. so that a publisher node can resume serving all shares after a restart, without the original
+    /// data. Chunks are validated against `header` in parallel, and the reassembled blob serves exactly the chunks it's
+    /// given. It starts out unsealed, with no share issued, and no extra share generated.
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - The `BlobHeader` of the blob.
+    /// * `chunks` - All chunks of all shares of the blob, in any order. Duplicates of a chunk are ignored. None, for an
+    ///   inline blob.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Self)` containing the reassembled `Blob` if successful.
+    /// - `Err(DecdsError::InvalidProofInChunk)` if a chunk isn't valid against `header`, which no chunk is, for an inline blob.
+    /// - `Err(DecdsError::MissingChunk)` if a chunk of some share of some chunkset isn't given.
+    pub fn reassemble(header: BlobHeader, chunks: impl IntoIterator<Item = ProofCarryingChunk>) -> Result<Self, DecdsError> {
+        let chunks = chunks.into_iter().collect::<Vec<ProofCarryingChunk>>();
+        if let Some(chunk) = chunks.par_iter().find_first(|chunk| !header.validate_chunk(chunk)) {
+            return Err(DecdsError::InvalidProofInChunk(chunk.get_chunkset_id(), chunk.get_local_chunk_id()));
+        }
+        if header.is_inline() {
+            return Ok(Self::from_parts(header, Vec::new()));
+        }
+
+        // Valid chunks are known to be of an existing chunkset and share.
+        let num_shares = header.config.get_num_shares();
+        let mut chunk_slots = vec![vec![None; num_shares]; header.get_num_chunksets()];
+        for chunk in chunks {
+            chunk_slots[chunk.get_chunkset_id()][chunk.get_local_chunk_id()].get_or_insert(chunk);
+        }
+
+        let body = chunk_slots
+            .into_iter()
+            .enumerate()
+            .map(|(chunkset_id, chunk_slots)| {
+                let chunks = chunk_slots
+                    .into_iter()
+                    .enumerate()
+                    .map(|(share_id, chunk)| chunk.ok_or(DecdsError::MissingChunk(chunkset_id, share_id)))
+                    .collect::<Result<Vec<ProofCarryingChunk>, DecdsError>>()?;
+
+                Ok(chunkset::ChunkSet::from_chunks(header.get_chunkset_commitment(chunkset_id)?, chunks))
+            })
+            .collect::<Result<Vec<chunkset::ChunkSet>, DecdsError>>()?;
+
+        Ok(Self::from_parts(header, body))
+    }
+
+    /// Creates a `Blob`, keeping all of `data` inline in its header, without erasure-coding it.
+    fn new_inline(data: Vec<u8>, config: &BlobConfig, digest: blake3::Hash) -> Self {
+        Blob {
+            header: BlobHeader {
+                byte_length: data.len(),
+                num_chunksets: 1,
+                config: *config,
+                content_flags: ContentFlags::default(),
+                digest,
+                root_commitment: digest,
+                chunkset_root_commitments: vec![digest],
+                inline_data: Some(data),
+                record_offsets: Vec::new(),
+            },
+            body: BlobBody::Materialized(Vec::new()),
+            issued_shares: AtomicU16::new(0),
+            sealed: false,
+            num_extra_shares: 0,
+        }
+    }
+
+    /// Assembles a `Blob` from its header and erasure-coded chunksets, carrying blob-level proofs.
+    pub(crate) fn from_parts(header: BlobHeader, body: Vec<chunkset::ChunkSet>) -> Self {
+        Blob {
+            header,
+            body: BlobBody::Materialized(body),
+            issued_shares: AtomicU16::new(0),
+            sealed: false,
+            num_extra_shares: 0,
+        }
+    }
+
+    /// Sets content flags of the blob, in its header and on all of its erasure-coded chunks, including those coded on demand.
+    /// See `DecdsBuilder::content_flags`.
+    pub(crate) fn with_content_flags(mut self, content_flags: ContentFlags) -> Self {
+        self.header.set_content_flags(content_flags);
+
+        match &mut self.body {
+            BlobBody::Materialized(chunksets) => chunksets.iter_mut().for_each(|chunkset| chunkset.set_content_flags(content_flags)),
+            BlobBody::Lazy(lazy_shares) => lazy_shares.set_content_flags(content_flags),
+        }
+        self
+    }
+
+    /// Registers start offsets of application records in the header of this blob. See `DecdsBuilder::record_offsets`.
+    pub(crate) fn with_record_offsets(mut self, record_offsets: &[usize]) -> Result<Self, DecdsError> {
+        self.header.set_record_offsets(record_offsets.to_vec())?;
+        Ok(self)
+    }
+
+    /// Returns a reference to the `BlobHeader` of this blob.
+    pub fn get_blob_header(&self) -> &BlobHeader {
+        &self.header
+    }
+
+    /// Retrieves a specific "share" (a collection of erasure-coded chunks, one from each chunkset)
+    /// based on the `share_id`.
+    ///
+    /// Each share represents a vertical slice through the blob's chunksets. An inline blob has no chunks,
+    /// so all of its shares are empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `share_id` - The ID of the share to retrieve (`0` to `BlobConfig::get_num_shares() - 1`).
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Vec<ProofCarryingChunk>)` containing a vector of proof-carrying chunks for the requested share.
+    /// - `Err(DecdsError::InvalidErasureCodedShareId)` if `share_id` is out of bounds.
+    /// - `Err(DecdsError::BlobSealed)` if the blob is sealed, and the share wasn't issued before sealing it.
+    pub fn get_share(&self, share_id: usize) -> Result<Vec<ProofCarryingChunk>, DecdsError> {
+        match &self.body {
+            BlobBody::Materialized(_) => Ok(self.iter_share(share_id)?.cloned().collect::<Vec<ProofCarryingChunk>>()),
+            BlobBody::Lazy(lazy_shares) => {
+                if share_id >= self.header.config.get_num_shares() {
+                    return Err(DecdsError::InvalidErasureCodedShareId(share_id));
+                }
+                self.issue_share(share_id)?;
+
+                Ok((0..lazy_shares.get_num_chunksets())
+                    .into_par_iter()
+                    .map(|chunkset_id| lazy_shares.get_chunk(chunkset_id, share_id))
+                    .collect::<Vec<ProofCarryingChunk>>())
+            }
+        }
+    }
+
+    /// Same as `Self::get_share`, but borrows chunks of the share, instead of cloning them. Blob-level proofs are attached
+    /// to chunks once, while building the blob, so a share can be extracted as many times as needed, e.g. when serving
+    /// storage nodes on demand, without recomputing proofs or copying erasure-coded data.
+    ///
+    /// # Arguments
+    ///
+    /// * `share_id` - The ID of the share to iterate over (`0` to `BlobConfig::get_num_shares() - 1`).
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(impl Iterator<Item = &ProofCarryingChunk>)` yielding proof-carrying chunks of the share, in order of chunkset ID.
+    /// - `Err(DecdsError::InvalidErasureCodedShareId)` if `share_id` is out of bounds.
+    /// - `Err(DecdsError::ChunksNotMaterialized)` if shares of this blob are generated on demand.
+    /// - `Err(DecdsError::BlobSealed)` if the blob is sealed, and the share wasn't issued before sealing it.
+    pub fn iter_share(&self, share_id: usize) -> Result<impl Iterator<Item = &ProofCarryingChunk> + '_, DecdsError> {
+        if share_id >= self.header.config.get_num_shares() {
+            return Err(DecdsError::InvalidErasureCodedShareId(share_id));
+        }
+
+        match &self.body {
+            BlobBody::Materialized(chunksets) => {
+                self.issue_share(share_id)?;
+                Ok(chunksets.iter().map(move |chunkset| unsafe { chunkset.get_chunk(share_id).unwrap_unchecked() }))
+            }
+            BlobBody::Lazy(_) => Err(DecdsError::ChunksNotMaterialized),
+        }
+    }
+
+    /// Same as `Self::iter_share`, but yields serialized chunks (see `ProofCarryingChunk::to_bytes`), each serialized only
+    /// when it is pulled. A consumer pushing a share to a slow remote node holds at most one serialized chunk at a time,
+    /// instead of buffering the whole serialized share. As it is a plain pull-based iterator, it can be turned into an
+    /// async stream with any runtime's `stream::iter`, or drained from a blocking task. If shares of this blob are generated
+    /// on demand, each chunk is also erasure-coded only when it is pulled.
+    ///
+    /// # Arguments
+    ///
+    /// * `share_id` - The ID of the share to stream (`0` to `DECDS_NUM_ERASURE_CODED_SHARES - 1`).
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(impl Iterator<Item = Result<Vec<u8>, DecdsError>>)` yielding serialized chunks of the share, in order of chunkset ID.
+    /// - `Err(DecdsError::InvalidErasureCodedShareId)` if `share_id` is out of bounds.
+    /// - `Err(DecdsError::BlobSealed)` if the blob is sealed, and the share wasn't issued before sealing it.
+    pub fn share_stream(&self, share_id: usize) -> Result<impl Iterator<Item = Result<Vec<u8>, DecdsError>> + '_, DecdsError> {
+        if share_id >= self.header.config.get_num_shares() {
+            return Err(DecdsError::InvalidErasureCodedShareId(share_id));
+        }
+        self.issue_share(share_id)?;
+
+        Ok((0..self.body.get_num_chunksets()).map(move |chunkset_id| match &self.body {
+            BlobBody::Materialized(chunksets) => unsafe { chunksets[chunkset_id].get_chunk(share_id).unwrap_unchecked() }.to_bytes(),
+            BlobBody::Lazy(lazy_shares) => lazy_shares.get_chunk(chunkset_id, share_id).to_bytes(),
+        }))
+    }
+
+    /// Returns a single erasure-coded chunk of this blob, carrying proof of inclusion in the blob, without cloning it.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset.
+    /// * `share_id` - The ID of the share (`0` to `DECDS_NUM_ERASURE_CODED_SHARES - 1`).
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(&ProofCarryingChunk)` containing a reference to the chunk if successful.
+    /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_id` is out of bounds, which is always the case for an inline blob.
+    /// - `Err(DecdsError::InvalidErasureCodedShareId)` if `share_id` is out of bounds.
+    /// - `Err(DecdsError::ChunksNotMaterialized)` if shares of this blob are generated on demand.
+    /// - `Err(DecdsError::BlobSealed)` if the blob is sealed, and the share wasn't issued before sealing it.
+    pub fn get_chunk(&self, chunkset_id: usize, share_id: usize) -> Result<&ProofCarryingChunk, DecdsError> {
+        match &self.body {
+            BlobBody::Materialized(chunksets) => {
+                let chunk = chunksets
+                    .get(chunkset_id)
+                    .ok_or(DecdsError::InvalidChunksetId(chunkset_id, chunksets.len()))?
+                    .get_chunk(share_id)?;
+                self.issue_share(share_id)?;
+
+                Ok(chunk)
+            }
+            BlobBody::Lazy(lazy_shares) => {
+                if chunkset_id >= lazy_shares.get_num_chunksets() {
+                    return Err(DecdsError::InvalidChunksetId(chunkset_id, lazy_shares.get_num_chunksets()));
+                }
+                if share_id >= self.header.config.get_num_shares() {
+                    return Err(DecdsError::InvalidErasureCodedShareId(share_id));
+                }
+
+                Err(DecdsError::ChunksNotMaterialized)
+            }
+        }
+    }
+
+    /// Returns BLAKE3 digest of every erasure-coded chunk of this blob, alongside its coordinates, in order of chunkset
+    /// ID and then share ID. These are the leaf nodes of the blob's Merkle tree, so external deduplication or indexing
+    /// systems can track content of the blob, without having to deserialize proof-carrying chunks. As coding vectors are
+    /// sampled randomly, digests are specific to this erasure-coded instance, not to the original blob data. An inline
+    /// blob has no chunks, so nothing is returned.
+    ///
+    /// # Returns
+    ///
+    /// Returns an iterator over `(chunkset_id, share_id, chunk_digest)` tuples.
+    pub fn chunk_digests(&self) -> impl Iterator<Item = (usize, usize, blake3::Hash)> + '_ {
+        let num_chunksets = if self.header.is_inline() { 0 } else { self.header.num_chunksets };
+
+        (0..num_chunksets).flat_map(move |chunkset_id| {
+            (0..self.header.config.get_num_shares()).map(move |share_id| match &self.body {
+                BlobBody::Materialized(chunksets) => {
+                    let chunk = unsafe { chunksets[chunkset_id].get_chunk(share_id).unwrap_unchecked() };
+                    (chunkset_id, share_id, chunk.get_chunk_digest())
+                }
+                BlobBody::Lazy(lazy_shares) => (chunkset_id, share_id, lazy_shares.get_chunk_digest(chunkset_id, share_id)),
+            })
+        })
+    }
+
+    /// Records share `share_id`, assumed to be in bounds, as issued, unless the blob is sealed and it wasn't issued before.
+    fn issue_share(&self, share_id: usize) -> Result<(), DecdsError> {
+        let share_bit = 1 << share_id;
+
+        if self.sealed {
+            return match self.issued_shares.load(Ordering::Relaxed) & share_bit {
+                0 => Err(DecdsError::BlobSealed(share_id)),
+                _ => Ok(()),
+            };
+        }
+
+        self.issued_shares.fetch_or(share_bit, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Returns the bitmap of share IDs, any chunk of which was handed out by this blob so far, where bit `share_id` is set
+    /// if the share was issued.
+    pub fn get_issued_shares(&self) -> ShareBitmap {
+        self.issued_shares.load(Ordering::Relaxed)
+    }
+
+    /// Checks whether this blob is sealed, i.e. whether it refuses to hand out shares, which it didn't hand out before.
+    pub fn is_sealed(&self) -> bool {
+        self.sealed
+    }
+
+    /// Seals this blob, after which it refuses to hand out chunks of shares, which it didn't hand out before. Shares issued
+    /// before sealing can still be handed out again, e.g. for re-uploading them to a storage node.
+    ///
+    /// Sealing an already sealed blob doesn't issue anything new, it just returns the same final header once again.
+    ///
+    /// # Returns
+    ///
+    /// Returns the unsigned final header of the blob, recording which shares were issued.
+    pub fn seal(&mut self) -> SealedBlobHeader {
+        self.sealed = true;
+        SealedBlobHeader::new(self.header.clone(), self.get_issued_shares())
+    }
+
+    /// Same as `Self::seal`, but signs the final header.
+    ///
+    /// # Arguments
+    ///
+    /// * `sign` - Signs the seal ID (see `SealedBlobHeader::get_seal_id`) on behalf of whoever seals the blob.
+    ///
+    /// # Returns
+    ///
+    /// Returns the signed final header of the blob, recording which shares were issued.
+    pub fn seal_with_signature(&mut self, sign: impl FnOnce(&blake3::Hash) -> Vec<u8>) -> SealedBlobHeader {
+        self.seal().with_signature(sign)
+    }
+
+    /// Generates `num_extra_shares` new shares, beyond those committed to by the header, so that operators can top up
+    /// redundancy after the initial distribution. Each chunk of an extra share is a fresh random linear combination of
+    /// chunks of its chunkset, so it's as useful as any chunk, with high probability. As the header can't commit to
+    /// extra shares, they are committed to by a `ShareExtension`, signed on behalf of whoever published the header, which
+    /// should be published alongside the header.
+    ///
+    /// Extra share IDs start at `DECDS_NUM_ERASURE_CODED_SHARES` and continue across calls, each call committing to its
+    /// own batch of extra shares, without invalidating earlier ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_extra_shares` - Number of extra shares to generate.
+    /// * `sign` - Signs the extension ID (see `ShareExtension::get_extension_id`) on behalf of whoever published the blob.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok((ShareExtension, Vec<Vec<ExtraChunk>>))` containing the commitment to the new shares, along with chunks of
+    ///   each share, in order of chunkset ID, if successful.
+    /// - `Err(DecdsError::NoExtraSharesToGenerate)` if `num_extra_shares` is zero, or the blob is stored inline.
+    /// - `Err(DecdsError::BlobSealed)` if the blob is sealed, as it refuses to hand out new shares. Extra shares issued
+    ///   before sealing aren't recorded by the seal; they stay bound to the publisher by the signatures over their
+    ///   extensions.
+    pub fn generate_extra_shares(
+        &mut self,
+        num_extra_shares: usize,
+        sign: impl FnOnce(&blake3::Hash) -> Vec<u8>,
+    ) -> Result<(ShareExtension, Vec<Vec<ExtraChunk>>), DecdsError> {
+        let first_share_id = DECDS_NUM_ERASURE_CODED_SHARES + self.num_extra_shares;
+
+        if num_extra_shares == 0 || self.header.get_inline_data().is_some() {
+            return Err(DecdsError::NoExtraSharesToGenerate);
+        }
+        if self.sealed {
+            return Err(DecdsError::BlobSealed(first_share_id));
+        }
+
+        // Chunksets are recoded one at a time per worker, so that copies of their chunks are short-lived.
+        let num_shares = self.header.config.get_num_shares();
+        let mut extra_chunks_per_chunkset = (0..self.body.get_num_chunksets())
+            .into_par_iter()
+            .map(|chunkset_id| {
+                let mut recoder = unsafe { Recoder::new(&self.header, chunkset_id).unwrap_unchecked() };
+
+                match &self.body {
+                    BlobBody::Materialized(chunksets) => (0..num_shares)
+                        .for_each(|share_id| recoder.add_chunk_unvalidated(unsafe { chunksets[chunkset_id].get_chunk(share_id).unwrap_unchecked() })),
+                    BlobBody::Lazy(lazy_shares) => {
+                        (0..num_shares).for_each(|share_id| recoder.add_chunk_unvalidated(&lazy_shares.get_chunk(chunkset_id, share_id)))
+                    }
+                }
+
+                (0..num_extra_shares)
+                    .map(|_| unsafe { recoder.recode().unwrap_unchecked() })
+                    .collect::<Vec<RecodedChunk>>()
+                    .into_iter()
+            })
+            .collect::<Vec<_>>();
+
+        let extra_shares = (0..num_extra_shares)
+            .map(|_| {
+                extra_chunks_per_chunkset
+                    .iter_mut()
+                    .map(|extra_chunks| unsafe { extra_chunks.next().unwrap_unchecked() })
+                    .collect::<Vec<RecodedChunk>>()
+            })
+            .collect::<Vec<Vec<RecodedChunk>>>();
+
+        self.num_extra_shares += num_extra_shares;
+        Ok(ShareExtension::new(&self.header, first_share_id, extra_shares, sign))
+    }
+}
+
+/// Decides whether a verified chunk is accepted for decoding by a `RepairingBlob`, given the source it came from, if known,
+/// and the chunk itself. See `RepairingBlob::set_acceptance_policy`.
+#[cfg(feature = "coding")]
+pub type ChunkAcceptancePolicy = Box<dyn FnMut(Option<&str>, &ProofCarryingChunk) -> bool + Send + Sync>;
+
+/// Status of a chunk, added by `RepairingBlob::add_chunks`, which turned out to be useful for repairing its chunkset.
+#[cfg(feature = "coding")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AddOutcome {
+    /// The chunk is fed to the decoder of its chunkset, which needs more chunks, before it can be repaired.
+    Accepted,
+    /// The chunk is fed to the decoder of its chunkset, which became ready to repair with it, so that no more chunks of
+    /// the chunkset have to be fetched.
+    ChunksetReady,
+}
+
+/// Represents a blob that is in the process of being incrementally repaired or reconstructed
+/// from received `ProofCarryingChunk`s.
+#[cfg(feature = "coding")]
+pub struct RepairingBlob {
+    header: BlobHeader,
+    body: HashMap<usize, Option<chunkset::RepairingChunkSet>>,
+    inline_data: Option<Vec<u8>>,
+    validation_cache: Option<ChunkValidationCache>,
+    repaired_chunkset_cache: Option<RepairedChunksetCache>,
+    share_weights: [u32; DECDS_NUM_ERASURE_CODED_SHARES],
+    suspect_sources: BTreeMap<String, usize>,
+    event_handler: Option<Box<dyn EventHandler>>,
+    acceptance_policy: Option<ChunkAcceptancePolicy>,
+    target_byte_range: Option<Range<usize>>,
+    num_streamed_bytes: usize,
+    is_checkpointing: bool,
+    progress: RepairProgress,
+}
+
+#[cfg(feature = "coding")]
+impl RepairingBlob {
+    /// Creates a new `RepairingBlob` instance from a `BlobHeader`.
+    ///
+    /// This initializes an empty `RepairingChunkSet` for each chunkset indicated in the header,
+    /// ready to receive chunks for repair. If the header carries inline data, its only chunkset
+    /// is immediately ready to repair, without needing any chunks.
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - The `BlobHeader` of the blob to be repaired. This header provides the necessary
+    ///   metadata, including chunkset commitments, for the repair process.
+    ///
+    /// # Returns
+    ///
+    /// A new `RepairingBlob` instance, prepared to accept chunks for reconstruction.
+    pub fn new(header: BlobHeader) -> Self {
+        if let Some(inline_data) = header.get_inline_data() {
+            return RepairingBlob {
+                body: HashMap::from_iter([(0, None)]),
+                inline_data: Some(inline_data.to_vec()),
+                validation_cache: None,
+                repaired_chunkset_cache: None,
+                share_weights: [0; DECDS_NUM_ERASURE_CODED_SHARES],
+                suspect_sources: BTreeMap::new(),
+                event_handler: None,
+                acceptance_policy: None,
+                target_byte_range: None,
+                num_streamed_bytes: 0,
+                is_checkpointing: false,
+                progress: RepairProgress::new_inline(),
+                header,
+            };
+        }
+
+        RepairingBlob {
+            body: HashMap::from_iter((0..header.get_num_chunksets()).map(|chunkset_id| {
+                (
+                    chunkset_id,
+                    Some(RepairingChunkSet::new_with_config(
+                        chunkset_id,
+                        unsafe { header.get_chunkset_commitment(chunkset_id).unwrap_unchecked() },
+                        &header.config,
+                    )),
+                )
+            })),
+            inline_data: None,
+            validation_cache: None,
+            repaired_chunkset_cache: None,
+            share_weights: [0; DECDS_NUM_ERASURE_CODED_SHARES],
+            suspect_sources: BTreeMap::new(),
+            event_handler: None,
+            acceptance_policy: None,
+            target_byte_range: None,
+            num_streamed_bytes: 0,
+            is_checkpointing: false,
+            progress: RepairProgress::new(header.get_num_chunksets(), header.config.get_num_original_chunks()),
+            header,
+        }
+    }
+
+    /// Creates a new `RepairingBlob` instance from a `BlobHeader`, which remembers digests of up to `capacity`
+    /// already validated chunks in a `ChunkValidationCache`.
+    ///
+    /// Re-ingesting a chunk, which is found in the cache (e.g. due to retries or overlapping sources), by
+    /// `Self::add_chunk`, `Self::add_chunk_owned` or `Self::add_chunks`, skips Merkle proof verification. The chunk is
+    /// still hashed in full, to look it up, so the cache saves far less than a full validation. Everything else behaves
+    /// exactly like a `RepairingBlob` created using `Self::new`.
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - The `BlobHeader` of the blob to be repaired.
+    /// * `capacity` - Maximum number of chunk digests to remember.
+    ///
+    /// # Returns
+    ///
+    /// A new `RepairingBlob` instance, prepared to accept chunks for reconstruction.
+    pub fn with_validation_cache(header: BlobHeader, capacity: usize) -> Self {
+        let mut repairer = Self::new(header);
+        repairer.validation_cache = Some(ChunkValidationCache::new(capacity));
+        repairer
+    }
+
+    /// Registers an `EventHandler`, which is notified of verified and invalid chunks, as they are added, of chunksets
+    /// becoming ready to repair, of repaired chunksets, as they are retrieved, and of the `RepairProgress` changing with
+    /// any of those. Replaces any previously registered handler.
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - The `EventHandler` to notify about repair progress.
+    pub fn set_event_handler(&mut self, handler: Box<dyn EventHandler>) {
+        self.event_handler = Some(handler);
+    }
+
+    /// Installs a policy, which is consulted for every chunk passing proof verification, right before it is handed to the
+    /// decoder, so that chunks can be refused based on their source, share ID or size, e.g. no more than some number of
+    /// chunks from a single node. Chunks added without a source, i.e. by `Self::add_chunk`, are passed `None`. Refused
+    /// chunks aren't counted against their source as garbage. Replaces any previously installed policy.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The `ChunkAcceptancePolicy`, returning `true` for chunks to accept.
+    pub fn set_acceptance_policy(&mut self, policy: ChunkAcceptancePolicy) {
+        self.acceptance_policy = Some(policy);
+    }
+
+    /// Registers the cost of fetching chunks of a share, e.g. a share on local disk being cheaper than one served by
+    /// a remote node. All shares have weight `0` by default. Weights decide the order returned by
+    /// `Self::get_preferred_share_order`, and the order `Self::add_chunks` decodes chunks of a chunkset in, so that
+    /// surplus chunks, turned away once the chunkset is ready to repair, are those of the costliest shares.
+    ///
+    /// # Arguments
+    ///
+    /// * `share_id` - The ID of the share (`0` to `num_shares - 1`, as per the blob's `BlobConfig`).
+    /// * `weight` - Cost of fetching a chunk of this share, lower is cheaper.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(())` if the weight is registered.
+    /// - `Err(DecdsError::InvalidErasureCodedShareId)` if `share_id` is not a share of the blob.
+    pub fn set_share_weight(&mut self, share_id: usize, weight: u32) -> Result<(), DecdsError> {
+        if share_id >= self.header.config.get_num_shares() {
+            return Err(DecdsError::InvalidErasureCodedShareId(share_id));
+        }
+
+        self.share_weights[share_id] = weight;
+        Ok(())
+    }
+
+    /// Returns the weight registered for a share, using `Self::set_share_weight`.
+    ///
+    /// # Arguments
+    ///
+    /// * `share_id` - The ID of the share (`0` to `num_shares - 1`, as per the blob's `BlobConfig`).
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(u32)` containing the weight of the share.
+    /// - `Err(DecdsError::InvalidErasureCodedShareId)` if `share_id` is not a share of the blob.
+    pub fn get_share_weight(&self, share_id: usize) -> Result<u32, DecdsError> {
+        if share_id >= self.header.config.get_num_shares() {
+            return Err(DecdsError::InvalidErasureCodedShareId(share_id));
+        }
+
+        Ok(self.share_weights[share_id])
+    }
+
+    /// Returns all share IDs, ordered from the cheapest to the costliest share, as per their registered weights.
+    /// Shares with equal weights are ordered by their IDs. When more chunks are available than needed for repairing
+    /// a chunkset, adding them in this order makes sure the cheapest sources are consumed first.
+    pub fn get_preferred_share_order(&self) -> Vec<usize> {
+        let mut share_ids = (0..self.header.config.get_num_shares()).collect::<Vec<usize>>();
+        share_ids.sort_by_key(|&share_id| self.share_weights[share_id]);
+        share_ids
+    }
+
+    /// Adds a `ProofCarryingChunk` to the appropriate `RepairingChunkSet` within the blob.
+    ///
+    /// This method first validates the chunk's inclusion using the blob header, then attempts
+    /// to add it to the relevant chunkset's decoder.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk` - A reference to the `ProofCarryingChunk` to add.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(())` if the chunk is successfully added.
+    /// - `Err(DecdsError::InvalidChunksetId)` if the chunk's `chunkset_id` does not exist in this blob.
+    /// - `Err(DecdsError::ChunksetOutsideTargetRange)` if the chunk's chunkset is outside the target byte range, see
+    ///   `RepairBuilder::byte_range`.
+    /// - `Err(DecdsError::ChunksetAlreadyRepaired)` if the target chunkset has already been repaired.
+    /// - `Err(DecdsError::InvalidProofInChunk)` if the chunk's proof of inclusion in the blob or chunkset is invalid.
+    /// - `Err(DecdsError::ChunksetReadyToRepair)` if the chunkset is already ready to repair (and thus cannot accept more chunks).
+    /// - `Err(DecdsError::NonInnovativeChunk)` if the chunk is valid, but linearly dependent on chunks added before, i.e.
+    ///   useless, but not a sign of corruption.
+    /// - Other `DecdsError` types may be returned from `RepairingChunkSet::add_chunk_unvalidated`.
+    pub fn add_chunk(&mut self, chunk: &chunk::ProofCarryingChunk) -> Result<(), DecdsError> {
+        self.add_chunk_with_digest(Cow::Borrowed(chunk), Some(chunk.get_chunk_digest()), None)
+    }
+
+    /// Same as `Self::add_chunk`, but takes ownership of the chunk, so that its erasure-coded data, which is 1MB by
+    /// default, is moved, instead of being copied, if it has to be kept for checkpointing, see
+    /// `RepairBuilder::checkpointing`, and is released right after decoding otherwise. Prefer this in receiver loops,
+    /// which don't need chunks after adding them, so that repairing a large blob doesn't double its memory traffic.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk` - The `ProofCarryingChunk` to add.
+    ///
+    /// # Returns
+    ///
+    /// Returns exactly what `Self::add_chunk` returns.
+    pub fn add_chunk_owned(&mut self, chunk: chunk::ProofCarryingChunk) -> Result<(), DecdsError> {
+        let chunk_digest = chunk.get_chunk_digest();
+        self.add_chunk_with_digest(Cow::Owned(chunk), Some(chunk_digest), None)
+    }
+
+    /// Same as `Self::add_chunk`, but takes a chunk already verified against the header of this blob, by
+    /// `BlobHeader::verify_chunk`, so its Merkle inclusion proofs aren't verified again. This lets verification of incoming
+    /// chunks run in a separate stage, e.g. on another thread, from RLNC decoding.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk` - A reference to the `VerifiedChunk` to add.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Err(DecdsError::InvalidProofInChunk)` if the chunk is verified against the header of another blob.
+    /// - Otherwise, exactly what `Self::add_chunk` returns.
+    pub fn add_verified_chunk(&mut self, chunk: &VerifiedChunk) -> Result<(), DecdsError> {
+        let chunk = if chunk.get_root_commitment() == self.header.get_root_commitment() {
+            chunk.get_chunk()
+        } else {
+            return Err(DecdsError::InvalidProofInChunk(
+                chunk.get_chunk().get_chunkset_id(),
+                chunk.get_chunk().get_local_chunk_id(),
+            ));
+        };
+
+        self.add_chunk_with_digest(Cow::Borrowed(chunk), None, None)
+    }
+
+    /// Same as `Self::add_chunk`, but uses already computed digest of the chunk. Without a digest, the chunk is taken as
+    /// already verified against the header of this blob. `source` is passed to the acceptance policy, if any. An owned
+    /// chunk is handed to the decoder as is.
+    fn add_chunk_with_digest(
+        &mut self,
+        chunk: Cow<'_, chunk::ProofCarryingChunk>,
+        chunk_digest: Option<blake3::Hash>,
+        source: Option<&str>,
+    ) -> Result<(), DecdsError> {
+        let (chunkset_id, global_chunk_id) = (chunk.get_chunkset_id(), chunk.get_global_chunk_id());
+        let result = self.validate_and_add_chunk(chunk, chunk_digest, source);
+
+        self.notify_chunk_outcome(chunkset_id, global_chunk_id, &result);
+        if result.is_ok() {
+            self.record_useful_chunks(chunkset_id, 1);
+        }
+
+        result
+    }
+
+    /// Adds a batch of `ProofCarryingChunk`s, e.g. everything received from the network since the last call, using all
+    /// cores: Merkle proofs of all chunks are verified in parallel, and then chunks of each chunkset are decoded on their
+    /// own thread, from the cheapest share to the costliest, as per `Self::set_share_weight`, and in the order they are
+    /// yielded by `chunks` among shares of equal weight, while different chunksets are decoded concurrently. So surplus
+    /// chunks of a chunkset, beyond those needed for repairing it, are the costliest ones. This cuts
+    /// wall-clock repair time on multicore machines, compared to calling `Self::add_chunk` for each chunk, on the caller
+    /// thread. Chunks are owned, so their erasure-coded data is never copied, see `Self::add_chunk_owned`.
+    ///
+    /// Each chunk is treated exactly as `Self::add_chunk` would treat it, except that the acceptance policy, if any, is
+    /// consulted for all valid chunks of chunksets not ready to repair before decoding starts, so it may see chunks of a
+    /// chunkset, which becomes ready within the batch. Event handler is notified of the outcome of each chunk, in order,
+    /// and then of progress made by each chunkset, once the whole batch is decoded.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunks` - Chunks to add, of any chunksets, in any order.
+    ///
+    /// # Returns
+    ///
+    /// Returns the outcome of adding each chunk, in the order chunks are yielded by `chunks`, each being:
+    /// - `Ok(AddOutcome::ChunksetReady)` if the chunk is added, and its chunkset became ready to repair with it.
+    /// - `Ok(AddOutcome::Accepted)` if the chunk is added, but its chunkset needs more chunks.
+    /// - Otherwise, exactly the error `Self::add_chunk` returns.
+    pub fn add_chunks(&mut self, chunks: impl IntoIterator<Item = ProofCarryingChunk>) -> Vec<Result<AddOutcome, DecdsError>> {
+        let chunks = chunks.into_iter().collect::<Vec<ProofCarryingChunk>>();
+
+        let header = &self.header;
+        let chunk_digests = chunks.par_iter().map(|chunk| chunk.get_chunk_digest()).collect::<Vec<blake3::Hash>>();
+
+        // Only chunks missing from the validation cache, if any, get their Merkle proofs verified.
+        let is_cached = match self.validation_cache.as_mut() {
+            Some(cache) => chunks
+                .iter()
+                .zip(&chunk_digests)
+                .map(|(chunk, chunk_digest)| header.validate_chunk_fields(chunk) && cache.contains(chunk_digest))
+                .collect::<Vec<bool>>(),
+            None => vec![false; chunks.len()],
+        };
+
+        let verdicts = chunks
+            .par_iter()
+            .zip(chunk_digests)
+            .zip(is_cached)
+            .map(|((chunk, chunk_digest), is_cached)| (chunk_digest, is_cached || header.validate_chunk_with_digest(chunk, chunk_digest)))
+            .collect::<Vec<(blake3::Hash, bool)>>();
+
+        // Chunks are moved to decoders, so their coordinates are kept for notifying the event handler.
+        let chunk_ids = chunks
+            .iter()
+            .map(|chunk| (chunk.get_chunkset_id(), chunk.get_global_chunk_id()))
+            .collect::<Vec<(usize, usize)>>();
+
+        let mut results = Vec::with_capacity(chunks.len());
+        let mut chunks_by_chunkset = BTreeMap::<usize, Vec<(usize, ProofCarryingChunk)>>::new();
+
+        for (chunk_idx, (chunk, (chunk_digest, is_valid))) in chunks.into_iter().zip(verdicts).enumerate() {
+            let chunkset_id = chunk.get_chunkset_id();
+
+            let result = match self.body.get(&chunkset_id) {
+                None => Err(untracked_chunkset_error(&self.header, chunkset_id)),
+                Some(None) if self.inline_data.is_some() => Err(DecdsError::ChunksetReadyToRepair(chunkset_id)),
+                Some(None) => Err(DecdsError::ChunksetAlreadyRepaired(chunkset_id)),
+                Some(Some(_)) if !is_valid => Err(DecdsError::InvalidProofInChunk(chunkset_id, chunk.get_local_chunk_id())),
+                Some(Some(chunkset)) if chunkset.is_ready_to_repair() => Err(DecdsError::ChunksetReadyToRepair(chunkset_id)),
+                Some(Some(_)) => {
+                    if let Some(cache) = self.validation_cache.as_mut() {
+                        cache.insert(chunk_digest);
+                    }
+
+                    if self.acceptance_policy.as_mut().is_some_and(|policy| !policy(None, &chunk)) {
+                        Err(DecdsError::ChunkRejectedByPolicy(chunkset_id, chunk.get_local_chunk_id()))
+                    } else {
+                        chunks_by_chunkset.entry(chunkset_id).or_default().push((chunk_idx, chunk));
+                        Ok(AddOutcome::Accepted)
+                    }
+                }
+            };
+
+            results.push(result);
+        }
+
+        // Decoders are moved out of the body, so that each chunkset can be decoded on its own thread, and put back after.
+        // Chunks of cheaper shares go first, the sort being stable, so that surplus chunks are those of costlier shares.
+        let share_weights = self.share_weights;
+        let mut decoding_tasks = chunks_by_chunkset
+            .into_iter()
+            .filter_map(|(chunkset_id, mut chunks)| {
+                let chunkset = self.body.get_mut(&chunkset_id)?.take()?;
+                chunks.sort_by_key(|(_, chunk)| share_weights[chunk.get_local_chunk_id()]);
+                Some((chunkset_id, chunkset, chunks))
+            })
+            .collect::<Vec<(usize, RepairingChunkSet, Vec<(usize, ProofCarryingChunk)>)>>();
+
+        let decoding_results = decoding_tasks
+            .par_iter_mut()
+            .map(|(_, chunkset, chunks)| {
+                chunks
+                    .drain(..)
+                    .map(|(chunk_idx, chunk)| {
+                        let result = chunkset.add_chunk_unvalidated_owned(chunk).map(|()| {
+                            if chunkset.is_ready_to_repair() {
+                                AddOutcome::ChunksetReady
+                            } else {
+                                AddOutcome::Accepted
+                            }
+                        });
+
+                        (chunk_idx, result)
+                    })
+                    .collect::<Vec<(usize, Result<AddOutcome, DecdsError>)>>()
+            })
+            .collect::<Vec<Vec<(usize, Result<AddOutcome, DecdsError>)>>>();
+
+        let mut num_useful_chunks_by_chunkset = Vec::with_capacity(decoding_tasks.len());
+        for ((chunkset_id, chunkset, _), chunkset_results) in decoding_tasks.into_iter().zip(decoding_results) {
+            self.body.insert(chunkset_id, Some(chunkset));
+
+            let num_useful_chunks = chunkset_results.iter().filter(|(_, result)| result.is_ok()).count();
+            num_useful_chunks_by_chunkset.push((chunkset_id, num_useful_chunks));
+
+            chunkset_results.into_iter().for_each(|(chunk_idx, result)| results[chunk_idx] = result);
+        }
+
+        chunk_ids
+            .into_iter()
+            .zip(&results)
+            .for_each(|((chunkset_id, global_chunk_id), result)| self.notify_chunk_outcome(chunkset_id, global_chunk_id, result));
+        num_useful_chunks_by_chunkset
+            .into_iter()
+            .filter(|&(_, num_useful_chunks)| num_useful_chunks > 0)
+            .for_each(|(chunkset_id, num_useful_chunks)| self.record_useful_chunks(chunkset_id, num_useful_chunks));
+
+        results
+    }
+
+    /// Notifies the event handler, if any, of a chunk being verified and accepted, or rejected as invalid.
+    fn notify_chunk_outcome<T>(&self, chunkset_id: usize, global_chunk_id: usize, result: &Result<T, DecdsError>) {
+        if let Some(handler) = self.event_handler.as_ref() {
+            match result {
+                Ok(_) => handler.on_chunk_verified(chunkset_id, global_chunk_id),
+                Err(e @ (DecdsError::InvalidProofInChunk(_, _) | DecdsError::InvalidChunkMetadata(_, _) | DecdsError::InvalidChunksetId(_, _))) => {
+                    handler.on_invalid_chunk(chunkset_id, global_chunk_id, e)
+                }
+                Err(_) => {}
+            }
+        }
+    }
+
+    /// Counts useful chunks, which were just added to a chunkset, in the repair progress, notifying the event handler, if
+    /// any, of the chunkset and, eventually, the whole blob becoming ready to repair.
+    fn record_useful_chunks(&mut self, chunkset_id: usize, num_useful_chunks: usize) {
+        // Chunks aren't accepted by chunksets, which are ready to repair, so it's these chunks making one ready.
+        let is_chunkset_ready = self.is_chunkset_ready_to_repair(chunkset_id).unwrap_or(false);
+        self.progress.record_useful_chunks(num_useful_chunks, is_chunkset_ready);
+
+        if let Some(handler) = self.event_handler.as_ref() {
+            if is_chunkset_ready {
+                handler.on_chunkset_ready(chunkset_id);
+            }
+            handler.on_progress(&self.progress);
+            if is_chunkset_ready && self.progress.is_ready() {
+                handler.on_blob_ready();
+            }
+        }
+    }
+
+    fn validate_and_add_chunk(
+        &mut self,
+        chunk: Cow<'_, chunk::ProofCarryingChunk>,
+        chunk_digest: Option<blake3::Hash>,
+        source: Option<&str>,
+    ) -> Result<(), DecdsError> {
+        let chunkset_id = chunk.get_chunkset_id();
+
+        match self
+            .body
+            .get_mut(&chunkset_id)
+            .ok_or_else(|| untracked_chunkset_error(&self.header, chunkset_id))?
+        {
+            Some(chunkset) => {
+                let is_valid = match (chunk_digest, self.validation_cache.as_mut()) {
+                    (Some(chunk_digest), Some(cache)) => self.header.validate_chunk_cached_with_digest(&chunk, chunk_digest, cache),
+                    (Some(chunk_digest), None) => self.header.validate_chunk_with_digest(&chunk, chunk_digest),
+                    (None, _) => true,
+                };
+
+                if is_valid {
+                    if chunkset.is_ready_to_repair() {
+                        Err(DecdsError::ChunksetReadyToRepair(chunkset_id))
+                    } else if self.acceptance_policy.as_mut().is_some_and(|policy| !policy(source, chunk.as_ref())) {
+                        Err(DecdsError::ChunkRejectedByPolicy(chunkset_id, chunk.get_local_chunk_id()))
+                    } else {
+                        match chunk {
+                            Cow::Borrowed(chunk) => chunkset.add_chunk_unvalidated(chunk),
+                            Cow::Owned(chunk) => chunkset.add_chunk_unvalidated_owned(chunk),
+                        }
+                    }
+                } else {
+                    Err(DecdsError::InvalidProofInChunk(chunkset_id, chunk.get_local_chunk_id()))
+                }
+            }
+            None => {
+                if self.inline_data.is_some() {
+                    Err(DecdsError::ChunksetReadyToRepair(chunkset_id))
+                } else {
+                    Err(DecdsError::ChunksetAlreadyRepaired(chunkset_id))
+                }
+            }
+        }
+    }
+
+    /// Adds a `RecodedChunk`, already validated by the caller, e.g. a chunk of an extra share, to the relevant chunkset's
+    /// decoder. Recoded chunks carry no Merkle proof, and a repaired chunkset isn't checked against the header, so a forged
+    /// one would silently corrupt it; that's why recoded chunks, as produced by a `Recoder`, aren't accepted from outside
+    /// the crate. Neither the acceptance policy nor the event handler see recoded chunks, though the latter is notified of
+    /// repair progress they make.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk` - A reference to the `RecodedChunk` to add.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(())` if the recoded chunk is successfully added.
+    /// - `Err(DecdsError::InvalidChunksetId)` if the chunk's `chunkset_id` does not exist in this blob.
+    /// - `Err(DecdsError::ChunksetAlreadyRepaired)` if the target chunkset has already been repaired.
+    /// - `Err(DecdsError::ChunksetReadyToRepair)` if the chunkset is already ready to repair (and thus cannot accept more chunks).
+    /// - Other `DecdsError` types may be returned from `RepairingChunkSet::add_recoded_chunk`.
+    pub(crate) fn add_recoded_chunk(&mut self, chunk: &RecodedChunk) -> Result<(), DecdsError> {
+        let chunkset_id = chunk.get_chunkset_id();
+
+        let result = match self
+            .body
+            .get_mut(&chunkset_id)
+            .ok_or_else(|| untracked_chunkset_error(&self.header, chunkset_id))?
+        {
+            Some(chunkset) => chunkset.add_recoded_chunk(chunk),
+            None => {
+                if self.inline_data.is_some() {
+                    Err(DecdsError::ChunksetReadyToRepair(chunkset_id))
+                } else {
+                    Err(DecdsError::ChunksetAlreadyRepaired(chunkset_id))
+                }
+            }
+        };
+        if result.is_ok() {
+            self.record_useful_chunks(chunkset_id, 1);
+        }
+
+        result
+    }
+
+    /// Adds a chunk of an extra share, generated by `Blob::generate_extra_shares`, to the relevant chunkset's decoder,
+    /// after validating it against `extension`, which must belong to this blob, and be signed by whoever published it.
+    /// As the header doesn't commit to extra shares, the signature is all that binds them to the publisher; an unsigned
+    /// extension could commit to garbage just as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `extension` - The `ShareExtension` committing to the extra share.
+    /// * `chunk` - A reference to the `ExtraChunk` to add.
+    /// * `verify_signature` - Checks a signature over the extension ID, returning `true` if it was made by whoever
+    ///   published the blob.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(())` if the chunk is successfully added.
+    /// - `Err(DecdsError::ForeignShareExtension)` if `extension` doesn't belong to this blob.
+    /// - `Err(DecdsError::InvalidShareExtensionSignature)` if `verify_signature` rejects the signature over `extension`.
+    /// - `Err(DecdsError::InvalidProofInChunk)` if the chunk's proof of inclusion in `extension` is invalid.
+    /// - Otherwise, exactly what `Self::add_recoded_chunk` returns.
+    pub fn add_extra_chunk(
+        &mut self,
+        extension: &ShareExtension,
+        chunk: &ExtraChunk,
+        verify_signature: impl FnOnce(&blake3::Hash, &[u8]) -> bool,
+    ) -> Result<(), DecdsError> {
+        if !extension.belongs_to(&self.header) {
+            return Err(DecdsError::ForeignShareExtension);
+        }
+        extension.verify_signature(verify_signature)?;
+        if !extension.validate_chunk(chunk) {
+            return Err(DecdsError::InvalidProofInChunk(chunk.get_chunkset_id(), chunk.get_share_id()));
+        }
+
+        self.add_recoded_chunk(chunk.get_recoded_chunk())
+    }
+
+    /// Same as `Self::add_chunk`, but also remembers where the chunk came from. If the chunk turns out to be garbage,
+    /// i.e. it fails proof verification or claims to belong to a chunkset it can't belong to, `source` is counted as
+    /// a suspect source, which shows up in `Self::get_suspect_sources`.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk` - A reference to the `ProofCarryingChunk` to add.
+    /// * `source` - Identifier of the source serving this chunk, e.g. a share ID, a file path or a node address.
+    ///
+    /// # Returns
+    ///
+    /// Returns exactly what `Self::add_chunk` returns.
+    pub fn add_chunk_from_source(&mut self, chunk: &chunk::ProofCarryingChunk, source: &str) -> Result<(), DecdsError> {
+        let result = self.add_chunk_with_digest(Cow::Borrowed(chunk), Some(chunk.get_chunk_digest()), Some(source));
+
+        if let Err(DecdsError::InvalidProofInChunk(_, _) | DecdsError::InvalidChunkMetadata(_, _) | DecdsError::InvalidChunksetId(_, _)) = result {
+            self.record_suspect_source(source);
+        }
+
+        result
+    }
+
+    /// Same as `Self::add_chunk_from_source`, but reads the serialized chunk from `reader` (see
+    /// `ProofCarryingChunk::from_reader`). Erasure-coded data is hashed as it is read, and that digest is used for
+    /// validating the chunk, so the chunk is never hashed again. Bytes, which can't be deserialized into a chunk, are
+    /// counted against `source` too.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader from which to deserialize the chunk.
+    /// * `source` - Identifier of the source serving this chunk, e.g. a share ID, a file path or a node address.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Err(DecdsError::ProofCarryingChunkDeserializationFailed)` if a chunk can't be deserialized from `reader`.
+    /// - Otherwise, exactly what `Self::add_chunk` returns.
+    pub fn add_chunk_from_reader<R: Read>(&mut self, reader: &mut R, source: &str) -> Result<(), DecdsError> {
+        let result = ProofCarryingChunk::from_reader(reader)
+            .and_then(|(chunk, chunk_digest)| self.add_chunk_with_digest(Cow::Owned(chunk), Some(chunk_digest), Some(source)));
 
-        match self
+        if let Err(
+            DecdsError::ProofCarryingChunkDeserializationFailed(_)
+            | DecdsError::InvalidProofInChunk(_, _)
+            | DecdsError::InvalidChunkMetadata(_, _)
+            | DecdsError::InvalidChunksetId(_, _),
+        ) = result
+        {
+            self.record_suspect_source(source);
+        }
+
+        result
+    }
+
+    /// Counts one more garbage chunk against `source`. This is useful for reporting failures detected before a chunk
+    /// reaches this repairer, e.g. bytes which can't even be deserialized into a `ProofCarryingChunk`.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - Identifier of the source, which served a garbage chunk.
+    pub fn record_suspect_source(&mut self, source: &str) {
+        *self.suspect_sources.entry(source.to_string()).or_default() += 1;
+    }
+
+    /// Returns the suspect sources report: all sources which served at least one garbage chunk, along with the number
+    /// of garbage chunks served by each, ordered from the most to the least suspicious source. Sources with equal
+    /// counts are ordered by their identifiers.
+    pub fn get_suspect_sources(&self) -> Vec<(&str, usize)> {
+        let mut suspect_sources = self
+            .suspect_sources
+            .iter()
+            .map(|(source, &num_invalid_chunks)| (source.as_str(), num_invalid_chunks))
+            .collect::<Vec<(&str, usize)>>();
+
+        suspect_sources.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        suspect_sources
+    }
+
+    /// Checks if a specific chunkset within the blob is ready to be repaired (reconstructed).
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset to check.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(bool)`: `true` if the chunkset is ready for repair, `false` otherwise.
+    /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_id` is out of bounds.
+    pub fn is_chunkset_ready_to_repair(&self, chunkset_id: usize) -> Result<bool, DecdsError> {
+        Ok(self
+            .body
+            .get(&chunkset_id)
+            .ok_or_else(|| untracked_chunkset_error(&self.header, chunkset_id))?
+            .as_ref()
+            .is_some_and(|x| x.is_ready_to_repair())
+            || self.inline_data.is_some())
+    }
+
+    /// Checks if a specific chunkset within the blob has already been successfully repaired.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset to check.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(bool)`: `true` if the chunkset has already been repaired, `false` otherwise.
+    /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_id` is out of bounds.
+    pub fn is_chunkset_already_repaired(&self, chunkset_id: usize) -> Result<bool, DecdsError> {
+        Ok(self
+            .body
+            .get(&chunkset_id)
+            .ok_or_else(|| untracked_chunkset_error(&self.header, chunkset_id))?
+            .is_none()
+            && self.inline_data.is_none())
+    }
+
+    /// Returns how many more useful, i.e. linearly independent, erasure-coded chunks are required to repair a specific
+    /// chunkset. It is zero for chunksets, which are ready to repair or already repaired.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset to check.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(usize)` containing the number of chunks still required.
+    /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_id` is out of bounds.
+    pub fn get_num_remaining_chunks(&self, chunkset_id: usize) -> Result<usize, DecdsError> {
+        Ok(self
+            .body
+            .get(&chunkset_id)
+            .ok_or_else(|| untracked_chunkset_error(&self.header, chunkset_id))?
+            .as_ref()
+            .filter(|_| self.inline_data.is_none())
+            .map_or(0, |x| x.get_num_remaining_chunks()))
+    }
+
+    /// Returns how far repair has come, i.e. how many tracked chunksets are ready to repair or already repaired, and how
+    /// many of the required useful chunks have been received. It's kept up to date as chunks are added and chunksets are
+    /// repaired, so it's cheap to call after every chunk, e.g. for driving a progress bar.
+    pub fn get_progress(&self) -> RepairProgress {
+        self.progress
+    }
+
+    /// Recounts the repair progress from the decoding state of tracked chunksets, after they've been restricted to a byte
+    /// range, or restored from a checkpoint.
+    pub(crate) fn recount_progress(&mut self) {
+        if self.header.get_inline_data().is_some() {
+            self.progress = RepairProgress::new_inline();
+            if self.inline_data.is_none() {
+                self.progress.record_repaired_chunkset();
+            }
+            return;
+        }
+
+        let num_original_chunks = self.header.config.get_num_original_chunks();
+        let mut progress = RepairProgress::new(self.body.len(), num_original_chunks);
+
+        self.body.values().for_each(|chunkset| match chunkset {
+            Some(chunkset) => progress.record_useful_chunks(
+                num_original_chunks.saturating_sub(chunkset.get_num_remaining_chunks()),
+                chunkset.is_ready_to_repair(),
+            ),
+            None => {
+                progress.record_useful_chunks(num_original_chunks, true);
+                progress.record_repaired_chunkset();
+            }
+        });
+
+        self.progress = progress;
+    }
+
+    /// Discards all chunks collected so far for a chunkset, recreating its decoder from scratch, without touching any other
+    /// chunkset of this repair session. Useful when a decoder got into a bad state, e.g. it keeps failing to decode chunks,
+    /// so that its chunkset can be repaired again from other sources. An already repaired chunkset can be reset too, making
+    /// it accept chunks again, and its repaired data is dropped from the repaired chunkset cache, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset to reset.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(())` if the chunkset is reset. It is a no-op for an inline blob, which doesn't need any chunks.
+    /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_id` is out of bounds.
+    pub fn reset_chunkset(&mut self, chunkset_id: usize) -> Result<(), DecdsError> {
+        let chunkset = self
             .body
             .get_mut(&chunkset_id)
-            .ok_or(DecdsError::InvalidChunksetId(chunkset_id, self.header.get_num_chunksets()))?
-        {
-            Some(chunkset) => {
-                if self.header.validate_chunk(chunk) {
-                    if !chunkset.is_ready_to_repair() {
-                        chunkset.add_chunk_unvalidated(chunk)
+            .ok_or_else(|| untracked_chunkset_error(&self.header, chunkset_id))?;
+
+        if self.inline_data.is_some() {
+            return Ok(());
+        }
+
+        let commitment = unsafe { self.header.get_chunkset_commitment(chunkset_id).unwrap_unchecked() };
+        let mut fresh_chunkset = RepairingChunkSet::new_with_config(chunkset_id, commitment, &self.header.config);
+        if self.is_checkpointing {
+            fresh_chunkset.enable_checkpointing();
+        }
+
+        *chunkset = Some(fresh_chunkset);
+
+        if let Some(cache) = self.repaired_chunkset_cache.as_mut() {
+            cache.remove(chunkset_id);
+        }
+
+        Ok(())
+    }
+
+    /// Returns an iterator over unfinished chunksets, i.e. those neither ready to repair nor already repaired, in ascending
+    /// order of their IDs. Each item is `(chunkset_id, num_remaining_chunks)`, where `num_remaining_chunks` is how many more
+    /// useful erasure-coded chunks that chunkset requires. Once it yields nothing, all chunksets can be repaired, so fetch
+    /// loops can terminate precisely.
+    pub fn missing(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.header.get_num_chunksets()).filter_map(|chunkset_id| {
+            let num_remaining_chunks = self.get_num_remaining_chunks(chunkset_id).unwrap_or_default();
+            (num_remaining_chunks > 0).then_some((chunkset_id, num_remaining_chunks))
+        })
+    }
+
+    /// Retrieves the repaired (reconstructed) data for a specific chunkset.
+    /// This method consumes the `RepairingChunkSet` for the given ID once successful,
+    /// as the data is fully reconstructed.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset to retrieve repaired data for.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Vec<u8>)` containing the repaired chunkset data if successful.
+    /// - `Err(DecdsError::ChunksetAlreadyRepaired)` if the chunkset has already been repaired and retrieved.
+    /// - `Err(DecdsError::ChunksetNotYetReadyToRepair)` if not enough chunks have been added to repair the chunkset.
+    /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_id` is out of bounds.
+    /// - `Err(DecdsError::ChunksetRepairingFailed)` if an error occurs during the underlying chunkset repair process.
+    pub fn get_repaired_chunkset(&mut self, chunkset_id: usize) -> Result<Vec<u8>, DecdsError> {
+        self.repair_chunkset_and_notify(chunkset_id, false)
+    }
+
+    /// Same as `Self::get_repaired_chunkset`, but also checks that the padding, dropped from the last chunkset, is all
+    /// zeros, as it was before erasure-coding. Non-zero padding means whoever erasure-coded the blob tampered with it,
+    /// which integrators may want to detect explicitly, even though the returned data isn't affected by it. Padding of a
+    /// chunkset is checked only once it's repaired, so a chunkset failing the check is consumed nevertheless.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset to retrieve repaired data for.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Vec<u8>)` containing the repaired chunkset data, exactly `BlobHeader::get_chunkset_size` bytes, if successful.
+    /// - `Err(DecdsError::NonZeroChunksetPadding)` if the padding of the repaired chunkset isn't all zeros.
+    /// - Otherwise, same errors as `Self::get_repaired_chunkset`.
+    pub fn get_repaired_chunkset_checked(&mut self, chunkset_id: usize) -> Result<Vec<u8>, DecdsError> {
+        self.repair_chunkset_and_notify(chunkset_id, true)
+    }
+
+    fn repair_chunkset_and_notify(&mut self, chunkset_id: usize, check_padding: bool) -> Result<Vec<u8>, DecdsError> {
+        let was_repaired = self.is_chunkset_already_repaired(chunkset_id);
+        let result = self.repair_chunkset(chunkset_id, check_padding);
+
+        // A ready chunkset is consumed by an attempt to repair it, even if that fails, so it's no longer pending either way.
+        let is_consumed = was_repaired == Ok(false) && self.is_chunkset_already_repaired(chunkset_id) == Ok(true);
+        if is_consumed {
+            self.progress.record_repaired_chunkset();
+        }
+
+        if let Some(handler) = self.event_handler.as_ref() {
+            if result.is_ok() {
+                handler.on_chunkset_repaired(chunkset_id);
+            }
+            if is_consumed {
+                handler.on_progress(&self.progress);
+            }
+        }
+
+        result
+    }
+
+    /// Keeps up to `capacity` repaired chunksets in memory, so that `Self::read_range` can serve overlapping byte ranges,
+    /// without losing chunksets it has already repaired. See `RepairBuilder::repaired_chunkset_cache`.
+    pub(crate) fn set_repaired_chunkset_cache(&mut self, capacity: usize) {
+        self.repaired_chunkset_cache = Some(RepairedChunksetCache::new(capacity));
+    }
+
+    /// Returns a byte range of the original blob, repairing chunksets covering it, as needed.
+    ///
+    /// Repairing a chunkset consumes it, so without a repaired chunkset cache (see `RepairBuilder::repaired_chunkset_cache`),
+    /// each chunkset can be read only once. With a cache, repaired chunksets are kept in memory, least recently used ones
+    /// being evicted first, so that range-serving workloads with overlapping requests don't need to repair again. Once
+    /// evicted, a chunkset can't be read anymore.
+    ///
+    /// # Arguments
+    ///
+    /// * `byte_range` - A range `impl RangeBounds<usize>` of bytes to read, within the blob.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Vec<u8>)` containing requested bytes of the original blob, if successful.
+    /// - `Err(DecdsError::InvalidStartBound)` if the range starts after it ends.
+    /// - `Err(DecdsError::InvalidEndBound)` if the range ends beyond the blob.
+    /// - `Err(DecdsError::ChunksetAlreadyRepaired)` if a covering chunkset was repaired before, but isn't cached.
+    /// - Otherwise, same errors as `Self::get_repaired_chunkset`.
+    pub fn read_range(&mut self, byte_range: impl RangeBounds<usize>) -> Result<Vec<u8>, DecdsError> {
+        let Range { start, end } = resolve_byte_range(self.header.get_blob_size(), byte_range)?;
+
+        let mut bytes = Vec::with_capacity(end - start);
+        if start == end {
+            return Ok(bytes);
+        }
+
+        for chunkset_id in self.header.get_chunkset_ids_for_byte_range(start..end)? {
+            let (from, to) = self.header.get_byte_range_for_chunkset(chunkset_id)?;
+            let (lo, hi) = (start.max(from) - from, end.min(to) - from);
+
+            if let Some(repaired) = self.repaired_chunkset_cache.as_mut().and_then(|cache| cache.get(chunkset_id)) {
+                bytes.extend_from_slice(&repaired[lo..hi]);
+                continue;
+            }
+
+            let repaired = self.get_repaired_chunkset(chunkset_id)?;
+            bytes.extend_from_slice(&repaired[lo..hi]);
+
+            if let Some(cache) = self.repaired_chunkset_cache.as_mut() {
+                cache.insert(chunkset_id, repaired);
+            }
+        }
+
+        Ok(bytes)
+    }
+
+    /// Returns data of an application record, registered at blob creation, repairing chunksets covering it, as
+    /// `Self::read_range` does. See `BlobHeader::get_chunkset_ids_for_record` for the chunksets, whose chunks are needed.
+    /// Record boundaries are taken from the header, as is, see `BlobHeader::get_record_offsets` for authenticating them.
+    ///
+    /// # Arguments
+    ///
+    /// * `record_id` - Index of the record, among those registered at blob creation.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Vec<u8>)` containing bytes of the record, if successful.
+    /// - `Err(DecdsError::InvalidRecordId)` if `record_id` is out of bounds.
+    /// - Otherwise, same errors as `Self::read_range`.
+    pub fn read_record(&mut self, record_id: usize) -> Result<Vec<u8>, DecdsError> {
+        let (from, to) = self.header.get_byte_range_for_record(record_id)?;
+        self.read_range(from..to)
+    }
+
+    /// Restricts this repairer to a byte range of the original blob, so that it only tracks and decodes chunksets
+    /// overlapping that range. Chunks of any other chunkset are turned away, before even validating them, with
+    /// `DecdsError::ChunksetOutsideTargetRange`, and `Self::missing` only reports chunksets overlapping the range. See
+    /// `RepairBuilder::byte_range`.
+    pub(crate) fn restrict_to_byte_range(&mut self, byte_range: impl RangeBounds<usize>) -> Result<(), DecdsError> {
+        let byte_range = resolve_byte_range(self.header.get_blob_size(), byte_range)?;
+
+        // An inline blob has a single chunkset, which needs no chunks, so there's nothing to leave out.
+        if self.inline_data.is_none() {
+            let chunkset_byte_length = self.header.config.get_chunkset_byte_length();
+            let target_chunkset_ids = byte_range.start / chunkset_byte_length..byte_range.end.div_ceil(chunkset_byte_length);
+
+            self.body.retain(|chunkset_id, _| target_chunkset_ids.contains(chunkset_id));
+            self.recount_progress();
+        }
+
+        self.target_byte_range = Some(byte_range);
+        Ok(())
+    }
+
+    /// Makes chunksets remember erasure-coded data of useful chunks they are fed, so that this repairer can be checkpointed
+    /// with `Self::to_bytes`. It must be enabled before any chunk is added. See `RepairBuilder::checkpointing`.
+    pub(crate) fn enable_checkpointing(&mut self) {
+        self.body.values_mut().flatten().for_each(|chunkset| chunkset.enable_checkpointing());
+        self.is_checkpointing = true;
+    }
+
+    /// Returns `true` if this repairer can be checkpointed with `Self::to_bytes`. See `RepairBuilder::checkpointing`.
+    pub fn is_checkpointing(&self) -> bool {
+        self.is_checkpointing
+    }
+
+    /// Returns IDs of all chunksets tracked by this repairer, in ascending order. Unless it's restricted to a byte range,
+    /// these are all chunksets of the blob.
+    pub(crate) fn get_tracked_chunkset_ids(&self) -> Vec<usize> {
+        let mut chunkset_ids = self.body.keys().copied().collect::<Vec<usize>>();
+        chunkset_ids.sort_unstable();
+        chunkset_ids
+    }
+
+    /// Returns the decoding state of a tracked chunkset, which is `None` once it's repaired, or if the blob is inline.
+    pub(crate) fn get_tracked_chunkset(&self, chunkset_id: usize) -> Option<&Option<RepairingChunkSet>> {
+        self.body.get(&chunkset_id)
+    }
+
+    /// Same as `Self::get_tracked_chunkset`, but mutable, for restoring the decoding state from a checkpoint.
+    pub(crate) fn get_tracked_chunkset_mut(&mut self, chunkset_id: usize) -> Option<&mut Option<RepairingChunkSet>> {
+        self.body.get_mut(&chunkset_id)
+    }
+
+    /// Returns the byte range of the original blob, this repairer is restricted to, if any. See `RepairBuilder::byte_range`.
+    pub fn get_target_byte_range(&self) -> Option<Range<usize>> {
+        self.target_byte_range.clone()
+    }
+
+    /// Returns exactly the bytes of the target byte range, this repairer is restricted to, repairing chunksets overlapping
+    /// it, as `Self::read_range` does. Without a target byte range, the whole blob is returned. Chunksets are consumed,
+    /// so, without a repaired chunkset cache, this can be called only once.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Vec<u8>)` containing bytes of the target byte range, if successful.
+    /// - Otherwise, same errors as `Self::read_range`.
+    pub fn repair_target_range(&mut self) -> Result<Vec<u8>, DecdsError> {
+        match self.get_target_byte_range() {
+            Some(byte_range) => self.read_range(byte_range),
+            None => self.read_range(..),
+        }
+    }
+
+    /// Streams repaired data of the blob, or of the target byte range, if any, to `sink`, in order, as far as chunksets are
+    /// ready. Starting where the previous call stopped, it repairs each next chunkset, writes its bytes and frees its
+    /// decoder, stopping at the first chunkset, which isn't ready yet. Call it again as more chunks arrive, until everything
+    /// is written, so that only about a chunkset of repaired data is held in memory at a time, instead of collecting and
+    /// concatenating all chunksets. Chunksets are consumed, as with `Self::get_repaired_chunkset`, so don't retrieve them
+    /// otherwise, unless a repaired chunkset cache keeps them.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - Writer to stream repaired data into, e.g. a `BufWriter` over a file. It's flushed, if anything is written.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(usize)` containing the number of bytes written during this call, which is zero once everything is written, or
+    ///   if the next chunkset isn't ready yet. See `Self::get_num_streamed_bytes` for the total.
+    /// - `Err(DecdsError::RepairedDataWriteFailed)` if writing to `sink` fails. The chunkset being written is consumed, so
+    ///   streaming can't go on, unless a repaired chunkset cache keeps it.
+    /// - `Err(DecdsError::ChunksetAlreadyRepaired)` if the next chunkset was retrieved before, but isn't cached.
+    /// - Otherwise, same errors as `Self::get_repaired_chunkset`.
+    pub fn write_repaired(&mut self, mut sink: impl Write) -> Result<usize, DecdsError> {
+        let Range { start, end } = self.get_target_byte_range().unwrap_or(0..self.header.get_blob_size());
+        let chunkset_byte_length = self.header.config.get_chunkset_byte_length();
+        let map_write_err = |err: std::io::Error| DecdsError::RepairedDataWriteFailed(err.to_string());
+
+        let mut num_written_bytes = 0;
+        while start + self.num_streamed_bytes < end {
+            let offset = start + self.num_streamed_bytes;
+            let chunkset_id = offset / chunkset_byte_length;
+
+            let (from, to) = self.header.get_byte_range_for_chunkset(chunkset_id)?;
+            let (lo, hi) = (offset - from, end.min(to) - from);
+
+            if let Some(repaired) = self.repaired_chunkset_cache.as_mut().and_then(|cache| cache.get(chunkset_id)) {
+                sink.write_all(&repaired[lo..hi]).map_err(map_write_err)?;
+            } else {
+                if self.is_chunkset_already_repaired(chunkset_id)? {
+                    return Err(DecdsError::ChunksetAlreadyRepaired(chunkset_id));
+                }
+                if !self.is_chunkset_ready_to_repair(chunkset_id)? {
+                    break;
+                }
+
+                let repaired = self.get_repaired_chunkset(chunkset_id)?;
+                sink.write_all(&repaired[lo..hi]).map_err(map_write_err)?;
+
+                if let Some(cache) = self.repaired_chunkset_cache.as_mut() {
+                    cache.insert(chunkset_id, repaired);
+                }
+            }
+
+            self.num_streamed_bytes += hi - lo;
+            num_written_bytes += hi - lo;
+        }
+
+        if num_written_bytes > 0 {
+            sink.flush().map_err(map_write_err)?;
+        }
+
+        Ok(num_written_bytes)
+    }
+
+    /// Returns the total number of bytes streamed by `Self::write_repaired` so far. Everything is written, once it equals
+    /// the length of the target byte range, if any, or the blob size.
+    pub fn get_num_streamed_bytes(&self) -> usize {
+        self.num_streamed_bytes
+    }
+
+    /// Restores the number of bytes streamed by `Self::write_repaired`, when resuming from a checkpoint.
+    pub(crate) fn set_num_streamed_bytes(&mut self, num_streamed_bytes: usize) {
+        self.num_streamed_bytes = num_streamed_bytes;
+    }
+
+    /// Returns an iterator, repairing and yielding contiguous ready chunksets, in ascending order of their IDs, starting
+    /// from the lowest chunkset, which isn't repaired yet. It stops at the first chunkset, which isn't ready, so consumers
+    /// can drain the blob front-to-back, as chunks arrive, without polling `Self::is_chunkset_ready_to_repair` themselves.
+    /// Yielded chunksets are consumed, as with `Self::get_repaired_chunkset`, so the next call resumes right after them.
+    ///
+    /// # Returns
+    ///
+    /// Returns an iterator over `Result`s which are:
+    /// - `Ok((usize, Vec<u8>))` containing the chunkset ID and its repaired data, exactly `BlobHeader::get_chunkset_size`
+    ///   bytes.
+    /// - `Err(DecdsError)` for the same errors as `Self::get_repaired_chunkset`, after which the iterator yields nothing.
+    pub fn drain_ready_in_order(&mut self) -> impl Iterator<Item = Result<(usize, Vec<u8>), DecdsError>> + '_ {
+        let mut chunkset_ids = self.get_tracked_chunkset_ids();
+        let num_repaired_chunksets = chunkset_ids
+            .iter()
+            .take_while(|&&chunkset_id| self.is_chunkset_already_repaired(chunkset_id).unwrap_or(false))
+            .count();
+        chunkset_ids.drain(..num_repaired_chunksets);
+
+        let mut has_failed = false;
+        chunkset_ids.into_iter().map_while(move |chunkset_id| {
+            if has_failed || !self.is_chunkset_ready_to_repair(chunkset_id).unwrap_or(false) {
+                return None;
+            }
+
+            let result = self.get_repaired_chunkset(chunkset_id).map(|repaired| (chunkset_id, repaired));
+            has_failed = result.is_err();
+            Some(result)
+        })
+    }
+
+    /// Same as `Self::get_repaired_chunkset`, but writes the repaired chunkset data into the beginning of the caller-provided
+    /// buffer `out`, instead of returning it. Repairing a whole blob into a single preallocated buffer, at offsets given by
+    /// `BlobHeader::get_byte_range_for_chunkset`, keeps peak memory at about the blob size, instead of growing a vector,
+    /// chunkset by chunkset. The buffer length is checked before anything is repaired, so the chunkset can still be
+    /// repaired with a larger buffer, if it is too small.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset to repair.
+    /// * `out` - Buffer to write the repaired data into, at least `BlobHeader::get_chunkset_size` bytes long.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(usize)` containing the number of bytes written into `out`, i.e. the size of the chunkset, if successful.
+    /// - `Err(DecdsError::RepairOutputBufferTooSmall)` if `out` is shorter than the chunkset.
+    /// - Otherwise, same errors as `Self::get_repaired_chunkset`.
+    pub fn repair_chunkset_into(&mut self, chunkset_id: usize, out: &mut [u8]) -> Result<usize, DecdsError> {
+        let chunkset_size = self.header.get_chunkset_size(chunkset_id)?;
+        if out.len() < chunkset_size {
+            return Err(DecdsError::RepairOutputBufferTooSmall(chunkset_id, out.len(), chunkset_size));
+        }
+
+        let repaired = self.get_repaired_chunkset(chunkset_id)?;
+        out[..repaired.len()].copy_from_slice(&repaired);
+
+        Ok(repaired.len())
+    }
+
+    fn repair_chunkset(&mut self, chunkset_id: usize, check_padding: bool) -> Result<Vec<u8>, DecdsError> {
+        self.is_chunkset_already_repaired(chunkset_id).and_then(|yes| {
+            if yes {
+                Err(DecdsError::ChunksetAlreadyRepaired(chunkset_id))
+            } else {
+                self.is_chunkset_ready_to_repair(chunkset_id).and_then(|yes| unsafe {
+                    if let Some(inline_data) = self.inline_data.take() {
+                        Ok(inline_data)
+                    } else if yes {
+                        self.body
+                            .insert(chunkset_id, None)
+                            .unwrap_unchecked()
+                            .unwrap_unchecked()
+                            .repair()
+                            .and_then(|mut repaired| {
+                                let chunkset_size = self.header.get_chunkset_size(chunkset_id).unwrap_unchecked();
+                                if check_padding && repaired[chunkset_size..].iter().any(|&byte| byte != 0) {
+                                    return Err(DecdsError::NonZeroChunksetPadding(chunkset_id));
+                                }
+
+                                repaired.truncate(chunkset_size);
+                                Ok(repaired)
+                            })
                     } else {
-                        Err(DecdsError::ChunksetReadyToRepair(chunkset_id))
+                        Err(DecdsError::ChunksetNotYetReadyToRepair(chunkset_id))
                     }
-                } else {
-                    Err(DecdsError::InvalidProofInChunk(chunkset_id))
-                }
+                })
+            }
+        })
+    }
+}
+
+#[cfg(all(test, feature = "coding"))]
+mod tests {
+    use crate::{
+        AddOutcome, BlobConfig, BlobHeader, BlobHeaderView, ChunkFault, ContentFlags, DecdsBuilder, MerkleTree, ProofCarryingChunk, RepairBuilder,
+        RepairingBlob, blob::Blob, chunkset::ChunkSet, consts, errors::DecdsError, pipeline::BlobPipeline,
+    };
+    use rand::{Rng, seq::SliceRandom};
+    use std::io::{Cursor, Read};
+
+    #[test]
+    fn prop_test_blob_preparation_and_commitment_works() {
+        const NUM_TEST_ITERATIONS: usize = 10;
+
+        const MIN_BLOB_DATA_BYTE_LEN: usize = 1usize;
+        const MAX_BLOB_DATA_BYTE_LEN: usize = 1usize << 30;
+
+        let mut rng = rand::rng();
+
+        (0..NUM_TEST_ITERATIONS).for_each(|_| {
+            let blob_byte_len = rng.random_range(MIN_BLOB_DATA_BYTE_LEN..=MAX_BLOB_DATA_BYTE_LEN);
+            let blob_data = (0..blob_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+
+            let blob = Blob::new(blob_data).expect("Must be able to prepare blob");
+            let blob_header = blob.get_blob_header();
+
+            assert!(
+                (0..consts::DECDS_NUM_ERASURE_CODED_SHARES)
+                    .flat_map(|share_id| blob.get_share(share_id).expect("Must be able to get erasure coded shares"))
+                    .all(|share| blob_header.validate_chunk(&share))
+            );
+        });
+    }
+
+    #[test]
+    fn test_blob_new_with_seed_is_reproducible() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..rng.random_range(1usize..=24usize << 20)).map(|_| rng.random()).collect::<Vec<u8>>();
+        let seed = rng.random::<[u8; 32]>();
+
+        let blob = Blob::new_with_seed(blob_data.clone(), &seed).expect("Must be able to prepare blob");
+        let other_blob = Blob::new_with_seed(blob_data.clone(), &seed).expect("Must be able to prepare blob");
+        assert_eq!(blob.get_blob_header(), other_blob.get_blob_header());
+
+        (0..consts::DECDS_NUM_ERASURE_CODED_SHARES).for_each(|share_id| {
+            assert_eq!(
+                blob.get_share(share_id).expect("Must be able to get erasure-coded share"),
+                other_blob.get_share(share_id).expect("Must be able to get erasure-coded share")
+            );
+        });
+
+        let mut other_seed = seed;
+        other_seed[0] ^= 1;
+
+        let blob_with_other_seed = Blob::new_with_seed(blob_data, &other_seed).expect("Must be able to prepare blob");
+        assert_ne!(
+            blob.get_blob_header().get_root_commitment(),
+            blob_with_other_seed.get_blob_header().get_root_commitment()
+        );
+    }
+
+    #[test]
+    fn test_get_chunkset_commitment() {
+        let mut rng = rand::rng();
+
+        let blob_byte_len = (ChunkSet::BYTE_LENGTH * 2) + (ChunkSet::BYTE_LENGTH / 2);
+        let blob_data = (0..blob_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+
+        let blob = Blob::new(blob_data).unwrap();
+        let header = blob.get_blob_header();
+
+        // Valid chunkset ID
+        let commitment = header.get_chunkset_commitment(0);
+        assert!(commitment.is_ok());
+
+        let commitment = header.get_chunkset_commitment(1);
+        assert!(commitment.is_ok());
+
+        // Invalid chunkset ID
+        let err = header.get_chunkset_commitment(header.get_num_chunksets());
+        assert_eq!(err, Err(DecdsError::InvalidChunksetId(header.get_num_chunksets(), header.get_num_chunksets())));
+    }
+
+    #[test]
+    fn test_get_chunkset_size() {
+        let mut rng = rand::rng();
+
+        // Blob size: 2.5 chunksets -> 2 full, 1 half
+        let blob_byte_len = (ChunkSet::BYTE_LENGTH * 2) + (ChunkSet::BYTE_LENGTH / 2);
+        let blob_data = (0..blob_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+
+        let blob = Blob::new(blob_data).unwrap();
+        let header = blob.get_blob_header();
+
+        // Full chunkset
+        assert_eq!(header.get_chunkset_size(0).unwrap(), ChunkSet::BYTE_LENGTH);
+        assert_eq!(header.get_chunkset_size(1).unwrap(), ChunkSet::BYTE_LENGTH);
+
+        // Partial chunkset
+        assert_eq!(header.get_chunkset_size(2).unwrap(), ChunkSet::BYTE_LENGTH / 2);
+
+        // Invalid chunkset ID
+        assert_eq!(
+            header.get_chunkset_size(header.get_num_chunksets()).unwrap_err(),
+            DecdsError::InvalidChunksetId(header.get_num_chunksets(), header.get_num_chunksets())
+        );
+    }
+
+    #[test]
+    fn test_get_byte_range_for_chunkset() {
+        let mut rng = rand::rng();
+
+        let blob_byte_len = (ChunkSet::BYTE_LENGTH * 2) + (ChunkSet::BYTE_LENGTH / 2);
+        let blob_data = (0..blob_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+
+        let blob = Blob::new(blob_data).unwrap();
+        let header = blob.get_blob_header();
+
+        // First chunkset
+        assert_eq!(header.get_byte_range_for_chunkset(0).unwrap(), (0, ChunkSet::BYTE_LENGTH));
+
+        // Second chunkset
+        assert_eq!(
+            header.get_byte_range_for_chunkset(1).unwrap(),
+            (ChunkSet::BYTE_LENGTH, ChunkSet::BYTE_LENGTH * 2)
+        );
+
+        // Last (partial) chunkset
+        assert_eq!(header.get_byte_range_for_chunkset(2).unwrap(), (ChunkSet::BYTE_LENGTH * 2, blob_byte_len));
+
+        // Invalid chunkset ID
+        assert_eq!(
+            header.get_byte_range_for_chunkset(header.get_num_chunksets()).unwrap_err(),
+            DecdsError::InvalidChunksetId(header.get_num_chunksets(), header.get_num_chunksets())
+        );
+    }
+
+    #[test]
+    fn test_get_chunkset_ids_for_byte_range() {
+        let mut rng = rand::rng();
+
+        let blob_byte_len = (ChunkSet::BYTE_LENGTH * 2) + (ChunkSet::BYTE_LENGTH / 2);
+        let blob_data = (0..blob_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+
+        let blob = Blob::new(blob_data).unwrap();
+        let header = blob.get_blob_header();
+
+        // Range within a single chunkset
+        assert_eq!(header.get_chunkset_ids_for_byte_range(0..10).unwrap(), vec![0]);
+        assert_eq!(
+            header
+                .get_chunkset_ids_for_byte_range(ChunkSet::BYTE_LENGTH + 10..ChunkSet::BYTE_LENGTH + 20)
+                .unwrap(),
+            vec![1]
+        );
+
+        // Range spanning multiple chunksets
+        assert_eq!(
+            header.get_chunkset_ids_for_byte_range(10..(ChunkSet::BYTE_LENGTH * 1 + 10)).unwrap(),
+            vec![0, 1]
+        );
+        assert_eq!(header.get_chunkset_ids_for_byte_range(10..blob_byte_len).unwrap(), vec![0, 1, 2]);
+
+        // Range exactly matching chunkset boundaries
+        assert_eq!(header.get_chunkset_ids_for_byte_range(0..ChunkSet::BYTE_LENGTH).unwrap(), vec![0]);
+        assert_eq!(header.get_chunkset_ids_for_byte_range(0..=(ChunkSet::BYTE_LENGTH - 1)).unwrap(), vec![0]);
+
+        // Edge cases for bounds
+        assert_eq!(header.get_chunkset_ids_for_byte_range(0..0).unwrap_err(), DecdsError::InvalidEndBound(0));
+        assert_eq!(header.get_chunkset_ids_for_byte_range(0..=0).unwrap(), vec![0]); // Covers first byte of first chunkset
+
+        // Invalid end bound (range beyond blob size)
+        let end_beyond_blob = header.get_blob_size() + ChunkSet::BYTE_LENGTH;
+        let expected_end_chunkset_id = end_beyond_blob.saturating_sub(1) / ChunkSet::BYTE_LENGTH;
+        assert_eq!(
+            header.get_chunkset_ids_for_byte_range(0..end_beyond_blob).unwrap_err(),
+            DecdsError::InvalidChunksetId(expected_end_chunkset_id, header.get_num_chunksets())
+        );
+
+        // Test for `InvalidEndBound(usize::MAX)` for unbounded ranges
+        assert_eq!(header.get_chunkset_ids_for_byte_range(..).unwrap_err(), DecdsError::InvalidEndBound(usize::MAX));
+        assert_eq!(
+            header.get_chunkset_ids_for_byte_range(0..).unwrap_err(),
+            DecdsError::InvalidEndBound(usize::MAX)
+        );
+    }
+
+    #[test]
+    fn test_blob_header_arithmetic_does_not_overflow() {
+        // Crafted header, claiming far more chunksets than its size allows
+        let header = BlobHeader {
+            byte_length: usize::MAX,
+            num_chunksets: usize::MAX,
+            config: BlobConfig::default(),
+            content_flags: ContentFlags::default(),
+            digest: blake3::hash(b"digest"),
+            root_commitment: blake3::hash(b"root_commitment"),
+            chunkset_root_commitments: Vec::new(),
+            inline_data: None,
+            record_offsets: Vec::new(),
+        };
+
+        assert_eq!(header.get_num_chunks(), usize::MAX);
+        assert_eq!(header.get_last_chunkset_size(), 0);
+        assert_eq!(header.get_padding_byte_length(), ChunkSet::BYTE_LENGTH);
+
+        let last_chunkset_id = usize::MAX - 1;
+        assert!(matches!(
+            header.get_byte_range_for_chunkset(last_chunkset_id),
+            Err(DecdsError::ArithmeticOverflow(_))
+        ));
+        assert!(matches!(header.get_chunkset_size(last_chunkset_id), Err(DecdsError::ArithmeticOverflow(_))));
+
+        let last_byte_chunkset_id = usize::MAX / ChunkSet::BYTE_LENGTH;
+        assert_eq!(
+            header.get_byte_range_for_chunkset(last_byte_chunkset_id),
+            Ok((last_byte_chunkset_id * ChunkSet::BYTE_LENGTH, usize::MAX))
+        );
+        assert_eq!(
+            header.get_chunkset_ids_for_byte_range(usize::MAX - 1..=usize::MAX),
+            Ok(vec![last_byte_chunkset_id])
+        );
+
+        // Chunk IDs of a huge chunkset can't be computed, so it can't be encoded either
+        assert!(matches!(
+            DecdsBuilder::new().encode_chunkset(usize::MAX / ChunkSet::NUM_ERASURE_CODED_CHUNKS + 1, vec![0u8; 1024]),
+            Err(DecdsError::ArithmeticOverflow(_))
+        ));
+    }
+
+    #[test]
+    fn test_blob_header_serialization_deserialization() {
+        let mut rng = rand::rng();
+
+        let blob_byte_len = ChunkSet::BYTE_LENGTH * 3;
+        let blob_data = (0..blob_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+
+        let blob = Blob::new(blob_data).unwrap();
+        let original_header = blob.get_blob_header().clone();
+
+        let serialized_header = original_header.to_bytes().expect("Header serialization failed");
+        let (deserialized_header, bytes_read) = BlobHeader::from_bytes(&serialized_header).expect("Header deserialization failed");
+
+        assert_eq!(original_header, deserialized_header);
+        assert_eq!(serialized_header.len(), bytes_read);
+
+        // Test deserialization failure with lesser bytes
+        assert!(BlobHeader::from_bytes(&serialized_header[..(serialized_header.len() / 2)]).is_err());
+
+        // Test deserialization failure with blob size not matching number of chunksets
+        [0, ChunkSet::BYTE_LENGTH * 2, ChunkSet::BYTE_LENGTH * 3 + 1, usize::MAX]
+            .iter()
+            .for_each(|&byte_length| {
+                let mut tampered_header = original_header.clone();
+                tampered_header.byte_length = byte_length;
+
+                let serialized_header = tampered_header.to_bytes().expect("Header serialization failed");
+                assert!(matches!(
+                    BlobHeader::from_bytes(&serialized_header),
+                    Err(DecdsError::BlobHeaderDeserializationFailed(_))
+                ));
+            });
+
+        // Test deserialization failure with a tampered list of chunkset commitments, which still passes count checks
+        let mut tampered_header = original_header.clone();
+        tampered_header.chunkset_root_commitments.swap(0, 2);
+        assert!(!tampered_header.verify_root_commitment());
+
+        let serialized_header = tampered_header.to_bytes().expect("Header serialization failed");
+        assert_eq!(
+            BlobHeader::from_bytes(&serialized_header),
+            Err(DecdsError::BlobHeaderDeserializationFailed(
+                "chunkset commitments do not match root commitment".to_string()
+            ))
+        );
+        assert!(BlobHeader::from_reader(&mut &serialized_header[..]).is_err());
+
+        // Each chunkset commitment is provable on its own, against the root commitment
+        for chunkset_id in 0..original_header.get_num_chunksets() {
+            let proof = original_header.get_chunkset_commitment_proof(chunkset_id).unwrap();
+            let commitment = original_header.get_chunkset_commitment(chunkset_id).unwrap();
+
+            assert!(MerkleTree::verify_proof(chunkset_id, commitment, &proof, original_header.get_root_commitment()));
+        }
+
+        let proof = original_header.get_chunkset_commitment_proof(0).unwrap();
+        let tampered_commitment = tampered_header.get_chunkset_commitment(0).unwrap();
+        assert!(!MerkleTree::verify_proof(0, tampered_commitment, &proof, original_header.get_root_commitment()));
+        assert_eq!(original_header.get_chunkset_commitment_proof(3), Err(DecdsError::InvalidChunksetId(3, 3)));
+
+        let mut resized_header = original_header.clone();
+        resized_header.byte_length = ChunkSet::BYTE_LENGTH * 2 + 1;
+        let serialized_header = resized_header.to_bytes().expect("Header serialization failed");
+        let (deserialized_header, _) = BlobHeader::from_bytes(&serialized_header).expect("Header deserialization failed");
+        assert_eq!(deserialized_header.get_chunkset_size(2).unwrap(), 1);
+    }
+
+    /// Reader serving at most a few bytes per read call, failing once `fail_at` bytes are served, if asked to.
+    struct TricklingReader {
+        data: Cursor<Vec<u8>>,
+        fail_at: Option<u64>,
+    }
+
+    impl Read for TricklingReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.fail_at.is_some_and(|fail_at| self.data.position() >= fail_at) {
+                return Err(std::io::Error::other("disk on fire"));
             }
-            None => Err(DecdsError::ChunksetAlreadyRepaired(chunkset_id)),
+
+            let len = buf.len().min(7);
+            self.data.read(&mut buf[..len])
         }
     }
 
-    /// Checks if a specific chunkset within the blob is ready to be repaired (reconstructed).
-    ///
-    /// # Arguments
-    ///
-    /// * `chunkset_id` - The ID of the chunkset to check.
-    ///
-    /// # Returns
-    ///
-    /// Returns a `Result` which is:
-    /// - `Ok(bool)`: `true` if the chunkset is ready for repair, `false` otherwise.
-    /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_id` is out of bounds.
-    pub fn is_chunkset_ready_to_repair(&self, chunkset_id: usize) -> Result<bool, DecdsError> {
-        Ok(self
-            .body
-            .get(&chunkset_id)
-            .ok_or(DecdsError::InvalidChunksetId(chunkset_id, self.header.get_num_chunksets()))?
-            .as_ref()
-            .is_some_and(|x| x.is_ready_to_repair()))
+    #[test]
+    fn test_blob_from_reader() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..1024 * 1024 + 1).map(|_| rng.random()).collect::<Vec<u8>>();
+
+        let blob = Blob::from_reader(Cursor::new(blob_data.clone())).expect("Must be able to build blob from reader");
+        let header = blob.get_blob_header();
+        assert_eq!(header.get_blob_size(), blob_data.len());
+        assert_eq!(header.get_blob_digest(), blake3::hash(&blob_data));
+        assert_eq!(header.get_num_chunksets(), 1);
+
+        let mut repairer = RepairingBlob::new(header.clone());
+        (0..consts::DECDS_NUM_ERASURE_CODED_SHARES).rev().for_each(|share_id| {
+            blob.iter_share(share_id).unwrap().for_each(|chunk| {
+                let _ = repairer.add_chunk(chunk);
+            });
+        });
+        assert_eq!(repairer.get_repaired_chunkset(0).unwrap(), blob_data);
+
+        // Short reads and chunkset boundaries give the same blob as building it from memory
+        let config = BlobConfig::new().chunkset_byte_length(1024);
+        let builder = DecdsBuilder::new().config(config).seed([7u8; 32]);
+
+        [1023, 1024, 1025, 3 * 1024].into_iter().for_each(|blob_byte_len| {
+            let reader = TricklingReader {
+                data: Cursor::new(blob_data[..blob_byte_len].to_vec()),
+                fail_at: None,
+            };
+
+            let streamed = builder.build_from_reader(reader).expect("Must be able to build blob from reader");
+            let expected = builder.build(blob_data[..blob_byte_len].to_vec()).unwrap();
+
+            assert_eq!(streamed.get_blob_header(), expected.get_blob_header());
+            assert_eq!(streamed.get_share(5).unwrap(), expected.get_share(5).unwrap());
+        });
+
+        // Inline threshold applies, even if the blob fills the whole first chunkset
+        let inline_blob = builder
+            .inline_threshold(1024)
+            .build_from_reader(Cursor::new(blob_data[..1024].to_vec()))
+            .unwrap();
+        assert_eq!(inline_blob.get_blob_header().get_inline_data(), Some(&blob_data[..1024]));
+
+        assert_eq!(Blob::from_reader(std::io::empty()).err(), Some(DecdsError::EmptyDataForBlob));
+        assert!(matches!(
+            builder.build_from_reader(TricklingReader {
+                data: Cursor::new(blob_data),
+                fail_at: Some(2000),
+            }),
+            Err(DecdsError::BlobDataReadFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_lazy_blob_generates_shares_on_demand() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..3 * 1024 + 1).map(|_| rng.random()).collect::<Vec<u8>>();
+        let config = BlobConfig::new().num_shares(12).chunkset_byte_length(1024);
+        let builder = DecdsBuilder::new().config(config).seed([7u8; 32]);
+
+        let eager = builder.build(blob_data.clone()).unwrap();
+        let lazy = builder.lazy_shares(true).build(blob_data.clone()).unwrap();
+        assert_eq!(lazy.get_blob_header(), eager.get_blob_header());
+        assert!(lazy.chunk_digests().eq(eager.chunk_digests()));
+
+        (0..config.get_num_shares()).for_each(|share_id| {
+            assert_eq!(lazy.get_share(share_id).unwrap(), eager.get_share(share_id).unwrap());
+            assert!(
+                lazy.share_stream(share_id)
+                    .unwrap()
+                    .map(Result::unwrap)
+                    .eq(eager.share_stream(share_id).unwrap().map(Result::unwrap))
+            );
+        });
+
+        assert_eq!(
+            lazy.get_share(config.get_num_shares()),
+            Err(DecdsError::InvalidErasureCodedShareId(config.get_num_shares()))
+        );
+        assert!(lazy.iter_share(0).is_err());
+        assert_eq!(lazy.get_chunk(0, 0), Err(DecdsError::ChunksNotMaterialized));
+        assert_eq!(lazy.get_chunk(4, 0), Err(DecdsError::InvalidChunksetId(4, 4)));
+
+        // Without a seed, one is sampled for the blob, so that shares still match the header
+        let lazy = DecdsBuilder::new().config(config).lazy_shares(true).build(blob_data.clone()).unwrap();
+        let header = lazy.get_blob_header();
+        let mut repairer = RepairingBlob::new(header.clone());
+
+        (0..config.get_num_shares()).rev().for_each(|share_id| {
+            lazy.get_share(share_id).unwrap().iter().for_each(|chunk| {
+                assert!(chunk.validate_inclusion_in_blob(header.get_root_commitment()));
+                let _ = repairer.add_chunk(chunk);
+            });
+        });
+
+        let repaired = (0..header.get_num_chunksets())
+            .flat_map(|chunkset_id| repairer.get_repaired_chunkset(chunkset_id).unwrap())
+            .collect::<Vec<u8>>();
+        assert_eq!(repaired, blob_data);
+    }
+
+    #[test]
+    fn test_blob_from_file() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..3 * 1024 + 1).map(|_| rng.random()).collect::<Vec<u8>>();
+        let blob_path = std::env::temp_dir().join(format!("decds-test-blob-from-file-{}", std::process::id()));
+        std::fs::write(&blob_path, &blob_data).unwrap();
+
+        // SAFETY: The file is private to this test, and is only rewritten in between mappings.
+        let blob = unsafe { Blob::from_file(&blob_path) }.expect("Must be able to build blob from file");
+        assert_eq!(blob.get_blob_header().get_blob_size(), blob_data.len());
+        assert_eq!(blob.get_blob_header().get_blob_digest(), blake3::hash(&blob_data));
+
+        let builder = DecdsBuilder::new().config(BlobConfig::new().chunkset_byte_length(1024)).seed([7u8; 32]);
+        let mapped = unsafe { builder.build_from_file(&blob_path) }.expect("Must be able to build blob from file");
+        let expected = builder.build(blob_data.clone()).unwrap();
+        assert_eq!(mapped.get_blob_header(), expected.get_blob_header());
+        assert_eq!(mapped.get_share(9).unwrap(), expected.get_share(9).unwrap());
+
+        let inline_blob = unsafe { builder.inline_threshold(1024).build_from_file(&blob_path) }.unwrap();
+        assert!(!inline_blob.get_blob_header().is_inline());
+
+        std::fs::write(&blob_path, &blob_data[..1024]).unwrap();
+        let inline_blob = unsafe { builder.inline_threshold(1024).build_from_file(&blob_path) }.unwrap();
+        assert_eq!(inline_blob.get_blob_header().get_inline_data(), Some(&blob_data[..1024]));
+
+        std::fs::write(&blob_path, []).unwrap();
+        assert_eq!(unsafe { Blob::from_file(&blob_path) }.err(), Some(DecdsError::EmptyDataForBlob));
+
+        std::fs::remove_file(&blob_path).unwrap();
+        assert!(matches!(unsafe { Blob::from_file(&blob_path) }, Err(DecdsError::BlobDataReadFailed(_))));
     }
 
-    /// Checks if a specific chunkset within the blob has already been successfully repaired.
-    ///
-    /// # Arguments
-    ///
-    /// * `chunkset_id` - The ID of the chunkset to check.
-    ///
-    /// # Returns
-    ///
-    /// Returns a `Result` which is:
-    /// - `Ok(bool)`: `true` if the chunkset has already been repaired, `false` otherwise.
-    /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_id` is out of bounds.
-    pub fn is_chunkset_already_repaired(&self, chunkset_id: usize) -> Result<bool, DecdsError> {
-        Ok(self
-            .body
-            .get(&chunkset_id)
-            .ok_or(DecdsError::InvalidChunksetId(chunkset_id, self.header.get_num_chunksets()))?
-            .is_none())
+    #[test]
+    fn test_blob_header_validate_chunkset() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..3 * 1024 + 1).map(|_| rng.random()).collect::<Vec<u8>>();
+        let blob = DecdsBuilder::new()
+            .config(BlobConfig::new().chunkset_byte_length(1024))
+            .build(blob_data)
+            .expect("Must be able to build blob");
+        let header = blob.get_blob_header();
+
+        let mut chunks = (0..header.get_config().get_num_shares())
+            .map(|share_id| blob.get_chunk(1, share_id).expect("Must be able to get chunk").clone())
+            .collect::<Vec<ProofCarryingChunk>>();
+        assert_eq!(header.validate_chunkset(&chunks), Ok(()));
+        assert_eq!(header.validate_chunkset(&[]), Ok(()));
+
+        chunks[7] = ChunkFault::FlipPayloadBit(0).apply(&chunks[7]);
+        chunks[9] = ChunkFault::FlipPayloadBit(0).apply(&chunks[9]);
+        let err = header.validate_chunkset(&chunks).expect_err("Tampered chunks must be rejected");
+        assert_eq!(err.get_chunk_index(), 7);
+        assert_eq!(err.get_error(), &DecdsError::InvalidProofInChunk(1, 7));
+
+        chunks[3] = blob.get_chunk(2, 3).expect("Must be able to get chunk").clone();
+        let err = header.validate_chunkset(&chunks).expect_err("Chunk of another chunkset must be rejected");
+        assert_eq!(err.get_chunk_index(), 3);
+        assert_eq!(err.get_error(), &DecdsError::InvalidChunkMetadata(2, 3));
     }
 
-    /// Retrieves the repaired (reconstructed) data for a specific chunkset.
-    /// This method consumes the `RepairingChunkSet` for the given ID once successful,
-    /// as the data is fully reconstructed.
-    ///
-    /// # Arguments
-    ///
-    /// * `chunkset_id` - The ID of the chunkset to retrieve repaired data for.
-    ///
-    /// # Returns
-    ///
-    /// Returns a `Result` which is:
-    /// - `Ok(Vec<u8>)` containing the repaired chunkset data if successful.
-    /// - `Err(DecdsError::ChunksetAlreadyRepaired)` if the chunkset has already been repaired and retrieved.
-    /// - `Err(DecdsError::ChunksetNotYetReadyToRepair)` if not enough chunks have been added to repair the chunkset.
-    /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_id` is out of bounds.
-    /// - `Err(DecdsError::ChunksetRepairingFailed)` if an error occurs during the underlying chunkset repair process.
-    pub fn get_repaired_chunkset(&mut self, chunkset_id: usize) -> Result<Vec<u8>, DecdsError> {
-        self.is_chunkset_already_repaired(chunkset_id).and_then(|yes| {
-            if yes {
-                Err(DecdsError::ChunksetAlreadyRepaired(chunkset_id))
-            } else {
-                self.is_chunkset_ready_to_repair(chunkset_id).and_then(|yes| unsafe {
-                    if yes {
-                        self.body
-                            .insert(chunkset_id, None)
-                            .unwrap_unchecked()
-                            .unwrap_unchecked()
-                            .repair()
-                            .map(|mut repaired| {
-                                repaired.truncate(self.header.get_chunkset_size(chunkset_id).unwrap_unchecked());
-                                repaired
-                            })
-                    } else {
-                        Err(DecdsError::ChunksetNotYetReadyToRepair(chunkset_id))
-                    }
-                })
-            }
-        })
+    #[test]
+    fn test_blob_reassemble() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..3 * 1024 + 1).map(|_| rng.random()).collect::<Vec<u8>>();
+        let builder = DecdsBuilder::new().config(BlobConfig::new().chunkset_byte_length(1024));
+        let blob = builder.build(blob_data.clone()).expect("Must be able to build blob");
+        let header = blob.get_blob_header().clone();
+        let num_shares = header.get_config().get_num_shares();
+
+        let mut stored_chunks = (0..num_shares)
+            .flat_map(|share_id| blob.get_share(share_id).expect("Must be able to get share"))
+            .collect::<Vec<ProofCarryingChunk>>();
+        stored_chunks.shuffle(&mut rng);
+
+        let duplicate_chunk = stored_chunks[0].clone();
+        let reassembled = Blob::reassemble(header.clone(), stored_chunks.iter().cloned().chain([duplicate_chunk])).expect("Must be able to reassemble blob");
+        assert_eq!(reassembled.get_blob_header(), &header);
+        assert_eq!(reassembled.get_issued_shares(), 0);
+        assert!((0..num_shares).all(|share_id| reassembled.get_share(share_id).unwrap() == blob.get_share(share_id).unwrap()));
+
+        let missing_chunk = stored_chunks.pop().expect("Must have a chunk");
+        assert_eq!(
+            Blob::reassemble(header.clone(), stored_chunks.clone()).err(),
+            Some(DecdsError::MissingChunk(missing_chunk.get_chunkset_id(), missing_chunk.get_local_chunk_id()))
+        );
+
+        stored_chunks.push(ChunkFault::FlipPayloadBit(0).apply(&missing_chunk));
+        assert_eq!(
+            Blob::reassemble(header, stored_chunks.clone()).err(),
+            Some(DecdsError::InvalidProofInChunk(
+                missing_chunk.get_chunkset_id(),
+                missing_chunk.get_local_chunk_id()
+            ))
+        );
+
+        let inline_blob = Blob::new_with_inline_threshold(blob_data[..1024].to_vec(), 1024).expect("Must be able to prepare inline blob");
+        let inline_header = inline_blob.get_blob_header().clone();
+        assert!(Blob::reassemble(inline_header.clone(), []).is_ok());
+        assert!(matches!(
+            Blob::reassemble(inline_header, stored_chunks),
+            Err(DecdsError::InvalidProofInChunk(_, _))
+        ));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::{BlobHeader, ProofCarryingChunk, RepairingBlob, blob::Blob, chunkset::ChunkSet, consts, errors::DecdsError};
-    use blake3;
-    use rand::Rng;
+    #[test]
+    fn test_inline_blob_building_and_repairing_works() {
+        let mut rng = rand::rng();
+
+        let blob_byte_len = 1024;
+        let blob_data = (0..blob_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+
+        let blob = Blob::new_with_inline_threshold(blob_data.clone(), blob_byte_len).expect("Must be able to prepare inline blob");
+        let blob_header = blob.get_blob_header().clone();
+
+        assert!(blob_header.is_inline());
+        assert_eq!(blob_header.get_inline_data(), Some(blob_data.as_slice()));
+        assert_eq!(blob_header.get_num_chunksets(), 1);
+        assert_eq!(blob_header.get_num_chunks(), 0);
+        assert!((0..consts::DECDS_NUM_ERASURE_CODED_SHARES).all(|share_id| blob.get_share(share_id).expect("Must be able to get share").is_empty()));
+
+        let serialized_header = blob_header.to_bytes().expect("Header serialization failed");
+        let (deserialized_header, _) = BlobHeader::from_bytes(&serialized_header).expect("Header deserialization failed");
+        assert_eq!(blob_header, deserialized_header);
+
+        let mut repairer = RepairingBlob::new(deserialized_header);
+        assert!(repairer.is_chunkset_ready_to_repair(0).unwrap());
+        assert!(!repairer.is_chunkset_already_repaired(0).unwrap());
+
+        assert_eq!(repairer.get_repaired_chunkset(0).unwrap(), blob_data);
+        assert!(repairer.is_chunkset_already_repaired(0).unwrap());
+        assert_eq!(repairer.get_repaired_chunkset(0).unwrap_err(), DecdsError::ChunksetAlreadyRepaired(0));
+    }
 
     #[test]
-    fn prop_test_blob_preparation_and_commitment_works() {
-        const NUM_TEST_ITERATIONS: usize = 10;
+    fn test_inline_blob_threshold_and_tampering() {
+        let mut rng = rand::rng();
 
-        const MIN_BLOB_DATA_BYTE_LEN: usize = 1usize;
-        const MAX_BLOB_DATA_BYTE_LEN: usize = 1usize << 30;
+        let blob_data = (0..1024).map(|_| rng.random()).collect::<Vec<u8>>();
+
+        // Blob larger than the threshold is erasure-coded as usual
+        let blob = Blob::new_with_inline_threshold(blob_data.clone(), blob_data.len() - 1).expect("Must be able to prepare blob");
+        assert!(!blob.get_blob_header().is_inline());
+        assert_eq!(blob.get_blob_header().get_num_chunks(), ChunkSet::NUM_ERASURE_CODED_CHUNKS);
+
+        // Inline data not matching the digest is rejected during deserialization
+        let blob = Blob::new_with_inline_threshold(blob_data, usize::MAX).expect("Must be able to prepare inline blob");
+        let mut tampered_header = blob.get_blob_header().clone();
+        if let Some(inline_data) = tampered_header.inline_data.as_mut() {
+            inline_data[0] ^= 1;
+        }
+
+        let serialized_header = tampered_header.to_bytes().expect("Header serialization failed");
+        assert!(BlobHeader::from_bytes(&serialized_header).is_err());
+    }
+
+    #[test]
+    fn test_blob_new_empty_data() {
+        assert_eq!(Blob::new(Vec::new()).err(), Some(DecdsError::EmptyDataForBlob));
+    }
 
+    #[test]
+    fn test_blob_get_share_invalid_id() {
         let mut rng = rand::rng();
 
-        (0..NUM_TEST_ITERATIONS).for_each(|_| {
-            let blob_byte_len = rng.random_range(MIN_BLOB_DATA_BYTE_LEN..=MAX_BLOB_DATA_BYTE_LEN);
-            let blob_data = (0..blob_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+        let blob_data: Vec<u8> = (0..(ChunkSet::BYTE_LENGTH * 2)).map(|_| rng.random()).collect();
+        let blob = Blob::new(blob_data).unwrap();
 
-            let blob = Blob::new(blob_data).expect("Must be able to prepare blob");
-            let blob_header = blob.get_blob_header();
+        // Test with an invalid share ID (out of bounds)
+        let invalid_share_id = consts::DECDS_NUM_ERASURE_CODED_SHARES;
+        assert_eq!(
+            blob.get_share(invalid_share_id).unwrap_err(),
+            DecdsError::InvalidErasureCodedShareId(invalid_share_id)
+        );
 
-            assert!(
-                (0..consts::DECDS_NUM_ERASURE_CODED_SHARES)
-                    .flat_map(|share_id| blob.get_share(share_id).expect("Must be able to get erasure coded shares"))
-                    .all(|share| blob_header.validate_chunk(&share))
-            );
-        });
+        // Test with a very large invalid share ID
+        let large_invalid_share_id = consts::DECDS_NUM_ERASURE_CODED_SHARES + 100;
+        assert_eq!(
+            blob.get_share(large_invalid_share_id).unwrap_err(),
+            DecdsError::InvalidErasureCodedShareId(large_invalid_share_id)
+        );
     }
 
     #[test]
-    fn test_get_chunkset_commitment() {
+    fn test_blob_header_diff() {
         let mut rng = rand::rng();
+        let seed = rng.random::<[u8; 32]>();
 
-        let blob_byte_len = (ChunkSet::BYTE_LENGTH * 2) + (ChunkSet::BYTE_LENGTH / 2);
-        let blob_data = (0..blob_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+        let old_data: Vec<u8> = (0..(ChunkSet::BYTE_LENGTH * 2 + 5)).map(|_| rng.random()).collect();
+        let mut new_data = old_data.clone();
+        new_data[ChunkSet::BYTE_LENGTH + 7] ^= 1;
+        new_data.extend((0..ChunkSet::BYTE_LENGTH).map(|_| rng.random::<u8>()));
 
-        let blob = Blob::new(blob_data).unwrap();
-        let header = blob.get_blob_header();
+        let old_header = DecdsBuilder::new().seed(seed).build(old_data.clone()).unwrap().get_blob_header().clone();
+        let new_header = DecdsBuilder::new().seed(seed).build(new_data).unwrap().get_blob_header().clone();
 
-        // Valid chunkset ID
-        let commitment = header.get_chunkset_commitment(0);
-        assert!(commitment.is_ok());
+        assert!(old_header.diff(&old_header).is_empty());
+        assert_eq!(old_header.diff(&new_header), vec![1, 2, 3]);
+        assert_eq!(new_header.diff(&old_header), vec![1, 2]);
 
-        let commitment = header.get_chunkset_commitment(1);
-        assert!(commitment.is_ok());
+        let reencoded_header = DecdsBuilder::new().seed(seed).build(old_data.clone()).unwrap().get_blob_header().clone();
+        assert_eq!(reencoded_header, old_header);
 
-        // Invalid chunkset ID
-        let err = header.get_chunkset_commitment(header.get_num_chunksets());
-        assert_eq!(err, Err(DecdsError::InvalidChunksetId(header.get_num_chunksets(), header.get_num_chunksets())));
+        let unseeded_header = Blob::new(old_data).unwrap().get_blob_header().clone();
+        assert_eq!(old_header.diff(&unseeded_header), vec![0, 1, 2]);
     }
 
     #[test]
-    fn test_get_chunkset_size() {
+    fn test_blob_header_consistency_proof() {
         let mut rng = rand::rng();
+        let seed = rng.random::<[u8; 32]>();
 
-        // Blob size: 2.5 chunksets -> 2 full, 1 half
-        let blob_byte_len = (ChunkSet::BYTE_LENGTH * 2) + (ChunkSet::BYTE_LENGTH / 2);
-        let blob_data = (0..blob_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+        let old_data: Vec<u8> = (0..ChunkSet::BYTE_LENGTH).map(|_| rng.random()).collect();
+        let mut new_data = old_data.clone();
+        new_data.extend((0..(ChunkSet::BYTE_LENGTH + ChunkSet::BYTE_LENGTH / 2)).map(|_| rng.random::<u8>()));
+
+        let old_header = DecdsBuilder::new().seed(seed).build(old_data).unwrap().get_blob_header().clone();
+        let new_header = DecdsBuilder::new().seed(seed).build(new_data.clone()).unwrap().get_blob_header().clone();
+
+        let proof = new_header.generate_consistency_proof(old_header.get_num_chunksets()).unwrap();
+        assert!(MerkleTree::verify_consistency_proof(
+            old_header.get_num_chunksets(),
+            old_header.get_root_commitment(),
+            new_header.get_num_chunksets(),
+            new_header.get_root_commitment(),
+            &proof
+        ));
+
+        // Rewriting data of the older blob breaks consistency
+        new_data[7] ^= 1;
+        let rewritten_header = DecdsBuilder::new().seed(seed).build(new_data).unwrap().get_blob_header().clone();
+        let proof = rewritten_header.generate_consistency_proof(old_header.get_num_chunksets()).unwrap();
+        assert!(!MerkleTree::verify_consistency_proof(
+            old_header.get_num_chunksets(),
+            old_header.get_root_commitment(),
+            rewritten_header.get_num_chunksets(),
+            rewritten_header.get_root_commitment(),
+            &proof
+        ));
+
+        assert_eq!(
+            old_header.generate_consistency_proof(new_header.get_num_chunksets()),
+            Err(DecdsError::InvalidNumberOfOldLeafNodes(3, 1))
+        );
+    }
+
+    #[test]
+    fn test_blob_iter_share_and_get_chunk() {
+        let mut rng = rand::rng();
 
+        let blob_data: Vec<u8> = (0..(ChunkSet::BYTE_LENGTH * 2 + 1)).map(|_| rng.random()).collect();
         let blob = Blob::new(blob_data).unwrap();
-        let header = blob.get_blob_header();
+        let num_chunksets = blob.get_blob_header().get_num_chunksets();
 
-        // Full chunkset
-        assert_eq!(header.get_chunkset_size(0).unwrap(), ChunkSet::BYTE_LENGTH);
-        assert_eq!(header.get_chunkset_size(1).unwrap(), ChunkSet::BYTE_LENGTH);
+        (0..consts::DECDS_NUM_ERASURE_CODED_SHARES).for_each(|share_id| {
+            let share = blob.get_share(share_id).unwrap();
+            assert!(blob.iter_share(share_id).unwrap().eq(share.iter()));
+            assert!(
+                blob.share_stream(share_id)
+                    .unwrap()
+                    .map(|chunk_bytes| chunk_bytes.unwrap())
+                    .eq(share.iter().map(|chunk| chunk.to_bytes().unwrap()))
+            );
 
-        // Partial chunkset
-        assert_eq!(header.get_chunkset_size(2).unwrap(), ChunkSet::BYTE_LENGTH / 2);
+            (0..num_chunksets).for_each(|chunkset_id| {
+                let chunk = blob.get_chunk(chunkset_id, share_id).unwrap();
 
-        // Invalid chunkset ID
+                assert_eq!(chunk, &share[chunkset_id]);
+                assert!(blob.get_blob_header().validate_chunk(chunk));
+            });
+        });
+
+        assert!(blob.iter_share(consts::DECDS_NUM_ERASURE_CODED_SHARES).is_err());
+        assert!(blob.share_stream(consts::DECDS_NUM_ERASURE_CODED_SHARES).is_err());
         assert_eq!(
-            header.get_chunkset_size(header.get_num_chunksets()).unwrap_err(),
-            DecdsError::InvalidChunksetId(header.get_num_chunksets(), header.get_num_chunksets())
+            blob.get_chunk(num_chunksets, 0).unwrap_err(),
+            DecdsError::InvalidChunksetId(num_chunksets, num_chunksets)
+        );
+        assert_eq!(
+            blob.get_chunk(0, consts::DECDS_NUM_ERASURE_CODED_SHARES).unwrap_err(),
+            DecdsError::InvalidErasureCodedShareId(consts::DECDS_NUM_ERASURE_CODED_SHARES)
         );
     }
 
     #[test]
-    fn test_get_byte_range_for_chunkset() {
+    fn test_repairing_blob_repair_chunkset_into() {
         let mut rng = rand::rng();
 
-        let blob_byte_len = (ChunkSet::BYTE_LENGTH * 2) + (ChunkSet::BYTE_LENGTH / 2);
-        let blob_data = (0..blob_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+        let blob_data: Vec<u8> = (0..(ChunkSet::BYTE_LENGTH * 2 + ChunkSet::BYTE_LENGTH / 2)).map(|_| rng.random()).collect();
+        let blob = Blob::new(blob_data.clone()).unwrap();
+        let header = blob.get_blob_header().clone();
 
-        let blob = Blob::new(blob_data).unwrap();
-        let header = blob.get_blob_header();
+        let mut repairer = RepairingBlob::new(header.clone());
+        (0..consts::DECDS_NUM_ERASURE_CODED_SHARES)
+            .flat_map(|share_id| blob.get_share(share_id).unwrap())
+            .for_each(|chunk| {
+                let _ = repairer.add_chunk(&chunk);
+            });
 
-        // First chunkset
-        assert_eq!(header.get_byte_range_for_chunkset(0).unwrap(), (0, ChunkSet::BYTE_LENGTH));
+        let mut repaired_blob = vec![0u8; header.get_blob_size()];
 
-        // Second chunkset
+        // Buffer too small for the chunkset is rejected, without consuming the chunkset
+        let (from, to) = header.get_byte_range_for_chunkset(0).unwrap();
         assert_eq!(
-            header.get_byte_range_for_chunkset(1).unwrap(),
-            (ChunkSet::BYTE_LENGTH, ChunkSet::BYTE_LENGTH * 2)
+            repairer.repair_chunkset_into(0, &mut repaired_blob[from..(to - 1)]),
+            Err(DecdsError::RepairOutputBufferTooSmall(0, to - from - 1, to - from))
         );
+        assert!(!repairer.is_chunkset_already_repaired(0).unwrap());
 
-        // Last (partial) chunkset
-        assert_eq!(header.get_byte_range_for_chunkset(2).unwrap(), (ChunkSet::BYTE_LENGTH * 2, blob_byte_len));
+        (0..header.get_num_chunksets()).for_each(|chunkset_id| {
+            let (from, to) = header.get_byte_range_for_chunkset(chunkset_id).unwrap();
+            assert_eq!(repairer.repair_chunkset_into(chunkset_id, &mut repaired_blob[from..]), Ok(to - from));
+        });
 
-        // Invalid chunkset ID
+        assert_eq!(repaired_blob, blob_data);
         assert_eq!(
-            header.get_byte_range_for_chunkset(header.get_num_chunksets()).unwrap_err(),
-            DecdsError::InvalidChunksetId(header.get_num_chunksets(), header.get_num_chunksets())
+            repairer.repair_chunkset_into(0, &mut repaired_blob),
+            Err(DecdsError::ChunksetAlreadyRepaired(0))
+        );
+        assert_eq!(
+            repairer.repair_chunkset_into(header.get_num_chunksets(), &mut repaired_blob),
+            Err(DecdsError::InvalidChunksetId(header.get_num_chunksets(), header.get_num_chunksets()))
         );
     }
 
     #[test]
-    fn test_get_chunkset_ids_for_byte_range() {
+    fn test_repairing_blob_read_range() {
         let mut rng = rand::rng();
 
-        let blob_byte_len = (ChunkSet::BYTE_LENGTH * 2) + (ChunkSet::BYTE_LENGTH / 2);
-        let blob_data = (0..blob_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+        let blob_data: Vec<u8> = (0..(ChunkSet::BYTE_LENGTH * 2 + ChunkSet::BYTE_LENGTH / 2)).map(|_| rng.random()).collect();
+        let blob = Blob::new(blob_data.clone()).unwrap();
+        let header = blob.get_blob_header().clone();
 
-        let blob = Blob::new(blob_data).unwrap();
-        let header = blob.get_blob_header();
+        let all_chunks: Vec<ProofCarryingChunk> = (0..consts::DECDS_NUM_ERASURE_CODED_SHARES)
+            .flat_map(|share_id| blob.get_share(share_id).unwrap())
+            .collect();
 
-        // Range within a single chunkset
-        assert_eq!(header.get_chunkset_ids_for_byte_range(0..10).unwrap(), vec![0]);
+        // Without a cache, a chunkset is consumed by the first read, which covers it
+        let mut repairer = RepairingBlob::new(header.clone());
+        all_chunks.iter().for_each(|chunk| {
+            let _ = repairer.add_chunk(chunk);
+        });
+
+        assert_eq!(repairer.read_range(10..20).unwrap(), blob_data[10..20]);
+        assert_eq!(repairer.read_range(30..40), Err(DecdsError::ChunksetAlreadyRepaired(0)));
+
+        // With a cache, overlapping ranges are served again, until the chunkset gets evicted
+        let mut repairer = RepairBuilder::new(header.clone()).repaired_chunkset_cache(2).build().unwrap();
+        all_chunks.iter().for_each(|chunk| {
+            let _ = repairer.add_chunk(chunk);
+        });
+
+        let boundary = ChunkSet::BYTE_LENGTH;
         assert_eq!(
-            header
-                .get_chunkset_ids_for_byte_range(ChunkSet::BYTE_LENGTH + 10..ChunkSet::BYTE_LENGTH + 20)
-                .unwrap(),
-            vec![1]
+            repairer.read_range(boundary - 100..boundary + 100).unwrap(),
+            blob_data[boundary - 100..boundary + 100]
         );
+        assert_eq!(repairer.read_range(..=boundary).unwrap(), blob_data[..=boundary]);
+        assert_eq!(repairer.read_range(boundary..boundary).unwrap(), Vec::<u8>::new());
 
-        // Range spanning multiple chunksets
+        assert_eq!(repairer.read_range(boundary * 2..).unwrap(), blob_data[boundary * 2..]);
+        assert_eq!(repairer.read_range(..10), Err(DecdsError::ChunksetAlreadyRepaired(0)));
+        assert_eq!(repairer.read_range(boundary..).unwrap(), blob_data[boundary..]);
+
+        // Out of bounds ranges are rejected
         assert_eq!(
-            header.get_chunkset_ids_for_byte_range(10..(ChunkSet::BYTE_LENGTH * 1 + 10)).unwrap(),
-            vec![0, 1]
+            repairer.read_range(..=header.get_blob_size()),
+            Err(DecdsError::InvalidEndBound(header.get_blob_size() + 1))
         );
-        assert_eq!(header.get_chunkset_ids_for_byte_range(10..blob_byte_len).unwrap(), vec![0, 1, 2]);
+        let (start, end) = (20, 10);
+        assert_eq!(repairer.read_range(start..end), Err(DecdsError::InvalidStartBound));
+    }
 
-        // Range exactly matching chunkset boundaries
-        assert_eq!(header.get_chunkset_ids_for_byte_range(0..ChunkSet::BYTE_LENGTH).unwrap(), vec![0]);
-        assert_eq!(header.get_chunkset_ids_for_byte_range(0..=(ChunkSet::BYTE_LENGTH - 1)).unwrap(), vec![0]);
+    #[test]
+    fn test_blob_record_offsets() {
+        let mut rng = rand::rng();
 
-        // Edge cases for bounds
-        assert_eq!(header.get_chunkset_ids_for_byte_range(0..0).unwrap_err(), DecdsError::InvalidEndBound(0));
-        assert_eq!(header.get_chunkset_ids_for_byte_range(0..=0).unwrap(), vec![0]); // Covers first byte of first chunkset
+        let blob_data: Vec<u8> = (0..(ChunkSet::BYTE_LENGTH * 2 + ChunkSet::BYTE_LENGTH / 2)).map(|_| rng.random()).collect();
+        let boundary = ChunkSet::BYTE_LENGTH;
+        let record_offsets = [0, 100, boundary - 10, boundary * 2 + 1];
 
-        // Invalid end bound (range beyond blob size)
-        let end_beyond_blob = header.get_blob_size() + ChunkSet::BYTE_LENGTH;
-        let expected_end_chunkset_id = end_beyond_blob.saturating_sub(1) / ChunkSet::BYTE_LENGTH;
+        let blob = DecdsBuilder::new().record_offsets(&record_offsets).build(blob_data.clone()).unwrap();
+        let header = blob.get_blob_header().clone();
+
+        assert_eq!(header.get_record_offsets(), record_offsets);
+        assert_eq!(header.get_num_records(), record_offsets.len());
+        assert_eq!(header.get_byte_range_for_record(1), Ok((100, boundary - 10)));
+        assert_eq!(header.get_byte_range_for_record(3), Ok((boundary * 2 + 1, blob_data.len())));
+        assert_eq!(header.get_byte_range_for_record(4), Err(DecdsError::InvalidRecordId(4, 4)));
+        assert_eq!(header.get_chunkset_ids_for_record(0), Ok(vec![0]));
+        assert_eq!(header.get_chunkset_ids_for_record(2), Ok(vec![0, 1, 2]));
+        assert_eq!(header.get_chunkset_ids_for_record(3), Ok(vec![2]));
+
+        // Offsets survive every way of serializing and reading the header back
+        let header_bytes = header.to_bytes().unwrap();
+        let mut written_bytes = Vec::new();
+        header.write_to(&mut written_bytes).unwrap();
+        assert_eq!(written_bytes, header_bytes);
+        assert_eq!(BlobHeader::from_bytes(&header_bytes).unwrap().0, header);
+        assert_eq!(BlobHeader::from_reader(&mut &header_bytes[..]).unwrap(), header);
         assert_eq!(
-            header.get_chunkset_ids_for_byte_range(0..end_beyond_blob).unwrap_err(),
-            DecdsError::InvalidChunksetId(expected_end_chunkset_id, header.get_num_chunksets())
+            BlobHeaderView::open(Cursor::new(&header_bytes)).unwrap().get_byte_range_for_record(1),
+            Ok((100, boundary - 10))
         );
 
-        // Test for `InvalidEndBound(usize::MAX)` for unbounded ranges
-        assert_eq!(header.get_chunkset_ids_for_byte_range(..).unwrap_err(), DecdsError::InvalidEndBound(usize::MAX));
+        // Offsets must be strictly ascending, and within the blob
         assert_eq!(
-            header.get_chunkset_ids_for_byte_range(0..).unwrap_err(),
-            DecdsError::InvalidEndBound(usize::MAX)
+            DecdsBuilder::new().record_offsets(&[0, 10, 10]).build(blob_data.clone()).err(),
+            Some(DecdsError::InvalidRecordOffset(2, 10))
+        );
+        assert_eq!(
+            DecdsBuilder::new().record_offsets(&[blob_data.len()]).build(blob_data.clone()).err(),
+            Some(DecdsError::InvalidRecordOffset(0, blob_data.len()))
         );
+
+        let tampered_header = BlobHeader {
+            record_offsets: vec![100, 10],
+            ..header.clone()
+        };
+        let tampered_header_bytes = tampered_header.to_bytes().unwrap();
+        assert!(matches!(
+            BlobHeader::from_bytes(&tampered_header_bytes),
+            Err(DecdsError::BlobHeaderDeserializationFailed(_))
+        ));
+        assert!(matches!(
+            BlobHeader::from_reader(&mut &tampered_header_bytes[..]),
+            Err(DecdsError::BlobHeaderDeserializationFailed(_))
+        ));
+
+        // A record is read back, repairing only chunksets it spans
+        let mut repairer = RepairBuilder::new(header.clone()).repaired_chunkset_cache(3).build().unwrap();
+        (0..consts::DECDS_NUM_ERASURE_CODED_SHARES)
+            .flat_map(|share_id| blob.get_share(share_id).unwrap())
+            .for_each(|chunk| {
+                let _ = repairer.add_chunk(&chunk);
+            });
+
+        for record_id in 0..header.get_num_records() {
+            let (from, to) = header.get_byte_range_for_record(record_id).unwrap();
+            assert_eq!(repairer.read_record(record_id).unwrap(), blob_data[from..to]);
+        }
+        assert_eq!(repairer.read_record(4), Err(DecdsError::InvalidRecordId(4, 4)));
     }
 
     #[test]
-    fn test_blob_header_serialization_deserialization() {
+    fn test_repairing_blob_write_repaired() {
         let mut rng = rand::rng();
 
-        let blob_byte_len = ChunkSet::BYTE_LENGTH * 3;
-        let blob_data = (0..blob_byte_len).map(|_| rng.random()).collect::<Vec<u8>>();
+        let blob_data: Vec<u8> = (0..(ChunkSet::BYTE_LENGTH * 2 + ChunkSet::BYTE_LENGTH / 2)).map(|_| rng.random()).collect();
+        let blob = Blob::new(blob_data.clone()).unwrap();
+        let header = blob.get_blob_header().clone();
 
-        let blob = Blob::new(blob_data).unwrap();
-        let original_header = blob.get_blob_header().clone();
+        let add_chunkset = |repairer: &mut RepairingBlob, chunkset_id: usize| {
+            (0..consts::DECDS_NUM_ERASURE_CODED_SHARES).for_each(|share_id| {
+                let _ = repairer.add_chunk(blob.get_chunk(chunkset_id, share_id).unwrap());
+            });
+        };
 
-        let serialized_header = original_header.to_bytes().expect("Header serialization failed");
-        let (deserialized_header, bytes_read) = BlobHeader::from_bytes(&serialized_header).expect("Header deserialization failed");
+        // Nothing is written until the first chunkset is ready, then ready chunksets are written in order
+        let mut repairer = RepairingBlob::new(header.clone());
+        let mut sink = Vec::new();
 
-        assert_eq!(original_header, deserialized_header);
-        assert_eq!(serialized_header.len(), bytes_read);
+        add_chunkset(&mut repairer, 1);
+        assert_eq!(repairer.write_repaired(&mut sink), Ok(0));
+        assert!(sink.is_empty());
 
-        // Test deserialization failure with lesser bytes
-        assert!(BlobHeader::from_bytes(&serialized_header[..(serialized_header.len() / 2)]).is_err());
+        add_chunkset(&mut repairer, 0);
+        assert_eq!(repairer.write_repaired(&mut sink), Ok(ChunkSet::BYTE_LENGTH * 2));
+        assert_eq!(sink, blob_data[..ChunkSet::BYTE_LENGTH * 2]);
+
+        add_chunkset(&mut repairer, 2);
+        assert_eq!(repairer.write_repaired(&mut sink), Ok(ChunkSet::BYTE_LENGTH / 2));
+        assert_eq!(repairer.write_repaired(&mut sink), Ok(0));
+        assert_eq!(repairer.get_num_streamed_bytes(), blob_data.len());
+        assert_eq!(sink, blob_data);
+
+        // Restricted to a target byte range, only bytes of that range are written
+        let byte_range = ChunkSet::BYTE_LENGTH - 100..ChunkSet::BYTE_LENGTH * 2 + 100;
+        let mut repairer = RepairBuilder::new(header.clone()).byte_range(byte_range.clone()).build().unwrap();
+        let mut sink = Vec::new();
+
+        (0..header.get_num_chunksets()).for_each(|chunkset_id| add_chunkset(&mut repairer, chunkset_id));
+        assert_eq!(repairer.write_repaired(&mut sink), Ok(byte_range.len()));
+        assert_eq!(sink, blob_data[byte_range]);
     }
 
     #[test]
-    fn test_blob_new_empty_data() {
-        assert_eq!(Blob::new(Vec::new()).err(), Some(DecdsError::EmptyDataForBlob));
+    fn test_repairing_blob_drain_ready_in_order() {
+        let mut rng = rand::rng();
+
+        let blob_data: Vec<u8> = (0..(ChunkSet::BYTE_LENGTH * 3 + ChunkSet::BYTE_LENGTH / 2)).map(|_| rng.random()).collect();
+        let blob = Blob::new(blob_data.clone()).unwrap();
+        let header = blob.get_blob_header().clone();
+
+        let add_chunkset = |repairer: &mut RepairingBlob, chunkset_id: usize| {
+            (0..consts::DECDS_NUM_ERASURE_CODED_SHARES).for_each(|share_id| {
+                let _ = repairer.add_chunk(blob.get_chunk(chunkset_id, share_id).unwrap());
+            });
+        };
+        let drain = |repairer: &mut RepairingBlob| repairer.drain_ready_in_order().collect::<Result<Vec<(usize, Vec<u8>)>, DecdsError>>().unwrap();
+
+        let mut repairer = RepairingBlob::new(header.clone());
+
+        // Nothing is yielded, while the lowest chunkset isn't ready, even though later ones are
+        add_chunkset(&mut repairer, 1);
+        add_chunkset(&mut repairer, 3);
+        assert!(drain(&mut repairer).is_empty());
+
+        // Contiguous ready chunksets are yielded, stopping at the first one, which isn't ready
+        add_chunkset(&mut repairer, 0);
+        let (chunkset_ids, repaired): (Vec<usize>, Vec<Vec<u8>>) = drain(&mut repairer).into_iter().unzip();
+        assert_eq!(chunkset_ids, vec![0, 1]);
+        assert_eq!(repaired.concat(), blob_data[..ChunkSet::BYTE_LENGTH * 2]);
+        assert!(drain(&mut repairer).is_empty());
+
+        // Draining resumes right after already repaired chunksets
+        add_chunkset(&mut repairer, 2);
+        let (chunkset_ids, repaired): (Vec<usize>, Vec<Vec<u8>>) = drain(&mut repairer).into_iter().unzip();
+        assert_eq!(chunkset_ids, vec![2, 3]);
+        assert_eq!(repaired.concat(), blob_data[ChunkSet::BYTE_LENGTH * 2..]);
+        assert!(drain(&mut repairer).is_empty());
     }
 
     #[test]
-    fn test_blob_get_share_invalid_id() {
+    fn test_repairing_blob_reset_chunkset() {
         let mut rng = rand::rng();
 
-        let blob_data: Vec<u8> = (0..(ChunkSet::BYTE_LENGTH * 2)).map(|_| rng.random()).collect();
-        let blob = Blob::new(blob_data).unwrap();
+        let blob_data: Vec<u8> = (0..(ChunkSet::BYTE_LENGTH + ChunkSet::BYTE_LENGTH / 2)).map(|_| rng.random()).collect();
+        let blob = Blob::new(blob_data.clone()).unwrap();
+        let header = blob.get_blob_header().clone();
+
+        let mut repairer = RepairBuilder::new(header.clone()).repaired_chunkset_cache(2).build().unwrap();
+        let add_share = |repairer: &mut RepairingBlob, share_id: usize| {
+            blob.get_share(share_id).unwrap().iter().for_each(|chunk| {
+                let _ = repairer.add_chunk(chunk);
+            });
+        };
+
+        (0..4).for_each(|share_id| add_share(&mut repairer, share_id));
+        assert_eq!(repairer.get_num_remaining_chunks(0), Ok(6));
+
+        // Resetting one chunkset leaves others untouched
+        assert_eq!(repairer.reset_chunkset(0), Ok(()));
+        assert_eq!(repairer.get_num_remaining_chunks(0), Ok(10));
+        assert_eq!(repairer.get_num_remaining_chunks(1), Ok(6));
+
+        (4..consts::DECDS_NUM_ERASURE_CODED_SHARES).for_each(|share_id| add_share(&mut repairer, share_id));
+        assert_eq!(repairer.read_range(..), Ok(blob_data.clone()));
+
+        // Repaired chunkset accepts chunks again, once reset, and isn't served from cache anymore
+        assert_eq!(repairer.reset_chunkset(1), Ok(()));
+        assert!(!repairer.is_chunkset_already_repaired(1).unwrap());
+        assert_eq!(repairer.read_range(ChunkSet::BYTE_LENGTH..), Err(DecdsError::ChunksetNotYetReadyToRepair(1)));
+        assert_eq!(repairer.read_range(..ChunkSet::BYTE_LENGTH), Ok(blob_data[..ChunkSet::BYTE_LENGTH].to_vec()));
+
+        (0..consts::DECDS_NUM_ERASURE_CODED_SHARES).for_each(|share_id| add_share(&mut repairer, share_id));
+        assert_eq!(repairer.read_range(ChunkSet::BYTE_LENGTH..), Ok(blob_data[ChunkSet::BYTE_LENGTH..].to_vec()));
 
-        // Test with an invalid share ID (out of bounds)
-        let invalid_share_id = consts::DECDS_NUM_ERASURE_CODED_SHARES;
         assert_eq!(
-            blob.get_share(invalid_share_id).unwrap_err(),
-            DecdsError::InvalidErasureCodedShareId(invalid_share_id)
+            repairer.reset_chunkset(header.get_num_chunksets()),
+            Err(DecdsError::InvalidChunksetId(header.get_num_chunksets(), header.get_num_chunksets()))
         );
+    }
 
-        // Test with a very large invalid share ID
-        let large_invalid_share_id = consts::DECDS_NUM_ERASURE_CODED_SHARES + 100;
+    #[test]
+    fn test_repairing_blob_acceptance_policy() {
+        let blob = Blob::new(vec![0xcd; 1024]).unwrap();
+        let header = blob.get_blob_header().clone();
+
+        // At most 4 chunks per source, and none without one
+        let mut num_accepted = std::collections::HashMap::<String, usize>::new();
+        let mut repairer = RepairBuilder::new(header)
+            .acceptance_policy(Box::new(move |source, _chunk| {
+                source.is_some_and(|source| {
+                    let num_accepted = num_accepted.entry(source.to_string()).or_default();
+                    *num_accepted += 1;
+                    *num_accepted <= 4
+                })
+            }))
+            .build()
+            .unwrap();
+
+        let chunk = |share_id| blob.get_chunk(0, share_id).unwrap();
+        assert_eq!(repairer.add_chunk(chunk(0)), Err(DecdsError::ChunkRejectedByPolicy(0, 0)));
+
+        (0..4).for_each(|share_id| assert_eq!(repairer.add_chunk_from_source(chunk(share_id), "node-a"), Ok(())));
+        assert_eq!(repairer.add_chunk_from_source(chunk(4), "node-a"), Err(DecdsError::ChunkRejectedByPolicy(0, 4)));
+
+        // Invalid chunks are refused before the policy is consulted, rejected ones aren't counted as garbage
+        let tampered_chunk = ChunkFault::FlipPayloadBit(0).apply(chunk(5));
         assert_eq!(
-            blob.get_share(large_invalid_share_id).unwrap_err(),
-            DecdsError::InvalidErasureCodedShareId(large_invalid_share_id)
+            repairer.add_chunk_from_source(&tampered_chunk, "node-b"),
+            Err(DecdsError::InvalidProofInChunk(0, 5))
         );
+        assert_eq!(repairer.get_suspect_sources(), vec![("node-b", 1)]);
+
+        (5..9).for_each(|share_id| assert_eq!(repairer.add_chunk_from_source(chunk(share_id), "node-b"), Ok(())));
+        (9..11).for_each(|share_id| assert_eq!(repairer.add_chunk_from_source(chunk(share_id), "node-c"), Ok(())));
+
+        assert_eq!(repairer.get_num_remaining_chunks(0), Ok(0));
+        assert_eq!(repairer.get_repaired_chunkset(0).unwrap(), vec![0xcd; 1024]);
     }
 
     #[test]
@@ -695,7 +3799,14 @@ mod tests {
 
             assert!(!repairer.is_chunkset_ready_to_repair(i).unwrap());
             assert!(!repairer.is_chunkset_already_repaired(i).unwrap());
+            assert_eq!(repairer.get_num_remaining_chunks(i).unwrap(), ChunkSet::NUM_ORIGINAL_CHUNKS);
         }
+
+        let num_chunksets = header.get_num_chunksets();
+        assert_eq!(
+            repairer.get_num_remaining_chunks(num_chunksets).unwrap_err(),
+            DecdsError::InvalidChunksetId(num_chunksets, num_chunksets)
+        );
     }
 
     #[test]
@@ -715,6 +3826,10 @@ mod tests {
         // Test valid chunk addition
         let chunk_to_add = &all_chunks[0];
         assert!(repairer.add_chunk(chunk_to_add).is_ok());
+        assert_eq!(
+            repairer.get_num_remaining_chunks(chunk_to_add.get_chunkset_id()).unwrap(),
+            ChunkSet::NUM_ORIGINAL_CHUNKS - 1
+        );
 
         // Simulate an invalid chunk proof by creating a new header with a different root commitment
         let mut invalid_header = blob_header.clone();
@@ -723,7 +3838,7 @@ mod tests {
         let mut repairer_invalid_header = RepairingBlob::new(invalid_header);
         assert_eq!(
             repairer_invalid_header.add_chunk(chunk_to_add).unwrap_err(),
-            DecdsError::InvalidProofInChunk(chunk_to_add.get_chunkset_id())
+            DecdsError::InvalidProofInChunk(chunk_to_add.get_chunkset_id(), chunk_to_add.get_local_chunk_id())
         );
 
         // Add enough chunks to make a chunkset ready for repair
@@ -741,6 +3856,7 @@ mod tests {
         }
 
         assert!(repairer_ready.is_chunkset_ready_to_repair(chunkset_id).unwrap());
+        assert_eq!(repairer_ready.get_num_remaining_chunks(chunkset_id).unwrap(), 0);
 
         // Try adding another chunk to a chunkset already ready for repair
         let extra_chunk = &all_chunks
@@ -758,12 +3874,473 @@ mod tests {
 
         assert!(!repairer_ready.is_chunkset_ready_to_repair(chunkset_id).unwrap());
         assert!(repairer_ready.is_chunkset_already_repaired(chunkset_id).unwrap());
+        assert_eq!(repairer_ready.get_num_remaining_chunks(chunkset_id).unwrap(), 0);
         assert_eq!(
             repairer_ready.add_chunk(chunk_to_add).unwrap_err(),
             DecdsError::ChunksetAlreadyRepaired(chunkset_id)
         );
     }
 
+    #[test]
+    fn test_repairing_blob_add_chunk_owned() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..(ChunkSet::BYTE_LENGTH + ChunkSet::BYTE_LENGTH / 2))
+            .map(|_| rng.random())
+            .collect::<Vec<u8>>();
+        let blob = Blob::new(blob_data.clone()).expect("Must be able to prepare blob");
+        let header = blob.get_blob_header().clone();
+
+        let mut repairer = RepairBuilder::new(header.clone())
+            .checkpointing(true)
+            .build()
+            .expect("Must be able to build repairer");
+
+        let tampered_chunk = ChunkFault::FlipPayloadBit(0).apply(blob.get_chunk(0, 0).expect("Must be able to get chunk"));
+        assert_eq!(repairer.add_chunk_owned(tampered_chunk), Err(DecdsError::InvalidProofInChunk(0, 0)));
+
+        // Owned chunks are moved into the checkpoint, which must still resume the repairer.
+        for share_id in 0..ChunkSet::NUM_ORIGINAL_CHUNKS / 2 {
+            let chunk = blob.get_chunk(0, share_id).expect("Must be able to get chunk").clone();
+            repairer.add_chunk_owned(chunk).expect("Must be able to add chunk");
+        }
+        assert_eq!(
+            repairer.add_chunk_owned(blob.get_chunk(0, 0).expect("Must be able to get chunk").clone()),
+            Err(DecdsError::NonInnovativeChunk(0, 0))
+        );
+
+        let checkpoint = repairer.to_bytes().expect("Must be able to checkpoint repairer");
+        let (mut resumed, _) = RepairingBlob::from_bytes(&checkpoint).expect("Must be able to resume repairer");
+
+        for chunkset_id in 0..header.get_num_chunksets() {
+            for share_id in 0..consts::DECDS_NUM_ERASURE_CODED_SHARES {
+                let chunk = blob.get_chunk(chunkset_id, share_id).expect("Must be able to get chunk").clone();
+                let _ = resumed.add_chunk_owned(chunk);
+            }
+        }
+
+        let repaired_data = (0..header.get_num_chunksets())
+            .flat_map(|chunkset_id| resumed.get_repaired_chunkset(chunkset_id).expect("Must be able to repair chunkset"))
+            .collect::<Vec<u8>>();
+        assert_eq!(repaired_data, blob_data);
+    }
+
+    #[test]
+    fn test_repairing_blob_add_chunks_matches_adding_one_by_one() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..(ChunkSet::BYTE_LENGTH * 2 + 1)).map(|_| rng.random()).collect::<Vec<u8>>();
+        let blob = Blob::new(blob_data.clone()).expect("Must be able to prepare blob");
+        let header = blob.get_blob_header().clone();
+
+        let mut chunks = (0..consts::DECDS_NUM_ERASURE_CODED_SHARES)
+            .flat_map(|share_id| blob.get_share(share_id).expect("Must be able to get share"))
+            .collect::<Vec<ProofCarryingChunk>>();
+        chunks.shuffle(&mut rng);
+
+        // Last byte of the proof, which precedes the content flags byte.
+        let mut tampered_chunk = chunks[0].to_bytes().expect("Must be able to serialize chunk");
+        let last_byte_idx = tampered_chunk.len() - 2;
+        tampered_chunk[last_byte_idx] ^= 1;
+        chunks.insert(1, ProofCarryingChunk::from_bytes(&tampered_chunk).expect("Must be able to deserialize chunk").0);
+        chunks.push(chunks[2].clone());
+
+        let mut sequential_repairer = RepairingBlob::new(header.clone());
+        let expected_results = chunks
+            .iter()
+            .map(|chunk| {
+                sequential_repairer.add_chunk(chunk).map(|()| {
+                    if sequential_repairer.is_chunkset_ready_to_repair(chunk.get_chunkset_id()).unwrap() {
+                        AddOutcome::ChunksetReady
+                    } else {
+                        AddOutcome::Accepted
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut repairer = RepairingBlob::new(header.clone());
+        let results = repairer.add_chunks(chunks.clone());
+        assert_eq!(results, expected_results);
+        assert!(matches!(results[1], Err(DecdsError::InvalidProofInChunk(_, _))));
+        assert_eq!(
+            results.iter().filter(|result| matches!(result, Ok(AddOutcome::ChunksetReady))).count(),
+            header.get_num_chunksets()
+        );
+        assert_eq!(repairer.get_progress(), sequential_repairer.get_progress());
+        assert!(repairer.get_progress().is_ready());
+
+        // Chunksets ready to repair take no more chunks, from a later batch.
+        assert!(
+            repairer
+                .add_chunks(chunks[..4].iter().cloned())
+                .iter()
+                .all(|result| matches!(result, Err(DecdsError::ChunksetReadyToRepair(_)) | Err(DecdsError::InvalidProofInChunk(_, _))))
+        );
+
+        let repaired = (0..header.get_num_chunksets())
+            .flat_map(|chunkset_id| repairer.get_repaired_chunkset(chunkset_id).expect("Must be able to repair chunkset"))
+            .collect::<Vec<u8>>();
+        assert_eq!(repaired, blob_data);
+        assert!(repairer.add_chunks([chunks[0].clone()])[0].is_err());
+    }
+
+    #[test]
+    fn test_blob_id_depends_only_on_blob_content() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..ChunkSet::BYTE_LENGTH).map(|_| rng.random()).collect::<Vec<u8>>();
+        let blob_a = Blob::new(blob_data.clone()).expect("Must be able to prepare blob");
+        let blob_b = Blob::new(blob_data.clone()).expect("Must be able to prepare blob");
+
+        assert_ne!(blob_a.get_blob_header().get_root_commitment(), blob_b.get_blob_header().get_root_commitment());
+        assert_eq!(blob_a.get_blob_header().get_blob_id(), blob_b.get_blob_header().get_blob_id());
+
+        let mut other_blob_data = blob_data;
+        other_blob_data.push(0);
+        let blob_c = Blob::new(other_blob_data).expect("Must be able to prepare blob");
+
+        assert_ne!(blob_a.get_blob_header().get_blob_id(), blob_c.get_blob_header().get_blob_id());
+    }
+
+    #[test]
+    fn test_repairing_blob_share_preference_order() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..ChunkSet::BYTE_LENGTH).map(|_| rng.random()).collect::<Vec<u8>>();
+        let blob = Blob::new(blob_data).expect("Must be able to prepare blob");
+        let mut repairer = RepairingBlob::new(blob.get_blob_header().clone());
+
+        assert_eq!(
+            repairer.get_preferred_share_order(),
+            (0..consts::DECDS_NUM_ERASURE_CODED_SHARES).collect::<Vec<usize>>()
+        );
+
+        repairer.set_share_weight(0, 10).expect("Must be able to set share weight");
+        repairer.set_share_weight(7, 5).expect("Must be able to set share weight");
+        repairer.set_share_weight(3, 5).expect("Must be able to set share weight");
+        assert_eq!(repairer.get_share_weight(7), Ok(5));
+
+        let preferred_order = repairer.get_preferred_share_order();
+        assert_eq!(preferred_order.len(), consts::DECDS_NUM_ERASURE_CODED_SHARES);
+        assert_eq!(&preferred_order[consts::DECDS_NUM_ERASURE_CODED_SHARES - 3..], &[3, 7, 0]);
+
+        assert_eq!(
+            repairer.set_share_weight(consts::DECDS_NUM_ERASURE_CODED_SHARES, 1),
+            Err(DecdsError::InvalidErasureCodedShareId(consts::DECDS_NUM_ERASURE_CODED_SHARES))
+        );
+        assert_eq!(
+            repairer.get_share_weight(consts::DECDS_NUM_ERASURE_CODED_SHARES),
+            Err(DecdsError::InvalidErasureCodedShareId(consts::DECDS_NUM_ERASURE_CODED_SHARES))
+        );
+
+        // Share IDs are checked against shares of the blob, not against share slots
+        let config = BlobConfig::new().num_shares(12).chunkset_byte_length(1024);
+        let blob = DecdsBuilder::new()
+            .config(config)
+            .build(vec![0xab; 3000])
+            .expect("Must be able to prepare blob");
+        let mut repairer = RepairingBlob::new(blob.get_blob_header().clone());
+
+        assert_eq!(repairer.set_share_weight(11, 1), Ok(()));
+        assert_eq!(repairer.set_share_weight(12, 1), Err(DecdsError::InvalidErasureCodedShareId(12)));
+        assert_eq!(repairer.get_share_weight(12), Err(DecdsError::InvalidErasureCodedShareId(12)));
+        assert_eq!(repairer.get_preferred_share_order().last(), Some(&11));
+    }
+
+    #[test]
+    fn test_repairing_blob_add_chunks_decodes_cheaper_shares_first() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..ChunkSet::BYTE_LENGTH).map(|_| rng.random()).collect::<Vec<u8>>();
+        let blob = Blob::new(blob_data.clone()).expect("Must be able to prepare blob");
+        let mut repairer = RepairingBlob::new(blob.get_blob_header().clone());
+
+        let num_costly_shares = consts::DECDS_NUM_ERASURE_CODED_SHARES - ChunkSet::NUM_ORIGINAL_CHUNKS;
+        (0..num_costly_shares).for_each(|share_id| repairer.set_share_weight(share_id, 10).expect("Must be able to set share weight"));
+
+        // Costly shares come first in the batch, but they are the surplus ones, turned away.
+        let chunks = (0..consts::DECDS_NUM_ERASURE_CODED_SHARES).map(|share_id| blob.get_chunk(0, share_id).expect("Must be able to get chunk").clone());
+        let results = repairer.add_chunks(chunks);
+
+        assert!(
+            results[..num_costly_shares]
+                .iter()
+                .all(|result| *result == Err(DecdsError::ChunksetReadyToRepair(0)))
+        );
+        assert!(results[num_costly_shares..].iter().all(|result| result.is_ok()));
+        assert_eq!(repairer.get_repaired_chunkset(0), Ok(blob_data));
+    }
+
+    #[test]
+    fn test_repairing_blob_suspect_sources_report() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..ChunkSet::BYTE_LENGTH * 2).map(|_| rng.random()).collect::<Vec<u8>>();
+        let blob = Blob::new(blob_data).expect("Must be able to prepare blob");
+        let mut repairer = RepairingBlob::new(blob.get_blob_header().clone());
+
+        let honest_share = blob.get_share(0).expect("Must be able to get share");
+        let mut garbage_share = blob.get_share(1).expect("Must be able to get share");
+        garbage_share.iter_mut().for_each(|chunk| {
+            let mut bytes = chunk.to_bytes().unwrap();
+            // Last byte of the proof, which precedes the content flags byte.
+            let last_proof_byte_idx = bytes.len() - 2;
+            bytes[last_proof_byte_idx] ^= 1;
+            *chunk = ProofCarryingChunk::from_bytes(&bytes).unwrap().0;
+        });
+
+        honest_share.iter().for_each(|chunk| {
+            assert!(repairer.add_chunk_from_source(chunk, "node-a").is_ok());
+        });
+        garbage_share.iter().for_each(|chunk| {
+            assert!(matches!(
+                repairer.add_chunk_from_source(chunk, "node-b"),
+                Err(DecdsError::InvalidProofInChunk(_, _))
+            ));
+        });
+        repairer.record_suspect_source("node-c");
+
+        assert_eq!(repairer.get_suspect_sources(), vec![("node-b", 2), ("node-c", 1)]);
+    }
+
+    #[test]
+    fn test_blob_chunk_digests() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..ChunkSet::BYTE_LENGTH * 2 + 1).map(|_| rng.random()).collect::<Vec<u8>>();
+        let blob = Blob::new(blob_data).expect("Must be able to prepare blob");
+        let chunk_digests = blob.chunk_digests().collect::<Vec<(usize, usize, blake3::Hash)>>();
+
+        assert_eq!(chunk_digests.len(), blob.get_blob_header().get_num_chunks());
+        chunk_digests
+            .iter()
+            .enumerate()
+            .for_each(|(global_chunk_id, &(chunkset_id, share_id, chunk_digest))| {
+                let chunk = &blob.get_share(share_id).unwrap()[chunkset_id];
+
+                assert_eq!(chunk.get_global_chunk_id(), global_chunk_id);
+                assert_eq!(chunk.get_chunk_digest(), chunk_digest);
+            });
+
+        let inline_blob = Blob::new_with_inline_threshold(vec![0xde, 0xad], 2).expect("Must be able to prepare inline blob");
+        assert_eq!(inline_blob.chunk_digests().count(), 0);
+    }
+
+    #[test]
+    fn test_repairing_blob_add_chunk_from_reader() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..ChunkSet::BYTE_LENGTH * 2).map(|_| rng.random()).collect::<Vec<u8>>();
+        let blob = Blob::new(blob_data.clone()).expect("Must be able to prepare blob");
+        let mut repairer = RepairingBlob::new(blob.get_blob_header().clone());
+
+        let mut tampered_chunk_bytes = blob.get_share(0).unwrap()[0].to_bytes().unwrap();
+        // Last byte of the proof, which precedes the content flags byte.
+        let last_proof_byte_idx = tampered_chunk_bytes.len() - 2;
+        tampered_chunk_bytes[last_proof_byte_idx] ^= 1;
+
+        assert!(matches!(
+            repairer.add_chunk_from_reader(&mut Cursor::new(tampered_chunk_bytes), "node-b"),
+            Err(DecdsError::InvalidProofInChunk(_, _))
+        ));
+        assert!(matches!(
+            repairer.add_chunk_from_reader(&mut Cursor::new(vec![0xffu8; 8]), "node-c"),
+            Err(DecdsError::ProofCarryingChunkDeserializationFailed(_))
+        ));
+
+        let serialized_chunks = (0..ChunkSet::NUM_ORIGINAL_CHUNKS)
+            .flat_map(|share_id| blob.get_share(share_id).unwrap())
+            .flat_map(|chunk| chunk.to_bytes().unwrap())
+            .collect::<Vec<u8>>();
+        let mut reader = Cursor::new(serialized_chunks);
+
+        (0..ChunkSet::NUM_ORIGINAL_CHUNKS * blob.get_blob_header().get_num_chunksets()).for_each(|_| {
+            assert!(repairer.add_chunk_from_reader(&mut reader, "node-a").is_ok());
+        });
+
+        assert_eq!(reader.position() as usize, reader.get_ref().len());
+        assert_eq!(repairer.get_suspect_sources(), vec![("node-b", 1), ("node-c", 1)]);
+        assert_eq!(
+            (0..blob.get_blob_header().get_num_chunksets())
+                .flat_map(|chunkset_id| repairer.get_repaired_chunkset(chunkset_id).unwrap())
+                .collect::<Vec<u8>>(),
+            blob_data
+        );
+    }
+
+    #[test]
+    fn test_repairing_blob_with_validation_cache() {
+        let mut rng = rand::rng();
+
+        let blob_data: Vec<u8> = (0..(ChunkSet::BYTE_LENGTH + ChunkSet::BYTE_LENGTH / 2)).map(|_| rng.random()).collect();
+        let blob = Blob::new(blob_data.clone()).unwrap();
+
+        let blob_header = blob.get_blob_header().clone();
+        let mut repairer = RepairingBlob::with_validation_cache(blob_header.clone(), 4);
+
+        let all_chunks: Vec<ProofCarryingChunk> = (0..consts::DECDS_NUM_ERASURE_CODED_SHARES)
+            .flat_map(|share_id| blob.get_share(share_id).unwrap())
+            .collect();
+
+        // Re-ingesting the same chunk hits the cache, and is then rejected by the decoder as not innovative
+        assert!(repairer.add_chunk(&all_chunks[0]).is_ok());
+        assert_eq!(
+            repairer.add_chunk(&all_chunks[0]),
+            Err(DecdsError::NonInnovativeChunk(
+                all_chunks[0].get_chunkset_id(),
+                all_chunks[0].get_local_chunk_id()
+            ))
+        );
+        assert_eq!(repairer.validation_cache.as_ref().unwrap().len(), 1);
+
+        // Chunks failing validation are never cached
+        let mut repairer_invalid_header = RepairingBlob::with_validation_cache(
+            BlobHeader {
+                root_commitment: blake3::hash(b"fake_root_commitment"),
+                ..blob_header.clone()
+            },
+            4,
+        );
+        assert_eq!(
+            repairer_invalid_header.add_chunk(&all_chunks[0]).unwrap_err(),
+            DecdsError::InvalidProofInChunk(all_chunks[0].get_chunkset_id(), all_chunks[0].get_local_chunk_id())
+        );
+        assert!(repairer_invalid_header.validation_cache.as_ref().unwrap().is_empty());
+
+        for chunk in &all_chunks {
+            let _ = repairer.add_chunk(chunk);
+        }
+
+        let repaired_blob_data = (0..blob_header.get_num_chunksets())
+            .flat_map(|chunkset_id| repairer.get_repaired_chunkset(chunkset_id).unwrap())
+            .collect::<Vec<u8>>();
+        assert_eq!(repaired_blob_data, blob_data);
+    }
+
+    #[test]
+    fn test_repairing_blob_validation_cache_checks_chunk_fields() {
+        let blob = Blob::new(vec![0xcd; 1024]).unwrap();
+        let mut repairer = RepairingBlob::with_validation_cache(blob.get_blob_header().clone(), 4);
+
+        let chunk = blob.get_chunk(0, 3).unwrap().clone();
+        assert!(repairer.add_chunk(&chunk).is_ok());
+        assert_eq!(repairer.validation_cache.as_ref().unwrap().len(), 1);
+
+        // Content flags aren't covered by the chunk digest, so a chunk with flipped flags hits the cache, but is still rejected
+        let flipped_chunk = chunk.clone().with_content_flags(ContentFlags::new().encrypted(true));
+        assert_eq!(flipped_chunk.get_chunk_digest(), chunk.get_chunk_digest());
+        assert_eq!(repairer.add_chunk(&flipped_chunk), Err(DecdsError::InvalidProofInChunk(0, 3)));
+        assert!(
+            !blob
+                .get_blob_header()
+                .validate_chunk_cached(&flipped_chunk, repairer.validation_cache.as_mut().unwrap())
+        );
+
+        // Batches consult the cache too, checking chunk fields just the same
+        assert_eq!(
+            repairer.add_chunks([chunk.clone(), flipped_chunk]),
+            vec![Err(DecdsError::NonInnovativeChunk(0, 3)), Err(DecdsError::InvalidProofInChunk(0, 3))]
+        );
+        assert_eq!(repairer.validation_cache.as_ref().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_repairing_blob_missing() {
+        let mut rng = rand::rng();
+
+        let blob_data: Vec<u8> = (0..(ChunkSet::BYTE_LENGTH * 2 + 1)).map(|_| rng.random()).collect();
+        let blob = Blob::new(blob_data).unwrap();
+        let header = blob.get_blob_header().clone();
+
+        let mut repairer = RepairingBlob::new(header.clone());
+        assert_eq!(
+            repairer.missing().collect::<Vec<_>>(),
+            vec![
+                (0, ChunkSet::NUM_ORIGINAL_CHUNKS),
+                (1, ChunkSet::NUM_ORIGINAL_CHUNKS),
+                (2, ChunkSet::NUM_ORIGINAL_CHUNKS)
+            ]
+        );
+
+        let share = blob.get_share(0).unwrap();
+        repairer.add_chunk(&share[1]).unwrap();
+        assert_eq!(
+            repairer.missing().collect::<Vec<_>>(),
+            vec![
+                (0, ChunkSet::NUM_ORIGINAL_CHUNKS),
+                (1, ChunkSet::NUM_ORIGINAL_CHUNKS - 1),
+                (2, ChunkSet::NUM_ORIGINAL_CHUNKS)
+            ]
+        );
+
+        (0..consts::DECDS_NUM_ERASURE_CODED_SHARES).for_each(|share_id| {
+            let _ = repairer.add_chunk(&blob.get_share(share_id).unwrap()[0]);
+        });
+        repairer.get_repaired_chunkset(0).unwrap();
+
+        (0..consts::DECDS_NUM_ERASURE_CODED_SHARES).for_each(|share_id| {
+            let _ = repairer.add_chunk(&blob.get_share(share_id).unwrap()[2]);
+        });
+
+        assert_eq!(repairer.missing().collect::<Vec<_>>(), vec![(1, ChunkSet::NUM_ORIGINAL_CHUNKS - 1)]);
+
+        (0..consts::DECDS_NUM_ERASURE_CODED_SHARES).for_each(|share_id| {
+            let _ = repairer.add_chunk(&blob.get_share(share_id).unwrap()[1]);
+        });
+        assert_eq!(repairer.missing().next(), None);
+    }
+
+    #[test]
+    fn test_repairing_blob_checks_chunkset_padding() {
+        let mut rng = rand::rng();
+
+        let config = BlobConfig::new().chunkset_byte_length(1024);
+        let blob_data = (0..1500).map(|_| rng.random()).collect::<Vec<u8>>();
+
+        let blob = DecdsBuilder::new().config(config).build(blob_data.clone()).unwrap();
+        let header = blob.get_blob_header();
+        assert_eq!(header.get_last_chunkset_size(), 476);
+        assert_eq!(header.get_padding_byte_length(), 548);
+
+        let mut repairer = RepairingBlob::new(header.clone());
+        (0..consts::DECDS_NUM_ERASURE_CODED_SHARES).for_each(|share_id| {
+            blob.iter_share(share_id).unwrap().for_each(|chunk| {
+                let _ = repairer.add_chunk(chunk);
+            });
+        });
+        assert_eq!(repairer.get_repaired_chunkset_checked(0).unwrap(), &blob_data[..1024]);
+        assert_eq!(repairer.get_repaired_chunkset_checked(1).unwrap(), &blob_data[1024..]);
+
+        let inline_blob = DecdsBuilder::new().inline_threshold(1500).build(blob_data.clone()).unwrap();
+        assert_eq!(inline_blob.get_blob_header().get_last_chunkset_size(), 1500);
+        assert_eq!(inline_blob.get_blob_header().get_padding_byte_length(), 0);
+
+        // Blob, erasure-coded with garbage in place of zero padding, repairs fine, unless padding is checked
+        let chunkset_data = vec![0xab; 1024];
+        let chunkset = ChunkSet::new_with_config(0, chunkset_data.clone(), &config, None).unwrap();
+        let tampered_blob = BlobPipeline::from_chunksets(1000, config, blake3::hash(&chunkset_data[..1000]), vec![chunkset])
+            .commit()
+            .unwrap()
+            .attach_proofs();
+
+        let repair_tampered_blob = |check_padding: bool| {
+            let mut repairer = RepairingBlob::new(tampered_blob.get_blob_header().clone());
+            (0..consts::DECDS_NUM_ERASURE_CODED_SHARES).for_each(|share_id| {
+                let _ = repairer.add_chunk(tampered_blob.get_chunk(0, share_id).unwrap());
+            });
+
+            if check_padding {
+                repairer.get_repaired_chunkset_checked(0)
+            } else {
+                repairer.get_repaired_chunkset(0)
+            }
+        };
+
+        assert_eq!(repair_tampered_blob(false), Ok(chunkset_data[..1000].to_vec()));
+        assert_eq!(repair_tampered_blob(true), Err(DecdsError::NonZeroChunksetPadding(0)));
+    }
+
     #[test]
     fn test_repairing_blob_get_repaired_chunkset() {
         let mut rng = rand::rng();
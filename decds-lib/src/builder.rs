@@ -0,0 +1,780 @@
+use crate::{
+    blob::{Blob, BlobHeader, ChunkAcceptancePolicy, RepairingBlob},
+    chunk::ProofCarryingChunk,
+    chunkset::ChunkSet,
+    config::BlobConfig,
+    errors::DecdsError,
+    events::{EventHandler, NoopEventHandler},
+    flags::ContentFlags,
+    pipeline::BlobPipeline,
+};
+use std::{
+    io::Read,
+    ops::{Bound, RangeBounds},
+    path::Path,
+};
+
+/// Builder-style entry point for erasure-coding a blob, gathering all knobs of `Blob` construction in one place, so that
+/// new ones can be added without multiplying `Blob::new_with_*` constructors. It returns a regular `Blob`.
+///
+/// ```rust
+/// use decds_lib::DecdsBuilder;
+///
+/// let blob = DecdsBuilder::new()
+///     .inline_threshold(1024)
+///     .num_threads(2)
+///     .build(vec![0xde; 100])
+///     .expect("Failed to create blob");
+///
+/// assert!(blob.get_blob_header().is_inline());
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct DecdsBuilder<'a> {
+    inline_threshold: usize,
+    event_handler: Option<&'a dyn EventHandler>,
+    num_threads: Option<usize>,
+    seed: Option<[u8; 32]>,
+    config: BlobConfig,
+    lazy_shares: bool,
+    content_flags: ContentFlags,
+    record_offsets: &'a [usize],
+}
+
+impl<'a> DecdsBuilder<'a> {
+    /// Creates a builder with default settings, i.e. blob is never stored inline, no event handler is registered and
+    /// chunksets are erasure-coded on the global `rayon` thread pool. Building with it is same as calling `Blob::new`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets maximum byte length of a blob, which is stored inline in the `BlobHeader`, instead of being erasure-coded.
+    /// See `Blob::new_with_inline_threshold`.
+    pub fn inline_threshold(mut self, inline_threshold: usize) -> Self {
+        self.inline_threshold = inline_threshold;
+        self
+    }
+
+    /// Registers an `EventHandler`, which is notified as each chunkset is erasure-coded. See `Blob::new_with_event_handler`.
+    pub fn event_handler(mut self, event_handler: &'a dyn EventHandler) -> Self {
+        self.event_handler = Some(event_handler);
+        self
+    }
+
+    /// Erasure-codes chunksets on a dedicated thread pool of `num_threads` threads, instead of the global `rayon` thread
+    /// pool, e.g. for keeping a few cores free for other work. Passing `0` lets `rayon` pick the number of threads.
+    pub fn num_threads(mut self, num_threads: usize) -> Self {
+        self.num_threads = Some(num_threads);
+        self
+    }
+
+    /// Derives RLNC coding vectors from `seed`, instead of sampling them randomly, so that same data, encoded with same seed,
+    /// always produces same chunks and commitments. Versions of an object, encoded with one seed, can then be compared with
+    /// `BlobHeader::diff`, as unchanged chunksets keep their commitments. Anyone knowing the seed can predict coding vectors.
+    pub fn seed(mut self, seed: [u8; 32]) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Splits and erasure-codes blobs with the geometry `config`, instead of the default one, i.e. 10MB chunksets, each
+    /// encoded into 16 shares, any 10 of which repair it. The geometry is recorded in the `BlobHeader`, so repairing needs
+    /// no extra setup. See `BlobConfig`.
+    pub fn config(mut self, config: BlobConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Makes `Self::build` keep the original blob data, instead of all erasure-coded chunks, which take `n/k` times as much
+    /// memory, and erasure-code a share again each time `Blob::get_share` or `Blob::share_stream` asks for it. Suits
+    /// uploaders pushing one share at a time. Chunks are still coded once while building, for committing to them, and can't
+    /// be borrowed, i.e. `Blob::iter_share` and `Blob::get_chunk` fail. Unless a seed is set, one is sampled for the blob.
+    pub fn lazy_shares(mut self, lazy_shares: bool) -> Self {
+        self.lazy_shares = lazy_shares;
+        self
+    }
+
+    /// Marks blobs as encrypted and/or compressed by the caller, recording `content_flags` in the `BlobHeader` and in the
+    /// wire envelope of each chunk, so that storage nodes can reject chunks of plaintext blobs. See `ContentFlags`.
+    pub fn content_flags(mut self, content_flags: ContentFlags) -> Self {
+        self.content_flags = content_flags;
+        self
+    }
+
+    /// Registers logical record boundaries of blobs, i.e. start offsets of application records, e.g. rows or messages,
+    /// in ascending order, recording them in the `BlobHeader`. Readers then resolve a record to the chunksets holding it,
+    /// with `BlobHeader::get_chunkset_ids_for_record`, and fetch only those, without keeping an offset index of their own.
+    /// Offsets don't affect chunk commitments, only the records digest, see `BlobHeader::get_records_digest`. See
+    /// `RepairingBlob::read_record`.
+    pub fn record_offsets(mut self, record_offsets: &'a [usize]) -> Self {
+        self.record_offsets = record_offsets;
+        self
+    }
+
+    /// Erasure-codes `data` into a `Blob`, using the configured settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The raw `Vec<u8>` representing the blob's content.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Blob)` containing the newly created `Blob` if successful.
+    /// - `Err(DecdsError::InvalidBlobConfig)` if the configured `BlobConfig` isn't valid.
+    /// - `Err(DecdsError::ThreadPoolBuildFailed)` if the dedicated thread pool can't be created.
+    /// - `Err(DecdsError::InvalidRecordOffset)` if record offsets aren't strictly ascending, or reach beyond the blob.
+    /// - Otherwise, same errors as `Blob::new`.
+    pub fn build(&self, data: Vec<u8>) -> Result<Blob, DecdsError> {
+        let event_handler = self.event_handler.unwrap_or(&NoopEventHandler);
+        let build = if self.lazy_shares { Blob::build_lazy } else { Blob::build };
+
+        let blob = match self.num_threads {
+            Some(num_threads) => rayon::ThreadPoolBuilder::new()
+                .num_threads(num_threads)
+                .build()
+                .map_err(|err| DecdsError::ThreadPoolBuildFailed(err.to_string()))?
+                .install(|| build(data, self.inline_threshold, &self.config, self.seed.as_ref(), event_handler)),
+            None => build(data, self.inline_threshold, &self.config, self.seed.as_ref(), event_handler),
+        }?;
+
+        blob.with_content_flags(self.content_flags).with_record_offsets(self.record_offsets)
+    }
+
+    /// Same as `Self::build`, but consumes blob data from `reader`, one chunkset at a time. See `Blob::from_reader`.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Source of the blob's content, read until its end.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Blob)` containing the newly created `Blob` if successful.
+    /// - `Err(DecdsError::InvalidBlobConfig)` if the configured `BlobConfig` isn't valid.
+    /// - `Err(DecdsError::ThreadPoolBuildFailed)` if the dedicated thread pool can't be created.
+    /// - `Err(DecdsError::InvalidRecordOffset)` if record offsets aren't strictly ascending, or reach beyond the blob.
+    /// - Otherwise, same errors as `Blob::from_reader`.
+    pub fn build_from_reader<R: Read + Send>(&self, reader: R) -> Result<Blob, DecdsError> {
+        let event_handler = self.event_handler.unwrap_or(&NoopEventHandler);
+        let build = || Blob::build_from_reader(reader, self.inline_threshold, &self.config, self.seed.as_ref(), event_handler);
+
+        let blob = match self.build_thread_pool()? {
+            Some(thread_pool) => thread_pool.install(build),
+            None => build(),
+        }?;
+
+        blob.with_content_flags(self.content_flags).with_record_offsets(self.record_offsets)
+    }
+
+    /// Same as `Self::build`, but erasure-codes the file at `path`, memory-mapping it. See `Blob::from_file`.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be truncated or modified, by this or any other process, until this function returns. See
+    /// `Blob::from_file`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the file holding the blob's content.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Blob)` containing the newly created `Blob` if successful.
+    /// - `Err(DecdsError::InvalidBlobConfig)` if the configured `BlobConfig` isn't valid.
+    /// - `Err(DecdsError::ThreadPoolBuildFailed)` if the dedicated thread pool can't be created.
+    /// - `Err(DecdsError::InvalidRecordOffset)` if record offsets aren't strictly ascending, or reach beyond the blob.
+    /// - Otherwise, same errors as `Blob::from_file`.
+    pub unsafe fn build_from_file<P: AsRef<Path>>(&self, path: P) -> Result<Blob, DecdsError> {
+        let event_handler = self.event_handler.unwrap_or(&NoopEventHandler);
+        let path = path.as_ref();
+        // Caller upholds the contract of `Blob::build_from_file`.
+        let build = || unsafe { Blob::build_from_file(path, self.inline_threshold, &self.config, self.seed.as_ref(), event_handler) };
+
+        let blob = match self.build_thread_pool()? {
+            Some(thread_pool) => thread_pool.install(build),
+            None => build(),
+        }?;
+
+        blob.with_content_flags(self.content_flags).with_record_offsets(self.record_offsets)
+    }
+
+    /// Erasure-codes a single chunkset, using the configured seed and thread pool, without building a whole `Blob`, e.g.
+    /// for services keeping their own blob-level index. Data shorter than a chunkset of the configured `BlobConfig` (10MB by
+    /// default) is zero-padded, just like the last chunkset of a blob, so the caller has to keep track of its length. Chunks carry proofs of inclusion in the
+    /// chunkset only, to be checked with `ProofCarryingChunk::validate_inclusion_in_chunkset` against the returned
+    /// commitment. The inline threshold doesn't apply here. Chunks carry the configured content flags, so the `BlobCoordinator`
+    /// assembling the header must be given the same ones, see `BlobCoordinator::content_flags`.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset, which chunk IDs and coding vectors derived from the seed depend on.
+    /// * `data` - At most `BlobConfig::get_chunkset_byte_length` bytes of data to erasure-code.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok((blake3::Hash, Vec<ProofCarryingChunk>))` containing the chunkset root commitment and its
+    ///   `BlobConfig::get_num_shares` chunks, ordered by their local chunk ID, if successful.
+    /// - `Err(DecdsError::InvalidBlobConfig)` if the configured `BlobConfig` isn't valid.
+    /// - `Err(DecdsError::CodecNotCompiledIn)` if the configured codec is disabled by a feature flag.
+    /// - `Err(DecdsError::InvalidChunksetSize)` if `data` is longer than a chunkset.
+    /// - `Err(DecdsError::ArithmeticOverflow)` if `chunkset_id` is so large, that global IDs of its chunks don't fit in a
+    ///   `usize`.
+    /// - `Err(DecdsError::ThreadPoolBuildFailed)` if the dedicated thread pool can't be created.
+    pub fn encode_chunkset(&self, chunkset_id: usize, data: Vec<u8>) -> Result<(blake3::Hash, Vec<ProofCarryingChunk>), DecdsError> {
+        self.config.validate()?;
+        self.config.get_codec().check_compiled_in()?;
+        if data.len() > self.config.get_chunkset_byte_length() {
+            return Err(DecdsError::InvalidChunksetSize(data.len()));
+        }
+        ChunkSet::check_global_chunk_ids(chunkset_id)?;
+
+        let thread_pool = self.build_thread_pool()?;
+        let mut chunkset = self.encode_padded_chunkset(thread_pool.as_ref(), chunkset_id, data);
+        chunkset.set_content_flags(self.content_flags);
+
+        let commitment = chunkset.get_root_commitment();
+        self.event_handler.unwrap_or(&NoopEventHandler).on_chunkset_encoded(chunkset_id, commitment);
+
+        Ok((commitment, chunkset.into_chunks()))
+    }
+
+    /// Transcodes an erasure-coded blob into a new `Blob`, erasure-coded with the configured settings, e.g. with a seed,
+    /// another geometry, or stored inline, without a manual repair and break round trip. Chunks are consumed in a streaming
+    /// fashion: as soon as enough chunks of a chunkset are in, it's repaired and immediately re-encoded, so that at most one
+    /// chunkset is held in decoded form, unless the new blob is to be stored inline, or split into chunksets of another
+    /// size. Chunks failing verification against `header`, or not needed anymore, are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - Header of the blob to transcode.
+    /// * `chunks` - Proof-carrying chunks of the blob to transcode, in any order.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Blob)` containing the transcoded `Blob`, with the same blob ID, if successful.
+    /// - `Err(DecdsError::InvalidBlobConfig)` if the configured `BlobConfig` isn't valid.
+    /// - `Err(DecdsError::CodecNotCompiledIn)` if the configured codec is disabled by a feature flag.
+    /// - `Err(DecdsError::ChunksetNotYetReadyToRepair)` for the first chunkset, which can't be repaired from `chunks`.
+    /// - `Err(DecdsError::ThreadPoolBuildFailed)` if the dedicated thread pool can't be created.
+    /// - Otherwise, same errors as `RepairingBlob::get_repaired_chunkset` or `Self::build`.
+    pub fn transcode<'c, I>(&self, header: &BlobHeader, chunks: I) -> Result<Blob, DecdsError>
+    where
+        I: IntoIterator<Item = &'c ProofCarryingChunk>,
+    {
+        self.config.validate()?;
+        self.config.get_codec().check_compiled_in()?;
+        let mut repairer = RepairingBlob::new(header.clone());
+
+        let chunkset_byte_length = self.config.get_chunkset_byte_length();
+        if header.is_inline()
+            || header.get_config().get_chunkset_byte_length() != chunkset_byte_length
+            || header.get_blob_size() <= self.inline_threshold.min(chunkset_byte_length)
+        {
+            chunks.into_iter().for_each(|chunk| {
+                let _ = repairer.add_chunk(chunk);
+            });
+
+            let data = (0..header.get_num_chunksets())
+                .map(|chunkset_id| repairer.get_repaired_chunkset(chunkset_id))
+                .collect::<Result<Vec<Vec<u8>>, DecdsError>>()?
+                .concat();
+            return self.build(data);
+        }
+
+        let thread_pool = self.build_thread_pool()?;
+        let event_handler = self.event_handler.unwrap_or(&NoopEventHandler);
+
+        let encode_chunkset = |chunkset_id: usize, chunkset_data: Vec<u8>| {
+            let chunkset = self.encode_padded_chunkset(thread_pool.as_ref(), chunkset_id, chunkset_data);
+            event_handler.on_chunkset_encoded(chunkset_id, chunkset.get_root_commitment());
+
+            chunkset
+        };
+
+        let mut chunksets = (0..header.get_num_chunksets()).map(|_| None).collect::<Vec<Option<ChunkSet>>>();
+        for chunk in chunks {
+            let chunkset_id = chunk.get_chunkset_id();
+            if repairer.add_chunk(chunk).is_ok() && repairer.is_chunkset_ready_to_repair(chunkset_id)? {
+                chunksets[chunkset_id] = Some(encode_chunkset(chunkset_id, repairer.get_repaired_chunkset(chunkset_id)?));
+            }
+        }
+
+        let chunksets = chunksets
+            .into_iter()
+            .enumerate()
+            .map(|(chunkset_id, chunkset)| chunkset.ok_or(DecdsError::ChunksetNotYetReadyToRepair(chunkset_id)))
+            .collect::<Result<Vec<ChunkSet>, DecdsError>>()?;
+
+        BlobPipeline::from_chunksets(header.get_blob_size(), self.config, header.get_blob_digest(), chunksets)
+            .commit()?
+            .attach_proofs()
+            .with_content_flags(self.content_flags)
+            .with_record_offsets(self.record_offsets)
+    }
+
+    /// Builds the dedicated thread pool, if the number of threads is configured.
+    fn build_thread_pool(&self) -> Result<Option<rayon::ThreadPool>, DecdsError> {
+        self.num_threads
+            .map(|num_threads| rayon::ThreadPoolBuilder::new().num_threads(num_threads).build())
+            .transpose()
+            .map_err(|err| DecdsError::ThreadPoolBuildFailed(err.to_string()))
+    }
+
+    /// Zero-pads at most a chunkset worth of data and erasure-codes it with the configured, already validated, geometry and
+    /// compiled in codec on `thread_pool`, or on the global one. Global IDs of chunks of chunkset `chunkset_id` must fit in a `usize`.
+    fn encode_padded_chunkset(&self, thread_pool: Option<&rayon::ThreadPool>, chunkset_id: usize, mut data: Vec<u8>) -> ChunkSet {
+        data.resize(self.config.get_chunkset_byte_length(), 0);
+
+        let encode = || unsafe { ChunkSet::new_with_config(chunkset_id, data, &self.config, self.seed.as_ref()).unwrap_unchecked() };
+        match thread_pool {
+            Some(thread_pool) => thread_pool.install(encode),
+            None => encode(),
+        }
+    }
+}
+
+/// Builder-style entry point for repairing a blob, gathering all knobs of `RepairingBlob` construction in one place, so
+/// that new ones can be added without multiplying `RepairingBlob` constructors and setters. It returns a regular
+/// `RepairingBlob`.
+///
+/// ```rust
+/// use decds_lib::{DecdsBuilder, RepairBuilder};
+///
+/// let blob = DecdsBuilder::new().build(vec![0xde; 1024]).expect("Failed to create blob");
+/// let mut repairer = RepairBuilder::new(blob.get_blob_header().clone())
+///     .validation_cache(64)
+///     .share_weight(0, 10)
+///     .build()
+///     .expect("Failed to create repairer");
+///
+/// assert_eq!(repairer.get_preferred_share_order().last(), Some(&0));
+/// ```
+pub struct RepairBuilder {
+    header: BlobHeader,
+    validation_cache_capacity: Option<usize>,
+    repaired_chunkset_cache_capacity: Option<usize>,
+    event_handler: Option<Box<dyn EventHandler>>,
+    acceptance_policy: Option<ChunkAcceptancePolicy>,
+    share_weights: Vec<(usize, u32)>,
+    byte_range: Option<(Bound<usize>, Bound<usize>)>,
+    checkpointing: bool,
+}
+
+impl RepairBuilder {
+    /// Creates a builder for repairing the blob described by `header`, with default settings, i.e. no validation cache,
+    /// no event handler and all shares weighing the same. Building with it is same as calling `RepairingBlob::new`.
+    pub fn new(header: BlobHeader) -> Self {
+        RepairBuilder {
+            header,
+            validation_cache_capacity: None,
+            repaired_chunkset_cache_capacity: None,
+            event_handler: None,
+            acceptance_policy: None,
+            share_weights: Vec::new(),
+            byte_range: None,
+            checkpointing: false,
+        }
+    }
+
+    /// Remembers digests of up to `capacity` validated chunks, so that re-ingested chunks skip Merkle proof verification.
+    /// See `RepairingBlob::with_validation_cache`.
+    pub fn validation_cache(mut self, capacity: usize) -> Self {
+        self.validation_cache_capacity = Some(capacity);
+        self
+    }
+
+    /// Keeps up to `capacity` repaired chunksets in memory, least recently used ones being evicted first, so that
+    /// overlapping byte ranges can be served again. See `RepairingBlob::read_range`.
+    pub fn repaired_chunkset_cache(mut self, capacity: usize) -> Self {
+        self.repaired_chunkset_cache_capacity = Some(capacity);
+        self
+    }
+
+    /// Registers an `EventHandler`, which is notified of verified and invalid chunks, of ready and repaired chunksets, and
+    /// of repair progress. See `RepairingBlob::set_event_handler`.
+    pub fn event_handler(mut self, event_handler: Box<dyn EventHandler>) -> Self {
+        self.event_handler = Some(event_handler);
+        self
+    }
+
+    /// Installs a policy, deciding which verified chunks are handed to the decoder, e.g. based on their source.
+    /// See `RepairingBlob::set_acceptance_policy`.
+    pub fn acceptance_policy(mut self, policy: ChunkAcceptancePolicy) -> Self {
+        self.acceptance_policy = Some(policy);
+        self
+    }
+
+    /// Registers the cost of fetching chunks of a share, lower is cheaper. See `RepairingBlob::set_share_weight`.
+    /// The share ID is checked when building.
+    pub fn share_weight(mut self, share_id: usize, weight: u32) -> Self {
+        self.share_weights.push((share_id, weight));
+        self
+    }
+
+    /// Restricts repair to a byte range of the blob, so that only chunksets overlapping it are tracked and decoded, while
+    /// chunks of other chunksets are turned away, see `RepairingBlob::repair_target_range`. The range is checked when
+    /// building.
+    pub fn byte_range(mut self, byte_range: impl RangeBounds<usize>) -> Self {
+        self.byte_range = Some((byte_range.start_bound().cloned(), byte_range.end_bound().cloned()));
+        self
+    }
+
+    /// Makes the repairer remember useful chunks it's fed, so that its progress can be checkpointed with
+    /// `RepairingBlob::to_bytes` and resumed with `RepairingBlob::from_bytes`, at the cost of holding them twice in memory,
+    /// once decoded and once as received. Disabled by default.
+    pub fn checkpointing(mut self, checkpointing: bool) -> Self {
+        self.checkpointing = checkpointing;
+        self
+    }
+
+    /// Creates a `RepairingBlob`, using the configured settings.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(RepairingBlob)` prepared to accept chunks for reconstruction, if successful.
+    /// - `Err(DecdsError::InvalidErasureCodedShareId)` if a share weight is set for a share ID, which is not less than
+    ///   the number of shares of the blob, as per its `BlobConfig`.
+    /// - `Err(DecdsError::InvalidStartBound)` or `Err(DecdsError::InvalidEndBound)` if the byte range doesn't lie within
+    ///   the blob.
+    pub fn build(self) -> Result<RepairingBlob, DecdsError> {
+        let num_shares = self.header.get_config().get_num_shares();
+        if let Some(&(share_id, _)) = self.share_weights.iter().find(|(share_id, _)| *share_id >= num_shares) {
+            return Err(DecdsError::InvalidErasureCodedShareId(share_id));
+        }
+
+        let mut repairer = match self.validation_cache_capacity {
+            Some(capacity) => RepairingBlob::with_validation_cache(self.header, capacity),
+            None => RepairingBlob::new(self.header),
+        };
+
+        self.share_weights.into_iter().for_each(|(share_id, weight)| unsafe {
+            repairer.set_share_weight(share_id, weight).unwrap_unchecked();
+        });
+
+        if let Some(capacity) = self.repaired_chunkset_cache_capacity {
+            repairer.set_repaired_chunkset_cache(capacity);
+        }
+
+        if let Some(event_handler) = self.event_handler {
+            repairer.set_event_handler(event_handler);
+        }
+
+        if let Some(policy) = self.acceptance_policy {
+            repairer.set_acceptance_policy(policy);
+        }
+
+        if let Some(byte_range) = self.byte_range {
+            repairer.restrict_to_byte_range(byte_range)?;
+        }
+
+        if self.checkpointing {
+            repairer.enable_checkpointing();
+        }
+
+        Ok(repairer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BlobConfig, BlobHeader, DECDS_NUM_ERASURE_CODED_SHARES, DecdsBuilder, DecdsError, EventHandler, ProofCarryingChunk, RepairBuilder, chunkset::ChunkSet,
+    };
+    use rand::Rng;
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    #[derive(Default)]
+    struct CountingEventHandler {
+        num_encoded_chunksets: AtomicUsize,
+        num_repaired_chunksets: AtomicUsize,
+    }
+
+    impl EventHandler for Arc<CountingEventHandler> {
+        fn on_chunkset_encoded(&self, _chunkset_id: usize, _commitment: blake3::Hash) {
+            self.num_encoded_chunksets.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_chunkset_repaired(&self, _chunkset_id: usize) {
+            self.num_repaired_chunksets.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_builders_build_and_repair_blob() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..ChunkSet::BYTE_LENGTH * 2 + 1).map(|_| rng.random()).collect::<Vec<u8>>();
+        let event_handler = Arc::new(CountingEventHandler::default());
+
+        let blob = DecdsBuilder::new()
+            .inline_threshold(1024)
+            .event_handler(&event_handler)
+            .num_threads(2)
+            .build(blob_data.clone())
+            .expect("Must be able to build blob");
+        let header = blob.get_blob_header().clone();
+
+        assert!(!header.is_inline());
+        assert_eq!(event_handler.num_encoded_chunksets.load(Ordering::Relaxed), header.get_num_chunksets());
+
+        let mut repairer = RepairBuilder::new(header.clone())
+            .validation_cache(header.get_num_chunks())
+            .event_handler(Box::new(event_handler.clone()))
+            .share_weight(0, 10)
+            .share_weight(1, 5)
+            .build()
+            .expect("Must be able to build repairer");
+
+        let preferred_share_order = repairer.get_preferred_share_order();
+        assert_eq!(&preferred_share_order[(DECDS_NUM_ERASURE_CODED_SHARES - 2)..], &[1, 0]);
+
+        preferred_share_order.iter().for_each(|&share_id| {
+            blob.iter_share(share_id).unwrap().for_each(|chunk| {
+                let _ = repairer.add_chunk(chunk);
+            });
+        });
+
+        let repaired_data = (0..header.get_num_chunksets())
+            .flat_map(|chunkset_id| repairer.get_repaired_chunkset(chunkset_id).expect("Must be able to repair chunkset"))
+            .collect::<Vec<u8>>();
+
+        assert_eq!(repaired_data, blob_data);
+        assert_eq!(event_handler.num_repaired_chunksets.load(Ordering::Relaxed), header.get_num_chunksets());
+    }
+
+    #[test]
+    fn test_builders_with_invalid_settings() {
+        let blob = DecdsBuilder::new()
+            .inline_threshold(1024)
+            .build(vec![0xde; 1024])
+            .expect("Must be able to build blob");
+        assert!(blob.get_blob_header().is_inline());
+
+        assert_eq!(
+            RepairBuilder::new(blob.get_blob_header().clone())
+                .share_weight(DECDS_NUM_ERASURE_CODED_SHARES, 1)
+                .build()
+                .err(),
+            Some(DecdsError::InvalidErasureCodedShareId(DECDS_NUM_ERASURE_CODED_SHARES))
+        );
+        assert_eq!(
+            RepairBuilder::new(blob.get_blob_header().clone()).byte_range(512..=1024).build().err(),
+            Some(DecdsError::InvalidEndBound(1025))
+        );
+        assert_eq!(DecdsBuilder::new().build(Vec::new()).err(), Some(DecdsError::EmptyDataForBlob));
+    }
+
+    #[test]
+    fn test_repair_builder_restricted_to_byte_range() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..ChunkSet::BYTE_LENGTH * 4 + 1).map(|_| rng.random()).collect::<Vec<u8>>();
+        let blob = DecdsBuilder::new().build(blob_data.clone()).expect("Must be able to build blob");
+        let header = blob.get_blob_header().clone();
+
+        // Straddles chunksets 1 and 2, leaving out 0, 3 and 4.
+        let byte_range = ChunkSet::BYTE_LENGTH + 7..ChunkSet::BYTE_LENGTH * 2 + 11;
+
+        let mut repairer = RepairBuilder::new(header.clone())
+            .byte_range(byte_range.clone())
+            .build()
+            .expect("Must be able to build repairer");
+        assert_eq!(repairer.get_target_byte_range(), Some(byte_range.clone()));
+
+        let num_original_chunks = header.get_config().get_num_original_chunks();
+        assert_eq!(repairer.missing().collect::<Vec<_>>(), vec![(1, num_original_chunks), (2, num_original_chunks)]);
+
+        for share_id in 0..DECDS_NUM_ERASURE_CODED_SHARES {
+            for chunk in blob.iter_share(share_id).expect("Must be able to get erasure-coded share") {
+                match repairer.add_chunk(chunk) {
+                    Ok(()) | Err(DecdsError::ChunksetReadyToRepair(_)) => assert!((1..=2).contains(&chunk.get_chunkset_id())),
+                    Err(DecdsError::ChunksetOutsideTargetRange(chunkset_id)) => assert!(!(1..=2).contains(&chunkset_id)),
+                    Err(err) => panic!("Unexpected error while adding chunk: {}", err),
+                }
+            }
+        }
+
+        assert_eq!(repairer.missing().next(), None);
+        assert_eq!(repairer.get_repaired_chunkset(0), Err(DecdsError::ChunksetOutsideTargetRange(0)));
+        assert_eq!(
+            repairer.get_repaired_chunkset(header.get_num_chunksets()),
+            Err(DecdsError::InvalidChunksetId(header.get_num_chunksets(), header.get_num_chunksets()))
+        );
+        assert_eq!(repairer.repair_target_range(), Ok(blob_data[byte_range].to_vec()));
+    }
+
+    #[test]
+    fn test_decds_builder_transcode() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..ChunkSet::BYTE_LENGTH + 1).map(|_| rng.random()).collect::<Vec<u8>>();
+        let blob = DecdsBuilder::new().build(blob_data.clone()).expect("Must be able to build blob");
+        let header = blob.get_blob_header();
+
+        // Chunks of both chunksets arrive interleaved, along with a few useless ones
+        let chunks = (0..DECDS_NUM_ERASURE_CODED_SHARES)
+            .rev()
+            .flat_map(|share_id| blob.get_share(share_id).unwrap())
+            .collect::<Vec<ProofCarryingChunk>>();
+
+        let event_handler = Arc::new(CountingEventHandler::default());
+        let transcoded = DecdsBuilder::new()
+            .seed([7u8; 32])
+            .event_handler(&event_handler)
+            .num_threads(2)
+            .transcode(header, &chunks)
+            .expect("Must be able to transcode blob");
+
+        // Same as encoding the original data with the new settings
+        let expected = DecdsBuilder::new().seed([7u8; 32]).build(blob_data.clone()).unwrap();
+        assert_eq!(transcoded.get_blob_header(), expected.get_blob_header());
+        assert_eq!(transcoded.get_blob_header().get_blob_id(), header.get_blob_id());
+        assert_eq!(transcoded.get_share(3).unwrap(), expected.get_share(3).unwrap());
+        assert_eq!(event_handler.num_encoded_chunksets.load(Ordering::Relaxed), header.get_num_chunksets());
+
+        // Not enough chunks of the last chunkset
+        let too_few_chunks = chunks
+            .iter()
+            .filter(|chunk| chunk.get_chunkset_id() == 0 || chunk.get_local_chunk_id() < ChunkSet::NUM_ORIGINAL_CHUNKS - 1)
+            .collect::<Vec<&ProofCarryingChunk>>();
+        assert_eq!(
+            DecdsBuilder::new().transcode(header, too_few_chunks).err(),
+            Some(DecdsError::ChunksetNotYetReadyToRepair(1))
+        );
+
+        // Small erasure-coded blob transcoded into an inline one, and back
+        let small_blob = DecdsBuilder::new().build(vec![0xde; 1024]).unwrap();
+        let inline_blob = DecdsBuilder::new()
+            .inline_threshold(1024)
+            .transcode(small_blob.get_blob_header(), &small_blob.get_share(0).unwrap())
+            .err();
+        assert_eq!(inline_blob, Some(DecdsError::ChunksetNotYetReadyToRepair(0)));
+
+        let small_blob_chunks = (0..DECDS_NUM_ERASURE_CODED_SHARES)
+            .flat_map(|share_id| small_blob.get_share(share_id).unwrap())
+            .collect::<Vec<ProofCarryingChunk>>();
+        let inline_blob = DecdsBuilder::new()
+            .inline_threshold(1024)
+            .transcode(small_blob.get_blob_header(), &small_blob_chunks)
+            .unwrap();
+        assert!(inline_blob.get_blob_header().is_inline());
+
+        let reencoded_blob = DecdsBuilder::new().transcode(inline_blob.get_blob_header(), []).unwrap();
+        assert!(!reencoded_blob.get_blob_header().is_inline());
+        assert_eq!(reencoded_blob.get_blob_header().get_blob_id(), small_blob.get_blob_header().get_blob_id());
+    }
+
+    #[test]
+    fn test_decds_builder_encode_chunkset() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..ChunkSet::BYTE_LENGTH + 5000).map(|_| rng.random()).collect::<Vec<u8>>();
+        let builder = DecdsBuilder::new().seed([7u8; 32]).num_threads(2);
+        let blob = builder.build(blob_data.clone()).expect("Must be able to build blob");
+
+        // Seeded chunkset encoding agrees with the one of a whole blob, except for blob-level proofs
+        let (commitment, chunks) = builder
+            .encode_chunkset(1, blob_data[ChunkSet::BYTE_LENGTH..].to_vec())
+            .expect("Must be able to encode chunkset");
+        assert_eq!(blob.get_blob_header().get_chunkset_commitment(1), Ok(commitment));
+        assert_eq!(chunks.len(), DECDS_NUM_ERASURE_CODED_SHARES);
+
+        let mut repairer = crate::RepairingChunkSet::new(1, commitment);
+        chunks.iter().enumerate().for_each(|(local_chunk_id, chunk)| {
+            assert_eq!(chunk.get_local_chunk_id(), local_chunk_id);
+            assert_eq!(chunk.get_proof().len(), ChunkSet::PROOF_SIZE);
+            assert!(chunk.validate_inclusion_in_chunkset(commitment));
+            let _ = repairer.add_chunk(chunk);
+        });
+
+        let repaired_data = repairer.repair().expect("Must be able to repair chunkset");
+        assert_eq!(&repaired_data[..5000], &blob_data[ChunkSet::BYTE_LENGTH..]);
+        assert!(repaired_data[5000..].iter().all(|&byte| byte == 0));
+
+        assert_eq!(
+            builder.encode_chunkset(0, vec![0; ChunkSet::BYTE_LENGTH + 1]),
+            Err(DecdsError::InvalidChunksetSize(ChunkSet::BYTE_LENGTH + 1))
+        );
+    }
+
+    #[test]
+    fn test_decds_builder_with_blob_config() {
+        let mut rng = rand::rng();
+
+        let config = BlobConfig::new().num_original_chunks(4).num_shares(6).chunkset_byte_length((1 << 20) + 3);
+        let blob_data = (0..config.get_chunkset_byte_length() * 3 - 100).map(|_| rng.random()).collect::<Vec<u8>>();
+
+        let builder = DecdsBuilder::new().config(config).seed([7u8; 32]);
+        let blob = builder.build(blob_data.clone()).expect("Must be able to build blob");
+        let header = blob.get_blob_header();
+
+        assert_eq!(header.get_config(), config);
+        assert_eq!(header.get_num_chunksets(), 3);
+        assert_eq!(header.get_num_chunks(), 3 * config.get_num_shares());
+        assert_eq!(blob.get_share(6).err(), Some(DecdsError::InvalidErasureCodedShareId(6)));
+
+        let (decoded_header, _) = BlobHeader::from_bytes(&header.to_bytes().unwrap()).expect("Must be able to deserialize blob header");
+        assert_eq!(&decoded_header, header);
+
+        // Chunks of all geometries carry proofs of same size, and any `num_original_chunks` shares repair the blob
+        let mut repairer = RepairBuilder::new(decoded_header).build().expect("Must be able to build repairer");
+        (2..config.get_num_shares()).for_each(|share_id| {
+            blob.iter_share(share_id).unwrap().for_each(|chunk| {
+                assert_eq!(chunk.get_erasure_coded_data().len(), config.get_erasure_coded_data_byte_length());
+                assert!(header.validate_chunk(chunk));
+                let _ = repairer.add_chunk(chunk);
+            });
+        });
+
+        let repaired_data = (0..header.get_num_chunksets())
+            .flat_map(|chunkset_id| repairer.get_repaired_chunkset(chunkset_id).expect("Must be able to repair chunkset"))
+            .collect::<Vec<u8>>();
+        assert_eq!(repaired_data, blob_data);
+
+        // Independently encoded chunksets and assembled chunks follow the configured geometry too
+        let (commitment, chunks) = builder
+            .encode_chunkset(2, blob_data[config.get_chunkset_byte_length() * 2..].to_vec())
+            .expect("Must be able to encode chunkset");
+        assert_eq!(header.get_chunkset_commitment(2), Ok(commitment));
+        assert_eq!(chunks.len(), config.get_num_shares());
+
+        let chunk = blob.get_share(5).unwrap().remove(0);
+        let assembled = ProofCarryingChunk::from_parts_with_config(
+            chunk.get_chunkset_id(),
+            chunk.get_local_chunk_id(),
+            chunk.get_erasure_coded_data().to_vec(),
+            chunk.get_proof().to_vec(),
+            &config,
+        );
+        assert_eq!(assembled, Ok(chunk.clone()));
+        assert_eq!(
+            ProofCarryingChunk::from_parts(0, 5, chunk.get_erasure_coded_data().to_vec(), chunk.get_proof().to_vec()).err(),
+            Some(DecdsError::InvalidProofCarryingChunkParts(format!(
+                "erasure-coded data is {}B, expected {}B",
+                config.get_erasure_coded_data_byte_length(),
+                BlobConfig::default().get_erasure_coded_data_byte_length()
+            )))
+        );
+
+        // Transcoding changes geometry, keeping the blob ID
+        let chunks = (0..config.get_num_shares())
+            .flat_map(|share_id| blob.get_share(share_id).unwrap())
+            .collect::<Vec<ProofCarryingChunk>>();
+        let transcoded = DecdsBuilder::new().transcode(header, &chunks).expect("Must be able to transcode blob");
+        assert_eq!(transcoded.get_blob_header().get_config(), BlobConfig::default());
+        assert_eq!(transcoded.get_blob_header().get_blob_id(), header.get_blob_id());
+
+        assert!(matches!(
+            DecdsBuilder::new()
+                .config(config.num_shares(DECDS_NUM_ERASURE_CODED_SHARES + 1))
+                .build(blob_data),
+            Err(DecdsError::InvalidBlobConfig(_))
+        ));
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::{BTreeMap, HashMap};
+
+/// A bounded, least-recently-used (LRU) cache of BLAKE3 digests of already validated chunks.
+///
+/// Validating a `ProofCarryingChunk` requires hashing its ~1MB erasure-coded payload and then walking up its Merkle
+/// inclusion proofs. When the same chunk is ingested again (retries, overlapping sources etc.), its digest is found in
+/// this cache and the Merkle proof verification is skipped. Hashing the payload isn't, as the digest is the only key,
+/// which vouches for the payload, so a hit saves hashing a few 32-bytes proof nodes, not the bulk of the work. A cache
+/// must only be used with chunks of a single blob, because it remembers digests, not the commitments they were validated
+/// against.
+#[derive(Clone, Debug)]
+pub struct ChunkValidationCache {
+    capacity: usize,
+    tick: u64,
+    last_used_at: HashMap<blake3::Hash, u64>,
+    by_recency: BTreeMap<u64, blake3::Hash>,
+}
+
+impl ChunkValidationCache {
+    /// Creates a new empty `ChunkValidationCache`, which can hold at most `capacity` digests.
+    /// A cache with zero capacity never remembers anything.
+    pub fn new(capacity: usize) -> Self {
+        ChunkValidationCache {
+            capacity,
+            tick: 0,
+            last_used_at: HashMap::with_capacity(capacity),
+            by_recency: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the maximum number of digests this cache can hold.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of digests currently held in this cache.
+    pub fn len(&self) -> usize {
+        self.last_used_at.len()
+    }
+
+    /// Returns `true` if this cache holds no digests.
+    pub fn is_empty(&self) -> bool {
+        self.last_used_at.is_empty()
+    }
+
+    /// Checks whether the digest of an already validated chunk is present in this cache.
+    /// A hit marks the digest as the most recently used one.
+    ///
+    /// # Arguments
+    ///
+    /// * `digest` - BLAKE3 digest of the chunk to look up.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the digest is present in the cache, `false` otherwise.
+    pub fn contains(&mut self, digest: &blake3::Hash) -> bool {
+        match self.last_used_at.get(digest).copied() {
+            Some(last_used_at) => {
+                self.by_recency.remove(&last_used_at);
+                self.touch(*digest);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remembers the digest of a validated chunk, evicting the least recently used digest if the cache is full.
+    ///
+    /// # Arguments
+    ///
+    /// * `digest` - BLAKE3 digest of the chunk, which has just been validated.
+    pub fn insert(&mut self, digest: blake3::Hash) {
+        if self.capacity == 0 || self.contains(&digest) {
+            return;
+        }
+
+        if self.len() == self.capacity {
+            if let Some((_, evicted)) = self.by_recency.pop_first() {
+                self.last_used_at.remove(&evicted);
+            }
+        }
+
+        self.touch(digest);
+    }
+
+    /// Marks `digest` as the most recently used one.
+    fn touch(&mut self, digest: blake3::Hash) {
+        self.tick += 1;
+        self.last_used_at.insert(digest, self.tick);
+        self.by_recency.insert(self.tick, digest);
+    }
+}
+
+/// A bounded, least-recently-used (LRU) cache of already repaired chunksets, keyed by chunkset ID.
+///
+/// Repairing a chunkset consumes its RLNC decoder, so when overlapping byte ranges of a blob are served, repaired data is
+/// kept here, instead of being decoded again, which isn't possible anyway. Each entry holds up to `ChunkSet::BYTE_LENGTH`
+/// bytes, so capacity should be chosen with memory in mind.
+#[cfg(feature = "coding")]
+#[derive(Clone, Debug)]
+pub(crate) struct RepairedChunksetCache {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<usize, (u64, Vec<u8>)>,
+    by_recency: BTreeMap<u64, usize>,
+}
+
+#[cfg(feature = "coding")]
+impl RepairedChunksetCache {
+    /// Creates a new empty `RepairedChunksetCache`, which can hold at most `capacity` chunksets.
+    pub fn new(capacity: usize) -> Self {
+        RepairedChunksetCache {
+            capacity,
+            tick: 0,
+            entries: HashMap::with_capacity(capacity),
+            by_recency: BTreeMap::new(),
+        }
+    }
+
+    /// Returns repaired data of a chunkset, if present. A hit marks the chunkset as the most recently used one.
+    pub fn get(&mut self, chunkset_id: usize) -> Option<&[u8]> {
+        self.tick += 1;
+
+        let (last_used_at, data) = self.entries.get_mut(&chunkset_id)?;
+        self.by_recency.remove(last_used_at);
+        self.by_recency.insert(self.tick, chunkset_id);
+        *last_used_at = self.tick;
+
+        Some(data.as_slice())
+    }
+
+    /// Remembers repaired data of a chunkset, evicting the least recently used chunkset if the cache is full.
+    pub fn insert(&mut self, chunkset_id: usize, data: Vec<u8>) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if let Some((last_used_at, _)) = self.entries.remove(&chunkset_id) {
+            self.by_recency.remove(&last_used_at);
+        } else if self.entries.len() == self.capacity {
+            if let Some((_, evicted)) = self.by_recency.pop_first() {
+                self.entries.remove(&evicted);
+            }
+        }
+
+        self.tick += 1;
+        self.entries.insert(chunkset_id, (self.tick, data));
+        self.by_recency.insert(self.tick, chunkset_id);
+    }
+
+    /// Forgets repaired data of a chunkset, if present.
+    pub fn remove(&mut self, chunkset_id: usize) {
+        if let Some((last_used_at, _)) = self.entries.remove(&chunkset_id) {
+            self.by_recency.remove(&last_used_at);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cache::ChunkValidationCache;
+    #[cfg(feature = "coding")]
+    use crate::cache::RepairedChunksetCache;
+
+    #[test]
+    fn test_chunk_validation_cache_evicts_least_recently_used() {
+        let digests = (0u8..4).map(|i| blake3::hash(&[i])).collect::<Vec<blake3::Hash>>();
+        let mut cache = ChunkValidationCache::new(3);
+
+        cache.insert(digests[0]);
+        cache.insert(digests[1]);
+        cache.insert(digests[2]);
+        assert_eq!(cache.len(), 3);
+
+        // Touch the oldest one, so that the second one becomes least recently used
+        assert!(cache.contains(&digests[0]));
+
+        cache.insert(digests[3]);
+        assert_eq!(cache.len(), 3);
+
+        assert!(cache.contains(&digests[0]));
+        assert!(!cache.contains(&digests[1]));
+        assert!(cache.contains(&digests[2]));
+        assert!(cache.contains(&digests[3]));
+    }
+
+    #[test]
+    fn test_chunk_validation_cache_with_zero_capacity() {
+        let mut cache = ChunkValidationCache::new(0);
+
+        cache.insert(blake3::hash(b"chunk"));
+        assert!(cache.is_empty());
+        assert!(!cache.contains(&blake3::hash(b"chunk")));
+    }
+
+    #[cfg(feature = "coding")]
+    #[test]
+    fn test_repaired_chunkset_cache_evicts_least_recently_used() {
+        let mut cache = RepairedChunksetCache::new(2);
+
+        cache.insert(0, vec![0; 4]);
+        cache.insert(1, vec![1; 4]);
+        assert_eq!(cache.get(0), Some([0u8; 4].as_slice()));
+
+        cache.insert(2, vec![2; 4]);
+        assert_eq!(cache.get(1), None);
+        assert_eq!(cache.get(0), Some([0u8; 4].as_slice()));
+        assert_eq!(cache.get(2), Some([2u8; 4].as_slice()));
+
+        cache.insert(2, vec![3; 4]);
+        assert_eq!(cache.get(2), Some([3u8; 4].as_slice()));
+        assert_eq!(cache.get(0), Some([0u8; 4].as_slice()));
+
+        let mut cache = RepairedChunksetCache::new(0);
+        cache.insert(0, vec![0; 4]);
+        assert_eq!(cache.get(0), None);
+    }
+}
@@ -0,0 +1,281 @@
+use crate::{
+    blob::{BlobHeader, RepairingBlob},
+    consts::DECDS_BINCODE_CONFIG,
+    errors::DecdsError,
+};
+use std::io::{Read, Write};
+
+/// Tag of a chunkset, whose data has already been repaired, in a checkpoint.
+const REPAIRED_CHUNKSET_TAG: u8 = 0;
+/// Tag of a chunkset, which is still being repaired, in a checkpoint. It's followed by useful chunks fed to its decoder.
+const PENDING_CHUNKSET_TAG: u8 = 1;
+
+impl RepairingBlob {
+    /// Checkpoints the progress of this repairer, so that a long repair, e.g. of a terabyte blob, can survive process
+    /// restarts. Resume it with `Self::from_bytes`. See `Self::write_checkpoint_to` for what is checkpointed.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Vec<u8>)` containing the serialized checkpoint if successful.
+    /// - `Err(DecdsError::CheckpointingNotEnabled)` if this repairer wasn't built with `RepairBuilder::checkpointing`.
+    /// - `Err(DecdsError::RepairCheckpointSerializationFailed)` if serialization fails.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, DecdsError> {
+        let mut bytes = Vec::new();
+        self.write_checkpoint_to(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Writes a checkpoint of this repairer into `writer`, chunkset after chunkset, without an intermediate buffer.
+    ///
+    /// The RLNC decoder state can't be serialized as such, but it's fully determined by the useful, i.e. linearly
+    /// independent, chunks fed to it, which checkpointing repairers remember. So a checkpoint carries the `BlobHeader`, the
+    /// target byte range, if any, how far repaired data is streamed by `Self::write_repaired`, and for each tracked
+    /// chunkset, either that it's repaired, or erasure-coded data of useful chunks received so far, which is at most as
+    /// large as the chunkset itself. Settings like caches, share weights, the
+    /// event handler and the acceptance policy aren't checkpointed, so they have to be set again on resumption.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer to serialize the checkpoint into.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(usize)` containing the number of bytes written if successful.
+    /// - `Err(DecdsError::CheckpointingNotEnabled)` if this repairer wasn't built with `RepairBuilder::checkpointing`.
+    /// - `Err(DecdsError::RepairCheckpointSerializationFailed)` if serialization or writing fails.
+    pub fn write_checkpoint_to<W: Write>(&self, writer: &mut W) -> Result<usize, DecdsError> {
+        if !self.is_checkpointing() {
+            return Err(DecdsError::CheckpointingNotEnabled);
+        }
+
+        let map_encode_err = |err: bincode::error::EncodeError| DecdsError::RepairCheckpointSerializationFailed(err.to_string());
+        let map_write_err = |err: std::io::Error| DecdsError::RepairCheckpointSerializationFailed(err.to_string());
+
+        let mut num_written_bytes = self
+            .get_blob_header()
+            .write_to(writer)
+            .map_err(|err| DecdsError::RepairCheckpointSerializationFailed(err.to_string()))?;
+        num_written_bytes += bincode::encode_into_std_write(
+            self.get_target_byte_range().map(|byte_range| (byte_range.start, byte_range.end)),
+            writer,
+            DECDS_BINCODE_CONFIG,
+        )
+        .map_err(map_encode_err)?;
+        num_written_bytes += bincode::encode_into_std_write(self.get_num_streamed_bytes(), writer, DECDS_BINCODE_CONFIG).map_err(map_encode_err)?;
+
+        let chunkset_ids = self.get_tracked_chunkset_ids();
+        num_written_bytes += bincode::encode_into_std_write(chunkset_ids.len(), writer, DECDS_BINCODE_CONFIG).map_err(map_encode_err)?;
+
+        for chunkset_id in chunkset_ids {
+            num_written_bytes += bincode::encode_into_std_write(chunkset_id, writer, DECDS_BINCODE_CONFIG).map_err(map_encode_err)?;
+
+            // Tracked chunksets always exist, and an inline blob's single chunkset is pending, until its data is taken.
+            if unsafe { self.is_chunkset_already_repaired(chunkset_id).unwrap_unchecked() } {
+                num_written_bytes += bincode::encode_into_std_write(REPAIRED_CHUNKSET_TAG, writer, DECDS_BINCODE_CONFIG).map_err(map_encode_err)?;
+                continue;
+            }
+
+            let useful_pieces = self
+                .get_tracked_chunkset(chunkset_id)
+                .and_then(|chunkset| chunkset.as_ref())
+                .and_then(|chunkset| chunkset.get_useful_pieces())
+                .unwrap_or_default();
+
+            num_written_bytes += bincode::encode_into_std_write(PENDING_CHUNKSET_TAG, writer, DECDS_BINCODE_CONFIG).map_err(map_encode_err)?;
+            num_written_bytes += bincode::encode_into_std_write(useful_pieces.len(), writer, DECDS_BINCODE_CONFIG).map_err(map_encode_err)?;
+            for useful_piece in useful_pieces {
+                writer.write_all(useful_piece).map_err(map_write_err)?;
+                num_written_bytes += useful_piece.len();
+            }
+        }
+
+        Ok(num_written_bytes)
+    }
+
+    /// Resumes a repairer from a checkpoint written by `Self::to_bytes`, rebuilding decoders of pending chunksets by
+    /// feeding them useful chunks received before the checkpoint. The resumed repairer keeps checkpointing.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - A byte slice beginning with the serialized checkpoint.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok((Self, usize))` containing the resumed `RepairingBlob` and the number of bytes consumed if successful.
+    /// - `Err(DecdsError::RepairCheckpointDeserializationFailed)` if deserialization fails, or the checkpoint is
+    ///   inconsistent, e.g. it tracks chunksets its target byte range doesn't cover, or carries chunks, which aren't useful.
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Self, usize), DecdsError> {
+        let mut reader = bytes;
+        let repairer = Self::from_checkpoint_reader(&mut reader)?;
+
+        Ok((repairer, bytes.len() - reader.len()))
+    }
+
+    /// Same as `Self::from_bytes`, but reads the checkpoint from `reader`, consuming exactly its serialized bytes, as
+    /// written by `Self::write_checkpoint_to`. Pass the reader wrapped in a `BufReader`, if it is unbuffered.
+    pub fn from_checkpoint_reader<R: Read>(reader: &mut R) -> Result<Self, DecdsError> {
+        let map_decode_err = |err: bincode::error::DecodeError| DecdsError::RepairCheckpointDeserializationFailed(err.to_string());
+        let map_read_err = |err: std::io::Error| DecdsError::RepairCheckpointDeserializationFailed(err.to_string());
+        let map_inconsistent = |reason: &str| DecdsError::RepairCheckpointDeserializationFailed(reason.to_string());
+
+        let header = BlobHeader::from_reader(reader).map_err(|err| DecdsError::RepairCheckpointDeserializationFailed(err.to_string()))?;
+        let erasure_coded_data_byte_length = header.get_config().get_erasure_coded_data_byte_length();
+        let target_byte_range = bincode::decode_from_std_read::<Option<(usize, usize)>, _, _>(reader, DECDS_BINCODE_CONFIG).map_err(map_decode_err)?;
+
+        let mut repairer = RepairingBlob::new(header);
+        repairer.enable_checkpointing();
+        if let Some((start, end)) = target_byte_range {
+            repairer
+                .restrict_to_byte_range(start..end)
+                .map_err(|err| DecdsError::RepairCheckpointDeserializationFailed(err.to_string()))?;
+        }
+
+        let num_streamed_bytes = bincode::decode_from_std_read::<usize, _, _>(reader, DECDS_BINCODE_CONFIG).map_err(map_decode_err)?;
+        if num_streamed_bytes
+            > repairer
+                .get_target_byte_range()
+                .map_or(repairer.get_blob_header().get_blob_size(), |byte_range| byte_range.len())
+        {
+            return Err(map_inconsistent("more bytes streamed than there are"));
+        }
+        repairer.set_num_streamed_bytes(num_streamed_bytes);
+
+        let chunkset_ids = repairer.get_tracked_chunkset_ids();
+        let num_chunksets = bincode::decode_from_std_read::<usize, _, _>(reader, DECDS_BINCODE_CONFIG).map_err(map_decode_err)?;
+        if num_chunksets != chunkset_ids.len() {
+            return Err(map_inconsistent("number of tracked chunksets doesn't match target byte range"));
+        }
+
+        let mut useful_piece = vec![0u8; erasure_coded_data_byte_length];
+        for expected_chunkset_id in chunkset_ids {
+            let chunkset_id = bincode::decode_from_std_read::<usize, _, _>(reader, DECDS_BINCODE_CONFIG).map_err(map_decode_err)?;
+            if chunkset_id != expected_chunkset_id {
+                return Err(map_inconsistent("tracked chunksets are out of order"));
+            }
+
+            match bincode::decode_from_std_read::<u8, _, _>(reader, DECDS_BINCODE_CONFIG).map_err(map_decode_err)? {
+                REPAIRED_CHUNKSET_TAG => {
+                    if repairer.get_blob_header().is_inline() {
+                        // Takes the inline data, as it was taken before the checkpoint.
+                        repairer.get_repaired_chunkset(chunkset_id)?;
+                    } else if let Some(chunkset) = repairer.get_tracked_chunkset_mut(chunkset_id) {
+                        *chunkset = None;
+                    }
+                }
+                PENDING_CHUNKSET_TAG => {
+                    let num_useful_pieces = bincode::decode_from_std_read::<usize, _, _>(reader, DECDS_BINCODE_CONFIG).map_err(map_decode_err)?;
+
+                    // Pieces are restored as they're read, so a bogus count can't make us allocate a lot upfront.
+                    for _ in 0..num_useful_pieces {
+                        let Some(Some(chunkset)) = repairer.get_tracked_chunkset_mut(chunkset_id) else {
+                            return Err(map_inconsistent("chunks of an inline blob"));
+                        };
+
+                        reader.read_exact(&mut useful_piece).map_err(map_read_err)?;
+                        chunkset.restore_useful_piece(&useful_piece)?;
+                    }
+                }
+                _ => return Err(map_inconsistent("unknown chunkset state")),
+            }
+        }
+
+        repairer.recount_progress();
+        Ok(repairer)
+    }
+}
+
+#[cfg(all(test, feature = "coding"))]
+mod tests {
+    use crate::{DecdsError, RepairBuilder, RepairingBlob, blob::Blob};
+    use rand::Rng;
+
+    #[test]
+    fn test_repair_resumes_from_checkpoint() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..rng.random_range(24usize << 20..=32usize << 20)).map(|_| rng.random()).collect::<Vec<u8>>();
+        let blob = Blob::new(blob_data.clone()).expect("Must be able to prepare blob");
+        let header = blob.get_blob_header().clone();
+
+        let num_chunksets = header.get_num_chunksets();
+        let num_original_chunks = header.get_config().get_num_original_chunks();
+
+        assert_eq!(RepairingBlob::new(header.clone()).to_bytes(), Err(DecdsError::CheckpointingNotEnabled));
+
+        let mut repairer = RepairBuilder::new(header.clone())
+            .checkpointing(true)
+            .build()
+            .expect("Must be able to build repairer");
+        assert!(repairer.is_checkpointing());
+
+        // Chunkset 0 gets repaired, chunkset 1 gets half of the chunks it needs, others get none.
+        for share_id in 0..num_original_chunks {
+            repairer
+                .add_chunk(blob.get_chunk(0, share_id).expect("Must be able to get chunk"))
+                .expect("Must be able to add chunk");
+        }
+        for share_id in 0..num_original_chunks / 2 {
+            repairer
+                .add_chunk(blob.get_chunk(1, share_id).expect("Must be able to get chunk"))
+                .expect("Must be able to add chunk");
+        }
+        let first_chunkset = repairer.get_repaired_chunkset(0).expect("Must be able to repair chunkset");
+
+        let mut checkpoint = repairer.to_bytes().expect("Must be able to checkpoint repairer");
+        checkpoint.extend_from_slice(b"trailer");
+
+        let (mut resumed, num_consumed_bytes) = RepairingBlob::from_bytes(&checkpoint).expect("Must be able to resume repairer");
+        assert_eq!(num_consumed_bytes, checkpoint.len() - b"trailer".len());
+        assert!(resumed.is_checkpointing());
+        assert_eq!(resumed.missing().collect::<Vec<_>>(), repairer.missing().collect::<Vec<_>>());
+        assert_eq!(resumed.get_progress(), repairer.get_progress());
+        assert_eq!(resumed.get_repaired_chunkset(0), Err(DecdsError::ChunksetAlreadyRepaired(0)));
+
+        // Chunks received before the checkpoint are still known to the resumed repairer.
+        for share_id in 0..num_original_chunks / 2 {
+            assert!(resumed.add_chunk(blob.get_chunk(1, share_id).expect("Must be able to get chunk")).is_err());
+        }
+        for chunkset_id in 1..num_chunksets {
+            let mut share_id = 0;
+            while !resumed.is_chunkset_ready_to_repair(chunkset_id).expect("Chunkset must exist") {
+                let _ = resumed.add_chunk(blob.get_chunk(chunkset_id, share_id).expect("Must be able to get chunk"));
+                share_id += 1;
+            }
+        }
+
+        let repaired = first_chunkset
+            .into_iter()
+            .chain((1..num_chunksets).flat_map(|chunkset_id| resumed.get_repaired_chunkset(chunkset_id).expect("Must be able to repair chunkset")))
+            .collect::<Vec<u8>>();
+        assert_eq!(repaired, blob_data);
+
+        // Truncated, a checkpoint is rejected.
+        let checkpoint = &checkpoint[..num_consumed_bytes];
+        assert!(RepairingBlob::from_bytes(&checkpoint[..checkpoint.len() - 1]).is_err());
+
+        // So is one carrying a chunk, which isn't useful, here, the only chunk of chunkset 0, given twice.
+        let mut repairer = RepairBuilder::new(header.clone())
+            .checkpointing(true)
+            .build()
+            .expect("Must be able to build repairer");
+        let chunk = blob.get_chunk(0, 0).expect("Must be able to get chunk");
+        repairer.add_chunk(chunk).expect("Must be able to add chunk");
+
+        // Header is followed by the target byte range tag, number of streamed bytes, number of chunksets, ID, tag and number
+        // of chunks of chunkset 0.
+        let num_chunks_offset = header.to_bytes().expect("Must be able to serialize header").len() + 5;
+        let mut tampered = repairer.to_bytes().expect("Must be able to checkpoint repairer");
+        assert_eq!(tampered[num_chunks_offset], 1);
+
+        tampered[num_chunks_offset] = 2;
+        let piece_end_offset = num_chunks_offset + 1 + chunk.get_erasure_coded_data().len();
+        tampered.splice(piece_end_offset..piece_end_offset, chunk.get_erasure_coded_data().iter().copied());
+        assert!(matches!(
+            RepairingBlob::from_bytes(&tampered),
+            Err(DecdsError::RepairCheckpointDeserializationFailed(_))
+        ));
+    }
+}
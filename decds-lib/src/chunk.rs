@@ -1,11 +1,17 @@
-use crate::{chunkset::ChunkSet, consts::DECDS_BINCODE_CONFIG, errors::DecdsError, merkle_tree::MerkleTree};
+use crate::{
+    blob::deserialize_addressable_usize, chunkset::ChunkSet, config::BlobConfig, consts::DECDS_BINCODE_CONFIG, errors::DecdsError, flags::ContentFlags,
+    merkle_tree::MerkleTree,
+};
 use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
 
 /// Represents a fixed-size (1MB = 2^20 bytes) data chunk within a chunkset in erasure-coded form.
 /// It contains metadata about its origin and the RLNC erasure-coded data.
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub(crate) struct Chunk {
+    #[serde(deserialize_with = "deserialize_addressable_usize")]
     chunkset_id: usize,
+    #[serde(deserialize_with = "deserialize_addressable_usize")]
     chunk_id: usize,
     erasure_coded_data: Vec<u8>,
 }
@@ -24,6 +30,7 @@ impl Chunk {
     /// # Returns
     ///
     /// Returns a new `Chunk` instance.
+    #[cfg(feature = "coding")]
     pub fn new(chunkset_id: usize, chunk_id: usize, erasure_coded_data: Vec<u8>) -> Self {
         Chunk {
             chunkset_id,
@@ -34,13 +41,17 @@ impl Chunk {
 
     /// Computes the BLAKE3 digest of the byte serialized representation of this chunk.
     ///
+    /// The digest is computed as `BLAKE3(chunkset_id || chunk_id || erasure_coded_data)`, where both IDs are encoded
+    /// as 8-bytes little-endian unsigned integers, irrespective of the target platform. This is the leaf node of the
+    /// Merkle inclusion proof of this chunk.
+    ///
     /// # Returns
     ///
     /// A `blake3::Hash` representing the digest of the chunk.
     pub fn digest(&self) -> blake3::Hash {
         blake3::Hasher::new()
-            .update(&self.chunkset_id.to_le_bytes())
-            .update(&self.chunk_id.to_le_bytes())
+            .update(&(self.chunkset_id as u64).to_le_bytes())
+            .update(&(self.chunk_id as u64).to_le_bytes())
             .update(&self.erasure_coded_data)
             .finalize()
     }
@@ -48,10 +59,13 @@ impl Chunk {
 
 /// Represents a `Chunk` augmented with a Merkle proof of its inclusion in the original blob.
 /// This structure is used for verifiable data retrieval and reconstruction.
+///
+/// Its wire envelope also carries `ContentFlags` of the blob, which aren't part of the chunk digest. See `ContentFlags`.
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub struct ProofCarryingChunk {
     chunk: Chunk,
     proof: Vec<blake3::Hash>,
+    content_flags: ContentFlags,
 }
 
 impl ProofCarryingChunk {
@@ -65,8 +79,95 @@ impl ProofCarryingChunk {
     /// # Assumes
     ///
     /// That `proof.len()` equals to `ChunkSet::PROOF_SIZE`.
+    #[cfg(feature = "coding")]
     pub(crate) fn new(chunk: Chunk, proof: Vec<blake3::Hash>) -> Self {
-        Self { chunk, proof }
+        Self {
+            chunk,
+            proof,
+            content_flags: ContentFlags::default(),
+        }
+    }
+
+    /// Assembles a `ProofCarryingChunk` from its parts, e.g. as produced by another process or an implementation in
+    /// another language, checking that they are well-formed. Whether the chunk really belongs to a blob is still up to
+    /// `BlobHeader::validate_chunk`, just like for deserialized chunks.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset this chunk belongs to.
+    /// * `local_chunk_id` - The ID of the erasure-coded share within its chunkset.
+    /// * `erasure_coded_data` - Coding vector of `ChunkSet::NUM_ORIGINAL_CHUNKS` bytes, followed by the coded piece.
+    /// * `proof` - Merkle inclusion proof of the chunk, at least proving its inclusion in the chunkset.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Self)` containing the assembled chunk if successful.
+    /// - `Err(DecdsError::InvalidErasureCodedShareId)` if `local_chunk_id` is not less than `ChunkSet::NUM_ERASURE_CODED_CHUNKS`.
+    /// - `Err(DecdsError::InvalidProofCarryingChunkParts)` if the global chunk ID overflows, the erasure-coded data is of
+    ///   unexpected length, or the proof is shorter than `ChunkSet::PROOF_SIZE`.
+    pub fn from_parts(chunkset_id: usize, local_chunk_id: usize, erasure_coded_data: Vec<u8>, proof: Vec<blake3::Hash>) -> Result<Self, DecdsError> {
+        Self::from_parts_with_config(chunkset_id, local_chunk_id, erasure_coded_data, proof, &BlobConfig::default())
+    }
+
+    /// Same as `Self::from_parts`, but for chunks of a blob erasure-coded with `config`, i.e. the erasure-coded data is
+    /// expected to be `config.get_erasure_coded_data_byte_length()` bytes, and `local_chunk_id` less than `config.get_num_shares()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset this chunk belongs to.
+    /// * `local_chunk_id` - The ID of the erasure-coded share within its chunkset.
+    /// * `erasure_coded_data` - Coding vector of `config.get_num_original_chunks()` bytes, followed by the coded piece.
+    /// * `proof` - Merkle inclusion proof of the chunk, at least proving its inclusion in the chunkset.
+    /// * `config` - Erasure-coding geometry of the blob, as found in its `BlobHeader`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Self)` containing the assembled chunk if successful.
+    /// - `Err(DecdsError::InvalidErasureCodedShareId)` if `local_chunk_id` is not less than `config.get_num_shares()`.
+    /// - `Err(DecdsError::InvalidProofCarryingChunkParts)` if the global chunk ID overflows, the erasure-coded data is of
+    ///   unexpected length, or the proof is shorter than `ChunkSet::PROOF_SIZE`.
+    pub fn from_parts_with_config(
+        chunkset_id: usize,
+        local_chunk_id: usize,
+        erasure_coded_data: Vec<u8>,
+        proof: Vec<blake3::Hash>,
+        config: &BlobConfig,
+    ) -> Result<Self, DecdsError> {
+        if local_chunk_id >= config.get_num_shares().min(ChunkSet::NUM_ERASURE_CODED_CHUNKS) {
+            return Err(DecdsError::InvalidErasureCodedShareId(local_chunk_id));
+        }
+
+        let chunk_id = ChunkSet::checked_global_chunk_id(chunkset_id, local_chunk_id)
+            .ok_or_else(|| DecdsError::InvalidProofCarryingChunkParts(format!("chunkset ID {} is too large", chunkset_id)))?;
+
+        let expected_data_byte_len = config.get_erasure_coded_data_byte_length();
+        if erasure_coded_data.len() != expected_data_byte_len {
+            return Err(DecdsError::InvalidProofCarryingChunkParts(format!(
+                "erasure-coded data is {}B, expected {}B",
+                erasure_coded_data.len(),
+                expected_data_byte_len
+            )));
+        }
+
+        if proof.len() < ChunkSet::PROOF_SIZE {
+            return Err(DecdsError::InvalidProofCarryingChunkParts(format!(
+                "proof has {} digests, expected at least {}",
+                proof.len(),
+                ChunkSet::PROOF_SIZE
+            )));
+        }
+
+        Ok(Self {
+            chunk: Chunk {
+                chunkset_id,
+                chunk_id,
+                erasure_coded_data,
+            },
+            proof,
+            content_flags: ContentFlags::default(),
+        })
     }
 
     /// Validates the inclusion of this chunk in the overall blob using the provided blob root commitment.
@@ -86,7 +187,15 @@ impl ProofCarryingChunk {
     ///
     /// Returns `true` if the chunk's inclusion proof in the blob is valid, `false` otherwise.
     pub fn validate_inclusion_in_blob(&self, blob_commitment: blake3::Hash) -> bool {
-        MerkleTree::verify_proof(self.get_global_chunk_id(), self.chunk.digest(), &self.proof, blob_commitment)
+        self.validate_inclusion_in_blob_with_digest(self.chunk.digest(), blob_commitment)
+    }
+
+    /// Same as `Self::validate_inclusion_in_blob`, but uses already computed digest of the chunk,
+    /// instead of hashing the erasure-coded data again.
+    pub(crate) fn validate_inclusion_in_blob_with_digest(&self, chunk_digest: blake3::Hash, blob_commitment: blake3::Hash) -> bool {
+        // A chunkset ID, whose high bits would be shifted out of the leaf index, must not alias a smaller one.
+        ChunkSet::checked_blob_leaf_index(self.get_chunkset_id(), self.get_local_chunk_id())
+            .is_some_and(|leaf_index| MerkleTree::verify_proof(leaf_index, chunk_digest, &self.proof, blob_commitment))
     }
 
     /// Validates the inclusion of this chunk within its specific chunkset using the provided chunkset root commitment.
@@ -101,12 +210,34 @@ impl ProofCarryingChunk {
     ///
     /// Returns `true` if the chunk's inclusion proof in its chunkset is valid, `false` otherwise.
     pub fn validate_inclusion_in_chunkset(&self, chunkset_commitment: blake3::Hash) -> bool {
-        MerkleTree::verify_proof(
-            self.get_local_chunk_id(),
-            self.chunk.digest(),
-            &self.proof[..ChunkSet::PROOF_SIZE],
-            chunkset_commitment,
-        )
+        self.validate_inclusion_in_chunkset_with_digest(self.chunk.digest(), chunkset_commitment)
+    }
+
+    /// Same as `Self::validate_inclusion_in_chunkset`, but uses already computed digest of the chunk,
+    /// instead of hashing the erasure-coded data again.
+    pub(crate) fn validate_inclusion_in_chunkset_with_digest(&self, chunk_digest: blake3::Hash, chunkset_commitment: blake3::Hash) -> bool {
+        self.proof
+            .get(..ChunkSet::PROOF_SIZE)
+            .is_some_and(|proof| MerkleTree::verify_proof(self.get_local_chunk_id(), chunk_digest, proof, chunkset_commitment))
+    }
+
+    /// Returns the BLAKE3 digest of the underlying chunk, which is the leaf node of its Merkle inclusion proof.
+    ///
+    /// The digest is computed as `BLAKE3(chunkset_id || global_chunk_id || erasure_coded_data)`, where both IDs are
+    /// encoded as 8-bytes little-endian unsigned integers.
+    pub fn get_chunk_digest(&self) -> blake3::Hash {
+        self.chunk.digest()
+    }
+
+    /// Returns the Merkle inclusion proof carried by this chunk.
+    ///
+    /// The first `ceil(log2(DECDS_NUM_ERASURE_CODED_SHARES))` sibling digests prove inclusion of the chunk digest in its
+    /// chunkset, at leaf index `get_local_chunk_id()`. Together with the remaining ones, they prove inclusion of the
+    /// chunk digest in the blob, at leaf index `(chunkset_id << n) | local_chunk_id`, where `n` is the number of chunkset
+    /// level sibling digests. With 16 share slots per chunkset, that is `get_global_chunk_id()`. Both can be checked using
+    /// `MerkleTree::verify_proof`.
+    pub fn get_proof(&self) -> &[blake3::Hash] {
+        &self.proof
     }
 
     /// Returns the ID of the chunkset this chunk belongs to.
@@ -129,6 +260,30 @@ impl ProofCarryingChunk {
         self.chunk.erasure_coded_data.as_ref()
     }
 
+    /// Consumes the chunk, returning its erasure-coded data, without copying it.
+    #[cfg(feature = "coding")]
+    pub(crate) fn into_erasure_coded_data(self) -> Vec<u8> {
+        self.chunk.erasure_coded_data
+    }
+
+    /// Returns content flags of the blob, carried in the wire envelope of this chunk, e.g. for a storage node to reject
+    /// chunks of plaintext blobs. Those are only trustworthy, once the chunk is validated against its `BlobHeader`.
+    pub fn get_content_flags(&self) -> ContentFlags {
+        self.content_flags
+    }
+
+    /// Sets content flags of the blob, this chunk is of, e.g. of a chunk assembled with `Self::from_parts`. They must match
+    /// those of the `BlobHeader`, for the chunk to be valid.
+    pub fn with_content_flags(mut self, content_flags: ContentFlags) -> Self {
+        self.set_content_flags(content_flags);
+        self
+    }
+
+    /// Same as `Self::with_content_flags`, but for a borrowed chunk, e.g. one held by a `ChunkSet`.
+    pub(crate) fn set_content_flags(&mut self, content_flags: ContentFlags) {
+        self.content_flags = content_flags;
+    }
+
     /// Appends additional Merkle proof hashes to the existing proof, proving blob-level inclusion.
     ///
     /// This is used to extend a chunkset-level proof to a blob-level proof. You are supposed to call this
@@ -150,7 +305,42 @@ impl ProofCarryingChunk {
     /// - `Ok(Vec<u8>)` containing the serialized bytes if successful.
     /// - `Err(DecdsError::ProofCarryingChunkSerializationFailed)` if serialization fails.
     pub fn to_bytes(&self) -> Result<Vec<u8>, DecdsError> {
-        bincode::serde::encode_to_vec(self, DECDS_BINCODE_CONFIG).map_err(|err| DecdsError::ProofCarryingChunkSerializationFailed(err.to_string()))
+        let mut bytes = Vec::with_capacity(self.chunk.erasure_coded_data.len() + (self.proof.len() + 4) * blake3::OUT_LEN);
+        self.write_to(&mut bytes)?;
+
+        Ok(bytes)
+    }
+
+    /// Serializes the `ProofCarryingChunk` straight into a writer, producing exactly the bytes `Self::to_bytes` returns,
+    /// without allocating an intermediate buffer for them. Pass the writer wrapped in a `BufWriter`, if it is unbuffered.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer to serialize the chunk into.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(usize)` containing the number of bytes written if successful.
+    /// - `Err(DecdsError::ProofCarryingChunkSerializationFailed)` if serialization or writing fails.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<usize, DecdsError> {
+        let map_encode_err = |err: bincode::error::EncodeError| DecdsError::ProofCarryingChunkSerializationFailed(err.to_string());
+
+        // Fields are written one by one, mirroring `Self::from_reader`, so that erasure-coded data goes out in a single
+        // write, instead of byte by byte, as `serde` would do it for a `Vec<u8>`.
+        let mut num_written_bytes = 0;
+        for field in [self.chunk.chunkset_id, self.chunk.chunk_id, self.chunk.erasure_coded_data.len()] {
+            num_written_bytes += bincode::encode_into_std_write(field, writer, DECDS_BINCODE_CONFIG).map_err(map_encode_err)?;
+        }
+
+        writer
+            .write_all(&self.chunk.erasure_coded_data)
+            .map_err(|err| DecdsError::ProofCarryingChunkSerializationFailed(err.to_string()))?;
+        num_written_bytes += self.chunk.erasure_coded_data.len();
+
+        num_written_bytes += bincode::serde::encode_into_std_write(&self.proof, writer, DECDS_BINCODE_CONFIG).map_err(map_encode_err)?;
+        num_written_bytes += bincode::encode_into_std_write(self.content_flags.bits(), writer, DECDS_BINCODE_CONFIG).map_err(map_encode_err)?;
+        Ok(num_written_bytes)
     }
 
     /// Deserializes a `ProofCarryingChunk` from a byte slice using `bincode`.
@@ -163,14 +353,143 @@ impl ProofCarryingChunk {
     ///
     /// Returns a `Result` which is:
     /// - `Ok((Self, usize))` containing the deserialized `ProofCarryingChunk` and the number of bytes read if successful.
-    /// - `Err(DecdsError::ProofCarryingChunkDeserializationFailed)` if deserialization fails.
+    /// - `Err(DecdsError::ProofCarryingChunkDeserializationFailed)` if deserialization fails, or content flags are invalid.
     pub fn from_bytes(bytes: &[u8]) -> Result<(Self, usize), DecdsError> {
-        bincode::serde::decode_from_slice::<ProofCarryingChunk, bincode::config::Configuration>(bytes, DECDS_BINCODE_CONFIG)
-            .map_err(|err| DecdsError::ProofCarryingChunkDeserializationFailed(err.to_string()))
+        let (chunk, n) = bincode::serde::decode_from_slice::<ProofCarryingChunk, bincode::config::Configuration>(bytes, DECDS_BINCODE_CONFIG)
+            .map_err(|err| DecdsError::ProofCarryingChunkDeserializationFailed(err.to_string()))?;
+        chunk
+            .content_flags
+            .validate()
+            .map_err(|err| DecdsError::ProofCarryingChunkDeserializationFailed(err.to_string()))?;
+
+        Ok((chunk, n))
+    }
+
+    /// Same as `Self::from_bytes`, but doesn't copy erasure-coded data out of `bytes`, e.g. of a memory-mapped chunk file,
+    /// hashing it in place instead. The returned chunk carries everything but its erasure-coded data, which is all that's
+    /// needed for validating it against a `BlobHeader`, given its digest.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok((Self, blake3::Hash, usize))` containing the chunk without erasure-coded data, its digest, exactly what
+    ///   `Self::get_chunk_digest` would return for the whole chunk, and the number of bytes read, if successful.
+    /// - `Err(DecdsError::ProofCarryingChunkDeserializationFailed)` if deserialization fails.
+    #[cfg(feature = "coding")]
+    pub(crate) fn from_bytes_without_data(bytes: &[u8]) -> Result<(Self, blake3::Hash, usize), DecdsError> {
+        let map_decode_err = |err: bincode::error::DecodeError| DecdsError::ProofCarryingChunkDeserializationFailed(err.to_string());
+        let decode_usize = |offset: &mut usize| -> Result<usize, DecdsError> {
+            let (value, n) = bincode::decode_from_slice::<usize, _>(&bytes[*offset..], DECDS_BINCODE_CONFIG).map_err(map_decode_err)?;
+            *offset += n;
+            Ok(value)
+        };
+
+        let mut offset = 0;
+        let chunkset_id = decode_usize(&mut offset)?;
+        let chunk_id = decode_usize(&mut offset)?;
+        let data_byte_len = decode_usize(&mut offset)?;
+
+        let erasure_coded_data = offset
+            .checked_add(data_byte_len)
+            .and_then(|end| bytes.get(offset..end))
+            .ok_or_else(|| DecdsError::ProofCarryingChunkDeserializationFailed(format!("erasure-coded data of {} bytes is truncated", data_byte_len)))?;
+        offset += data_byte_len;
+
+        let chunk_digest = blake3::Hasher::new()
+            .update(&(chunkset_id as u64).to_le_bytes())
+            .update(&(chunk_id as u64).to_le_bytes())
+            .update(erasure_coded_data)
+            .finalize();
+
+        let (proof, n) = bincode::serde::decode_from_slice::<Vec<blake3::Hash>, _>(&bytes[offset..], DECDS_BINCODE_CONFIG).map_err(map_decode_err)?;
+        offset += n;
+
+        let (content_flags, n) = bincode::decode_from_slice::<u8, _>(&bytes[offset..], DECDS_BINCODE_CONFIG).map_err(map_decode_err)?;
+        let content_flags = decode_content_flags(content_flags)?;
+        offset += n;
+
+        Ok((
+            ProofCarryingChunk {
+                chunk: Chunk {
+                    chunkset_id,
+                    chunk_id,
+                    erasure_coded_data: Vec::new(),
+                },
+                proof,
+                content_flags,
+            },
+            chunk_digest,
+            offset,
+        ))
     }
+
+    /// Byte length of pieces, in which erasure-coded data is read and hashed by `Self::from_reader`.
+    const READ_PIECE_BYTE_LEN: usize = 1usize << 16;
+
+    /// Deserializes a `ProofCarryingChunk` from a reader, consuming exactly its serialized bytes, as written by
+    /// `Self::to_bytes`.
+    ///
+    /// The erasure-coded data is hashed piece by piece, as it is read, so the chunk digest is ready as soon as the
+    /// chunk is, without a second pass over the data. Pass the reader wrapped in a `BufReader`, if it is unbuffered.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader from which to deserialize the chunk.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok((Self, blake3::Hash))` containing the deserialized `ProofCarryingChunk` and its digest, exactly what
+    ///   `Self::get_chunk_digest` returns, if successful.
+    /// - `Err(DecdsError::ProofCarryingChunkDeserializationFailed)` if deserialization or reading fails.
+    pub fn from_reader<R: Read>(reader: &mut R) -> Result<(Self, blake3::Hash), DecdsError> {
+        let map_decode_err = |err: bincode::error::DecodeError| DecdsError::ProofCarryingChunkDeserializationFailed(err.to_string());
+
+        let chunkset_id = bincode::decode_from_std_read::<usize, _, _>(reader, DECDS_BINCODE_CONFIG).map_err(map_decode_err)?;
+        let chunk_id = bincode::decode_from_std_read::<usize, _, _>(reader, DECDS_BINCODE_CONFIG).map_err(map_decode_err)?;
+        let data_byte_len = bincode::decode_from_std_read::<usize, _, _>(reader, DECDS_BINCODE_CONFIG).map_err(map_decode_err)?;
+
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&(chunkset_id as u64).to_le_bytes());
+        hasher.update(&(chunk_id as u64).to_le_bytes());
+
+        // The buffer grows as data arrives, so that a bogus length prefix can't make us allocate a lot upfront.
+        let mut erasure_coded_data = Vec::with_capacity(data_byte_len.min(2 * Chunk::BYTE_LENGTH));
+        while erasure_coded_data.len() < data_byte_len {
+            let from = erasure_coded_data.len();
+            let to = (from + Self::READ_PIECE_BYTE_LEN).min(data_byte_len);
+
+            erasure_coded_data.resize(to, 0);
+            reader
+                .read_exact(&mut erasure_coded_data[from..to])
+                .map_err(|err| DecdsError::ProofCarryingChunkDeserializationFailed(err.to_string()))?;
+            hasher.update(&erasure_coded_data[from..to]);
+        }
+
+        let proof = bincode::serde::decode_from_std_read::<Vec<blake3::Hash>, _, _>(reader, DECDS_BINCODE_CONFIG).map_err(map_decode_err)?;
+        let content_flags = decode_content_flags(bincode::decode_from_std_read::<u8, _, _>(reader, DECDS_BINCODE_CONFIG).map_err(map_decode_err)?)?;
+
+        Ok((
+            ProofCarryingChunk {
+                chunk: Chunk {
+                    chunkset_id,
+                    chunk_id,
+                    erasure_coded_data,
+                },
+                proof,
+                content_flags,
+            },
+            hasher.finalize(),
+        ))
+    }
+}
+
+/// Decodes content flags, trailing a serialized `ProofCarryingChunk`.
+fn decode_content_flags(bits: u8) -> Result<ContentFlags, DecdsError> {
+    ContentFlags::from_bits(bits).map_err(|err| DecdsError::ProofCarryingChunkDeserializationFailed(err.to_string()))
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "coding"))]
 mod tests {
     use super::*;
     use blake3;
@@ -187,8 +506,8 @@ mod tests {
 
         // Manually compute the expected digest
         let expected_digest = blake3::Hasher::new()
-            .update(&chunkset_id.to_le_bytes())
-            .update(&chunk_id.to_le_bytes())
+            .update(&(chunkset_id as u64).to_le_bytes())
+            .update(&(chunk_id as u64).to_le_bytes())
             .update(&erasure_coded_data)
             .finalize();
 
@@ -230,4 +549,92 @@ mod tests {
         // Test deserialization with lesser bytes
         assert!(ProofCarryingChunk::from_bytes(&serialized_pcc_bytes[..(serialized_pcc_bytes.len() / 2)]).is_err());
     }
+
+    #[test]
+    fn test_proof_carrying_chunk_from_reader() {
+        let mut rng = rand::rng();
+
+        let chunks = (0..2)
+            .map(|chunk_id| {
+                let erasure_coded_data: Vec<u8> = (0..(Chunk::BYTE_LENGTH + 11)).map(|_| rng.random()).collect();
+                let proof = (0..ChunkSet::PROOF_SIZE)
+                    .map(|_| blake3::Hash::from_bytes(rng.random()))
+                    .collect::<Vec<blake3::Hash>>();
+
+                ProofCarryingChunk::new(Chunk::new(0, chunk_id, erasure_coded_data), proof)
+            })
+            .collect::<Vec<ProofCarryingChunk>>();
+
+        // Back to back serialized chunks must be read one by one, each consuming exactly its own bytes.
+        let serialized = chunks.iter().flat_map(|chunk| chunk.to_bytes().unwrap()).collect::<Vec<u8>>();
+        let mut reader = std::io::Cursor::new(&serialized);
+
+        chunks.iter().for_each(|chunk| {
+            let (read_chunk, chunk_digest) = ProofCarryingChunk::from_reader(&mut reader).expect("Deserialization failed");
+
+            assert_eq!(&read_chunk, chunk);
+            assert_eq!(chunk_digest, chunk.get_chunk_digest());
+        });
+        assert_eq!(reader.position() as usize, serialized.len());
+
+        // Writing chunks back to back must produce the same bytes as serializing them one by one, using `serde`
+        let mut written = Vec::new();
+        chunks.iter().for_each(|chunk| {
+            let serde_serialized = bincode::serde::encode_to_vec(chunk, DECDS_BINCODE_CONFIG).unwrap();
+
+            assert_eq!(chunk.write_to(&mut written), Ok(serde_serialized.len()));
+            assert_eq!(chunk.to_bytes().unwrap(), serde_serialized);
+        });
+        assert_eq!(written, serialized);
+
+        // Truncated input must fail, wherever it is cut
+        let serialized_chunk = chunks[0].to_bytes().unwrap();
+        [1, 10, serialized_chunk.len() / 2, serialized_chunk.len() - 1].iter().for_each(|&till| {
+            assert!(matches!(
+                ProofCarryingChunk::from_reader(&mut &serialized_chunk[..till]),
+                Err(DecdsError::ProofCarryingChunkDeserializationFailed(_))
+            ));
+        });
+    }
+
+    #[test]
+    fn test_proof_carrying_chunk_from_parts() {
+        let blob = crate::Blob::new(vec![0xab; 1024]).expect("Failed to create blob");
+        let header = blob.get_blob_header();
+        let chunk = blob.get_share(0).expect("Failed to get share").into_iter().next().unwrap();
+
+        let assembled = ProofCarryingChunk::from_parts(
+            chunk.get_chunkset_id(),
+            chunk.get_local_chunk_id(),
+            chunk.get_erasure_coded_data().to_vec(),
+            chunk.get_proof().to_vec(),
+        )
+        .expect("Failed to assemble chunk");
+        assert_eq!(assembled, chunk);
+        assert!(header.validate_chunk(&assembled));
+
+        let data = chunk.get_erasure_coded_data().to_vec();
+        let proof = chunk.get_proof().to_vec();
+
+        assert_eq!(
+            ProofCarryingChunk::from_parts(0, ChunkSet::NUM_ERASURE_CODED_CHUNKS, data.clone(), proof.clone()),
+            Err(DecdsError::InvalidErasureCodedShareId(ChunkSet::NUM_ERASURE_CODED_CHUNKS))
+        );
+        assert!(matches!(
+            ProofCarryingChunk::from_parts(usize::MAX, 0, data.clone(), proof.clone()),
+            Err(DecdsError::InvalidProofCarryingChunkParts(_))
+        ));
+        assert!(matches!(
+            ProofCarryingChunk::from_parts(0, 0, data[1..].to_vec(), proof.clone()),
+            Err(DecdsError::InvalidProofCarryingChunkParts(_))
+        ));
+        assert!(matches!(
+            ProofCarryingChunk::from_parts(0, 0, data.clone(), proof[..ChunkSet::PROOF_SIZE - 1].to_vec()),
+            Err(DecdsError::InvalidProofCarryingChunkParts(_))
+        ));
+
+        // Well-formed, but not belonging to the blob
+        let forged = ProofCarryingChunk::from_parts(0, 1, data, proof).expect("Failed to assemble chunk");
+        assert!(!header.validate_chunk(&forged));
+    }
 }
@@ -1,15 +1,29 @@
 use crate::{
     chunk::{self, Chunk},
     consts::DECDS_NUM_ERASURE_CODED_SHARES,
+};
+#[cfg(feature = "coding")]
+use crate::{
+    codec::{Codec, ErasureCodec},
+    config::BlobConfig,
     errors::DecdsError,
+    flags::ContentFlags,
     merkle_tree::MerkleTree,
+    recoder::RecodedChunk,
 };
+#[cfg(feature = "coding")]
+use rand::Rng;
+#[cfg(feature = "coding")]
+use rayon::prelude::*;
+#[cfg(feature = "coding")]
+use std::borrow::Cow;
 
 /// Represents a fixed set (= 16) of erasure-coded chunks, along with its Merkle root commitment.
 /// This structure is used for encoding a fixed size (10MB = 10 * 2^20 bytes) portion of the original
 /// blob data into `NUM_ERASURE_CODED_CHUNKS` (= 16) erasure-coded verifiable chunks, each carrying
 /// a merkle proof of inclusion in both this chunkset and the blob.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(not(feature = "coding"), allow(dead_code))]
 pub(crate) struct ChunkSet {
     commitment: blake3::Hash,
     chunks: Vec<chunk::ProofCarryingChunk>,
@@ -19,10 +33,46 @@ impl ChunkSet {
     pub const NUM_ORIGINAL_CHUNKS: usize = 10;
     pub const BYTE_LENGTH: usize = Self::NUM_ORIGINAL_CHUNKS * Chunk::BYTE_LENGTH;
     pub const NUM_ERASURE_CODED_CHUNKS: usize = DECDS_NUM_ERASURE_CODED_SHARES;
-    pub const PROOF_SIZE: usize = usize::ilog2(Self::NUM_ERASURE_CODED_CHUNKS) as usize;
+    /// The padded byte length of individual chunks used in RLNC encoding.
+    /// It ensures that the total chunkset size is a multiple of `NUM_ORIGINAL_CHUNKS`,
+    /// after appending a single byte end-of-data marker.
+    #[cfg(any(feature = "coding", test))]
+    pub(crate) const PADDED_CHUNK_BYTE_LEN: usize = (Self::BYTE_LENGTH + 1).div_ceil(Self::NUM_ORIGINAL_CHUNKS);
+    /// Number of sibling digests proving inclusion of a chunk in its chunkset, i.e. `ceil(log2(NUM_ERASURE_CODED_CHUNKS))`.
+    /// Every chunkset keeps `DECDS_NUM_ERASURE_CODED_SHARES` share slots, no matter how many shares it's encoded into (see
+    /// `BlobConfig`), so this is the same for all blobs, and shares beyond those slots, e.g. 20 or 24, aren't supported.
+    pub const PROOF_SIZE: usize = Self::NUM_ERASURE_CODED_CHUNKS.next_power_of_two().ilog2() as usize;
+
+    /// Returns index of a chunk among leaf nodes of the blob-level Merkle tree, i.e. `chunkset_id || local_chunk_id`,
+    /// where `local_chunk_id` takes `PROOF_SIZE` bits. Each chunkset tree is a subtree of the blob-level tree, padded to
+    /// `2^PROOF_SIZE` leaves, so this equals the global chunk ID only when the share slot count is a power of two.
+    pub const fn get_blob_leaf_index(chunkset_id: usize, local_chunk_id: usize) -> usize {
+        (chunkset_id << Self::PROOF_SIZE) | local_chunk_id
+    }
+
+    /// Same as `Self::get_blob_leaf_index`, but returns `None`, instead of shifting high bits of `chunkset_id` out, if the
+    /// index doesn't fit in a `usize`, e.g. for a chunkset ID claimed by an adversarial chunk.
+    pub(crate) fn checked_blob_leaf_index(chunkset_id: usize, local_chunk_id: usize) -> Option<usize> {
+        chunkset_id
+            .checked_mul(1 << Self::PROOF_SIZE)
+            .map(|first_leaf_index| first_leaf_index | local_chunk_id)
+    }
+
+    /// Returns the global ID of a chunk, i.e. `chunkset_id * NUM_ERASURE_CODED_CHUNKS + local_chunk_id`, or `None` if it
+    /// doesn't fit in a `usize`.
+    pub(crate) fn checked_global_chunk_id(chunkset_id: usize, local_chunk_id: usize) -> Option<usize> {
+        chunkset_id.checked_mul(Self::NUM_ERASURE_CODED_CHUNKS)?.checked_add(local_chunk_id)
+    }
+}
+
+#[cfg(feature = "coding")]
+impl ChunkSet {
+    /// BLAKE3 key derivation context, used for deriving RLNC coding vectors from a seed.
+    const CODING_VECTOR_KDF_CONTEXT: &str = "decds 2025-07-01 RLNC coding vector";
 
     /// Creates a new `ChunkSet` by taking a fixed sized block of data, splits into 10 equal sized chunks,
     /// each of 1MB, RLNC encoding them into 16 erasure-coded chunks, and building a Merkle tree over these chunks.
+    /// Coding vectors are derived from a randomly sampled seed.
     ///
     /// # Arguments
     ///
@@ -34,38 +84,228 @@ impl ChunkSet {
     /// Returns a `Result` which is:
     /// - `Ok(ChunkSet)` containing the newly created `ChunkSet` if successful.
     /// - `Err(DecdsError::InvalidChunksetSize)` if the `data` length does not match `ChunkSet::BYTE_LENGTH`.
+    #[cfg(test)]
     pub fn new(chunkset_id: usize, data: Vec<u8>) -> Result<ChunkSet, DecdsError> {
-        if data.len() != Self::BYTE_LENGTH {
+        Self::new_with_config(chunkset_id, data, &BlobConfig::default(), None)
+    }
+
+    /// Same as `Self::new`, but RLNC coding vectors of all chunks are deterministically derived from `seed`,
+    /// using `Self::derive_coding_vector`. Same data, chunkset ID and seed always produce the same `ChunkSet`.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The unique identifier for this chunkset.
+    /// * `data` - The raw data (10MB) to be erasure-coded into chunks for this chunkset.
+    /// * `seed` - 32-bytes seed, used for deriving coding vectors.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(ChunkSet)` containing the newly created `ChunkSet` if successful.
+    /// - `Err(DecdsError::InvalidChunksetSize)` if the `data` length does not match `ChunkSet::BYTE_LENGTH`.
+    #[cfg(test)]
+    pub fn new_with_seed(chunkset_id: usize, data: Vec<u8>, seed: &[u8; 32]) -> Result<ChunkSet, DecdsError> {
+        Self::new_with_config(chunkset_id, data, &BlobConfig::default(), Some(seed))
+    }
+
+    /// Same as `Self::new`, or `Self::new_with_seed` if `seed` is given, but splits data into
+    /// `config.get_num_original_chunks()` pieces and erasure-codes them into `config.get_num_shares()` chunks, with the
+    /// codec `config.get_codec()`. Only RLNC coding vectors depend on the seed.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The unique identifier for this chunkset.
+    /// * `data` - The raw data (`config.get_chunkset_byte_length()` bytes) to be erasure-coded into chunks for this chunkset.
+    /// * `config` - Valid erasure-coding geometry.
+    /// * `seed` - 32-bytes seed, used for deriving coding vectors, or `None` for sampling one randomly.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(ChunkSet)` containing the newly created `ChunkSet` if successful.
+    /// - `Err(DecdsError::InvalidChunksetSize)` if the `data` length does not match `config.get_chunkset_byte_length()`.
+    /// - `Err(DecdsError::ArithmeticOverflow)` if global IDs of chunks of the chunkset don't fit in a `usize`.
+    /// - `Err(DecdsError::CodecNotCompiledIn)` if the codec `config.get_codec()` is disabled by a feature flag.
+    pub fn new_with_config(chunkset_id: usize, data: Vec<u8>, config: &BlobConfig, seed: Option<&[u8; 32]>) -> Result<ChunkSet, DecdsError> {
+        if data.len() != config.get_chunkset_byte_length() {
             return Err(DecdsError::InvalidChunksetSize(data.len()));
         }
+        Self::check_global_chunk_ids(chunkset_id)?;
 
-        let mut rng = rand::rng();
-        let encoder = unsafe { rlnc::full::encoder::Encoder::new(data, Self::NUM_ORIGINAL_CHUNKS).unwrap_unchecked() };
+        let seed = seed.copied().unwrap_or_else(|| rand::rng().random::<[u8; 32]>());
+        let codec = Codec::new_encoding_codec(config, seed)?;
+
+        let encoder = unsafe { rlnc::full::encoder::Encoder::new(data, config.get_num_original_chunks()).unwrap_unchecked() };
 
-        let chunks = (0..Self::NUM_ERASURE_CODED_CHUNKS)
+        // Each chunk is hashed right after it is coded, on the same worker, so that digest computation is spread across
+        // cores along with encoding, even when the blob has only a few chunksets to parallelize over.
+        let (chunks, merkle_leaves): (Vec<Chunk>, Vec<blake3::Hash>) = (0..config.get_num_shares())
+            .into_par_iter()
             .map(|i| {
-                let chunk_id = chunkset_id * Self::NUM_ERASURE_CODED_CHUNKS + i;
-                let erasure_coded_data = encoder.code(&mut rng);
+                let chunk = Self::code_chunk(&encoder, chunkset_id, i, codec.as_ref());
+                let digest = chunk.digest();
 
-                chunk::Chunk::new(chunkset_id, chunk_id, erasure_coded_data)
+                (chunk, digest)
             })
-            .collect::<Vec<Chunk>>();
+            .unzip();
 
-        let merkle_leaves = chunks.iter().map(|chunk| chunk.digest()).collect::<Vec<blake3::Hash>>();
-        let merkle_tree = unsafe { MerkleTree::new(merkle_leaves).unwrap_unchecked() };
+        Ok(Self::commit_to_chunks(chunks, merkle_leaves))
+    }
+
+    /// Checks that global IDs of all chunks of chunkset `chunkset_id` fit in a `usize`, so that they can be computed
+    /// unchecked afterwards, see `Self::code_chunk`.
+    pub(crate) fn check_global_chunk_ids(chunkset_id: usize) -> Result<(), DecdsError> {
+        Self::checked_global_chunk_id(chunkset_id, Self::NUM_ERASURE_CODED_CHUNKS - 1)
+            .map(|_| ())
+            .ok_or_else(|| DecdsError::ArithmeticOverflow(format!("global chunk IDs of chunkset {}", chunkset_id)))
+    }
+
+    /// Erasure-codes a single chunk of a chunkset, with the coding vector `codec` chooses for it, so that the very same
+    /// chunk can be coded again later, from the chunkset data and codec alone.
+    pub(crate) fn code_chunk(encoder: &rlnc::full::encoder::Encoder, chunkset_id: usize, local_chunk_id: usize, codec: &dyn ErasureCodec) -> Chunk {
+        let chunk_id = chunkset_id * Self::NUM_ERASURE_CODED_CHUNKS + local_chunk_id;
+        let coding_vector = codec.get_coding_vector(chunkset_id, local_chunk_id);
+        let erasure_coded_data = unsafe { encoder.code_with_coding_vector(&coding_vector).unwrap_unchecked() };
 
+        chunk::Chunk::new(chunkset_id, chunk_id, erasure_coded_data)
+    }
+
+    /// Builds the Merkle tree over digests of chunks of a chunkset, attaching proofs of inclusion in the chunkset to them.
+    /// Digests of unused share slots are left as zero hashes, so that all chunkset trees are of same height.
+    fn commit_to_chunks(chunks: Vec<Chunk>, mut merkle_leaves: Vec<blake3::Hash>) -> ChunkSet {
+        merkle_leaves.resize(Self::NUM_ERASURE_CODED_CHUNKS, blake3::Hash::from_bytes([0u8; blake3::OUT_LEN]));
+
+        let merkle_tree = unsafe { MerkleTree::new(merkle_leaves).unwrap_unchecked() };
         let commitment = merkle_tree.get_root_commitment();
 
         let proof_carrying_chunks = chunks
             .into_iter()
-            .enumerate()
-            .map(|(leaf_idx, chunk)| chunk::ProofCarryingChunk::new(chunk, unsafe { merkle_tree.generate_proof(leaf_idx).unwrap_unchecked() }))
+            .zip(merkle_tree.generate_all_proofs())
+            .map(|(chunk, proof)| chunk::ProofCarryingChunk::new(chunk, proof))
             .collect::<Vec<chunk::ProofCarryingChunk>>();
 
-        Ok(ChunkSet {
+        ChunkSet {
             commitment,
             chunks: proof_carrying_chunks,
-        })
+        }
+    }
+
+    /// Wraps shards of a chunkset, erasure-coded by another `(NUM_ORIGINAL_CHUNKS, NUM_ERASURE_CODED_CHUNKS)` linear code
+    /// over GF(2^8), e.g. a Reed-Solomon code, into chunks, without re-encoding them. Row `i` of `coding_matrix` becomes
+    /// the RLNC coding vector of shard `i`. Shards are decoded and checked against their coding vectors, so that only
+    /// consistent shards are committed to.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The unique identifier for this chunkset.
+    /// * `coding_matrix` - `NUM_ERASURE_CODED_CHUNKS` rows of `NUM_ORIGINAL_CHUNKS` coefficients, i.e. the generator matrix,
+    ///   assumed to be of right shape.
+    /// * `shards` - `NUM_ERASURE_CODED_CHUNKS` shards, each of `ChunkSet::PADDED_CHUNK_BYTE_LEN` bytes.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok((ChunkSet, Vec<u8>))` containing the `ChunkSet` and its decoded `ChunkSet::BYTE_LENGTH` bytes of data, if successful.
+    /// - `Err(DecdsError::IncompatibleCodedShards)` if shards are of unexpected number or length, if the coding matrix is
+    ///   rank deficient, if decoded data isn't padded the way `ChunkSet::new` pads it, or if a shard doesn't match its
+    ///   coding vector.
+    /// - `Err(DecdsError::ArithmeticOverflow)` if global IDs of chunks of the chunkset don't fit in a `usize`.
+    pub(crate) fn from_coded_shards(chunkset_id: usize, coding_matrix: &[Vec<u8>], shards: Vec<Vec<u8>>) -> Result<(ChunkSet, Vec<u8>), DecdsError> {
+        let incompatible = |reason: String| DecdsError::IncompatibleCodedShards(chunkset_id, reason);
+        Self::check_global_chunk_ids(chunkset_id)?;
+
+        if shards.len() != Self::NUM_ERASURE_CODED_CHUNKS {
+            return Err(incompatible(format!(
+                "expected {} shards, got {}",
+                Self::NUM_ERASURE_CODED_CHUNKS,
+                shards.len()
+            )));
+        }
+        if let Some(share_id) = shards.iter().position(|shard| shard.len() != ChunkSet::PADDED_CHUNK_BYTE_LEN) {
+            return Err(incompatible(format!(
+                "shard {} is of {}B, expected {}B",
+                share_id,
+                shards[share_id].len(),
+                ChunkSet::PADDED_CHUNK_BYTE_LEN
+            )));
+        }
+
+        let erasure_coded_data = coding_matrix
+            .iter()
+            .zip(shards)
+            .map(|(coding_vector, shard)| [coding_vector.as_slice(), &shard].concat())
+            .collect::<Vec<Vec<u8>>>();
+
+        let mut decoder = unsafe { rlnc::full::decoder::Decoder::new(ChunkSet::PADDED_CHUNK_BYTE_LEN, Self::NUM_ORIGINAL_CHUNKS).unwrap_unchecked() };
+        for coded_data in &erasure_coded_data {
+            if decoder.is_already_decoded() {
+                break;
+            }
+            let _ = decoder.decode(coded_data);
+        }
+
+        if !decoder.is_already_decoded() {
+            return Err(incompatible("coding matrix is rank deficient".to_string()));
+        }
+
+        let data = decoder.get_decoded_data().map_err(|err| incompatible(err.to_string()))?;
+        if data.len() != Self::BYTE_LENGTH {
+            return Err(incompatible(format!(
+                "decoded {}B of data, expected {}B, shards are not padded like decds pads chunksets",
+                data.len(),
+                Self::BYTE_LENGTH
+            )));
+        }
+
+        let encoder = unsafe { rlnc::full::encoder::Encoder::new(data.clone(), Self::NUM_ORIGINAL_CHUNKS).unwrap_unchecked() };
+        if let Some(share_id) = erasure_coded_data
+            .par_iter()
+            .position_first(|coded_data| encoder.code_with_coding_vector(&coded_data[..Self::NUM_ORIGINAL_CHUNKS]).as_ref() != Ok(coded_data))
+        {
+            return Err(incompatible(format!("shard {} doesn't match its coding vector", share_id)));
+        }
+
+        let (chunks, merkle_leaves): (Vec<Chunk>, Vec<blake3::Hash>) = erasure_coded_data
+            .into_par_iter()
+            .enumerate()
+            .map(|(i, coded_data)| {
+                let chunk = chunk::Chunk::new(chunkset_id, chunkset_id * Self::NUM_ERASURE_CODED_CHUNKS + i, coded_data);
+                let digest = chunk.digest();
+
+                (chunk, digest)
+            })
+            .unzip();
+
+        Ok((Self::commit_to_chunks(chunks, merkle_leaves), data))
+    }
+
+    /// Derives the RLNC coding vector of a chunk from a seed.
+    ///
+    /// The coding vector is the first `num_original_chunks` bytes of the BLAKE3 extendable output of
+    /// `seed || chunkset_id || local_chunk_id`, hashed in key derivation mode with `CODING_VECTOR_KDF_CONTEXT`.
+    /// Both IDs are encoded as 8-bytes little-endian unsigned integers.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - 32-bytes seed.
+    /// * `chunkset_id` - The ID of the chunkset the chunk belongs to.
+    /// * `local_chunk_id` - The local ID (`>= 0 && < Self::NUM_ERASURE_CODED_CHUNKS`) of the chunk within its chunkset.
+    /// * `num_original_chunks` - Number of pieces the chunkset is split into, `Self::NUM_ORIGINAL_CHUNKS` by default.
+    ///
+    /// # Returns
+    ///
+    /// Returns `num_original_chunks` bytes long coding vector.
+    pub fn derive_coding_vector(seed: &[u8; 32], chunkset_id: usize, local_chunk_id: usize, num_original_chunks: usize) -> Vec<u8> {
+        let mut coding_vector = vec![0u8; num_original_chunks];
+
+        blake3::Hasher::new_derive_key(Self::CODING_VECTOR_KDF_CONTEXT)
+            .update(seed)
+            .update(&(chunkset_id as u64).to_le_bytes())
+            .update(&(local_chunk_id as u64).to_le_bytes())
+            .finalize_xof()
+            .fill(&mut coding_vector);
+
+        coding_vector
     }
 
     /// Returns the Merkle root commitment of this `ChunkSet`.
@@ -88,6 +328,22 @@ impl ChunkSet {
         self.chunks.get(chunk_id).ok_or(DecdsError::InvalidErasureCodedShareId(chunk_id))
     }
 
+    /// Assembles a `ChunkSet` from its root commitment and chunks, already validated against it and ordered by their local
+    /// chunk ID, e.g. as read back from storage. See `Blob::reassemble`.
+    pub(crate) fn from_chunks(commitment: blake3::Hash, chunks: Vec<chunk::ProofCarryingChunk>) -> ChunkSet {
+        ChunkSet { commitment, chunks }
+    }
+
+    /// Consumes the `ChunkSet`, returning its chunks, ordered by their local chunk ID.
+    pub(crate) fn into_chunks(self) -> Vec<chunk::ProofCarryingChunk> {
+        self.chunks
+    }
+
+    /// Sets content flags of the blob, this chunkset is of, on all of its chunks. See `ContentFlags`.
+    pub(crate) fn set_content_flags(&mut self, content_flags: ContentFlags) {
+        self.chunks.iter_mut().for_each(|chunk| chunk.set_content_flags(content_flags));
+    }
+
     /// Appends a Merkle proof for the blob inclusion to all `ProofCarryingChunk`s within this `ChunkSet`.
     /// This extends the chunkset-level proof to a blob-level proof for each chunk.
     ///
@@ -104,18 +360,20 @@ impl ChunkSet {
 
 /// A structure designed to help incrementally reconstruct the original data of a `ChunkSet`
 /// by collecting enough erasure-coded chunks, verifying their integrity, and performing RLNC decoding.
+#[cfg(feature = "coding")]
 pub struct RepairingChunkSet {
     chunkset_id: usize,
     commitment: blake3::Hash,
+    config: BlobConfig,
+    codec: Option<Box<dyn ErasureCodec>>,
     decoder: rlnc::full::decoder::Decoder,
+    /// Erasure-coded data of every useful chunk fed to the decoder, kept only if checkpointing is enabled, as the decoder
+    /// state can't be serialized, but can be rebuilt from them.
+    useful_pieces: Option<Vec<Vec<u8>>>,
 }
 
+#[cfg(feature = "coding")]
 impl RepairingChunkSet {
-    /// The padded byte length of individual chunks used in RLNC encoding.
-    /// It ensures that the total chunkset size is a multiple of `NUM_ORIGINAL_CHUNKS`,
-    /// after appending a single byte end-of-data marker.
-    const PADDED_CHUNK_BYTE_LEN: usize = (ChunkSet::BYTE_LENGTH + 1).div_ceil(ChunkSet::NUM_ORIGINAL_CHUNKS);
-
     /// Creates a new `RepairingChunkSet` instance.
     ///
     /// # Arguments
@@ -127,10 +385,29 @@ impl RepairingChunkSet {
     ///
     /// A new `RepairingChunkSet` instance.
     pub fn new(chunkset_id: usize, commitment: blake3::Hash) -> Self {
+        Self::new_with_config(chunkset_id, commitment, &BlobConfig::default())
+    }
+
+    /// Same as `Self::new`, but for a chunkset erasure-coded with the geometry `config`, as recorded in the `BlobHeader`.
+    /// Chunks, whose coding vector the codec `config.get_codec()` wouldn't have chosen, are rejected.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset being repaired.
+    /// * `commitment` - The expected Merkle root commitment of the chunkset, used for validating chunk inclusion in chunkset.
+    /// * `config` - Valid erasure-coding geometry of the chunkset.
+    ///
+    /// # Returns
+    ///
+    /// A new `RepairingChunkSet` instance.
+    pub fn new_with_config(chunkset_id: usize, commitment: blake3::Hash, config: &BlobConfig) -> Self {
         RepairingChunkSet {
             chunkset_id,
             commitment,
-            decoder: unsafe { rlnc::full::decoder::Decoder::new(Self::PADDED_CHUNK_BYTE_LEN, ChunkSet::NUM_ORIGINAL_CHUNKS).unwrap_unchecked() },
+            config: *config,
+            codec: Codec::new_decoding_codec(config),
+            decoder: unsafe { rlnc::full::decoder::Decoder::new(config.get_piece_byte_length(), config.get_num_original_chunks()).unwrap_unchecked() },
+            useful_pieces: None,
         }
     }
 
@@ -147,12 +424,12 @@ impl RepairingChunkSet {
     /// - `Ok(())` if the chunk is successfully added and validated.
     /// - `Err(DecdsError::InvalidProofInChunk)` if the chunk's inclusion proof is invalid for this chunkset.
     /// - `Err(DecdsError::InvalidChunkMetadata)` if the chunk's `chunkset_id` does not match this `RepairingChunkSet`.
-    /// - `Err(DecdsError::ChunkDecodingFailed)` if the underlying RLNC decoding operation fails.
+    /// - Otherwise, exactly what `Self::add_chunk_unvalidated` returns.
     pub fn add_chunk(&mut self, chunk: &chunk::ProofCarryingChunk) -> Result<(), DecdsError> {
         if chunk.validate_inclusion_in_chunkset(self.commitment) {
             self.add_chunk_unvalidated(chunk)
         } else {
-            Err(DecdsError::InvalidProofInChunk(chunk.get_chunkset_id()))
+            Err(DecdsError::InvalidProofInChunk(chunk.get_chunkset_id(), chunk.get_local_chunk_id()))
         }
     }
 
@@ -169,18 +446,161 @@ impl RepairingChunkSet {
     /// - `Ok(())` if the chunk is successfully added.
     /// - `Err(DecdsError::InvalidChunkMetadata)` if the chunk's `chunkset_id` does not match this `RepairingChunkSet`.
     /// - `Err(DecdsError::ChunksetReadyToRepair)` if the chunkset is ready to repair, no more chunks are required. Just call `repair`.
+    /// - `Err(DecdsError::CodecNotCompiledIn)` if the codec of the chunkset is disabled by a feature flag.
+    /// - `Err(DecdsError::UnexpectedCodingVector)` if the codec of the chunkset wouldn't have coded the chunk with its coding vector.
+    /// - `Err(DecdsError::NonInnovativeChunk)` if the chunk is linearly dependent on chunks added before, so it's of no use.
     /// - `Err(DecdsError::ChunkDecodingFailed)` if the underlying RLNC decoding operation fails.
     pub fn add_chunk_unvalidated(&mut self, chunk: &chunk::ProofCarryingChunk) -> Result<(), DecdsError> {
+        self.check_chunk(chunk)?;
+        self.decode_chunk(chunk.get_local_chunk_id(), Cow::Borrowed(chunk.get_erasure_coded_data()))
+    }
+
+    /// Same as `Self::add_chunk`, but takes ownership of the chunk, so that its erasure-coded data, which is 1MB by
+    /// default, doesn't have to be copied, in case it has to be kept around for checkpointing, and is released right after
+    /// decoding otherwise. Prefer this, when the chunk isn't needed after adding it, e.g. as just received from the network.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk` - The `ProofCarryingChunk` to add.
+    ///
+    /// # Returns
+    ///
+    /// Returns exactly what `Self::add_chunk` returns.
+    pub fn add_chunk_owned(&mut self, chunk: chunk::ProofCarryingChunk) -> Result<(), DecdsError> {
+        if chunk.validate_inclusion_in_chunkset(self.commitment) {
+            self.add_chunk_unvalidated_owned(chunk)
+        } else {
+            Err(DecdsError::InvalidProofInChunk(chunk.get_chunkset_id(), chunk.get_local_chunk_id()))
+        }
+    }
+
+    /// Same as `Self::add_chunk_unvalidated`, but takes ownership of the chunk. See `Self::add_chunk_owned`.
+    pub(crate) fn add_chunk_unvalidated_owned(&mut self, chunk: chunk::ProofCarryingChunk) -> Result<(), DecdsError> {
+        self.check_chunk(&chunk)?;
+
+        let local_chunk_id = chunk.get_local_chunk_id();
+        self.decode_chunk(local_chunk_id, Cow::Owned(chunk.into_erasure_coded_data()))
+    }
+
+    /// Checks that a chunk belongs to this chunkset, is coded as its codec would have coded it, and can still be decoded.
+    fn check_chunk(&self, chunk: &chunk::ProofCarryingChunk) -> Result<(), DecdsError> {
         if self.chunkset_id != chunk.get_chunkset_id() {
-            return Err(DecdsError::InvalidChunkMetadata(chunk.get_chunkset_id()));
+            return Err(DecdsError::InvalidChunkMetadata(chunk.get_chunkset_id(), chunk.get_local_chunk_id()));
+        }
+        self.config.get_codec().check_compiled_in()?;
+
+        let erasure_coded_data = chunk.get_erasure_coded_data();
+        let coding_vector = &erasure_coded_data[..self.config.get_num_original_chunks().min(erasure_coded_data.len())];
+        if self
+            .codec
+            .as_ref()
+            .is_some_and(|codec| !codec.is_valid_coding_vector(self.chunkset_id, chunk.get_local_chunk_id(), coding_vector))
+        {
+            return Err(DecdsError::UnexpectedCodingVector(chunk.get_chunkset_id(), chunk.get_local_chunk_id()));
+        }
+        if self.is_ready_to_repair() {
+            return Err(DecdsError::ChunksetReadyToRepair(self.chunkset_id));
+        }
+
+        Ok(())
+    }
+
+    /// Feeds erasure-coded data of an already checked chunk of share `local_chunk_id` to the decoder.
+    fn decode_chunk(&mut self, local_chunk_id: usize, erasure_coded_data: Cow<'_, [u8]>) -> Result<(), DecdsError> {
+        match self.decode(erasure_coded_data) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(DecdsError::NonInnovativeChunk(self.chunkset_id, local_chunk_id)),
+            Err(err) => Err(DecdsError::ChunkDecodingFailed(self.chunkset_id, local_chunk_id, err)),
         }
+    }
+
+    /// Adds a `RecodedChunk` to the `RepairingChunkSet`. Recoded chunks carry no Merkle proof, so only their chunkset and
+    /// length are checked, and their coding vectors, being random combinations, aren't checked against the codec. The caller
+    /// must have validated the chunk otherwise, as nothing checks the repaired chunkset.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk` - The `RecodedChunk` to add.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(())` if the recoded chunk is successfully added.
+    /// - `Err(DecdsError::InvalidRecodedChunk)` if it doesn't belong to this chunkset, or is of unexpected length.
+    /// - `Err(DecdsError::ChunksetReadyToRepair)` if the chunkset is ready to repair, no more chunks are required. Just call `repair`.
+    /// - `Err(DecdsError::NonInnovativeRecodedChunk)` if the chunk is linearly dependent on chunks added before.
+    /// - `Err(DecdsError::RecodedChunkDecodingFailed)` if the underlying RLNC decoding operation fails.
+    pub(crate) fn add_recoded_chunk(&mut self, chunk: &RecodedChunk) -> Result<(), DecdsError> {
+        chunk.check_shape(self.chunkset_id, self.config.get_erasure_coded_data_byte_length())?;
         if self.is_ready_to_repair() {
             return Err(DecdsError::ChunksetReadyToRepair(self.chunkset_id));
         }
 
-        self.decoder
-            .decode(chunk.get_erasure_coded_data())
-            .map_err(|err| DecdsError::ChunkDecodingFailed(chunk.get_chunkset_id(), err.to_string()))
+        match self.decode(Cow::Borrowed(chunk.get_erasure_coded_data())) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(DecdsError::NonInnovativeRecodedChunk(self.chunkset_id)),
+            Err(err) => Err(DecdsError::RecodedChunkDecodingFailed(self.chunkset_id, err)),
+        }
+    }
+
+    /// Feeds erasure-coded data of a chunk to the decoder, remembering it, if it turns out useful and checkpointing is
+    /// enabled. Returns whether the chunk was useful, i.e. linearly independent of chunks fed to the decoder before.
+    /// A well-formed chunk, which the decoder doesn't learn anything from, isn't an error, but a non-innovative chunk.
+    /// Owned data is remembered as is, borrowed data is copied.
+    fn decode(&mut self, erasure_coded_data: Cow<'_, [u8]>) -> Result<bool, String> {
+        let num_remaining_chunks = self.get_num_remaining_chunks();
+        let decoding_result = self.decoder.decode(&erasure_coded_data);
+
+        let is_useful = self.decoder.get_remaining_piece_count() < num_remaining_chunks;
+        if let Err(err) = decoding_result {
+            if is_useful || erasure_coded_data.len() != self.config.get_erasure_coded_data_byte_length() {
+                return Err(err.to_string());
+            }
+        }
+
+        if let Some(useful_pieces) = self.useful_pieces.as_mut() {
+            if is_useful {
+                useful_pieces.push(erasure_coded_data.into_owned());
+            }
+        }
+
+        Ok(is_useful)
+    }
+
+    /// Starts remembering erasure-coded data of useful chunks, so that the decoder state can be checkpointed, see
+    /// `RepairingBlob::to_bytes`. Chunks fed to the decoder before are forgotten, so it's enabled on fresh chunksets only.
+    pub(crate) fn enable_checkpointing(&mut self) {
+        self.useful_pieces.get_or_insert_with(Vec::new);
+    }
+
+    /// Returns erasure-coded data of all useful chunks fed to the decoder so far, in arrival order, or `None` if
+    /// checkpointing isn't enabled.
+    pub(crate) fn get_useful_pieces(&self) -> Option<&[Vec<u8>]> {
+        self.useful_pieces.as_deref()
+    }
+
+    /// Feeds erasure-coded data of a chunk, read back from a checkpoint, to the decoder. It was checked against the codec
+    /// before it was checkpointed, so it only has to be of expected length and still useful.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(())` if the chunk is restored.
+    /// - `Err(DecdsError::RepairCheckpointDeserializationFailed)` if it's of unexpected length, or it isn't useful.
+    pub(crate) fn restore_useful_piece(&mut self, erasure_coded_data: &[u8]) -> Result<(), DecdsError> {
+        let num_remaining_chunks = self.get_num_remaining_chunks();
+        let is_useful = erasure_coded_data.len() == self.config.get_erasure_coded_data_byte_length()
+            && num_remaining_chunks > 0
+            && self.decode(Cow::Borrowed(erasure_coded_data)) == Ok(true);
+
+        if !is_useful {
+            return Err(DecdsError::RepairCheckpointDeserializationFailed(format!(
+                "chunk of chunkset {} isn't useful",
+                self.chunkset_id
+            )));
+        }
+
+        Ok(())
     }
 
     /// Checks if enough useful erasure-coded chunks have been collected to repair the original data for this chunkset.
@@ -188,6 +608,11 @@ impl RepairingChunkSet {
         self.decoder.is_already_decoded()
     }
 
+    /// Returns how many more useful, i.e. linearly independent, erasure-coded chunks are required to repair this chunkset.
+    pub fn get_num_remaining_chunks(&self) -> usize {
+        self.decoder.get_remaining_piece_count()
+    }
+
     /// Repairs the original data of the chunkset if enough chunks have been collected.
     /// This consumes the `RepairingChunkSet` as the decoding process is final.
     ///
@@ -206,13 +631,39 @@ impl RepairingChunkSet {
             Err(DecdsError::ChunksetNotYetReadyToRepair(self.chunkset_id))
         }
     }
+
+    /// Same as `Self::repair`, but writes the repaired data, which is always `BlobConfig::get_chunkset_byte_length` bytes
+    /// long, i.e. `ChunkSet::BYTE_LENGTH` by default, into the beginning of the caller-provided buffer `out`, instead of
+    /// returning it. The buffer length is checked before the `RepairingChunkSet` is consumed.
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - Buffer to write the repaired data into, at least as long as a chunkset.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(usize)` containing the number of bytes written into `out` if successful.
+    /// - `Err(DecdsError::RepairOutputBufferTooSmall)` if `out` is shorter than a chunkset.
+    /// - Otherwise, same errors as `Self::repair`.
+    pub fn repair_into(self, out: &mut [u8]) -> Result<usize, DecdsError> {
+        let chunkset_byte_length = self.config.get_chunkset_byte_length();
+        if out.len() < chunkset_byte_length {
+            return Err(DecdsError::RepairOutputBufferTooSmall(self.chunkset_id, out.len(), chunkset_byte_length));
+        }
+
+        let repaired = self.repair()?;
+        out[..repaired.len()].copy_from_slice(&repaired);
+
+        Ok(repaired.len())
+    }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "coding"))]
 mod tests {
     use crate::{
         DecdsError,
-        chunk::ProofCarryingChunk,
+        chunk::{Chunk, ProofCarryingChunk},
         chunkset::{ChunkSet, RepairingChunkSet},
         merkle_tree::{MerkleTree, tests::flip_a_bit},
     };
@@ -282,6 +733,27 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_chunkset_new_with_seed_is_deterministic() {
+        let mut rng = rand::rng();
+
+        let data = (0..ChunkSet::BYTE_LENGTH).map(|_| rng.random()).collect::<Vec<u8>>();
+        let seed = rng.random::<[u8; 32]>();
+
+        let chunkset_a = ChunkSet::new_with_seed(3, data.clone(), &seed).expect("Must be able to build erasure-coded ChunkSet");
+        let chunkset_b = ChunkSet::new_with_seed(3, data.clone(), &seed).expect("Must be able to build erasure-coded ChunkSet");
+        assert_eq!(chunkset_a, chunkset_b);
+
+        let chunk = chunkset_a.get_chunk(5).unwrap();
+        assert_eq!(
+            &chunk.get_erasure_coded_data()[..ChunkSet::NUM_ORIGINAL_CHUNKS],
+            ChunkSet::derive_coding_vector(&seed, 3, 5, ChunkSet::NUM_ORIGINAL_CHUNKS).as_slice()
+        );
+
+        let chunkset_c = ChunkSet::new_with_seed(4, data, &seed).expect("Must be able to build erasure-coded ChunkSet");
+        assert_ne!(chunkset_a.get_root_commitment(), chunkset_c.get_root_commitment());
+    }
+
     #[test]
     fn test_chunkset_new_invalid_size() {
         let data_too_small = vec![0u8; ChunkSet::BYTE_LENGTH - 1];
@@ -412,8 +884,41 @@ mod tests {
         // Adding this valid chunk to a repairing_chunkset with a tampered commitment should fail
         assert_eq!(
             repairing_chunkset.add_chunk(valid_chunk).unwrap_err(),
-            DecdsError::InvalidProofInChunk(valid_chunk.get_chunkset_id())
+            DecdsError::InvalidProofInChunk(valid_chunk.get_chunkset_id(), valid_chunk.get_local_chunk_id())
+        );
+    }
+
+    #[test]
+    fn test_repairing_chunkset_add_chunk_with_short_proof() {
+        let mut rng = rand::rng();
+
+        let data = (0..ChunkSet::BYTE_LENGTH).map(|_| rng.random()).collect::<Vec<u8>>();
+        let chunkset = ChunkSet::new(0, data).expect("Must be able to build erasure-coded ChunkSet");
+        let mut repairing_chunkset = RepairingChunkSet::new(0, chunkset.get_root_commitment());
+
+        let valid_chunk = chunkset.get_chunk(0).unwrap();
+        let short_proof_chunk = ProofCarryingChunk::new(
+            Chunk::new(0, 0, valid_chunk.get_erasure_coded_data().to_vec()),
+            valid_chunk.get_proof()[..ChunkSet::PROOF_SIZE - 1].to_vec(),
+        );
+
+        assert_eq!(
+            repairing_chunkset.add_chunk(&short_proof_chunk).unwrap_err(),
+            DecdsError::InvalidProofInChunk(0, 0)
         );
+        assert!(repairing_chunkset.add_chunk(valid_chunk).is_ok());
+    }
+
+    #[test]
+    fn test_chunkset_blob_leaf_index_is_global_chunk_id() {
+        (0..3).for_each(|chunkset_id| {
+            (0..ChunkSet::NUM_ERASURE_CODED_CHUNKS).for_each(|local_chunk_id| {
+                assert_eq!(
+                    ChunkSet::get_blob_leaf_index(chunkset_id, local_chunk_id),
+                    chunkset_id * ChunkSet::NUM_ERASURE_CODED_CHUNKS + local_chunk_id
+                );
+            });
+        });
     }
 
     #[test]
@@ -431,7 +936,7 @@ mod tests {
         // Attempt to add a chunk that belongs to chunkset_id 0 to a repairing_chunkset for chunkset_id 1
         assert_eq!(
             repairing_chunkset.add_chunk_unvalidated(chunk_from_chunkset_0).unwrap_err(),
-            DecdsError::InvalidChunkMetadata(chunk_from_chunkset_0.get_chunkset_id())
+            DecdsError::InvalidChunkMetadata(chunk_from_chunkset_0.get_chunkset_id(), chunk_from_chunkset_0.get_local_chunk_id())
         );
     }
 
@@ -452,6 +957,36 @@ mod tests {
         assert_eq!(repairing_chunkset.repair(), Err(DecdsError::ChunksetNotYetReadyToRepair(0)));
     }
 
+    #[test]
+    fn test_repairing_chunkset_repair_into() {
+        let mut rng = rand::rng();
+
+        let data = (0..ChunkSet::BYTE_LENGTH).map(|_| rng.random()).collect::<Vec<u8>>();
+        let chunkset = ChunkSet::new(0, data.clone()).expect("Must be able to build erasure-coded ChunkSet");
+
+        let new_ready_repairing_chunkset = || {
+            let mut repairing_chunkset = RepairingChunkSet::new(0, chunkset.get_root_commitment());
+            (0..ChunkSet::NUM_ERASURE_CODED_CHUNKS).for_each(|i| {
+                if !repairing_chunkset.is_ready_to_repair() {
+                    let _ = repairing_chunkset.add_chunk(chunkset.get_chunk(i).unwrap());
+                }
+            });
+
+            repairing_chunkset
+        };
+
+        let mut out = vec![0u8; ChunkSet::BYTE_LENGTH - 1];
+        assert_eq!(
+            new_ready_repairing_chunkset().repair_into(&mut out),
+            Err(DecdsError::RepairOutputBufferTooSmall(0, ChunkSet::BYTE_LENGTH - 1, ChunkSet::BYTE_LENGTH))
+        );
+
+        let mut out = vec![0xffu8; ChunkSet::BYTE_LENGTH + 1];
+        assert_eq!(new_ready_repairing_chunkset().repair_into(&mut out), Ok(ChunkSet::BYTE_LENGTH));
+        assert_eq!(&out[..ChunkSet::BYTE_LENGTH], data.as_slice());
+        assert_eq!(out[ChunkSet::BYTE_LENGTH], 0xff);
+    }
+
     #[test]
     fn test_repairing_chunkset_add_chunk_after_ready_to_repair() {
         let mut rng = rand::rng();
@@ -478,4 +1013,19 @@ mod tests {
         let repaired_chunkset = repairing_chunkset.repair().expect("Must be able to repair chunkset");
         assert_eq!(repaired_chunkset, data);
     }
+
+    #[test]
+    fn test_repairing_chunkset_add_non_innovative_chunk() {
+        let mut rng = rand::rng();
+
+        let data = (0..ChunkSet::BYTE_LENGTH).map(|_| rng.random()).collect::<Vec<u8>>();
+        let chunkset = ChunkSet::new(0, data).expect("Must be able to build erasure-coded ChunkSet");
+        let mut repairing_chunkset = RepairingChunkSet::new(0, chunkset.get_root_commitment());
+
+        let chunk = chunkset.get_chunk(3).expect("Must be able to lookup chunk by id");
+        repairing_chunkset.add_chunk(chunk).expect("Must be able to add valid chunk");
+
+        assert_eq!(repairing_chunkset.add_chunk(chunk), Err(DecdsError::NonInnovativeChunk(0, 3)));
+        assert_eq!(repairing_chunkset.get_num_remaining_chunks(), ChunkSet::NUM_ORIGINAL_CHUNKS - 1);
+    }
 }
@@ -0,0 +1,380 @@
+#[cfg(feature = "coding")]
+use crate::{chunkset::ChunkSet, config::BlobConfig, errors::DecdsError};
+use serde::{Deserialize, Serialize};
+
+/// Erasure code, chunksets of a blob are coded with. It's recorded in the `BlobConfig`, hence in the `BlobHeader`, so that
+/// `RepairingBlob` checks chunks against the right code.
+///
+/// All codecs are linear codes over GF(2^8), with reduction polynomial `0x11d`. Every chunk carries the coding vector it
+/// was coded with, so chunks of any codec are decoded the same way, by Gaussian elimination. Codecs differ in how coding
+/// vectors are chosen, see `ErasureCodec`.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub enum Codec {
+    /// Random linear network coding, where coding vectors are derived from a seed, sampled per blob, unless one is set
+    /// with `DecdsBuilder::seed`. Any `num_original_chunks` shares repair a chunkset with overwhelming probability.
+    #[default]
+    Rlnc,
+    /// Systematic Reed-Solomon code, where the first `num_original_chunks` shares are the chunkset data, as is, and any
+    /// `num_original_chunks` shares are guaranteed to repair a chunkset. Coding vectors don't depend on any seed. Blobs
+    /// coded with it can only be erasure-coded and repaired with the `reed-solomon` feature, but their headers and chunks
+    /// are verified without it.
+    ReedSolomon,
+}
+
+/// Chooses coding vectors of erasure-coded chunks of a chunkset, i.e. `num_original_chunks` coefficients over GF(2^8), pieces
+/// of the chunkset are linearly combined with. `ChunkSet` erasure-codes chunks with the coding vectors it's handed, and
+/// `RepairingChunkSet` rejects chunks, whose coding vector the codec of their blob wouldn't have chosen.
+#[cfg(feature = "coding")]
+pub trait ErasureCodec: Send + Sync {
+    /// Returns the coding vector of chunk `local_chunk_id` of chunkset `chunkset_id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset the chunk belongs to.
+    /// * `local_chunk_id` - The local ID (`>= 0 && < BlobConfig::get_num_shares()`) of the chunk within its chunkset.
+    ///
+    /// # Returns
+    ///
+    /// Returns `num_original_chunks` bytes long coding vector.
+    fn get_coding_vector(&self, chunkset_id: usize, local_chunk_id: usize) -> Vec<u8>;
+
+    /// Checks whether chunk `local_chunk_id` of chunkset `chunkset_id` may carry `coding_vector`. Defaults to comparing it
+    /// with `Self::get_coding_vector`.
+    fn is_valid_coding_vector(&self, chunkset_id: usize, local_chunk_id: usize, coding_vector: &[u8]) -> bool {
+        self.get_coding_vector(chunkset_id, local_chunk_id) == coding_vector
+    }
+}
+
+#[cfg(feature = "coding")]
+impl Codec {
+    /// Checks that the codec is compiled in, so that chunksets can be erasure-coded and repaired with it.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(())` if the codec is compiled in.
+    /// - `Err(DecdsError::CodecNotCompiledIn)` if the feature enabling the codec is off.
+    pub(crate) fn check_compiled_in(&self) -> Result<(), DecdsError> {
+        match self {
+            Codec::Rlnc => Ok(()),
+            #[cfg(feature = "reed-solomon")]
+            Codec::ReedSolomon => Ok(()),
+            #[cfg(not(feature = "reed-solomon"))]
+            Codec::ReedSolomon => Err(DecdsError::CodecNotCompiledIn(*self)),
+        }
+    }
+
+    /// Returns the codec, chunksets of a blob of geometry `config` are erasure-coded with. RLNC coding vectors are derived
+    /// from `seed`, other codecs ignore it.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Box<dyn ErasureCodec>)` containing the codec if successful.
+    /// - `Err(DecdsError::CodecNotCompiledIn)` if the feature enabling the codec is off.
+    pub(crate) fn new_encoding_codec(config: &BlobConfig, seed: [u8; 32]) -> Result<Box<dyn ErasureCodec>, DecdsError> {
+        match config.get_codec() {
+            Codec::Rlnc => Ok(Box::new(RlncCodec::new(config, seed))),
+            #[cfg(feature = "reed-solomon")]
+            Codec::ReedSolomon => Ok(Box::new(ReedSolomonCodec::from_valid_config(config))),
+            #[cfg(not(feature = "reed-solomon"))]
+            codec @ Codec::ReedSolomon => Err(DecdsError::CodecNotCompiledIn(codec)),
+        }
+    }
+
+    /// Returns the codec, coding vectors of chunks of a blob of geometry `config` are checked with, while repairing it, or
+    /// `None` if any coding vector is acceptable. That's the case for RLNC, as the seed isn't recorded in the `BlobHeader`.
+    /// It's `None` for codecs, which aren't compiled in, too, so chunks have to be rejected with `Self::check_compiled_in`.
+    pub(crate) fn new_decoding_codec(config: &BlobConfig) -> Option<Box<dyn ErasureCodec>> {
+        match config.get_codec() {
+            Codec::Rlnc => None,
+            #[cfg(feature = "reed-solomon")]
+            Codec::ReedSolomon => Some(Box::new(ReedSolomonCodec::from_valid_config(config))),
+            #[cfg(not(feature = "reed-solomon"))]
+            Codec::ReedSolomon => None,
+        }
+    }
+}
+
+/// Random linear network coding, deriving coding vectors from a seed, with `ChunkSet::derive_coding_vector`.
+///
+/// ```rust
+/// use decds_lib::{BlobConfig, ErasureCodec, RlncCodec};
+///
+/// let codec = RlncCodec::new(&BlobConfig::default(), [7u8; 32]);
+/// assert_eq!(codec.get_coding_vector(0, 3).len(), 10);
+/// assert_eq!(codec.get_coding_vector(0, 3), RlncCodec::new(&BlobConfig::default(), [7u8; 32]).get_coding_vector(0, 3));
+/// ```
+#[cfg(feature = "coding")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RlncCodec {
+    seed: [u8; 32],
+    num_original_chunks: usize,
+}
+
+#[cfg(feature = "coding")]
+impl RlncCodec {
+    /// Creates an RLNC codec for chunksets of geometry `config`, deriving coding vectors from `seed`.
+    pub fn new(config: &BlobConfig, seed: [u8; 32]) -> Self {
+        RlncCodec {
+            seed,
+            num_original_chunks: config.get_num_original_chunks(),
+        }
+    }
+}
+
+#[cfg(feature = "coding")]
+impl ErasureCodec for RlncCodec {
+    fn get_coding_vector(&self, chunkset_id: usize, local_chunk_id: usize) -> Vec<u8> {
+        ChunkSet::derive_coding_vector(&self.seed, chunkset_id, local_chunk_id, self.num_original_chunks)
+    }
+}
+
+/// Exponentials of the generator `2` of GF(2^8)'s multiplicative group, with reduction polynomial `0x11d`, repeated twice,
+/// so that logarithms can be added without reducing them modulo 255.
+#[cfg(feature = "coding")]
+const GF_EXP: [u8; 512] = {
+    let mut exp = [0u8; 512];
+    let mut x = 1u16;
+
+    let mut i = 0;
+    while i < exp.len() {
+        exp[i] = x as u8;
+        x <<= 1;
+        if x & 0x100 != 0 {
+            x ^= 0x11d;
+        }
+        i += 1;
+    }
+
+    exp
+};
+
+/// Discrete logarithms of non-zero elements of GF(2^8), to the base `2`.
+#[cfg(feature = "coding")]
+const GF_LOG: [u8; 256] = {
+    let mut log = [0u8; 256];
+
+    let mut i = 0;
+    while i < 255 {
+        log[GF_EXP[i] as usize] = i as u8;
+        i += 1;
+    }
+
+    log
+};
+
+/// Multiplication in GF(2^8), the field RLNC and Reed-Solomon coding vectors are over.
+#[cfg(feature = "coding")]
+pub(crate) fn gf_mul(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        0
+    } else {
+        GF_EXP[GF_LOG[a as usize] as usize + GF_LOG[b as usize] as usize]
+    }
+}
+
+/// Multiplicative inverse of a non-zero element of GF(2^8).
+#[cfg(feature = "reed-solomon")]
+fn gf_inv(a: u8) -> u8 {
+    GF_EXP[255 - GF_LOG[a as usize] as usize]
+}
+
+/// Systematic Reed-Solomon code, whose generator matrix is the `num_shares x num_original_chunks` Vandermonde matrix over
+/// points `0, 1, .., num_shares - 1`, multiplied by the inverse of its top square submatrix. Its first `num_original_chunks`
+/// rows form the identity matrix, so those shares carry chunkset data as is, and any `num_original_chunks` of its rows are
+/// linearly independent, as they are for the Vandermonde matrix, so any that many shares repair a chunkset. Row `i` is the
+/// coding vector of share `i`, for every chunkset.
+///
+/// ```rust
+/// use decds_lib::{BlobConfig, ErasureCodec, ReedSolomonCodec};
+///
+/// let codec = ReedSolomonCodec::new(&BlobConfig::default()).expect("Failed to create codec");
+/// assert_eq!(codec.get_coding_vector(0, 3), vec![0, 0, 0, 1, 0, 0, 0, 0, 0, 0]);
+/// assert_eq!(codec.get_coding_vector(0, 12), codec.get_coding_vector(5, 12));
+/// ```
+#[cfg(feature = "reed-solomon")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReedSolomonCodec {
+    generator_matrix: Vec<Vec<u8>>,
+}
+
+#[cfg(feature = "reed-solomon")]
+impl ReedSolomonCodec {
+    /// Creates a Reed-Solomon codec for chunksets of geometry `config`.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Erasure-coding geometry, i.e. number of pieces each chunkset is split into and number of shares.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Self)` containing the codec if successful.
+    /// - `Err(DecdsError::InvalidBlobConfig)` if `config` isn't valid.
+    pub fn new(config: &BlobConfig) -> Result<Self, DecdsError> {
+        config.validate()?;
+        Ok(Self::from_valid_config(config))
+    }
+
+    /// Same as `Self::new`, but assumes `config` is valid.
+    pub(crate) fn from_valid_config(config: &BlobConfig) -> Self {
+        let num_original_chunks = config.get_num_original_chunks();
+
+        let vandermonde_matrix = (0..config.get_num_shares())
+            .map(|point| {
+                (0..num_original_chunks)
+                    .scan(1u8, |power, _| {
+                        let coefficient = *power;
+                        *power = gf_mul(*power, point as u8);
+                        Some(coefficient)
+                    })
+                    .collect::<Vec<u8>>()
+            })
+            .collect::<Vec<Vec<u8>>>();
+
+        // Points are distinct, so the top square submatrix is invertible.
+        let top_inverse = Self::invert(vandermonde_matrix[..num_original_chunks].to_vec());
+        let generator_matrix = vandermonde_matrix
+            .iter()
+            .map(|row| {
+                (0..num_original_chunks)
+                    .map(|col_idx| row.iter().zip(&top_inverse).fold(0, |acc, (&lhs, rhs)| acc ^ gf_mul(lhs, rhs[col_idx])))
+                    .collect::<Vec<u8>>()
+            })
+            .collect::<Vec<Vec<u8>>>();
+
+        ReedSolomonCodec { generator_matrix }
+    }
+
+    /// Inverts an invertible square matrix over GF(2^8), by Gauss-Jordan elimination.
+    fn invert(mut matrix: Vec<Vec<u8>>) -> Vec<Vec<u8>> {
+        let n = matrix.len();
+        let mut inverse = (0..n)
+            .map(|row_idx| (0..n).map(|col_idx| (row_idx == col_idx) as u8).collect::<Vec<u8>>())
+            .collect::<Vec<Vec<u8>>>();
+
+        for col_idx in 0..n {
+            let pivot_idx = unsafe { (col_idx..n).find(|&row_idx| matrix[row_idx][col_idx] != 0).unwrap_unchecked() };
+            matrix.swap(col_idx, pivot_idx);
+            inverse.swap(col_idx, pivot_idx);
+
+            let pivot_inverse = gf_inv(matrix[col_idx][col_idx]);
+            matrix[col_idx]
+                .iter_mut()
+                .for_each(|coefficient| *coefficient = gf_mul(*coefficient, pivot_inverse));
+            inverse[col_idx]
+                .iter_mut()
+                .for_each(|coefficient| *coefficient = gf_mul(*coefficient, pivot_inverse));
+
+            for row_idx in (0..n).filter(|&row_idx| row_idx != col_idx) {
+                let factor = matrix[row_idx][col_idx];
+                if factor == 0 {
+                    continue;
+                }
+
+                for k in 0..n {
+                    matrix[row_idx][k] ^= gf_mul(factor, matrix[col_idx][k]);
+                    inverse[row_idx][k] ^= gf_mul(factor, inverse[col_idx][k]);
+                }
+            }
+        }
+
+        inverse
+    }
+}
+
+#[cfg(feature = "reed-solomon")]
+impl ErasureCodec for ReedSolomonCodec {
+    fn get_coding_vector(&self, _chunkset_id: usize, local_chunk_id: usize) -> Vec<u8> {
+        self.generator_matrix[local_chunk_id].clone()
+    }
+
+    fn is_valid_coding_vector(&self, _chunkset_id: usize, local_chunk_id: usize, coding_vector: &[u8]) -> bool {
+        self.generator_matrix.get(local_chunk_id).is_some_and(|row| row == coding_vector)
+    }
+}
+
+#[cfg(all(test, feature = "reed-solomon"))]
+mod tests {
+    use crate::{BlobConfig, DECDS_NUM_ERASURE_CODED_SHARES, DecdsBuilder, RepairingBlob, RepairingChunkSet, codec::Codec, errors::DecdsError};
+    use rand::{Rng, seq::SliceRandom};
+
+    #[test]
+    fn test_reed_solomon_codec_repairs_from_any_shares() {
+        let mut rng = rand::rng();
+
+        let config = BlobConfig::new().codec(Codec::ReedSolomon).chunkset_byte_length(1 << 12);
+        let blob_data = (0..(3 * config.get_chunkset_byte_length() + 7)).map(|_| rng.random()).collect::<Vec<u8>>();
+
+        let blob = DecdsBuilder::new().config(config).build(blob_data.clone()).expect("Must be able to build blob");
+        let header = blob.get_blob_header().clone();
+        assert_eq!(header.get_config().get_codec(), Codec::ReedSolomon);
+
+        // Systematic: first chunk of first share is the first piece of blob data, as is
+        let piece_byte_length = config.get_piece_byte_length();
+        let chunk = blob.get_chunk(0, 0).unwrap();
+        assert_eq!(
+            &chunk.get_erasure_coded_data()[config.get_num_original_chunks()..],
+            &blob_data[..piece_byte_length]
+        );
+
+        let mut share_ids = (0..DECDS_NUM_ERASURE_CODED_SHARES).collect::<Vec<usize>>();
+        share_ids.shuffle(&mut rng);
+
+        // Any `num_original_chunks` shares repair the blob
+        let mut repairer = RepairingBlob::new(header.clone());
+        share_ids[..config.get_num_original_chunks()].iter().for_each(|&share_id| {
+            blob.get_share(share_id).unwrap().iter().for_each(|chunk| repairer.add_chunk(chunk).unwrap());
+        });
+
+        let repaired = (0..header.get_num_chunksets())
+            .flat_map(|chunkset_id| repairer.get_repaired_chunkset(chunkset_id).unwrap())
+            .collect::<Vec<u8>>();
+        assert_eq!(repaired, blob_data);
+
+        // Chunks of RLNC coded blobs, sharing chunkset commitments or not, carry coding vectors the codec wouldn't choose
+        let rlnc_blob = DecdsBuilder::new()
+            .config(config.codec(Codec::Rlnc))
+            .build(blob_data)
+            .expect("Must be able to build blob");
+        let rlnc_chunk = rlnc_blob.get_chunk(0, 12).unwrap();
+
+        let mut repairing_chunkset = RepairingChunkSet::new_with_config(0, header.get_chunkset_commitment(0).unwrap(), &config);
+        assert_eq!(
+            repairing_chunkset.add_chunk_unvalidated(rlnc_chunk),
+            Err(DecdsError::UnexpectedCodingVector(0, 12))
+        );
+        assert_eq!(repairing_chunkset.add_chunk_unvalidated(blob.get_chunk(0, 12).unwrap()), Ok(()));
+    }
+}
+
+#[cfg(all(test, feature = "coding", not(feature = "reed-solomon")))]
+mod tests_without_reed_solomon {
+    use crate::{BlobConfig, DecdsBuilder, RepairingChunkSet, codec::Codec, errors::DecdsError};
+
+    #[test]
+    fn test_reed_solomon_codec_not_compiled_in() {
+        let config = BlobConfig::new().chunkset_byte_length(1 << 12);
+        let reed_solomon_config = config.codec(Codec::ReedSolomon);
+        let blob_data = vec![0xcd; 3 * config.get_chunkset_byte_length() + 7];
+
+        assert_eq!(
+            DecdsBuilder::new().config(reed_solomon_config).build(blob_data.clone()).err(),
+            Some(DecdsError::CodecNotCompiledIn(Codec::ReedSolomon))
+        );
+        assert_eq!(
+            DecdsBuilder::new().config(reed_solomon_config).encode_chunkset(0, vec![0xcd; 1024]).err(),
+            Some(DecdsError::CodecNotCompiledIn(Codec::ReedSolomon))
+        );
+
+        let blob = DecdsBuilder::new().config(config).build(blob_data).expect("Must be able to build blob");
+        let header = blob.get_blob_header();
+
+        let mut repairing_chunkset = RepairingChunkSet::new_with_config(0, header.get_chunkset_commitment(0).unwrap(), &reed_solomon_config);
+        assert_eq!(
+            repairing_chunkset.add_chunk(blob.get_chunk(0, 0).unwrap()),
+            Err(DecdsError::CodecNotCompiledIn(Codec::ReedSolomon))
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use crate::{chunk::ProofCarryingChunk, chunkset::ChunkSet, errors::DecdsError, merkle_tree::MerkleTree};
+
+/// Recomputes the Merkle root commitment of a chunkset from digests of all of its erasure-coded chunks.
+///
+/// This lets one independently check a chunkset commitment, listed in a `BlobHeader`, given externally
+/// supplied chunk digests (see `ProofCarryingChunk::get_chunk_digest` for the digest format).
+///
+/// # Arguments
+///
+/// * `chunk_digests` - Digests of all chunks of the chunkset, i.e. `BlobConfig::get_num_shares` many, ordered by their local
+///   chunk ID. Unused share slots are filled with zero hashes, just like when the chunkset was erasure-coded.
+///
+/// # Returns
+///
+/// Returns a `Result` which is:
+/// - `Ok(blake3::Hash)` containing the chunkset root commitment if successful.
+/// - `Err(DecdsError::InvalidNumberOfChunkDigests)` if `chunk_digests` is empty or has more than `DECDS_NUM_ERASURE_CODED_SHARES` digests.
+pub fn compute_chunkset_commitment(chunk_digests: &[blake3::Hash]) -> Result<blake3::Hash, DecdsError> {
+    if chunk_digests.is_empty() || chunk_digests.len() > ChunkSet::NUM_ERASURE_CODED_CHUNKS {
+        return Err(DecdsError::InvalidNumberOfChunkDigests(chunk_digests.len()));
+    }
+
+    let mut merkle_leaves = chunk_digests.to_vec();
+    merkle_leaves.resize(ChunkSet::NUM_ERASURE_CODED_CHUNKS, blake3::Hash::from_bytes([0u8; blake3::OUT_LEN]));
+
+    MerkleTree::new(merkle_leaves).map(|merkle_tree| merkle_tree.get_root_commitment())
+}
+
+/// Recomputes the Merkle root commitment of a blob from root commitments of all of its chunksets.
+///
+/// # Arguments
+///
+/// * `chunkset_commitments` - Root commitments of all chunksets of the blob, ordered by their chunkset ID.
+///
+/// # Returns
+///
+/// Returns a `Result` which is:
+/// - `Ok(blake3::Hash)` containing the blob root commitment if successful.
+/// - `Err(DecdsError::NoLeafNodesToBuildMerkleTreeOn)` if `chunkset_commitments` is empty.
+pub fn compute_blob_root_commitment(chunkset_commitments: &[blake3::Hash]) -> Result<blake3::Hash, DecdsError> {
+    MerkleTree::new(chunkset_commitments.to_vec()).map(|merkle_tree| merkle_tree.get_root_commitment())
+}
+
+/// Extends chunkset-level Merkle proofs of independently encoded chunks, e.g. by `DecdsBuilder::encode_chunkset` running
+/// on different machines, to blob-level proofs, so that they can be stitched under one blob root commitment by a
+/// coordinator, which only needs to collect chunkset commitments. Either all chunks get their proof extended, or none.
+///
+/// # Arguments
+///
+/// * `chunks` - Chunks carrying proofs of inclusion in their chunkset only, in any order.
+/// * `blob_tree` - Merkle tree over root commitments of all chunksets of the blob, ordered by their chunkset ID.
+///
+/// # Returns
+///
+/// Returns a `Result` which is:
+/// - `Ok(())` if successful, after which chunks validate against `blob_tree.get_root_commitment()`.
+/// - `Err(DecdsError::InvalidChunksetId)` if a chunk belongs to a chunkset, which isn't a leaf of `blob_tree`.
+/// - `Err(DecdsError::InvalidProofInChunk)` if a chunk doesn't carry a chunkset-level proof only, or it fails validation
+///   against its chunkset commitment in `blob_tree`.
+pub fn attach_blob_proofs(chunks: &mut [ProofCarryingChunk], blob_tree: &MerkleTree) -> Result<(), DecdsError> {
+    let chunkset_commitments = blob_tree.get_leaf_nodes();
+
+    chunks.iter().try_for_each(|chunk| {
+        let chunkset_commitment = chunkset_commitments
+            .get(chunk.get_chunkset_id())
+            .ok_or(DecdsError::InvalidChunksetId(chunk.get_chunkset_id(), chunkset_commitments.len()))?;
+
+        if chunk.get_proof().len() != ChunkSet::PROOF_SIZE || !chunk.validate_inclusion_in_chunkset(*chunkset_commitment) {
+            return Err(DecdsError::InvalidProofInChunk(chunk.get_chunkset_id(), chunk.get_local_chunk_id()));
+        }
+
+        Ok(())
+    })?;
+
+    let blob_proofs = blob_tree.generate_all_proofs();
+    chunks
+        .iter_mut()
+        .for_each(|chunk| chunk.append_proof_to_blob_root(&blob_proofs[chunk.get_chunkset_id()]));
+
+    Ok(())
+}
+
+/// Same as `attach_blob_proofs`, but for a worker, which only got the blob-level proof of its chunkset, e.g. from
+/// `BlobCoordinator::get_blob_proofs`, instead of the whole blob tree. Either all chunks get their proof extended, or none.
+///
+/// # Arguments
+///
+/// * `chunks` - Chunks of a single chunkset, carrying proofs of inclusion in their chunkset only.
+/// * `blob_proof` - Proof of inclusion of the chunkset commitment in the blob root commitment.
+/// * `blob_root_commitment` - Root commitment of the blob, as found in its `BlobHeader`.
+///
+/// # Returns
+///
+/// Returns a `Result` which is:
+/// - `Ok(())` if successful, after which chunks validate against `blob_root_commitment`.
+/// - `Err(DecdsError::InvalidProofInChunk)` if a chunk doesn't carry a chunkset-level proof only, or its proof, extended
+///   by `blob_proof`, fails validation against `blob_root_commitment`.
+pub fn attach_blob_proof(chunks: &mut [ProofCarryingChunk], blob_proof: &[blake3::Hash], blob_root_commitment: blake3::Hash) -> Result<(), DecdsError> {
+    chunks.iter().try_for_each(|chunk| {
+        let is_valid = chunk.get_proof().len() == ChunkSet::PROOF_SIZE
+            && MerkleTree::verify_proof(
+                ChunkSet::get_blob_leaf_index(chunk.get_chunkset_id(), chunk.get_local_chunk_id()),
+                chunk.get_chunk_digest(),
+                &[chunk.get_proof(), blob_proof].concat(),
+                blob_root_commitment,
+            );
+
+        if !is_valid {
+            return Err(DecdsError::InvalidProofInChunk(chunk.get_chunkset_id(), chunk.get_local_chunk_id()));
+        }
+
+        Ok(())
+    })?;
+
+    chunks.iter_mut().for_each(|chunk| chunk.append_proof_to_blob_root(blob_proof));
+
+    Ok(())
+}
+
+#[cfg(all(test, feature = "coding"))]
+mod tests {
+    use crate::{
+        Blob, DecdsBuilder, MerkleTree, ProofCarryingChunk,
+        chunkset::ChunkSet,
+        commitment::{attach_blob_proofs, compute_blob_root_commitment, compute_chunkset_commitment},
+        consts,
+        errors::DecdsError,
+    };
+    use rand::Rng;
+
+    #[test]
+    fn test_recomputing_commitments_matches_blob_header() {
+        let mut rng = rand::rng();
+
+        let blob_data: Vec<u8> = (0..(ChunkSet::BYTE_LENGTH * 2 + ChunkSet::BYTE_LENGTH / 2)).map(|_| rng.random()).collect();
+        let blob = Blob::new(blob_data).unwrap();
+        let header = blob.get_blob_header();
+
+        let shares = (0..consts::DECDS_NUM_ERASURE_CODED_SHARES)
+            .map(|share_id| blob.get_share(share_id).unwrap())
+            .collect::<Vec<Vec<ProofCarryingChunk>>>();
+
+        let chunkset_commitments = (0..header.get_num_chunksets())
+            .map(|chunkset_id| {
+                let chunk_digests = shares.iter().map(|share| share[chunkset_id].get_chunk_digest()).collect::<Vec<blake3::Hash>>();
+                compute_chunkset_commitment(&chunk_digests).expect("Must be able to recompute chunkset commitment")
+            })
+            .collect::<Vec<blake3::Hash>>();
+
+        (0..header.get_num_chunksets()).for_each(|chunkset_id| {
+            assert_eq!(chunkset_commitments[chunkset_id], header.get_chunkset_commitment(chunkset_id).unwrap());
+        });
+
+        assert_eq!(compute_blob_root_commitment(&chunkset_commitments).unwrap(), header.get_root_commitment());
+        assert!(header.verify_root_commitment());
+    }
+
+    #[test]
+    fn test_recomputing_commitments_with_invalid_input() {
+        let chunk_digests = vec![blake3::hash(b"chunk"); ChunkSet::NUM_ERASURE_CODED_CHUNKS + 1];
+
+        assert_eq!(
+            compute_chunkset_commitment(&chunk_digests),
+            Err(DecdsError::InvalidNumberOfChunkDigests(ChunkSet::NUM_ERASURE_CODED_CHUNKS + 1))
+        );
+        assert_eq!(compute_chunkset_commitment(&[]), Err(DecdsError::InvalidNumberOfChunkDigests(0)));
+        assert_eq!(compute_blob_root_commitment(&[]), Err(DecdsError::NoLeafNodesToBuildMerkleTreeOn));
+    }
+
+    #[test]
+    fn test_attach_blob_proofs_to_independently_encoded_chunks() {
+        let mut rng = rand::rng();
+
+        let blob_data: Vec<u8> = (0..(ChunkSet::BYTE_LENGTH + 100)).map(|_| rng.random()).collect();
+        let builder = DecdsBuilder::new().seed([7u8; 32]);
+        let blob = builder.build(blob_data.clone()).unwrap();
+
+        let (commitments, mut chunks): (Vec<blake3::Hash>, Vec<Vec<ProofCarryingChunk>>) = blob_data
+            .chunks(ChunkSet::BYTE_LENGTH)
+            .enumerate()
+            .map(|(chunkset_id, chunkset_data)| builder.encode_chunkset(chunkset_id, chunkset_data.to_vec()).unwrap())
+            .unzip();
+        let blob_tree = MerkleTree::new(commitments).unwrap();
+
+        // Chunks of a foreign chunkset, or already carrying a blob-level proof, are rejected, leaving all chunks untouched
+        let mut misplaced = chunks.concat();
+        let foreign_chunk = builder.encode_chunkset(2, vec![0xde; 100]).unwrap().1.remove(3);
+        misplaced.push(foreign_chunk);
+        assert_eq!(attach_blob_proofs(&mut misplaced, &blob_tree), Err(DecdsError::InvalidChunksetId(2, 2)));
+
+        let mut with_blob_proof = blob.get_share(5).unwrap();
+        assert_eq!(attach_blob_proofs(&mut with_blob_proof, &blob_tree), Err(DecdsError::InvalidProofInChunk(0, 5)));
+
+        let mut swapped = vec![chunks[1][0].clone(), chunks[0][1].clone()];
+        swapped[0] = ProofCarryingChunk::from_parts(0, 0, swapped[0].get_erasure_coded_data().to_vec(), swapped[0].get_proof().to_vec()).unwrap();
+        assert_eq!(attach_blob_proofs(&mut swapped, &blob_tree), Err(DecdsError::InvalidProofInChunk(0, 0)));
+        assert_eq!(swapped[1].get_proof().len(), ChunkSet::PROOF_SIZE);
+
+        chunks.iter_mut().for_each(|chunks| attach_blob_proofs(chunks, &blob_tree).unwrap());
+        assert_eq!(blob_tree.get_root_commitment(), blob.get_blob_header().get_root_commitment());
+
+        (0..consts::DECDS_NUM_ERASURE_CODED_SHARES).for_each(|share_id| {
+            let share = chunks.iter().map(|chunks| chunks[share_id].clone()).collect::<Vec<ProofCarryingChunk>>();
+            assert_eq!(share, blob.get_share(share_id).unwrap());
+        });
+    }
+}
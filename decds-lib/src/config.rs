@@ -0,0 +1,164 @@
+use crate::{blob::deserialize_addressable_usize, chunkset::ChunkSet, codec::Codec, consts::DECDS_NUM_ERASURE_CODED_SHARES, errors::DecdsError};
+use serde::{Deserialize, Serialize};
+
+/// Erasure-coding geometry of a blob, i.e. how many bytes each chunkset holds, how many erasure-coded shares each chunkset
+/// is encoded into, how many of those are enough for repairing it, and which `Codec` encodes them. It's recorded in the `BlobHeader`, so that
+/// `RepairingBlob` reconstructs chunksets with the geometry they were encoded with.
+///
+/// Every chunkset keeps `DECDS_NUM_ERASURE_CODED_SHARES` share slots, no matter how many shares it's encoded into, so chunk
+/// IDs and Merkle proofs are laid out the same way for all geometries, and chunks can be verified without knowing it.
+/// Defaults to 10MB chunksets, RLNC encoded into 16 shares, any 10 of which repair a chunkset.
+///
+/// ```rust
+/// use decds_lib::BlobConfig;
+///
+/// let config = BlobConfig::new().num_original_chunks(4).num_shares(6).chunkset_byte_length(1 << 20);
+/// assert_eq!(config.validate(), Ok(()));
+/// assert_eq!(config.get_piece_byte_length(), ((1 << 20) + 1usize).div_ceil(4));
+/// ```
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct BlobConfig {
+    #[serde(deserialize_with = "deserialize_addressable_usize")]
+    num_original_chunks: usize,
+    #[serde(deserialize_with = "deserialize_addressable_usize")]
+    num_shares: usize,
+    #[serde(deserialize_with = "deserialize_addressable_usize")]
+    chunkset_byte_length: usize,
+    codec: Codec,
+}
+
+impl Default for BlobConfig {
+    fn default() -> Self {
+        BlobConfig {
+            num_original_chunks: ChunkSet::NUM_ORIGINAL_CHUNKS,
+            num_shares: DECDS_NUM_ERASURE_CODED_SHARES,
+            chunkset_byte_length: ChunkSet::BYTE_LENGTH,
+            codec: Codec::default(),
+        }
+    }
+}
+
+impl BlobConfig {
+    /// Creates the default configuration, i.e. 10MB chunksets, each encoded into 16 shares, any 10 of which repair it.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of pieces each chunkset is split into, before erasure-coding, i.e. how many shares are enough for
+    /// repairing a chunkset.
+    pub fn num_original_chunks(mut self, num_original_chunks: usize) -> Self {
+        self.num_original_chunks = num_original_chunks;
+        self
+    }
+
+    /// Sets the number of erasure-coded shares each chunkset is encoded into, at most `DECDS_NUM_ERASURE_CODED_SHARES`.
+    pub fn num_shares(mut self, num_shares: usize) -> Self {
+        self.num_shares = num_shares;
+        self
+    }
+
+    /// Sets the byte length of chunksets, the blob is split into. The last one is zero-padded.
+    pub fn chunkset_byte_length(mut self, chunkset_byte_length: usize) -> Self {
+        self.chunkset_byte_length = chunkset_byte_length;
+        self
+    }
+
+    /// Sets the erasure code chunksets are encoded with, RLNC by default. See `Codec`.
+    pub fn codec(mut self, codec: Codec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// Returns the number of pieces each chunkset is split into, before erasure-coding.
+    pub fn get_num_original_chunks(&self) -> usize {
+        self.num_original_chunks
+    }
+
+    /// Returns the number of erasure-coded shares of each chunkset.
+    pub fn get_num_shares(&self) -> usize {
+        self.num_shares
+    }
+
+    /// Returns the byte length of chunksets.
+    pub fn get_chunkset_byte_length(&self) -> usize {
+        self.chunkset_byte_length
+    }
+
+    /// Returns the erasure code chunksets are encoded with.
+    pub fn get_codec(&self) -> Codec {
+        self.codec
+    }
+
+    /// Returns the byte length of each erasure-coded piece, i.e. of chunkset data, followed by a single byte end-of-data
+    /// marker, zero-padded to a multiple of `num_original_chunks` and split into as many pieces.
+    pub fn get_piece_byte_length(&self) -> usize {
+        self.chunkset_byte_length.saturating_add(1).div_ceil(self.num_original_chunks.max(1))
+    }
+
+    /// Returns the byte length of erasure-coded data of each chunk, i.e. its coding vector, followed by its piece.
+    pub fn get_erasure_coded_data_byte_length(&self) -> usize {
+        self.num_original_chunks.saturating_add(self.get_piece_byte_length())
+    }
+
+    /// Checks that the configuration describes a usable erasure code.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(())` if the configuration is valid.
+    /// - `Err(DecdsError::InvalidBlobConfig)` if `num_original_chunks` is zero or more than `num_shares`, if `num_shares`
+    ///   is more than `DECDS_NUM_ERASURE_CODED_SHARES`, or if `chunkset_byte_length` is zero or can't be padded.
+    pub fn validate(&self) -> Result<(), DecdsError> {
+        if self.num_original_chunks == 0 || self.num_original_chunks > self.num_shares {
+            return Err(DecdsError::InvalidBlobConfig(format!(
+                "{} original chunks can't be erasure-coded into {} shares",
+                self.num_original_chunks, self.num_shares
+            )));
+        }
+        if self.num_shares > DECDS_NUM_ERASURE_CODED_SHARES {
+            return Err(DecdsError::InvalidBlobConfig(format!(
+                "{} shares exceed maximum of {}",
+                self.num_shares, DECDS_NUM_ERASURE_CODED_SHARES
+            )));
+        }
+        if self.chunkset_byte_length == 0 || self.chunkset_byte_length == usize::MAX {
+            return Err(DecdsError::InvalidBlobConfig(format!(
+                "chunksets of {}B can't be erasure-coded",
+                self.chunkset_byte_length
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BlobConfig, DECDS_NUM_ERASURE_CODED_SHARES, chunkset::ChunkSet, errors::DecdsError};
+
+    #[test]
+    fn test_blob_config() {
+        let config = BlobConfig::new();
+        assert_eq!(config.validate(), Ok(()));
+        assert_eq!(config.get_piece_byte_length(), ChunkSet::PADDED_CHUNK_BYTE_LEN);
+        assert_eq!(
+            config.get_erasure_coded_data_byte_length(),
+            ChunkSet::NUM_ORIGINAL_CHUNKS + ChunkSet::PADDED_CHUNK_BYTE_LEN
+        );
+
+        let config = BlobConfig::new().num_original_chunks(1).num_shares(1).chunkset_byte_length(1);
+        assert_eq!(config.validate(), Ok(()));
+        assert_eq!(config.get_piece_byte_length(), 2);
+
+        [
+            BlobConfig::new().num_original_chunks(0),
+            BlobConfig::new().num_original_chunks(DECDS_NUM_ERASURE_CODED_SHARES + 1),
+            BlobConfig::new().num_shares(DECDS_NUM_ERASURE_CODED_SHARES + 1),
+            BlobConfig::new().num_shares(ChunkSet::NUM_ORIGINAL_CHUNKS - 1),
+            BlobConfig::new().chunkset_byte_length(0),
+            BlobConfig::new().chunkset_byte_length(usize::MAX),
+        ]
+        .iter()
+        .for_each(|config| assert!(matches!(config.validate(), Err(DecdsError::InvalidBlobConfig(_)))));
+    }
+}
@@ -1,5 +1,6 @@
 /// Fixed configuration for `bincode` serialization and deserialization.
 pub const DECDS_BINCODE_CONFIG: bincode::config::Configuration = bincode::config::standard();
 
-/// Number of erasure-coded shares for each fixed-size (=10MB) chunkset.
+/// Number of erasure-coded share slots of each chunkset, i.e. the default and the maximum number of erasure-coded shares
+/// of a chunkset, see `BlobConfig`.
 pub const DECDS_NUM_ERASURE_CODED_SHARES: usize = 16;
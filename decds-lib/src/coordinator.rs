@@ -0,0 +1,363 @@
+use crate::{blob::BlobHeader, config::BlobConfig, errors::DecdsError, flags::ContentFlags, merkle_tree::MerkleTree};
+use std::ops::Range;
+
+/// Coordinates erasure-coding of a blob by many workers, e.g. on different machines, each encoding a disjoint range of
+/// chunksets with `DecdsBuilder::encode_chunkset`. Workers submit commitments of their chunksets only. Once all of them
+/// are in, the coordinator builds the blob-level Merkle tree and the `BlobHeader`, and hands blob-level proofs back to
+/// workers, which extend proofs of their chunks with `attach_blob_proof`. Erasure-coded data never goes through the
+/// coordinator, so breaking very large blobs scales horizontally.
+///
+/// The BLAKE3 digest of the whole blob is needed upfront, e.g. computed while streaming the blob to workers.
+///
+/// ```rust
+/// # #[cfg(feature = "coding")]
+/// # {
+/// use decds_lib::{BlobConfig, BlobCoordinator, DecdsBuilder, attach_blob_proof};
+///
+/// let data = vec![0xde; 3 * 1024 + 100];
+/// let config = BlobConfig::new().chunkset_byte_length(1024);
+/// let mut coordinator = BlobCoordinator::new(data.len(), blake3::hash(&data), config).expect("Failed to create coordinator");
+///
+/// // Each worker encodes its own range of chunksets, submitting their commitments
+/// let builder = DecdsBuilder::new().config(config);
+/// let mut workers = coordinator
+///     .assign_chunksets(2)
+///     .into_iter()
+///     .map(|chunkset_ids| {
+///         let (commitments, chunks): (Vec<_>, Vec<_>) = chunkset_ids
+///             .clone()
+///             .map(|chunkset_id| {
+///                 let (from, to) = coordinator.get_byte_range_for_chunksets(chunkset_id..chunkset_id + 1).unwrap();
+///                 builder.encode_chunkset(chunkset_id, data[from..to].to_vec()).expect("Failed to encode chunkset")
+///             })
+///             .unzip();
+///
+///         coordinator.submit_commitments(chunkset_ids.start, &commitments).expect("Failed to submit commitments");
+///         (chunkset_ids, chunks)
+///     })
+///     .collect::<Vec<_>>();
+///
+/// let header = coordinator.finalize().expect("Failed to finalize blob");
+///
+/// // Blob-level proofs are handed back to workers
+/// for (chunkset_ids, chunks) in workers.iter_mut() {
+///     let blob_proofs = coordinator.get_blob_proofs(chunkset_ids.clone()).expect("Failed to get blob proofs");
+///     for (chunks, blob_proof) in chunks.iter_mut().zip(blob_proofs) {
+///         attach_blob_proof(chunks, &blob_proof, header.get_root_commitment()).expect("Failed to attach proof");
+///     }
+/// }
+///
+/// assert!(workers.iter().flat_map(|(_, chunks)| chunks.iter().flatten()).all(|chunk| header.validate_chunk(chunk)));
+/// # }
+/// ```
+pub struct BlobCoordinator {
+    byte_length: usize,
+    config: BlobConfig,
+    content_flags: ContentFlags,
+    digest: blake3::Hash,
+    chunkset_commitments: Vec<Option<blake3::Hash>>,
+    blob_tree: Option<MerkleTree>,
+}
+
+impl BlobCoordinator {
+    /// Creates a new `BlobCoordinator` for a blob, which is to be split and erasure-coded with the geometry `config`.
+    ///
+    /// # Arguments
+    ///
+    /// * `byte_length` - Byte length of the blob.
+    /// * `digest` - BLAKE3 digest of the blob.
+    /// * `config` - Erasure-coding geometry, workers encode chunksets with.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Self)` if successful.
+    /// - `Err(DecdsError::EmptyDataForBlob)` if `byte_length` is zero.
+    /// - `Err(DecdsError::InvalidBlobConfig)` if `config` isn't valid.
+    pub fn new(byte_length: usize, digest: blake3::Hash, config: BlobConfig) -> Result<Self, DecdsError> {
+        if byte_length == 0 {
+            return Err(DecdsError::EmptyDataForBlob);
+        }
+        config.validate()?;
+
+        Ok(BlobCoordinator {
+            byte_length,
+            config,
+            content_flags: ContentFlags::default(),
+            digest,
+            chunkset_commitments: vec![None; byte_length.div_ceil(config.get_chunkset_byte_length())],
+            blob_tree: None,
+        })
+    }
+
+    /// Records `content_flags` in the header of the blob, which must be the ones workers encode chunks with, see
+    /// `DecdsBuilder::content_flags`.
+    pub fn content_flags(mut self, content_flags: ContentFlags) -> Self {
+        self.content_flags = content_flags;
+        self
+    }
+
+    /// Returns the number of chunksets, the blob is split into.
+    pub fn get_num_chunksets(&self) -> usize {
+        self.chunkset_commitments.len()
+    }
+
+    /// Returns the erasure-coding geometry, workers encode chunksets with.
+    pub fn get_config(&self) -> BlobConfig {
+        self.config
+    }
+
+    /// Splits chunksets of the blob into at most `num_workers` disjoint, contiguous ranges of chunkset IDs, differing in
+    /// length by at most one, so that each worker gets a similar share of the work. There are never more ranges than
+    /// chunksets, and at least one range is returned.
+    pub fn assign_chunksets(&self, num_workers: usize) -> Vec<Range<usize>> {
+        let num_chunksets = self.get_num_chunksets();
+        let num_ranges = num_workers.clamp(1, num_chunksets);
+
+        let (min_range_len, num_longer_ranges) = (num_chunksets / num_ranges, num_chunksets % num_ranges);
+        (0..num_ranges)
+            .scan(0, |start, range_idx| {
+                let range = *start..(*start + min_range_len + (range_idx < num_longer_ranges) as usize);
+                *start = range.end;
+                Some(range)
+            })
+            .collect()
+    }
+
+    /// Returns the byte range `[start, end)` of the blob, a worker needs for encoding a range of chunksets. The last
+    /// chunkset is zero-padded by the worker, as `DecdsBuilder::encode_chunkset` does.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_ids` - Non-empty range of chunkset IDs.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok((usize, usize))` containing a tuple `[start_byte_idx, end_byte_idx)` if successful.
+    /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_ids` is empty or out of bounds.
+    /// - `Err(DecdsError::ArithmeticOverflow)` if the byte offset of the first chunkset doesn't fit in a `usize`.
+    pub fn get_byte_range_for_chunksets(&self, chunkset_ids: Range<usize>) -> Result<(usize, usize), DecdsError> {
+        self.check_chunkset_ids(&chunkset_ids)?;
+
+        let chunkset_byte_length = self.config.get_chunkset_byte_length();
+        let from = chunkset_ids
+            .start
+            .checked_mul(chunkset_byte_length)
+            .ok_or_else(|| DecdsError::ArithmeticOverflow(format!("byte offset of chunkset {}", chunkset_ids.start)))?;
+        let to = chunkset_ids.end.saturating_mul(chunkset_byte_length).min(self.byte_length);
+
+        Ok((from, to))
+    }
+
+    /// Records commitments of consecutive chunksets, as encoded by a worker. Submitting the same commitment again, e.g.
+    /// when a worker is retried, is fine. Either all commitments are recorded, or none.
+    ///
+    /// # Arguments
+    ///
+    /// * `first_chunkset_id` - The ID of the chunkset, the first commitment belongs to.
+    /// * `commitments` - Root commitments of chunksets, starting from `first_chunkset_id`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(())` if successful.
+    /// - `Err(DecdsError::InvalidChunksetId)` if `commitments` is empty, or goes past the last chunkset.
+    /// - `Err(DecdsError::ConflictingChunksetCommitment)` if another commitment was already submitted for a chunkset.
+    pub fn submit_commitments(&mut self, first_chunkset_id: usize, commitments: &[blake3::Hash]) -> Result<(), DecdsError> {
+        let chunkset_ids = first_chunkset_id..first_chunkset_id.saturating_add(commitments.len());
+        self.check_chunkset_ids(&chunkset_ids)?;
+
+        if let Some(chunkset_id) = chunkset_ids.clone().zip(commitments).find_map(|(chunkset_id, commitment)| {
+            self.chunkset_commitments[chunkset_id]
+                .is_some_and(|known| known != *commitment)
+                .then_some(chunkset_id)
+        }) {
+            return Err(DecdsError::ConflictingChunksetCommitment(chunkset_id));
+        }
+
+        self.chunkset_commitments[chunkset_ids]
+            .iter_mut()
+            .zip(commitments)
+            .for_each(|(known, &commitment)| *known = Some(commitment));
+
+        Ok(())
+    }
+
+    /// Returns IDs of chunksets, whose commitments are yet to be submitted, in ascending order.
+    pub fn get_missing_chunksets(&self) -> Vec<usize> {
+        self.chunkset_commitments
+            .iter()
+            .enumerate()
+            .filter_map(|(chunkset_id, commitment)| commitment.is_none().then_some(chunkset_id))
+            .collect()
+    }
+
+    /// Builds the blob-level Merkle tree over all chunkset commitments and assembles the `BlobHeader`, which is the same
+    /// as if the blob was erasure-coded on a single machine, with the same coding vectors. Can be called again, returning
+    /// the same header.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(BlobHeader)` containing the header of the blob if successful.
+    /// - `Err(DecdsError::MissingChunksetCommitment)` for the first chunkset, whose commitment is yet to be submitted.
+    pub fn finalize(&mut self) -> Result<BlobHeader, DecdsError> {
+        let chunkset_commitments = self
+            .chunkset_commitments
+            .iter()
+            .enumerate()
+            .map(|(chunkset_id, commitment)| commitment.ok_or(DecdsError::MissingChunksetCommitment(chunkset_id)))
+            .collect::<Result<Vec<blake3::Hash>, DecdsError>>()?;
+
+        let blob_tree = match self.blob_tree.take() {
+            Some(blob_tree) => blob_tree,
+            None => MerkleTree::new(chunkset_commitments.clone())?,
+        };
+        let mut header = BlobHeader::new(
+            self.byte_length,
+            self.config,
+            self.digest,
+            blob_tree.get_root_commitment(),
+            chunkset_commitments,
+        );
+        header.set_content_flags(self.content_flags);
+        self.blob_tree = Some(blob_tree);
+
+        Ok(header)
+    }
+
+    /// Returns blob-level proofs of a range of chunksets, i.e. proofs of inclusion of chunkset commitments in the blob
+    /// root commitment, to be handed back to the worker, which encoded them, for `attach_blob_proof`.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_ids` - Non-empty range of chunkset IDs.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Vec<Vec<blake3::Hash>>)` containing a blob-level proof per chunkset, ordered by chunkset ID, if successful.
+    /// - `Err(DecdsError::BlobNotYetFinalized)` if `Self::finalize` hasn't succeeded yet.
+    /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_ids` is empty or out of bounds.
+    pub fn get_blob_proofs(&self, chunkset_ids: Range<usize>) -> Result<Vec<Vec<blake3::Hash>>, DecdsError> {
+        let blob_tree = self.blob_tree.as_ref().ok_or(DecdsError::BlobNotYetFinalized)?;
+        self.check_chunkset_ids(&chunkset_ids)?;
+
+        chunkset_ids.map(|chunkset_id| blob_tree.generate_proof(chunkset_id)).collect()
+    }
+
+    /// Checks that `chunkset_ids` is a non-empty range of existing chunksets.
+    fn check_chunkset_ids(&self, chunkset_ids: &Range<usize>) -> Result<(), DecdsError> {
+        let num_chunksets = self.get_num_chunksets();
+
+        if chunkset_ids.is_empty() {
+            return Err(DecdsError::InvalidChunksetId(chunkset_ids.start, num_chunksets));
+        }
+        if chunkset_ids.end > num_chunksets {
+            return Err(DecdsError::InvalidChunksetId(chunkset_ids.end - 1, num_chunksets));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "coding"))]
+mod tests {
+    use crate::{BlobConfig, BlobCoordinator, DecdsBuilder, DecdsError, ProofCarryingChunk, attach_blob_proof};
+    use rand::Rng;
+
+    #[test]
+    fn test_blob_coordinator_assembles_same_blob_as_single_machine() {
+        let mut rng = rand::rng();
+
+        let config = BlobConfig::new().num_original_chunks(4).num_shares(6).chunkset_byte_length(1 << 16);
+        let blob_data = (0..config.get_chunkset_byte_length() * 4 + 1).map(|_| rng.random()).collect::<Vec<u8>>();
+
+        let builder = DecdsBuilder::new().config(config).seed([7u8; 32]);
+        let blob = builder.build(blob_data.clone()).expect("Must be able to build blob");
+
+        let mut coordinator = BlobCoordinator::new(blob_data.len(), blake3::hash(&blob_data), config).expect("Must be able to create coordinator");
+        assert_eq!(coordinator.get_num_chunksets(), 5);
+
+        let assignments = coordinator.assign_chunksets(3);
+        assert_eq!(assignments, vec![0..2, 2..4, 4..5]);
+        assert_eq!(coordinator.assign_chunksets(0), vec![0..5]);
+        assert_eq!(coordinator.assign_chunksets(10).len(), 5);
+
+        let workers = assignments
+            .into_iter()
+            .map(|chunkset_ids| {
+                let (from, to) = coordinator.get_byte_range_for_chunksets(chunkset_ids.clone()).unwrap();
+                let (commitments, chunks): (Vec<blake3::Hash>, Vec<Vec<ProofCarryingChunk>>) = blob_data[from..to]
+                    .chunks(config.get_chunkset_byte_length())
+                    .zip(chunkset_ids.clone())
+                    .map(|(data, chunkset_id)| builder.encode_chunkset(chunkset_id, data.to_vec()).unwrap())
+                    .unzip();
+
+                (chunkset_ids, commitments, chunks)
+            })
+            .collect::<Vec<_>>();
+
+        // Commitments arrive out of order, one of them twice
+        workers.iter().rev().for_each(|(chunkset_ids, commitments, _)| {
+            assert_eq!(coordinator.finalize().err(), Some(DecdsError::MissingChunksetCommitment(0)));
+            assert_eq!(coordinator.get_blob_proofs(chunkset_ids.clone()).err(), Some(DecdsError::BlobNotYetFinalized));
+            coordinator.submit_commitments(chunkset_ids.start, commitments).unwrap();
+        });
+        assert_eq!(coordinator.submit_commitments(2, &workers[1].1), Ok(()));
+        assert_eq!(
+            coordinator.submit_commitments(2, &workers[0].1),
+            Err(DecdsError::ConflictingChunksetCommitment(2))
+        );
+        assert!(coordinator.get_missing_chunksets().is_empty());
+
+        let header = coordinator.finalize().expect("Must be able to finalize blob");
+        assert_eq!(&header, blob.get_blob_header());
+        assert_eq!(coordinator.finalize(), Ok(header.clone()));
+
+        workers.into_iter().for_each(|(chunkset_ids, _, mut chunks)| {
+            let blob_proofs = coordinator.get_blob_proofs(chunkset_ids.clone()).unwrap();
+
+            chunks
+                .iter_mut()
+                .zip(chunkset_ids)
+                .zip(blob_proofs)
+                .for_each(|((chunks, chunkset_id), blob_proof)| {
+                    // Proof of another chunkset is rejected, leaving chunks untouched
+                    let other_blob_proof = coordinator.get_blob_proofs((chunkset_id + 1) % 5..(chunkset_id + 1) % 5 + 1).unwrap().remove(0);
+                    assert_eq!(
+                        attach_blob_proof(chunks, &other_blob_proof, header.get_root_commitment()),
+                        Err(DecdsError::InvalidProofInChunk(chunkset_id, 0))
+                    );
+
+                    attach_blob_proof(chunks, &blob_proof, header.get_root_commitment()).expect("Must be able to attach blob proof");
+                    chunks.iter().enumerate().for_each(|(share_id, chunk)| {
+                        assert_eq!(blob.get_chunk(chunkset_id, share_id), Ok(chunk));
+                    });
+                });
+        });
+    }
+
+    #[test]
+    fn test_blob_coordinator_with_invalid_input() {
+        let config = BlobConfig::new().chunkset_byte_length(1024);
+
+        assert_eq!(BlobCoordinator::new(0, blake3::hash(b""), config).err(), Some(DecdsError::EmptyDataForBlob));
+        assert!(matches!(
+            BlobCoordinator::new(1, blake3::hash(b"a"), config.num_shares(0)),
+            Err(DecdsError::InvalidBlobConfig(_))
+        ));
+
+        let mut coordinator = BlobCoordinator::new(2048, blake3::hash(&[0u8; 2048]), config).unwrap();
+        assert_eq!(coordinator.get_byte_range_for_chunksets(1..2), Ok((1024, 2048)));
+        assert_eq!(coordinator.get_byte_range_for_chunksets(1..1), Err(DecdsError::InvalidChunksetId(1, 2)));
+        assert_eq!(coordinator.get_byte_range_for_chunksets(0..3), Err(DecdsError::InvalidChunksetId(2, 2)));
+        assert_eq!(coordinator.submit_commitments(0, &[]), Err(DecdsError::InvalidChunksetId(0, 2)));
+        assert_eq!(
+            coordinator.submit_commitments(usize::MAX, &[blake3::hash(b"a")]),
+            Err(DecdsError::InvalidChunksetId(usize::MAX, 2))
+        );
+        assert_eq!(coordinator.get_missing_chunksets(), vec![0, 1]);
+    }
+}
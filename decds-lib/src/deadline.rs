@@ -0,0 +1,190 @@
+use crate::{blob::RepairingBlob, chunk::ProofCarryingChunk, errors::DecdsError};
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+/// Outcome of `RepairingBlob::repair_with_deadline`: chunksets repaired during the call, and exactly what is still
+/// missing, so that a batch job with a time budget can persist its progress and pick up from there in the next run.
+#[derive(Debug)]
+pub struct PartialRepair {
+    repaired_chunksets: BTreeMap<usize, Vec<u8>>,
+    failed_chunksets: BTreeMap<usize, DecdsError>,
+    missing_chunks: Vec<(usize, usize)>,
+    num_consumed_chunks: usize,
+    num_rejected_chunks: usize,
+    is_deadline_reached: bool,
+}
+
+impl PartialRepair {
+    /// Returns data of chunksets repaired during the call, keyed by chunkset ID. Each is exactly
+    /// `BlobHeader::get_chunkset_size` bytes. Chunksets repaired before the call aren't included.
+    pub fn get_repaired_chunksets(&self) -> &BTreeMap<usize, Vec<u8>> {
+        &self.repaired_chunksets
+    }
+
+    /// Consumes the outcome, returning data of chunksets repaired during the call, keyed by chunkset ID.
+    pub fn into_repaired_chunksets(self) -> BTreeMap<usize, Vec<u8>> {
+        self.repaired_chunksets
+    }
+
+    /// Returns chunksets, which had enough chunks, but failed to repair, keyed by chunkset ID.
+    pub fn get_failed_chunksets(&self) -> &BTreeMap<usize, DecdsError> {
+        &self.failed_chunksets
+    }
+
+    /// Returns `(chunkset_id, num_remaining_chunks)` of every chunkset, which still lacks useful chunks, in ascending order
+    /// of chunkset IDs, as `RepairingBlob::missing` yields them.
+    pub fn get_missing_chunks(&self) -> &[(usize, usize)] {
+        &self.missing_chunks
+    }
+
+    /// Returns the number of chunks pulled from the source before returning, rejected ones included.
+    pub fn get_num_consumed_chunks(&self) -> usize {
+        self.num_consumed_chunks
+    }
+
+    /// Returns the number of consumed chunks, which were rejected as invalid, i.e. failing proof verification or
+    /// claiming an out of bounds chunkset.
+    pub fn get_num_rejected_chunks(&self) -> usize {
+        self.num_rejected_chunks
+    }
+
+    /// Returns `true` if chunk ingestion stopped because the deadline was reached, rather than because the blob became
+    /// fully repairable or the source ran dry.
+    pub fn is_deadline_reached(&self) -> bool {
+        self.is_deadline_reached
+    }
+
+    /// Returns `true` if nothing is missing anymore, i.e. every chunkset is either repaired or failed to repair.
+    pub fn is_complete(&self) -> bool {
+        self.missing_chunks.is_empty()
+    }
+}
+
+impl RepairingBlob {
+    /// Time-boxed repair: pulls chunks from `chunks`, adding them to the blob, until either every chunkset is ready to
+    /// repair, the source runs dry or `duration` elapses, whichever happens first. Then repairs all chunksets, which are
+    /// ready, but not yet repaired, and reports them along with what is still missing.
+    ///
+    /// The deadline is checked before pulling each chunk, so a slow source can overshoot it by at most one chunk. Repairing
+    /// ready chunksets after the deadline is cheap, as chunks are decoded on arrival. Chunks being unuseful, e.g. of
+    /// chunksets already ready or linearly dependent, are skipped silently. Repaired chunksets are consumed, as with
+    /// `Self::get_repaired_chunkset`, so calling this again, with more chunks, only reports chunksets repaired since.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunks` - Source of chunks, e.g. lazily reading them from disk or network.
+    /// * `duration` - Time budget for pulling chunks, from now on.
+    ///
+    /// # Returns
+    ///
+    /// A `PartialRepair` describing repaired chunksets and what is missing.
+    pub fn repair_with_deadline<I>(&mut self, chunks: I, duration: Duration) -> PartialRepair
+    where
+        I: IntoIterator<Item = ProofCarryingChunk>,
+    {
+        let deadline = Instant::now().checked_add(duration);
+
+        let mut num_consumed_chunks = 0;
+        let mut num_rejected_chunks = 0;
+        let mut is_deadline_reached = false;
+
+        let mut chunks = chunks.into_iter();
+        while self.missing().next().is_some() {
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                is_deadline_reached = true;
+                break;
+            }
+
+            let Some(chunk) = chunks.next() else {
+                break;
+            };
+
+            num_consumed_chunks += 1;
+            if let Err(DecdsError::InvalidProofInChunk(..) | DecdsError::InvalidChunkMetadata(..) | DecdsError::InvalidChunksetId(..)) = self.add_chunk(&chunk)
+            {
+                num_rejected_chunks += 1;
+            }
+        }
+
+        let mut repaired_chunksets = BTreeMap::new();
+        let mut failed_chunksets = BTreeMap::new();
+
+        for chunkset_id in 0..self.get_blob_header().get_num_chunksets() {
+            let is_repairable =
+                self.is_chunkset_ready_to_repair(chunkset_id).unwrap_or_default() && !self.is_chunkset_already_repaired(chunkset_id).unwrap_or_default();
+            if !is_repairable {
+                continue;
+            }
+
+            match self.get_repaired_chunkset(chunkset_id) {
+                Ok(repaired) => {
+                    repaired_chunksets.insert(chunkset_id, repaired);
+                }
+                Err(err) => {
+                    failed_chunksets.insert(chunkset_id, err);
+                }
+            }
+        }
+
+        PartialRepair {
+            repaired_chunksets,
+            failed_chunksets,
+            missing_chunks: self.missing().collect(),
+            num_consumed_chunks,
+            num_rejected_chunks,
+            is_deadline_reached,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "coding"))]
+mod tests {
+    use crate::{ProofCarryingChunk, RepairingBlob, blob::Blob, consts::DECDS_NUM_ERASURE_CODED_SHARES};
+    use rand::Rng;
+    use std::time::Duration;
+
+    #[test]
+    fn test_repair_with_deadline_makes_progress_across_calls() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..rng.random_range(24usize << 20..=32usize << 20)).map(|_| rng.random()).collect::<Vec<u8>>();
+        let blob = Blob::new(blob_data.clone()).expect("Must be able to prepare blob");
+        let header = blob.get_blob_header().clone();
+
+        let num_chunksets = header.get_num_chunksets();
+        let num_original_chunks = header.get_config().get_num_original_chunks();
+
+        let chunks = (0..DECDS_NUM_ERASURE_CODED_SHARES)
+            .flat_map(|share_id| blob.get_share(share_id).expect("Must be able to get erasure-coded share"))
+            .collect::<Vec<ProofCarryingChunk>>();
+
+        let mut repairer = RepairingBlob::new(header.clone());
+
+        // Out of time right away, nothing is consumed.
+        let partial = repairer.repair_with_deadline(chunks.clone(), Duration::ZERO);
+        assert!(partial.is_deadline_reached());
+        assert_eq!(partial.get_num_consumed_chunks(), 0);
+        assert_eq!(
+            partial.get_missing_chunks(),
+            (0..num_chunksets).map(|chunkset_id| (chunkset_id, num_original_chunks)).collect::<Vec<_>>()
+        );
+
+        // Source runs dry after a single share.
+        let partial = repairer.repair_with_deadline(chunks[..num_chunksets].to_vec(), Duration::from_secs(600));
+        assert!(!partial.is_deadline_reached());
+        assert!(partial.get_repaired_chunksets().is_empty());
+        assert_eq!(partial.get_num_consumed_chunks(), num_chunksets);
+        assert_eq!(
+            partial.get_missing_chunks(),
+            (0..num_chunksets).map(|chunkset_id| (chunkset_id, num_original_chunks - 1)).collect::<Vec<_>>()
+        );
+
+        let partial = repairer.repair_with_deadline(chunks, Duration::from_secs(600));
+        assert!(partial.is_complete());
+        assert!(!partial.is_deadline_reached());
+        assert_eq!(partial.get_num_rejected_chunks(), 0);
+        assert_eq!(partial.into_repaired_chunksets().into_values().flatten().collect::<Vec<u8>>(), blob_data);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{chunkset::ChunkSet, consts};
+use crate::{chunkset::ChunkSet, codec::Codec, consts};
 
 #[derive(Debug, PartialEq)]
 pub enum DecdsError {
@@ -18,15 +18,39 @@ pub enum DecdsError {
     ProofCarryingChunkSerializationFailed(String),
     /// Returned when `ProofCarryingChunk` deserialization fails. Contains the error message from the underlying deserialization library.
     ProofCarryingChunkDeserializationFailed(String),
+    /// Returned when a chunk can't be split into segments of the requested maximum byte length. Contains that length.
+    InvalidMaxSegmentByteLength(usize),
+    /// Returned when a chunk segment is malformed or can't be accepted by a `ChunkReassembler`. Contains the reason.
+    InvalidChunkSegment(String),
+    /// Returned when bytes reassembled from chunk segments don't match the digest those segments carry.
+    ChunkSegmentsDigestMismatch,
 
     /// Returned when attempting to add a chunk to a `RepairingChunkSet` that is already ready for repair. Contains the chunkset ID.
     ChunksetReadyToRepair(usize),
+    /// Returned when a chunk is refused by the acceptance policy of a `RepairingBlob`. Contains the chunkset ID and the share ID.
+    ChunkRejectedByPolicy(usize, usize),
     /// Returned when attempting to repair a `RepairingChunkSet` that is not yet ready. Contains the chunkset ID.
     ChunksetNotYetReadyToRepair(usize),
     /// Returned when attempting to add a chunk to a `RepairingChunkSet` that has already been repaired. Contains the chunkset ID.
     ChunksetAlreadyRepaired(usize),
     /// Returned when `RepairingChunkSet` fails to repair its data. Contains the chunkset ID and an error message.
     ChunksetRepairingFailed(usize, String),
+    /// Returned when the buffer, repaired chunkset data is to be written into, is too small. Contains the chunkset ID, the
+    /// buffer length and the required length.
+    RepairOutputBufferTooSmall(usize, usize, usize),
+    /// Returned when the padding of a repaired chunkset, dropped from its data, isn't all zeros. Contains the chunkset ID.
+    NonZeroChunksetPadding(usize),
+    /// Returned when a `RepairingBlob`, restricted to a byte range of the blob, is given a chunk of, or asked about, a
+    /// chunkset not overlapping that range. Contains the chunkset ID.
+    ChunksetOutsideTargetRange(usize),
+    /// Returned when a sealed `Blob` is asked for a share, which it didn't hand out before being sealed. Contains the share ID.
+    BlobSealed(usize),
+    /// Returned when asked for zero extra shares, or for extra shares of a blob stored inline in its header.
+    NoExtraSharesToGenerate,
+    /// Returned when an `ExtraChunk` is added along with a `ShareExtension` of another blob.
+    ForeignShareExtension,
+    /// Returned when the signature over a `ShareExtension` is rejected, i.e. it wasn't issued by whoever published the blob.
+    InvalidShareExtensionSignature,
 
     /// Returned when an invalid erasure-coded share ID is provided. Contains the invalid share ID.
     InvalidErasureCodedShareId(usize),
@@ -34,17 +58,137 @@ pub enum DecdsError {
     InvalidChunksetId(usize, usize),
     /// Returned when creating a `ChunkSet` with data of an invalid size. Contains the provided size.
     InvalidChunksetSize(usize),
-    /// Returned when a chunk contains metadata (e.g., chunkset ID) that does not match the expected context. Contains the chunkset ID.
-    InvalidChunkMetadata(usize),
-    /// Returned when a `ProofCarryingChunk` fails its Merkle proof validation. Contains the chunkset ID.
-    InvalidProofInChunk(usize),
-    /// Returned when decoding a chunk fails during the repair process. Contains the chunkset ID and an error message.
-    ChunkDecodingFailed(usize, String),
+    /// Returned when a chunk contains metadata (e.g., chunkset ID) that does not match the expected context. Contains the chunkset ID
+    /// and the share ID, the chunk claims.
+    InvalidChunkMetadata(usize, usize),
+    /// Returned when a `ProofCarryingChunk` fails its Merkle proof validation. Contains the chunkset ID and the share ID, the chunk claims.
+    InvalidProofInChunk(usize, usize),
+    /// Returned when recomputing a chunkset commitment from an unexpected number of chunk digests. Contains the provided count.
+    InvalidNumberOfChunkDigests(usize),
+    /// Returned when a chunk carries a coding vector, which the codec of its blob wouldn't have coded it with. Contains the
+    /// chunkset ID and the share ID, the chunk claims.
+    UnexpectedCodingVector(usize, usize),
+    /// Returned when chunksets are to be erasure-coded or repaired with a codec, whose feature flag is off. Contains the codec.
+    CodecNotCompiledIn(Codec),
+    /// Returned when a valid chunk is linearly dependent on chunks already added to its chunkset, so it doesn't bring the
+    /// chunkset any closer to being repaired. Such a chunk is useless, but honest, unlike a corrupted one. Contains the
+    /// chunkset ID and the share ID of the chunk.
+    NonInnovativeChunk(usize, usize),
+    /// Returned when decoding a chunk fails during the repair process. Contains the chunkset ID, the share ID and an error message.
+    ChunkDecodingFailed(usize, usize, String),
+    /// Returned when a `RecodedChunk` doesn't belong to the chunkset, or its erasure-coded data is of unexpected length.
+    /// Contains the chunkset ID, the chunk claims, and an error message.
+    InvalidRecodedChunk(usize, String),
+    /// Returned when decoding a `RecodedChunk` fails during the repair process. Contains the chunkset ID and an error message.
+    RecodedChunkDecodingFailed(usize, String),
+    /// Returned when a `RecodedChunk` is linearly dependent on chunks already added to its chunkset. Contains the chunkset ID.
+    NonInnovativeRecodedChunk(usize),
+    /// Returned when a `Recoder` is asked to recode before being given any chunk. Contains the chunkset ID.
+    NoChunksToRecode(usize),
 
     /// Returned when attempting to build a Merkle tree with no leaf nodes.
     NoLeafNodesToBuildMerkleTreeOn,
     /// Returned when a Merkle tree operation specifies an invalid leaf node index. Contains the invalid index and the total number of leaves.
     InvalidLeafNodeIndex(usize, usize),
+    /// Returned when a Merkle consistency proof is requested against an older tree, which is empty or larger than the
+    /// current tree. Contains the number of leaf nodes of the older tree and of the current tree.
+    InvalidNumberOfOldLeafNodes(usize, usize),
+
+    /// Returned when `VersionedBlob` serialization fails. Contains the error message from the underlying serialization library.
+    VersionedBlobSerializationFailed(String),
+    /// Returned when `VersionedBlob` deserialization fails. Contains the error message from the underlying deserialization library.
+    VersionedBlobDeserializationFailed(String),
+    /// Returned when a version doesn't extend its predecessor in a `VersionedBlob`. Contains the index of the version.
+    InvalidVersionChain(usize),
+    /// Returned when a version of a `VersionedBlob` isn't properly signed. Contains the index of the version.
+    InvalidVersionSignature(usize),
+    /// Returned when a `BlobAnchor` can't be parsed from bytes. Contains the reason.
+    InvalidBlobAnchor(String),
+    /// Returned when a blob header doesn't match its `BlobAnchor`. Contains the name of the first mismatching field.
+    BlobAnchorMismatch(&'static str),
+    /// Returned when a `ChunkInventory` can't be serialized. Contains the error message from `bincode`.
+    ChunkInventorySerializationFailed(String),
+    /// Returned when a `ChunkInventory` can't be deserialized or is malformed. Contains the reason.
+    ChunkInventoryDeserializationFailed(String),
+    /// Returned when merging inventories of different blobs.
+    ChunkInventoryMismatch,
+    /// Returned when uniting availability of blobs of different number of chunksets. Contains both numbers of chunksets.
+    MismatchingNumberOfChunksets(usize, usize),
+
+    /// Returned when a generator matrix of a foreign erasure code isn't of `DECDS_NUM_ERASURE_CODED_SHARES` rows, each of
+    /// `NUM_ORIGINAL_CHUNKS` coefficients. Contains the reason.
+    InvalidCodingMatrix(String),
+    /// Returned when the number of imported stripes doesn't match the blob size. Contains number of stripes and the expected one.
+    InvalidNumberOfCodedStripes(usize, usize),
+    /// Returned when imported shards of a chunkset can't be wrapped into chunks. Contains the chunkset ID and the reason.
+    IncompatibleCodedShards(usize, String),
+    /// Returned when a `HeaderRegistry` can't be serialized. Contains the error message from `bincode`.
+    HeaderRegistrySerializationFailed(String),
+    /// Returned when a `HeaderRegistry` can't be deserialized or is inconsistent. Contains the reason.
+    HeaderRegistryDeserializationFailed(String),
+    /// Returned when a blob is tagged with an empty name, or one having whitespaces or control characters. Contains the name.
+    InvalidTagName(String),
+    /// Returned when a `DurabilitySimulation` is misconfigured. Contains the reason.
+    InvalidSimulationParameter(String),
+    /// Returned when parts handed to `ProofCarryingChunk::from_parts` can't make up a chunk. Contains the reason.
+    InvalidProofCarryingChunkParts(String),
+    /// Returned when a `BlobConfig` doesn't describe a usable erasure code. Contains the reason.
+    InvalidBlobConfig(String),
+    /// Returned when a `BlobCoordinator` is handed a chunkset commitment, other than the one already submitted for it. Contains the chunkset ID.
+    ConflictingChunksetCommitment(usize),
+    /// Returned when a `BlobCoordinator` is finalized, while a chunkset commitment is yet to be submitted. Contains the chunkset ID.
+    MissingChunksetCommitment(usize),
+    /// Returned when blob-level proofs are requested from a `BlobCoordinator`, which isn't finalized yet.
+    BlobNotYetFinalized,
+    /// Returned when reading blob data, to be erasure-coded, from a reader fails. Contains the error message.
+    BlobDataReadFailed(String),
+    /// Returned when a chunk file, to be verified, can't be opened or mapped. Contains the error message.
+    ChunkFileReadFailed(String),
+    /// Returned when chunks of a `Blob`, whose shares are generated on demand, are borrowed. Use `Blob::get_share` instead.
+    ChunksNotMaterialized,
+    /// Returned when reassembling a `Blob` from stored chunks, while a chunk of it isn't given. Contains the chunkset ID and
+    /// the share ID of the missing chunk.
+    MissingChunk(usize, usize),
+    /// Returned when an `AuditRecord` can't be serialized. Contains the error message from `bincode`.
+    AuditRecordSerializationFailed(String),
+    /// Returned when an `AuditRecord` can't be deserialized. Contains the error message from `bincode`.
+    AuditRecordDeserializationFailed(String),
+    /// Returned when a record of an audit log is out of sequence, or doesn't commit to its previous record. Contains the sequence number, the record was expected to have.
+    AuditLogChainBroken(u64),
+    /// Returned when a `SealedBlobHeader` can't be serialized. Contains the error message from `bincode`.
+    SealedBlobHeaderSerializationFailed(String),
+    /// Returned when a `SealedBlobHeader` can't be deserialized or is inconsistent. Contains the reason.
+    SealedBlobHeaderDeserializationFailed(String),
+    /// Returned when a `SealedBlobHeader` isn't signed, or its signature is rejected.
+    InvalidSealSignature,
+    /// Returned when a `BlobHeaderSubset` can't be serialized. Contains the error message from `bincode`.
+    BlobHeaderSubsetSerializationFailed(String),
+    /// Returned when a `BlobHeaderSubset` can't be deserialized or is inconsistent. Contains the reason.
+    BlobHeaderSubsetDeserializationFailed(String),
+    /// Returned when a `BlobHeaderSubset` is asked for a chunkset commitment, which it doesn't include. Contains the chunkset ID.
+    ChunksetNotInHeaderSubset(usize),
+    /// Returned when a `RepairingBlob` is checkpointed, but it wasn't built with checkpointing enabled.
+    CheckpointingNotEnabled,
+    /// Returned when a checkpoint of a `RepairingBlob` can't be serialized. Contains the error message from `bincode`.
+    RepairCheckpointSerializationFailed(String),
+    /// Returned when a checkpoint of a `RepairingBlob` can't be deserialized or is inconsistent. Contains the reason.
+    RepairCheckpointDeserializationFailed(String),
+    /// Returned when an ID, a byte offset or a count doesn't fit in a `usize`, e.g. as computed from an adversarial header
+    /// or chunk. Contains what was being computed.
+    ArithmeticOverflow(String),
+    /// Returned when repaired data can't be written to the sink given to `RepairingBlob::write_repaired`. Contains the
+    /// error message from the sink.
+    RepairedDataWriteFailed(String),
+    /// Returned when content flags have bits set, which have no meaning. Contains the encoded flags.
+    InvalidContentFlags(u8),
+    /// Returned when start offsets of application records aren't strictly ascending, or reach beyond the blob. Contains the
+    /// index of the first offending record and its start offset.
+    InvalidRecordOffset(usize, usize),
+    /// Returned when an application record index is out of bounds. Contains the index and the number of records.
+    InvalidRecordId(usize, usize),
+
+    /// Returned when a dedicated thread pool for erasure-coding can't be created. Contains the error message from `rayon`.
+    ThreadPoolBuildFailed(String),
 }
 
 impl std::fmt::Display for DecdsError {
@@ -59,11 +203,28 @@ impl std::fmt::Display for DecdsError {
 
             DecdsError::ProofCarryingChunkSerializationFailed(err) => write!(f, "failed to serialize proof carrying chunk: {}", err),
             DecdsError::ProofCarryingChunkDeserializationFailed(err) => write!(f, "failed to deserialize proof carrying chunk: {}", err),
+            DecdsError::InvalidMaxSegmentByteLength(byte_len) => write!(f, "invalid maximum segment byte length: {}", byte_len),
+            DecdsError::InvalidChunkSegment(err) => write!(f, "invalid chunk segment: {}", err),
+            DecdsError::ChunkSegmentsDigestMismatch => write!(f, "reassembled chunk segments don't match their digest"),
 
             DecdsError::ChunksetReadyToRepair(id) => write!(f, "chunkset {} is ready to repair", id),
+            DecdsError::ChunkRejectedByPolicy(chunkset_id, share_id) => {
+                write!(f, "chunk of share {} for chunkset {} rejected by acceptance policy", share_id, chunkset_id)
+            }
             DecdsError::ChunksetNotYetReadyToRepair(id) => write!(f, "chunkset {} is not ready to repair", id),
             DecdsError::ChunksetAlreadyRepaired(id) => write!(f, "chunkset {} is already repaired", id),
             DecdsError::ChunksetRepairingFailed(id, err) => write!(f, "chunkset {} repairing failed: {}", id, err),
+            DecdsError::RepairOutputBufferTooSmall(id, len, required_len) => write!(
+                f,
+                "output buffer of {}B is too small for repaired chunkset {}, expected at least: {}B",
+                len, id, required_len
+            ),
+            DecdsError::NonZeroChunksetPadding(id) => write!(f, "padding of repaired chunkset {} isn't all zeros", id),
+            DecdsError::ChunksetOutsideTargetRange(id) => write!(f, "chunkset {} is outside the target byte range of the repair", id),
+            DecdsError::BlobSealed(share_id) => write!(f, "blob is sealed, share {} wasn't issued before sealing it", share_id),
+            DecdsError::NoExtraSharesToGenerate => write!(f, "no extra shares to generate"),
+            DecdsError::ForeignShareExtension => write!(f, "share extension doesn't belong to the blob"),
+            DecdsError::InvalidShareExtensionSignature => write!(f, "invalid signature of share extension"),
 
             DecdsError::InvalidErasureCodedShareId(id) => write!(
                 f,
@@ -73,12 +234,125 @@ impl std::fmt::Display for DecdsError {
             ),
             DecdsError::InvalidChunksetId(id, num_chunksets) => write!(f, "invalid chunkset id: {} (num_chunksets: {})", id, num_chunksets),
             DecdsError::InvalidChunksetSize(size) => write!(f, "invalid chunkset size: {}B, expected: {}B", size, ChunkSet::BYTE_LENGTH),
-            DecdsError::InvalidChunkMetadata(chunkset_id) => write!(f, "invalid chunk for chunkset {}", chunkset_id),
-            DecdsError::InvalidProofInChunk(chunkset_id) => write!(f, "invalid proof carrying chunk for chunkset {}", chunkset_id),
-            DecdsError::ChunkDecodingFailed(chunkset_id, err) => write!(f, "decoding chunk for chunkset {} failed: {}", chunkset_id, err),
+            DecdsError::InvalidChunkMetadata(chunkset_id, share_id) => write!(f, "invalid chunk of share {} for chunkset {}", share_id, chunkset_id),
+            DecdsError::InvalidProofInChunk(chunkset_id, share_id) => {
+                write!(f, "invalid proof carrying chunk of share {} for chunkset {}", share_id, chunkset_id)
+            }
+            DecdsError::InvalidNumberOfChunkDigests(count) => write!(
+                f,
+                "invalid number of chunk digests: {}, expected: 1 to {}",
+                count,
+                ChunkSet::NUM_ERASURE_CODED_CHUNKS
+            ),
+            DecdsError::UnexpectedCodingVector(chunkset_id, share_id) => {
+                write!(f, "unexpected coding vector in chunk of share {} for chunkset {}", share_id, chunkset_id)
+            }
+            DecdsError::CodecNotCompiledIn(codec) => write!(f, "codec {:?} is not compiled in, enable its feature", codec),
+            DecdsError::NonInnovativeChunk(chunkset_id, share_id) => {
+                write!(f, "chunk of share {} for chunkset {} is not innovative", share_id, chunkset_id)
+            }
+            DecdsError::ChunkDecodingFailed(chunkset_id, share_id, err) => {
+                write!(f, "decoding chunk of share {} for chunkset {} failed: {}", share_id, chunkset_id, err)
+            }
+            DecdsError::InvalidRecodedChunk(chunkset_id, err) => write!(f, "invalid recoded chunk for chunkset {}: {}", chunkset_id, err),
+            DecdsError::RecodedChunkDecodingFailed(chunkset_id, err) => write!(f, "decoding recoded chunk for chunkset {} failed: {}", chunkset_id, err),
+            DecdsError::NonInnovativeRecodedChunk(chunkset_id) => write!(f, "recoded chunk for chunkset {} is not innovative", chunkset_id),
+            DecdsError::NoChunksToRecode(chunkset_id) => write!(f, "no chunks to recode for chunkset {}", chunkset_id),
 
             DecdsError::NoLeafNodesToBuildMerkleTreeOn => write!(f, "no leaf nodes to build merkle tree on"),
             DecdsError::InvalidLeafNodeIndex(leaf_index, num_leaves) => write!(f, "invalid leaf node index: {} (num_leaves: {})", leaf_index, num_leaves),
+            DecdsError::InvalidNumberOfOldLeafNodes(old_num_leaves, num_leaves) => {
+                write!(f, "invalid number of leaf nodes in older tree: {} (num_leaves: {})", old_num_leaves, num_leaves)
+            }
+
+            DecdsError::VersionedBlobSerializationFailed(err) => write!(f, "failed to serialize versioned blob: {}", err),
+            DecdsError::VersionedBlobDeserializationFailed(err) => write!(f, "failed to deserialize versioned blob: {}", err),
+            DecdsError::InvalidVersionChain(version_idx) => write!(f, "version {} doesn't extend its predecessor", version_idx),
+            DecdsError::InvalidVersionSignature(version_idx) => write!(f, "invalid signature of version {}", version_idx),
+
+            DecdsError::InvalidBlobAnchor(err) => write!(f, "invalid blob anchor: {}", err),
+            DecdsError::BlobAnchorMismatch(field) => write!(f, "blob header doesn't match anchor: {} differs", field),
+            DecdsError::ChunkInventorySerializationFailed(err) => write!(f, "failed to serialize chunk inventory: {}", err),
+            DecdsError::ChunkInventoryDeserializationFailed(err) => write!(f, "failed to deserialize chunk inventory: {}", err),
+            DecdsError::ChunkInventoryMismatch => write!(f, "chunk inventories are of different blobs"),
+            DecdsError::MismatchingNumberOfChunksets(num_chunksets, expected) => {
+                write!(
+                    f,
+                    "availability of {} chunksets can't be united with availability of {} chunksets",
+                    num_chunksets, expected
+                )
+            }
+
+            DecdsError::InvalidCodingMatrix(err) => write!(f, "invalid coding matrix: {}", err),
+            DecdsError::InvalidNumberOfCodedStripes(num_stripes, expected) => write!(f, "got {} coded stripes, expected {}", num_stripes, expected),
+            DecdsError::IncompatibleCodedShards(chunkset_id, err) => write!(f, "incompatible coded shards of chunkset {}: {}", chunkset_id, err),
+            DecdsError::HeaderRegistrySerializationFailed(err) => write!(f, "failed to serialize header registry: {}", err),
+            DecdsError::HeaderRegistryDeserializationFailed(err) => write!(f, "failed to deserialize header registry: {}", err),
+            DecdsError::InvalidTagName(name) => write!(f, "invalid tag name: {:?}", name),
+            DecdsError::InvalidSimulationParameter(err) => write!(f, "invalid simulation parameter: {}", err),
+            DecdsError::InvalidProofCarryingChunkParts(err) => write!(f, "invalid proof carrying chunk parts: {}", err),
+            DecdsError::InvalidBlobConfig(err) => write!(f, "invalid blob config: {}", err),
+            DecdsError::ConflictingChunksetCommitment(chunkset_id) => write!(f, "conflicting commitment submitted for chunkset {}", chunkset_id),
+            DecdsError::MissingChunksetCommitment(chunkset_id) => write!(f, "commitment of chunkset {} is not yet submitted", chunkset_id),
+            DecdsError::BlobNotYetFinalized => write!(f, "blob is not yet finalized"),
+            DecdsError::BlobDataReadFailed(err) => write!(f, "failed to read blob data: {}", err),
+            DecdsError::ChunkFileReadFailed(err) => write!(f, "failed to read chunk file: {}", err),
+            DecdsError::ChunksNotMaterialized => write!(f, "chunks are generated on demand, they can't be borrowed"),
+            DecdsError::MissingChunk(chunkset_id, share_id) => write!(f, "chunk of share {} for chunkset {} is missing", share_id, chunkset_id),
+            DecdsError::AuditRecordSerializationFailed(err) => write!(f, "failed to serialize audit record: {}", err),
+            DecdsError::AuditRecordDeserializationFailed(err) => write!(f, "failed to deserialize audit record: {}", err),
+            DecdsError::AuditLogChainBroken(sequence) => write!(f, "audit log hash chain is broken at record {}", sequence),
+            DecdsError::SealedBlobHeaderSerializationFailed(err) => write!(f, "failed to serialize sealed blob header: {}", err),
+            DecdsError::SealedBlobHeaderDeserializationFailed(err) => write!(f, "failed to deserialize sealed blob header: {}", err),
+            DecdsError::InvalidSealSignature => write!(f, "invalid signature of sealed blob header"),
+            DecdsError::BlobHeaderSubsetSerializationFailed(err) => write!(f, "failed to serialize blob header subset: {}", err),
+            DecdsError::BlobHeaderSubsetDeserializationFailed(err) => write!(f, "failed to deserialize blob header subset: {}", err),
+            DecdsError::ChunksetNotInHeaderSubset(id) => write!(f, "chunkset {} isn't included in blob header subset", id),
+            DecdsError::CheckpointingNotEnabled => write!(f, "checkpointing isn't enabled for this repairer"),
+            DecdsError::RepairCheckpointSerializationFailed(err) => write!(f, "failed to serialize repair checkpoint: {}", err),
+            DecdsError::RepairCheckpointDeserializationFailed(err) => write!(f, "failed to deserialize repair checkpoint: {}", err),
+            DecdsError::ArithmeticOverflow(what) => write!(f, "arithmetic overflow while computing {}", what),
+            DecdsError::RepairedDataWriteFailed(err) => write!(f, "failed to write repaired data: {}", err),
+            DecdsError::InvalidContentFlags(bits) => write!(f, "invalid content flags {:#04x}", bits),
+            DecdsError::InvalidRecordOffset(record_id, record_offset) => write!(
+                f,
+                "record {} starts at invalid offset {}, record offsets must be ascending and within the blob",
+                record_id, record_offset
+            ),
+            DecdsError::InvalidRecordId(record_id, num_records) => write!(f, "invalid record id: {} (num_records: {})", record_id, num_records),
+
+            DecdsError::ThreadPoolBuildFailed(err) => write!(f, "failed to build thread pool: {}", err),
         }
     }
 }
+
+/// Returned by `BlobHeader::validate_chunkset`, when a chunk of a chunkset is rejected. Points at the first rejected chunk,
+/// so that an ingest pipeline can report it, while rejecting the whole upload.
+#[derive(Debug, PartialEq)]
+pub struct ChunksetValidationError {
+    chunk_index: usize,
+    error: DecdsError,
+}
+
+impl ChunksetValidationError {
+    pub(crate) fn new(chunk_index: usize, error: DecdsError) -> Self {
+        ChunksetValidationError { chunk_index, error }
+    }
+
+    /// Returns index of the first rejected chunk, in the slice given to `BlobHeader::validate_chunkset`.
+    pub fn get_chunk_index(&self) -> usize {
+        self.chunk_index
+    }
+
+    /// Returns why the chunk was rejected, i.e. `DecdsError::InvalidProofInChunk`, or `DecdsError::InvalidChunkMetadata`,
+    /// if it's of another chunkset than the first chunk.
+    pub fn get_error(&self) -> &DecdsError {
+        &self.error
+    }
+}
+
+impl std::fmt::Display for ChunksetValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "chunk at index {} rejected: {}", self.chunk_index, self.error)
+    }
+}
@@ -0,0 +1,180 @@
+use crate::{errors::DecdsError, progress::RepairProgress};
+
+/// Hooks, which are invoked while a blob is being built or repaired, so that embedders can drive UIs, collect metrics or
+/// trigger replication logic, without polling.
+///
+/// All methods have empty default implementations, so one only needs to implement the events they care about. Chunksets
+/// are erasure-coded in parallel, hence the handler must be `Send + Sync` and `on_chunkset_encoded` may be invoked
+/// concurrently and out of chunkset order.
+///
+/// Pass a handler to `Blob::new_with_event_handler` or `BlobPipeline::encode_with_event_handler` for build time events,
+/// and to `RepairingBlob::set_event_handler` for repair time events.
+pub trait EventHandler: Send + Sync {
+    /// Invoked once a chunkset is erasure-coded and committed to.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the encoded chunkset.
+    /// * `commitment` - The Merkle root commitment of the encoded chunkset.
+    fn on_chunkset_encoded(&self, _chunkset_id: usize, _commitment: blake3::Hash) {}
+
+    /// Invoked once a chunk passes proof verification and is accepted for repairing its chunkset.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset, the chunk belongs to.
+    /// * `chunk_id` - The global ID of the chunk.
+    fn on_chunk_verified(&self, _chunkset_id: usize, _chunk_id: usize) {}
+
+    /// Invoked once a chunkset is repaired, i.e. its original data is reconstructed.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the repaired chunkset.
+    fn on_chunkset_repaired(&self, _chunkset_id: usize) {}
+
+    /// Invoked when a chunk is rejected, because it fails proof verification or carries invalid metadata.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset, the chunk claims to belong to.
+    /// * `chunk_id` - The global ID, the chunk claims to have.
+    /// * `error` - Why the chunk is rejected.
+    fn on_invalid_chunk(&self, _chunkset_id: usize, _chunk_id: usize, _error: &DecdsError) {}
+
+    /// Invoked once a chunkset receives enough useful chunks to be repaired, i.e. right after the chunk making it ready is
+    /// verified, so that it can be retrieved with `RepairingBlob::get_repaired_chunkset`.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset, which is ready to repair.
+    fn on_chunkset_ready(&self, _chunkset_id: usize) {}
+
+    /// Invoked whenever repair progress changes, i.e. a useful chunk is added or a chunkset is repaired, so that UIs can
+    /// update a progress bar, without polling.
+    ///
+    /// # Arguments
+    ///
+    /// * `progress` - Repair progress, right after the change, see `RepairProgress::get_fraction`.
+    fn on_progress(&self, _progress: &RepairProgress) {}
+
+    /// Invoked once every chunkset tracked by the repairer is ready to repair or repaired, i.e. no more chunks are
+    /// required, so that a daemon can stop fetching chunks.
+    fn on_blob_ready(&self) {}
+}
+
+/// An `EventHandler`, which ignores all events.
+pub(crate) struct NoopEventHandler;
+
+impl EventHandler for NoopEventHandler {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Blob, DECDS_NUM_ERASURE_CODED_SHARES, DecdsError, EventHandler, ProofCarryingChunk, RepairProgress, RepairingBlob, chunkset::ChunkSet};
+    use rand::Rng;
+    use std::sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    #[derive(Default)]
+    struct CountingEventHandler {
+        num_encoded_chunksets: AtomicUsize,
+        num_verified_chunks: AtomicUsize,
+        num_repaired_chunksets: AtomicUsize,
+        num_invalid_chunks: AtomicUsize,
+        ready_chunkset_ids: Mutex<Vec<usize>>,
+        last_progress: Mutex<RepairProgress>,
+        num_blob_ready: AtomicUsize,
+    }
+
+    impl EventHandler for Arc<CountingEventHandler> {
+        fn on_chunkset_encoded(&self, _chunkset_id: usize, _commitment: blake3::Hash) {
+            self.num_encoded_chunksets.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_chunk_verified(&self, _chunkset_id: usize, _chunk_id: usize) {
+            self.num_verified_chunks.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_chunkset_repaired(&self, _chunkset_id: usize) {
+            self.num_repaired_chunksets.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_invalid_chunk(&self, _chunkset_id: usize, _chunk_id: usize, error: &DecdsError) {
+            assert!(matches!(error, DecdsError::InvalidProofInChunk(_, _)));
+            self.num_invalid_chunks.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn on_chunkset_ready(&self, chunkset_id: usize) {
+            self.ready_chunkset_ids.lock().unwrap().push(chunkset_id);
+        }
+
+        fn on_progress(&self, progress: &RepairProgress) {
+            let mut last_progress = self.last_progress.lock().unwrap();
+            assert!(progress.get_fraction() >= last_progress.get_fraction());
+            *last_progress = *progress;
+        }
+
+        fn on_blob_ready(&self) {
+            self.num_blob_ready.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_event_handler_is_invoked_during_build_and_repair() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..(ChunkSet::BYTE_LENGTH * 2 + 1)).map(|_| rng.random()).collect::<Vec<u8>>();
+        let handler = Arc::new(CountingEventHandler::default());
+
+        let blob = Blob::new_with_event_handler(blob_data.clone(), &handler).expect("Must be able to prepare blob");
+        let header = blob.get_blob_header();
+        assert_eq!(handler.num_encoded_chunksets.load(Ordering::Relaxed), header.get_num_chunksets());
+
+        let mut repairer = RepairingBlob::new(header.clone());
+        repairer.set_event_handler(Box::new(handler.clone()));
+
+        let mut tampered_chunk = blob.get_share(0).unwrap()[0].to_bytes().unwrap();
+        // Last byte of the proof, which precedes the content flags byte.
+        let last_byte_idx = tampered_chunk.len() - 2;
+        tampered_chunk[last_byte_idx] ^= 1;
+        let tampered_chunk = ProofCarryingChunk::from_bytes(&tampered_chunk).unwrap().0;
+        assert!(repairer.add_chunk(&tampered_chunk).is_err());
+
+        (0..DECDS_NUM_ERASURE_CODED_SHARES).for_each(|share_id| {
+            blob.get_share(share_id).unwrap().iter().for_each(|chunk| {
+                let _ = repairer.add_chunk(chunk);
+            });
+        });
+
+        let mut ready_chunkset_ids = handler.ready_chunkset_ids.lock().unwrap().clone();
+        ready_chunkset_ids.sort_unstable();
+        assert_eq!(ready_chunkset_ids, (0..header.get_num_chunksets()).collect::<Vec<usize>>());
+        assert_eq!(handler.num_blob_ready.load(Ordering::Relaxed), 1);
+
+        let progress = repairer.get_progress();
+        assert_eq!(*handler.last_progress.lock().unwrap(), progress);
+        assert_eq!(progress.get_num_ready_chunksets(), header.get_num_chunksets());
+        assert_eq!(progress.get_num_useful_chunks(), progress.get_num_required_chunks());
+        assert_eq!(progress.get_fraction(), 1.0);
+        assert!(progress.is_ready() && !progress.is_complete());
+
+        let repaired = (0..header.get_num_chunksets())
+            .flat_map(|chunkset_id| repairer.get_repaired_chunkset(chunkset_id).expect("Must be able to repair chunkset"))
+            .collect::<Vec<u8>>();
+        assert_eq!(repaired, blob_data);
+
+        let progress = repairer.get_progress();
+        assert_eq!(*handler.last_progress.lock().unwrap(), progress);
+        assert_eq!(progress.get_num_repaired_chunksets(), header.get_num_chunksets());
+        assert!(progress.is_complete());
+
+        assert_eq!(handler.num_invalid_chunks.load(Ordering::Relaxed), 1);
+        assert_eq!(
+            handler.num_verified_chunks.load(Ordering::Relaxed),
+            header.get_num_chunksets() * ChunkSet::NUM_ORIGINAL_CHUNKS
+        );
+        assert_eq!(handler.num_repaired_chunksets.load(Ordering::Relaxed), header.get_num_chunksets());
+    }
+}
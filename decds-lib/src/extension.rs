@@ -0,0 +1,306 @@
+use crate::{blob::BlobHeader, errors::DecdsError, merkle_tree::MerkleTree, recoder::RecodedChunk};
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// Commitment to a batch of extra shares of a blob, generated after its initial distribution with
+/// `Blob::generate_extra_shares`, to top up redundancy.
+///
+/// The `BlobHeader` commits to a fixed number of shares, so extra shares can't be part of the blob commitment. Instead,
+/// each batch of extra shares gets its own Merkle tree, over digests of their chunks, laid out share after share, each
+/// share being one chunk per chunkset. The batch names the blob by its root commitment, and is signed by whoever
+/// published the header, as anyone can commit to a batch of extra shares of any blob, garbage ones included. Just like
+/// `VersionedBlob`, signing and verification are left to the caller, as closures over the 32-bytes extension ID. Extra
+/// share IDs continue after `DECDS_NUM_ERASURE_CODED_SHARES`, batch after batch, so commitments are appendable: older
+/// batches and their chunks stay valid when new ones are issued.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct ShareExtension {
+    blob_root_commitment: blake3::Hash,
+    num_chunksets: usize,
+    first_share_id: usize,
+    num_shares: usize,
+    root_commitment: blake3::Hash,
+    signature: Vec<u8>,
+}
+
+/// An erasure-coded chunk of an extra share, carrying a Merkle proof of its inclusion in a `ShareExtension`.
+///
+/// Its coding vector is a fresh random combination, over the original pieces of the chunkset, like that of a
+/// `RecodedChunk`, which it wraps.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct ExtraChunk {
+    share_id: usize,
+    chunk: RecodedChunk,
+    proof: Vec<blake3::Hash>,
+}
+
+impl ShareExtension {
+    const EXTRA_CHUNK_DIGEST_KDF_CONTEXT: &str = "decds 2025-07-01 extra share chunk digest";
+    /// BLAKE3 key derivation context, used for computing extension IDs.
+    const EXTENSION_ID_KDF_CONTEXT: &str = "decds 2025-07-01 share extension id";
+
+    /// Commits to extra shares `first_share_id..first_share_id + num_shares` of the blob described by `header`, whose
+    /// erasure-coded data are `extra_shares`, share after share, each holding one chunk per chunkset, and signs the
+    /// commitment with `sign`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the extension, along with chunks of each extra share, carrying proofs of inclusion in the extension.
+    pub(crate) fn new(
+        header: &BlobHeader,
+        first_share_id: usize,
+        extra_shares: Vec<Vec<RecodedChunk>>,
+        sign: impl FnOnce(&blake3::Hash) -> Vec<u8>,
+    ) -> (Self, Vec<Vec<ExtraChunk>>) {
+        let num_chunksets = header.get_num_chunksets();
+        let num_shares = extra_shares.len();
+
+        let leaf_nodes = extra_shares
+            .iter()
+            .enumerate()
+            .flat_map(|(share_idx, share)| share.iter().map(move |chunk| Self::digest_extra_chunk(first_share_id + share_idx, chunk)))
+            .collect::<Vec<blake3::Hash>>();
+
+        // Callers never ask for zero extra shares of a blob having no chunksets.
+        let merkle_tree = unsafe { MerkleTree::new(leaf_nodes).unwrap_unchecked() };
+        let mut proofs = merkle_tree.generate_all_proofs().into_iter();
+
+        let mut extension = ShareExtension {
+            blob_root_commitment: header.get_root_commitment(),
+            num_chunksets,
+            first_share_id,
+            num_shares,
+            root_commitment: merkle_tree.get_root_commitment(),
+            signature: Vec::new(),
+        };
+        extension.signature = sign(&extension.get_extension_id());
+
+        let extra_chunks = extra_shares
+            .into_iter()
+            .enumerate()
+            .map(|(share_idx, share)| {
+                share
+                    .into_iter()
+                    .map(|chunk| ExtraChunk {
+                        share_id: first_share_id + share_idx,
+                        chunk,
+                        proof: unsafe { proofs.next().unwrap_unchecked() },
+                    })
+                    .collect::<Vec<ExtraChunk>>()
+            })
+            .collect::<Vec<Vec<ExtraChunk>>>();
+
+        (extension, extra_chunks)
+    }
+
+    /// Returns the Merkle root commitment of the blob, this extension belongs to.
+    pub fn get_blob_root_commitment(&self) -> blake3::Hash {
+        self.blob_root_commitment
+    }
+
+    /// Returns the range of extra share IDs, this extension commits to.
+    pub fn get_share_ids(&self) -> Range<usize> {
+        self.first_share_id..self.first_share_id.saturating_add(self.num_shares)
+    }
+
+    /// Returns the Merkle root commitment over chunk digests of all extra shares, this extension commits to.
+    pub fn get_root_commitment(&self) -> blake3::Hash {
+        self.root_commitment
+    }
+
+    /// Returns the ID of this extension, which is what gets signed.
+    ///
+    /// The ID is computed as `BLAKE3(blob_root_commitment || num_chunksets || first_share_id || num_shares || root_commitment)`,
+    /// hashed in key derivation mode with `EXTENSION_ID_KDF_CONTEXT`, where counts and IDs are encoded as 8-bytes
+    /// little-endian unsigned integers.
+    pub fn get_extension_id(&self) -> blake3::Hash {
+        blake3::Hasher::new_derive_key(Self::EXTENSION_ID_KDF_CONTEXT)
+            .update(self.blob_root_commitment.as_bytes())
+            .update(&(self.num_chunksets as u64).to_le_bytes())
+            .update(&(self.first_share_id as u64).to_le_bytes())
+            .update(&(self.num_shares as u64).to_le_bytes())
+            .update(self.root_commitment.as_bytes())
+            .finalize()
+    }
+
+    /// Returns the signature over the extension ID.
+    pub fn get_signature(&self) -> &[u8] {
+        &self.signature
+    }
+
+    /// Checks whether this extension belongs to the blob described by `header`. It says nothing about who issued the
+    /// extension, see `Self::verify_signature`.
+    pub fn belongs_to(&self, header: &BlobHeader) -> bool {
+        self.blob_root_commitment == header.get_root_commitment() && self.num_chunksets == header.get_num_chunksets()
+    }
+
+    /// Checks the signature over the extension ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `verify_signature` - Checks a signature over the extension ID, returning `true` if it was made by whoever
+    ///   published the blob.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(())` if the signature is valid.
+    /// - `Err(DecdsError::InvalidShareExtensionSignature)` if `verify_signature` rejects the signature.
+    pub fn verify_signature(&self, verify_signature: impl FnOnce(&blake3::Hash, &[u8]) -> bool) -> Result<(), DecdsError> {
+        if verify_signature(&self.get_extension_id(), &self.signature) {
+            Ok(())
+        } else {
+            Err(DecdsError::InvalidShareExtensionSignature)
+        }
+    }
+
+    /// Validates the Merkle proof of inclusion of an extra chunk in this extension.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk` - The `ExtraChunk` to validate.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the chunk belongs to one of the extra shares this extension commits to, `false` otherwise.
+    pub fn validate_chunk(&self, chunk: &ExtraChunk) -> bool {
+        let chunkset_id = chunk.get_chunkset_id();
+        if !self.get_share_ids().contains(&chunk.share_id) || chunkset_id >= self.num_chunksets {
+            return false;
+        }
+
+        let leaf_index = (chunk.share_id - self.first_share_id) * self.num_chunksets + chunkset_id;
+        MerkleTree::verify_proof(
+            leaf_index,
+            Self::digest_extra_chunk(chunk.share_id, &chunk.chunk),
+            &chunk.proof,
+            self.root_commitment,
+        )
+    }
+
+    /// Computes the digest of a chunk of an extra share, as
+    /// `BLAKE3-derive-key(chunkset_id || share_id || erasure_coded_data)`, both IDs being encoded as 8-bytes little-endian
+    /// unsigned integers. Keyed derivation keeps these digests apart from those of chunks committed to by the header.
+    fn digest_extra_chunk(share_id: usize, chunk: &RecodedChunk) -> blake3::Hash {
+        blake3::Hasher::new_derive_key(Self::EXTRA_CHUNK_DIGEST_KDF_CONTEXT)
+            .update(&(chunk.get_chunkset_id() as u64).to_le_bytes())
+            .update(&(share_id as u64).to_le_bytes())
+            .update(chunk.get_erasure_coded_data())
+            .finalize()
+    }
+}
+
+impl ExtraChunk {
+    /// Returns the ID of the extra share this chunk belongs to, which is at least `DECDS_NUM_ERASURE_CODED_SHARES`.
+    pub fn get_share_id(&self) -> usize {
+        self.share_id
+    }
+
+    /// Returns the ID of the chunkset this chunk belongs to.
+    pub fn get_chunkset_id(&self) -> usize {
+        self.chunk.get_chunkset_id()
+    }
+
+    /// Returns the erasure-coded data of this chunk, i.e. its coding vector, followed by its coded piece.
+    pub fn get_erasure_coded_data(&self) -> &[u8] {
+        self.chunk.get_erasure_coded_data()
+    }
+
+    /// Returns the Merkle proof of inclusion of this chunk in its `ShareExtension`.
+    pub fn get_proof(&self) -> &[blake3::Hash] {
+        &self.proof
+    }
+
+    /// Returns the wrapped chunk, which can be used in repairing its chunkset, as any `RecodedChunk`.
+    pub(crate) fn get_recoded_chunk(&self) -> &RecodedChunk {
+        &self.chunk
+    }
+}
+
+#[cfg(all(test, feature = "coding"))]
+mod tests {
+    use crate::{DecdsError, RepairingBlob, blob::Blob, consts::DECDS_NUM_ERASURE_CODED_SHARES};
+    use rand::Rng;
+
+    fn sign_with(key: u8) -> impl Fn(&blake3::Hash) -> Vec<u8> {
+        move |extension_id| blake3::keyed_hash(&[key; 32], extension_id.as_bytes()).as_bytes().to_vec()
+    }
+
+    fn verify_with(key: u8) -> impl Fn(&blake3::Hash, &[u8]) -> bool {
+        move |extension_id, signature| sign_with(key)(extension_id) == signature
+    }
+
+    #[test]
+    fn test_extra_shares_repair_blob_along_with_original_ones() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..rng.random_range(12usize << 20..=24usize << 20)).map(|_| rng.random()).collect::<Vec<u8>>();
+        let mut blob = Blob::new(blob_data.clone()).expect("Must be able to prepare blob");
+        let header = blob.get_blob_header().clone();
+        let num_original_chunks = header.get_config().get_num_original_chunks();
+
+        assert_eq!(
+            blob.generate_extra_shares(0, sign_with(7)).map(|_| ()),
+            Err(DecdsError::NoExtraSharesToGenerate)
+        );
+
+        let (first_extension, first_shares) = blob.generate_extra_shares(2, sign_with(7)).expect("Must be able to generate extra shares");
+        let (second_extension, second_shares) = blob.generate_extra_shares(2, sign_with(7)).expect("Must be able to generate extra shares");
+        assert_eq!(
+            first_extension.get_share_ids(),
+            DECDS_NUM_ERASURE_CODED_SHARES..DECDS_NUM_ERASURE_CODED_SHARES + 2
+        );
+        assert_eq!(
+            second_extension.get_share_ids(),
+            DECDS_NUM_ERASURE_CODED_SHARES + 2..DECDS_NUM_ERASURE_CODED_SHARES + 4
+        );
+
+        let mut repairer = RepairingBlob::new(header.clone());
+
+        // Chunks of one batch don't validate against another batch.
+        assert_eq!(
+            repairer.add_extra_chunk(&second_extension, &first_shares[0][0], verify_with(7)),
+            Err(DecdsError::InvalidProofInChunk(0, DECDS_NUM_ERASURE_CODED_SHARES))
+        );
+
+        let other_blob = Blob::new(vec![0xde; 12 << 20]).expect("Must be able to prepare blob");
+        assert_eq!(
+            RepairingBlob::new(other_blob.get_blob_header().clone()).add_extra_chunk(&first_extension, &first_shares[0][0], verify_with(7)),
+            Err(DecdsError::ForeignShareExtension)
+        );
+
+        // Anyone can commit to extra shares of the blob, but only the publisher's extensions are accepted.
+        let mut forged_extension = first_extension.clone();
+        forged_extension.signature = sign_with(8)(&forged_extension.get_extension_id());
+        assert!(forged_extension.belongs_to(&header));
+        assert_eq!(
+            repairer.add_extra_chunk(&forged_extension, &first_shares[0][0], verify_with(7)),
+            Err(DecdsError::InvalidShareExtensionSignature)
+        );
+
+        let mut tampered_extension = first_extension.clone();
+        tampered_extension.num_shares += 1;
+        assert_eq!(
+            repairer.add_extra_chunk(&tampered_extension, &first_shares[0][0], verify_with(7)),
+            Err(DecdsError::InvalidShareExtensionSignature)
+        );
+
+        for (extension, shares) in [(&first_extension, &first_shares), (&second_extension, &second_shares)] {
+            for chunk in shares.iter().flatten() {
+                repairer
+                    .add_extra_chunk(extension, chunk, verify_with(7))
+                    .expect("Must be able to add extra chunk");
+            }
+        }
+
+        for share_id in 0..num_original_chunks - 4 {
+            for chunk in blob.get_share(share_id).expect("Must be able to get erasure-coded share") {
+                repairer.add_chunk(&chunk).expect("Must be able to add chunk");
+            }
+        }
+
+        let repaired = (0..header.get_num_chunksets())
+            .flat_map(|chunkset_id| repairer.get_repaired_chunkset(chunkset_id).expect("Must be able to repair chunkset"))
+            .collect::<Vec<u8>>();
+        assert_eq!(repaired, blob_data);
+    }
+}
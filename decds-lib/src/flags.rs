@@ -0,0 +1,155 @@
+use crate::errors::DecdsError;
+use serde::{Deserialize, Serialize};
+
+/// Blob-level content flags, telling whether the blob data was encrypted and/or compressed, before it was handed to
+/// `decds`. They're recorded in the `BlobHeader`, and carried in the wire envelope of each of its `ProofCarryingChunk`s, so
+/// that storage nodes and relays, which never see the header, can reject chunks of plaintext blobs, in deployments that
+/// mandate ciphertext-only storage.
+///
+/// `decds` neither encrypts nor compresses anything itself, the flags only describe what the caller did. They aren't part
+/// of chunk digests, so they don't change commitments, but `BlobHeader::validate_chunk` rejects chunks, whose flags differ
+/// from those of the header, so flags stripped off a chunk in transit don't go unnoticed.
+///
+/// ```rust
+/// use decds_lib::ContentFlags;
+///
+/// let flags = ContentFlags::new().encrypted(true);
+/// assert!(flags.is_encrypted());
+/// assert!(!flags.is_compressed());
+/// assert_eq!(ContentFlags::from_bits(flags.bits()), Ok(flags));
+/// ```
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ContentFlags(u8);
+
+impl ContentFlags {
+    /// Bit set if the blob data is ciphertext.
+    const ENCRYPTED: u8 = 1 << 0;
+    /// Bit set if the blob data is compressed.
+    const COMPRESSED: u8 = 1 << 1;
+    /// All bits, which have a meaning. Others must be unset.
+    const KNOWN_BITS: u8 = Self::ENCRYPTED | Self::COMPRESSED;
+
+    /// Creates flags of a blob, which is neither encrypted nor compressed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the blob data is encrypted.
+    pub fn encrypted(self, encrypted: bool) -> Self {
+        self.with_bit(Self::ENCRYPTED, encrypted)
+    }
+
+    /// Sets whether the blob data is compressed.
+    pub fn compressed(self, compressed: bool) -> Self {
+        self.with_bit(Self::COMPRESSED, compressed)
+    }
+
+    /// Returns `true` if the blob data is encrypted.
+    pub fn is_encrypted(&self) -> bool {
+        self.0 & Self::ENCRYPTED != 0
+    }
+
+    /// Returns `true` if the blob data is compressed.
+    pub fn is_compressed(&self) -> bool {
+        self.0 & Self::COMPRESSED != 0
+    }
+
+    /// Returns the flags as a single byte, as they are encoded on the wire.
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
+
+    /// Decodes flags from a single byte, as returned by `Self::bits`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bits` - Encoded flags.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Self)` containing the decoded flags if successful.
+    /// - `Err(DecdsError::InvalidContentFlags)` if any bit, which has no meaning, is set, e.g. by a newer version.
+    pub fn from_bits(bits: u8) -> Result<Self, DecdsError> {
+        let flags = ContentFlags(bits);
+        flags.validate()?;
+
+        Ok(flags)
+    }
+
+    /// Checks that no bit, which has no meaning, is set, e.g. in flags deserialized as part of a header or chunk.
+    pub(crate) fn validate(&self) -> Result<(), DecdsError> {
+        if self.0 & !Self::KNOWN_BITS != 0 {
+            return Err(DecdsError::InvalidContentFlags(self.0));
+        }
+
+        Ok(())
+    }
+
+    fn with_bit(mut self, bit: u8, set: bool) -> Self {
+        if set {
+            self.0 |= bit;
+        } else {
+            self.0 &= !bit;
+        }
+        self
+    }
+}
+
+#[cfg(all(test, feature = "coding"))]
+mod tests {
+    use crate::{Blob, BlobHeader, ContentFlags, DecdsBuilder, DecdsError, ProofCarryingChunk};
+
+    #[test]
+    fn test_content_flags_are_carried_by_header_and_chunks() {
+        assert_eq!(ContentFlags::from_bits(0b100), Err(DecdsError::InvalidContentFlags(0b100)));
+        assert_eq!(
+            ContentFlags::new().encrypted(true).compressed(true).encrypted(false),
+            ContentFlags::new().compressed(true)
+        );
+
+        let flags = ContentFlags::new().encrypted(true);
+        let blob_data = vec![0xde; 3 << 20];
+
+        for lazy_shares in [false, true] {
+            let blob = DecdsBuilder::new()
+                .content_flags(flags)
+                .lazy_shares(lazy_shares)
+                .build(blob_data.clone())
+                .expect("Must be able to prepare blob");
+            let header = blob.get_blob_header();
+            assert_eq!(header.get_content_flags(), flags);
+
+            let header_bytes = header.to_bytes().expect("Must be able to serialize header");
+            let (deserialized_header, _) = BlobHeader::from_bytes(&header_bytes).expect("Must be able to deserialize header");
+            assert_eq!(deserialized_header.get_content_flags(), flags);
+            assert_eq!(&BlobHeader::from_reader(&mut &header_bytes[..]).expect("Must be able to read header"), header);
+
+            for chunk in blob.get_share(0).expect("Must be able to get share") {
+                assert_eq!(chunk.get_content_flags(), flags);
+
+                let chunk_bytes = chunk.to_bytes().expect("Must be able to serialize chunk");
+                let (deserialized_chunk, _) = ProofCarryingChunk::from_bytes(&chunk_bytes).expect("Must be able to deserialize chunk");
+                let (read_chunk, _) = ProofCarryingChunk::from_reader(&mut &chunk_bytes[..]).expect("Must be able to read chunk");
+                assert_eq!(deserialized_chunk, chunk);
+                assert_eq!(read_chunk, chunk);
+                assert!(header.validate_chunk(&chunk));
+
+                // Flags aren't committed to by the chunk digest, but the header catches chunks, whose flags were stripped.
+                let stripped_chunk = chunk.clone().with_content_flags(ContentFlags::new());
+                assert_eq!(stripped_chunk.get_chunk_digest(), chunk.get_chunk_digest());
+                assert!(!header.validate_chunk(&stripped_chunk));
+            }
+        }
+
+        let plain_blob = Blob::new(blob_data).expect("Must be able to prepare blob");
+        assert_eq!(plain_blob.get_blob_header().get_content_flags(), ContentFlags::new());
+        assert!(
+            !plain_blob
+                .get_chunk(0, 0)
+                .expect("Must be able to get chunk")
+                .get_content_flags()
+                .is_encrypted()
+        );
+    }
+}
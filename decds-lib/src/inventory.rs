@@ -0,0 +1,330 @@
+use crate::{
+    availability::{ShareAvailability, ShareBitmap},
+    consts::{DECDS_BINCODE_CONFIG, DECDS_NUM_ERASURE_CODED_SHARES},
+    errors::DecdsError,
+};
+use serde::{Deserialize, Serialize};
+
+/// Availability of erasure-coded chunks of a blob across storage nodes, i.e. which node holds which share of which
+/// chunkset. Each node keeps a bitmap of available share IDs per chunkset, of `ChunkInventory::BITMAP_BYTE_LEN` bytes,
+/// so that inventories are cheap to exchange between nodes and to merge.
+///
+/// Nodes are kept in the order they are first recorded, which is the order of preference for fetching chunks.
+///
+/// ```rust
+/// use decds_lib::{Blob, ChunkInventory};
+///
+/// let blob = Blob::new(vec![0xcd; 1024]).expect("Failed to create blob");
+/// let header = blob.get_blob_header();
+///
+/// let mut inventory = ChunkInventory::new(header.get_blob_id(), header.get_num_chunksets());
+/// inventory.record("node-a", 0, 3).expect("Failed to record chunk");
+///
+/// let mut other = ChunkInventory::new(header.get_blob_id(), header.get_num_chunksets());
+/// other.record("node-b", 0, 7).expect("Failed to record chunk");
+///
+/// let bytes = other.to_bytes().expect("Failed to serialize inventory");
+/// inventory.merge(&ChunkInventory::from_bytes(&bytes).expect("Failed to deserialize inventory")).expect("Failed to merge inventory");
+///
+/// assert_eq!(inventory.entries().collect::<Vec<_>>(), vec![(0, 3, "node-a"), (0, 7, "node-b")]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChunkInventory {
+    blob_id: blake3::Hash,
+    num_chunksets: u64,
+    nodes: Vec<(String, Vec<u8>)>,
+}
+
+impl ChunkInventory {
+    /// Byte length of the bitmap of available share IDs of a chunkset, where bit `share_id % 8` of byte `share_id / 8` is
+    /// set if the share is available.
+    pub const BITMAP_BYTE_LEN: usize = DECDS_NUM_ERASURE_CODED_SHARES.div_ceil(8);
+
+    /// Creates an empty inventory of the blob with `blob_id`, having `num_chunksets` chunksets.
+    pub fn new(blob_id: blake3::Hash, num_chunksets: usize) -> Self {
+        ChunkInventory {
+            blob_id,
+            num_chunksets: num_chunksets as u64,
+            nodes: Vec::new(),
+        }
+    }
+
+    /// Returns the ID of the blob, this inventory is of.
+    pub fn get_blob_id(&self) -> blake3::Hash {
+        self.blob_id
+    }
+
+    /// Returns the number of chunksets of the blob, this inventory is of.
+    pub fn get_num_chunksets(&self) -> usize {
+        self.num_chunksets as usize
+    }
+
+    /// Returns the nodes listed in this inventory, in order of preference.
+    pub fn get_nodes(&self) -> impl Iterator<Item = &str> + '_ {
+        self.nodes.iter().map(|(node, _)| node.as_str())
+    }
+
+    /// Records that `node` holds the chunk of share `share_id` of chunkset `chunkset_id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - Identifier of the storage node, e.g. its address.
+    /// * `chunkset_id` - The ID of the chunkset.
+    /// * `share_id` - The ID of the erasure-coded share.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(())` if successful.
+    /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_id` is out of bounds.
+    /// - `Err(DecdsError::InvalidErasureCodedShareId)` if `share_id` is not less than `DECDS_NUM_ERASURE_CODED_SHARES`.
+    pub fn record(&mut self, node: &str, chunkset_id: usize, share_id: usize) -> Result<(), DecdsError> {
+        if chunkset_id >= self.get_num_chunksets() {
+            return Err(DecdsError::InvalidChunksetId(chunkset_id, self.get_num_chunksets()));
+        }
+        if share_id >= DECDS_NUM_ERASURE_CODED_SHARES {
+            return Err(DecdsError::InvalidErasureCodedShareId(share_id));
+        }
+
+        let bitmaps = self.get_bitmaps_mut(node);
+        bitmaps[chunkset_id * Self::BITMAP_BYTE_LEN + share_id / 8] |= 1u8 << (share_id % 8);
+
+        Ok(())
+    }
+
+    /// Returns `true` if `node` is known to hold the chunk of share `share_id` of chunkset `chunkset_id`.
+    pub fn has_chunk(&self, node: &str, chunkset_id: usize, share_id: usize) -> bool {
+        chunkset_id < self.get_num_chunksets()
+            && share_id < DECDS_NUM_ERASURE_CODED_SHARES
+            && self
+                .nodes
+                .iter()
+                .find(|(known_node, _)| known_node == node)
+                .is_some_and(|(_, bitmaps)| Self::is_bit_set(bitmaps, chunkset_id, share_id))
+    }
+
+    /// Returns availability of shares held by `node`, or `None` if the node isn't listed in this inventory.
+    pub fn get_node_availability(&self, node: &str) -> Option<ShareAvailability> {
+        self.nodes
+            .iter()
+            .find(|(known_node, _)| known_node == node)
+            .map(|(_, bitmaps)| Self::to_availability(bitmaps))
+    }
+
+    /// Returns availability of shares held by any of the listed nodes.
+    pub fn get_availability(&self) -> ShareAvailability {
+        let mut bitmaps = vec![0 as ShareBitmap; self.get_num_chunksets()];
+
+        self.nodes.iter().for_each(|(_, node_bitmaps)| {
+            bitmaps
+                .iter_mut()
+                .zip(node_bitmaps.chunks_exact(Self::BITMAP_BYTE_LEN))
+                .for_each(|(bitmap, node_bitmap)| *bitmap |= Self::to_share_bitmap(node_bitmap));
+        });
+
+        ShareAvailability::from_bitmaps(bitmaps)
+    }
+
+    /// Returns all available chunks, as `(chunkset_id, share_id, node)` tuples, ordered by node preference, then by
+    /// chunkset ID and then by share ID.
+    pub fn entries(&self) -> impl Iterator<Item = (usize, usize, &str)> + '_ {
+        self.nodes.iter().flat_map(move |(node, bitmaps)| {
+            (0..self.get_num_chunksets()).flat_map(move |chunkset_id| {
+                (0..DECDS_NUM_ERASURE_CODED_SHARES)
+                    .filter(move |&share_id| Self::is_bit_set(bitmaps, chunkset_id, share_id))
+                    .map(move |share_id| (chunkset_id, share_id, node.as_str()))
+            })
+        })
+    }
+
+    /// Merges availability information of another inventory of the same blob into this one. Chunks of nodes known to both
+    /// are united, while nodes only known to `other` are appended, after all nodes of this inventory.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The `ChunkInventory` to merge in.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(())` if successful.
+    /// - `Err(DecdsError::ChunkInventoryMismatch)` if `other` is an inventory of another blob.
+    pub fn merge(&mut self, other: &ChunkInventory) -> Result<(), DecdsError> {
+        if self.blob_id != other.blob_id || self.num_chunksets != other.num_chunksets {
+            return Err(DecdsError::ChunkInventoryMismatch);
+        }
+
+        other.nodes.iter().for_each(|(node, other_bitmaps)| {
+            self.get_bitmaps_mut(node)
+                .iter_mut()
+                .zip(other_bitmaps)
+                .for_each(|(byte, other_byte)| *byte |= other_byte);
+        });
+
+        Ok(())
+    }
+
+    /// Serializes the `ChunkInventory` into a vector of bytes using `bincode`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Vec<u8>)` containing the serialized bytes if successful.
+    /// - `Err(DecdsError::ChunkInventorySerializationFailed)` if serialization fails.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, DecdsError> {
+        bincode::serde::encode_to_vec(self, DECDS_BINCODE_CONFIG).map_err(|err| DecdsError::ChunkInventorySerializationFailed(err.to_string()))
+    }
+
+    /// Deserializes a `ChunkInventory` from a byte slice using `bincode`, checking that every node has a bitmap of each
+    /// chunkset and that no share beyond `DECDS_NUM_ERASURE_CODED_SHARES` is marked available.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The byte slice holding exactly one serialized inventory.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Self)` containing the deserialized `ChunkInventory` if successful.
+    /// - `Err(DecdsError::ChunkInventoryDeserializationFailed)` if deserialization fails or the inventory is malformed.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecdsError> {
+        let (inventory, n) = bincode::serde::decode_from_slice::<ChunkInventory, bincode::config::Configuration>(bytes, DECDS_BINCODE_CONFIG)
+            .map_err(|err| DecdsError::ChunkInventoryDeserializationFailed(err.to_string()))?;
+
+        if n != bytes.len() {
+            return Err(DecdsError::ChunkInventoryDeserializationFailed(format!(
+                "{} trailing bytes after chunk inventory",
+                bytes.len() - n
+            )));
+        }
+
+        let expected_bitmaps_byte_len = usize::try_from(inventory.num_chunksets)
+            .ok()
+            .and_then(|num_chunksets| num_chunksets.checked_mul(Self::BITMAP_BYTE_LEN));
+        let unused_bits_mask = !(u8::MAX >> ((8 - DECDS_NUM_ERASURE_CODED_SHARES % 8) % 8));
+
+        for (node, bitmaps) in &inventory.nodes {
+            if Some(bitmaps.len()) != expected_bitmaps_byte_len {
+                return Err(DecdsError::ChunkInventoryDeserializationFailed(format!(
+                    "node {} has {}B of bitmaps, for {} chunksets",
+                    node,
+                    bitmaps.len(),
+                    inventory.num_chunksets
+                )));
+            }
+            if bitmaps
+                .chunks(Self::BITMAP_BYTE_LEN)
+                .any(|bitmap| bitmap[Self::BITMAP_BYTE_LEN - 1] & unused_bits_mask != 0)
+            {
+                return Err(DecdsError::ChunkInventoryDeserializationFailed(format!(
+                    "node {} holds non-existent shares",
+                    node
+                )));
+            }
+        }
+
+        Ok(inventory)
+    }
+
+    fn get_bitmaps_mut(&mut self, node: &str) -> &mut Vec<u8> {
+        let node_idx = match self.nodes.iter().position(|(known_node, _)| known_node == node) {
+            Some(node_idx) => node_idx,
+            None => {
+                self.nodes.push((node.to_string(), vec![0u8; self.get_num_chunksets() * Self::BITMAP_BYTE_LEN]));
+                self.nodes.len() - 1
+            }
+        };
+
+        &mut self.nodes[node_idx].1
+    }
+
+    fn to_availability(bitmaps: &[u8]) -> ShareAvailability {
+        ShareAvailability::from_bitmaps(bitmaps.chunks_exact(Self::BITMAP_BYTE_LEN).map(Self::to_share_bitmap).collect())
+    }
+
+    fn to_share_bitmap(bitmap: &[u8]) -> ShareBitmap {
+        bitmap
+            .iter()
+            .rev()
+            .fold(0 as ShareBitmap, |share_bitmap, &byte| (share_bitmap << 8) | byte as ShareBitmap)
+    }
+
+    fn is_bit_set(bitmaps: &[u8], chunkset_id: usize, share_id: usize) -> bool {
+        (bitmaps[chunkset_id * Self::BITMAP_BYTE_LEN + share_id / 8] >> (share_id % 8)) & 1 == 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DECDS_NUM_ERASURE_CODED_SHARES, errors::DecdsError, inventory::ChunkInventory};
+
+    #[test]
+    fn test_chunk_inventory_record_merge_and_serialize() {
+        let blob_id = blake3::hash(b"blob");
+
+        let mut inventory = ChunkInventory::new(blob_id, 3);
+        inventory.record("node-b", 2, 15).unwrap();
+        inventory.record("node-a", 0, 0).unwrap();
+        inventory.record("node-b", 0, 9).unwrap();
+
+        assert_eq!(inventory.record("node-a", 3, 0), Err(DecdsError::InvalidChunksetId(3, 3)));
+        assert_eq!(
+            inventory.record("node-a", 0, DECDS_NUM_ERASURE_CODED_SHARES),
+            Err(DecdsError::InvalidErasureCodedShareId(DECDS_NUM_ERASURE_CODED_SHARES))
+        );
+
+        let mut other = ChunkInventory::new(blob_id, 3);
+        other.record("node-c", 1, 1).unwrap();
+        other.record("node-a", 1, 8).unwrap();
+        inventory.merge(&other).unwrap();
+
+        assert_eq!(inventory.get_nodes().collect::<Vec<&str>>(), vec!["node-b", "node-a", "node-c"]);
+        assert_eq!(
+            inventory.entries().collect::<Vec<(usize, usize, &str)>>(),
+            vec![(0, 9, "node-b"), (2, 15, "node-b"), (0, 0, "node-a"), (1, 8, "node-a"), (1, 1, "node-c")]
+        );
+        assert!(inventory.has_chunk("node-a", 1, 8));
+        assert!(!inventory.has_chunk("node-a", 1, 9));
+        assert!(!inventory.has_chunk("node-d", 1, 8));
+
+        let availability = inventory.get_availability();
+        assert_eq!(availability.count(0), 2);
+        assert!(availability.contains(2, 15));
+        assert!(availability.contains(1, 1) && availability.contains(1, 8));
+        assert_eq!(inventory.get_node_availability("node-a").map(|availability| availability.count(1)), Some(1));
+        assert_eq!(inventory.get_node_availability("node-d"), None);
+
+        let bytes = inventory.to_bytes().unwrap();
+        assert_eq!(ChunkInventory::from_bytes(&bytes), Ok(inventory.clone()));
+        assert!(matches!(
+            ChunkInventory::from_bytes(&[bytes.as_slice(), &[0]].concat()),
+            Err(DecdsError::ChunkInventoryDeserializationFailed(_))
+        ));
+
+        assert_eq!(
+            inventory.merge(&ChunkInventory::new(blake3::hash(b"other blob"), 3)),
+            Err(DecdsError::ChunkInventoryMismatch)
+        );
+        assert_eq!(inventory.merge(&ChunkInventory::new(blob_id, 4)), Err(DecdsError::ChunkInventoryMismatch));
+    }
+
+    #[test]
+    fn test_chunk_inventory_rejects_malformed_bitmaps() {
+        let mut inventory = ChunkInventory::new(blake3::hash(b"blob"), 2);
+        inventory.record("node-a", 1, 3).unwrap();
+
+        let mut truncated = inventory.clone();
+        truncated.nodes[0].1.pop();
+        assert!(matches!(
+            ChunkInventory::from_bytes(&truncated.to_bytes().unwrap()),
+            Err(DecdsError::ChunkInventoryDeserializationFailed(_))
+        ));
+
+        let mut huge = inventory.clone();
+        huge.num_chunksets = u64::MAX;
+        assert!(matches!(
+            ChunkInventory::from_bytes(&huge.to_bytes().unwrap()),
+            Err(DecdsError::ChunkInventoryDeserializationFailed(_))
+        ));
+    }
+}
@@ -0,0 +1,189 @@
+use std::path::{Path, PathBuf};
+
+/// On-disk layout of an erasure-coded blob directory, as written by `decds break`.
+///
+/// ```text
+/// <root>/
+/// ├── metadata.commit              # serialized `BlobHeader`
+/// ├── chunkset.0/
+/// │   ├── share00.data             # serialized `ProofCarryingChunk` of share 0
+/// │   └── ...
+/// ├── chunkset.1/
+/// │   └── ...
+/// └── quarantine/                  # chunk files which failed verification
+///     ├── chunkset.0.share07.data
+///     └── reasons.log
+/// ```
+///
+/// All paths are built component-wise on top of the root path, so non UTF-8 roots are handled as is. On Windows, the
+/// root is turned into an absolute, extended-length (`\\?\` prefixed) path, so that deeply nested blob directories are
+/// not limited by `MAX_PATH`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlobDirLayout {
+    root: PathBuf,
+}
+
+impl BlobDirLayout {
+    /// Name of the file holding the serialized `BlobHeader`.
+    pub const METADATA_FILE_NAME: &str = "metadata.commit";
+    /// Name of the directory holding chunk files, which failed verification.
+    pub const QUARANTINE_DIR_NAME: &str = "quarantine";
+    /// Name of the log file, in the quarantine directory, recording why chunk files are quarantined.
+    pub const QUARANTINE_REASONS_LOG_FILE_NAME: &str = "reasons.log";
+
+    /// Creates the layout of a blob directory rooted at `root`.
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        BlobDirLayout {
+            root: to_extended_length_path(root.as_ref()),
+        }
+    }
+
+    /// Returns the root directory of the blob.
+    pub fn get_root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the path of the file holding the serialized `BlobHeader`.
+    pub fn get_metadata_path(&self) -> PathBuf {
+        self.root.join(Self::METADATA_FILE_NAME)
+    }
+
+    /// Returns the name of the directory holding all chunks of a chunkset.
+    pub fn get_chunkset_dir_name(chunkset_id: usize) -> String {
+        format!("chunkset.{}", chunkset_id)
+    }
+
+    /// Returns the name of the file holding the chunk of a share, within its chunkset directory.
+    pub fn get_chunk_file_name(share_id: usize) -> String {
+        format!("share{:02}.data", share_id)
+    }
+
+    /// Returns the path of the directory holding all chunks of a chunkset.
+    pub fn get_chunkset_dir_path(&self, chunkset_id: usize) -> PathBuf {
+        self.root.join(Self::get_chunkset_dir_name(chunkset_id))
+    }
+
+    /// Returns the path of the file holding the chunk of a share in a chunkset.
+    pub fn get_chunk_path(&self, chunkset_id: usize, share_id: usize) -> PathBuf {
+        self.get_chunkset_dir_path(chunkset_id).join(Self::get_chunk_file_name(share_id))
+    }
+
+    /// Returns the path of the quarantine directory.
+    pub fn get_quarantine_dir_path(&self) -> PathBuf {
+        self.root.join(Self::QUARANTINE_DIR_NAME)
+    }
+
+    /// Returns the path, a chunk file is moved to, when it is quarantined.
+    pub fn get_quarantined_chunk_path(&self, chunkset_id: usize, share_id: usize) -> PathBuf {
+        self.get_quarantine_dir_path()
+            .join(format!("{}.{}", Self::get_chunkset_dir_name(chunkset_id), Self::get_chunk_file_name(share_id)))
+    }
+
+    /// Returns the path of the log file, recording why chunk files are quarantined.
+    pub fn get_quarantine_reasons_log_path(&self) -> PathBuf {
+        self.get_quarantine_dir_path().join(Self::QUARANTINE_REASONS_LOG_FILE_NAME)
+    }
+}
+
+/// Turns `path` into an absolute, extended-length path on Windows, i.e. `C:\dir` becomes `\\?\C:\dir` and
+/// `\\server\share\dir` becomes `\\?\UNC\server\share\dir`. Paths which are already verbatim are kept as is.
+#[cfg(windows)]
+fn to_extended_length_path(path: &Path) -> PathBuf {
+    use std::{
+        ffi::OsString,
+        path::{Component, Prefix},
+    };
+
+    let Ok(absolute_path) = std::path::absolute(path) else {
+        return path.to_path_buf();
+    };
+
+    let mut components = absolute_path.components();
+    let mut extended_path = match components.next() {
+        Some(Component::Prefix(prefix)) => match prefix.kind() {
+            Prefix::Disk(_) => {
+                let mut extended_path = OsString::from(r"\\?\");
+                extended_path.push(prefix.as_os_str());
+                extended_path
+            }
+            Prefix::UNC(server, share) => {
+                let mut extended_path = OsString::from(r"\\?\UNC\");
+                extended_path.push(server);
+                extended_path.push(r"\");
+                extended_path.push(share);
+                extended_path
+            }
+            _ => return absolute_path,
+        },
+        _ => return absolute_path,
+    };
+
+    let mut has_normal_component = false;
+    components.for_each(|component| {
+        if let Component::Normal(name) = component {
+            extended_path.push(r"\");
+            extended_path.push(name);
+            has_normal_component = true;
+        }
+    });
+
+    if !has_normal_component {
+        extended_path.push(r"\");
+    }
+
+    PathBuf::from(extended_path)
+}
+
+#[cfg(not(windows))]
+fn to_extended_length_path(path: &Path) -> PathBuf {
+    path.to_path_buf()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::layout::BlobDirLayout;
+    use std::path::Path;
+
+    #[test]
+    fn test_blob_dir_layout_paths() {
+        let layout = BlobDirLayout::new("blob-dir");
+        let root = layout.get_root().to_path_buf();
+
+        assert_eq!(layout.get_metadata_path(), root.join("metadata.commit"));
+        assert_eq!(layout.get_chunkset_dir_path(3), root.join("chunkset.3"));
+        assert_eq!(layout.get_chunk_path(3, 7), root.join("chunkset.3").join("share07.data"));
+        assert_eq!(layout.get_chunk_path(12, 15), root.join("chunkset.12").join("share15.data"));
+        assert_eq!(layout.get_quarantine_dir_path(), root.join("quarantine"));
+        assert_eq!(layout.get_quarantined_chunk_path(3, 7), root.join("quarantine").join("chunkset.3.share07.data"));
+        assert_eq!(layout.get_quarantine_reasons_log_path(), root.join("quarantine").join("reasons.log"));
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_blob_dir_layout_keeps_root_as_is() {
+        assert_eq!(BlobDirLayout::new("blob-dir").get_root(), Path::new("blob-dir"));
+        assert_eq!(BlobDirLayout::new("/tmp/a/../blob-dir").get_root(), Path::new("/tmp/a/../blob-dir"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_blob_dir_layout_with_non_utf8_root() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt, path::PathBuf};
+
+        let root = PathBuf::from(OsStr::from_bytes(b"blob-\xff-dir"));
+        let layout = BlobDirLayout::new(&root);
+
+        assert_eq!(layout.get_chunk_path(0, 1), root.join("chunkset.0").join("share01.data"));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_blob_dir_layout_uses_extended_length_root() {
+        assert_eq!(BlobDirLayout::new(r"C:\blobs\blob-dir").get_root(), Path::new(r"\\?\C:\blobs\blob-dir"));
+        assert_eq!(
+            BlobDirLayout::new(r"\\server\share\blob-dir").get_root(),
+            Path::new(r"\\?\UNC\server\share\blob-dir")
+        );
+        assert_eq!(BlobDirLayout::new(r"\\?\C:\blob-dir").get_root(), Path::new(r"\\?\C:\blob-dir"));
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{
+    blob::BlobHeader,
+    chunk::ProofCarryingChunk,
+    chunkset::ChunkSet,
+    codec::{Codec, ErasureCodec},
+    config::BlobConfig,
+    errors::DecdsError,
+    events::EventHandler,
+    flags::ContentFlags,
+    merkle_tree::MerkleTree,
+};
+use rayon::prelude::*;
+
+/// Erasure-coded body of a blob, which keeps only the original blob data, the codec choosing coding vectors, e.g. derived
+/// from a seed, and Merkle trees over chunk digests, instead of all erasure-coded chunks. A chunk is coded again, whenever
+/// it's asked for. As the codec chooses the same coding vectors again, it's the very chunk, which was committed to while
+/// building the blob.
+pub(crate) struct LazyShares {
+    data: Vec<u8>,
+    config: BlobConfig,
+    codec: Box<dyn ErasureCodec>,
+    chunkset_trees: Vec<MerkleTree>,
+    blob_proofs: Vec<Vec<blake3::Hash>>,
+    content_flags: ContentFlags,
+}
+
+impl LazyShares {
+    /// Erasure-codes all chunks of all chunksets of `data` once, only for committing to their digests, dropping each chunk
+    /// right after it's hashed.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The raw blob data, neither empty nor padded.
+    /// * `digest` - BLAKE3 digest of `data`.
+    /// * `config` - Valid erasure-coding geometry.
+    /// * `seed` - 32-bytes seed, used for deriving coding vectors.
+    /// * `handler` - The `EventHandler` to notify about build progress.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok((BlobHeader, LazyShares))` containing the header of the blob and its lazily coded body if successful.
+    /// - `Err(DecdsError::NoLeafNodesToBuildMerkleTreeOn)` if `data` is empty.
+    /// - `Err(DecdsError::CodecNotCompiledIn)` if the codec `config.get_codec()` is disabled by a feature flag.
+    pub(crate) fn new(
+        data: Vec<u8>,
+        digest: blake3::Hash,
+        config: &BlobConfig,
+        seed: [u8; 32],
+        handler: &dyn EventHandler,
+    ) -> Result<(BlobHeader, Self), DecdsError> {
+        let codec = Codec::new_encoding_codec(config, seed)?;
+        let chunkset_trees = data
+            .par_chunks(config.get_chunkset_byte_length())
+            .enumerate()
+            .map(|(chunkset_id, chunkset_data)| {
+                let encoder = Self::build_encoder(chunkset_data, config);
+
+                let mut merkle_leaves = (0..config.get_num_shares())
+                    .into_par_iter()
+                    .map(|local_chunk_id| ChunkSet::code_chunk(&encoder, chunkset_id, local_chunk_id, codec.as_ref()).digest())
+                    .collect::<Vec<blake3::Hash>>();
+                merkle_leaves.resize(ChunkSet::NUM_ERASURE_CODED_CHUNKS, blake3::Hash::from_bytes([0u8; blake3::OUT_LEN]));
+
+                let merkle_tree = unsafe { MerkleTree::new(merkle_leaves).unwrap_unchecked() };
+                handler.on_chunkset_encoded(chunkset_id, merkle_tree.get_root_commitment());
+
+                merkle_tree
+            })
+            .collect::<Vec<MerkleTree>>();
+
+        let blob_tree = MerkleTree::new(chunkset_trees.iter().map(|merkle_tree| merkle_tree.get_root_commitment()).collect())?;
+        let blob_proofs = blob_tree.generate_all_proofs();
+        let header = BlobHeader::new(data.len(), *config, digest, blob_tree.get_root_commitment(), blob_tree.into_leaf_nodes());
+
+        Ok((
+            header,
+            LazyShares {
+                data,
+                config: *config,
+                codec,
+                chunkset_trees,
+                blob_proofs,
+                content_flags: ContentFlags::default(),
+            },
+        ))
+    }
+
+    /// Sets content flags of the blob, which every coded chunk carries. See `ContentFlags`.
+    pub(crate) fn set_content_flags(&mut self, content_flags: ContentFlags) {
+        self.content_flags = content_flags;
+    }
+
+    /// Returns the number of chunksets of the blob.
+    pub(crate) fn get_num_chunksets(&self) -> usize {
+        self.chunkset_trees.len()
+    }
+
+    /// Erasure-codes a single chunk, attaching proof of its inclusion in the blob to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset, assumed to be in bounds.
+    /// * `share_id` - The ID of the share, assumed to be in bounds.
+    ///
+    /// # Returns
+    ///
+    /// Returns the proof-carrying chunk, same as the one an eagerly built blob would hold.
+    pub(crate) fn get_chunk(&self, chunkset_id: usize, share_id: usize) -> ProofCarryingChunk {
+        let chunkset_byte_length = self.config.get_chunkset_byte_length();
+        let chunkset_data = self.data.chunks(chunkset_byte_length).nth(chunkset_id).unwrap_or_default();
+
+        let encoder = Self::build_encoder(chunkset_data, &self.config);
+        let chunk = ChunkSet::code_chunk(&encoder, chunkset_id, share_id, self.codec.as_ref());
+
+        let mut proof = unsafe { self.chunkset_trees[chunkset_id].generate_proof(share_id).unwrap_unchecked() };
+        proof.extend_from_slice(&self.blob_proofs[chunkset_id]);
+
+        ProofCarryingChunk::new(chunk, proof).with_content_flags(self.content_flags)
+    }
+
+    /// Returns digest of a single chunk, without coding it.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset, assumed to be in bounds.
+    /// * `share_id` - The ID of the share, assumed to be in bounds.
+    pub(crate) fn get_chunk_digest(&self, chunkset_id: usize, share_id: usize) -> blake3::Hash {
+        self.chunkset_trees[chunkset_id].get_leaf_nodes()[share_id]
+    }
+
+    /// Zero-pads at most a chunkset worth of data, preparing it for erasure-coding with the geometry `config`.
+    fn build_encoder(chunkset_data: &[u8], config: &BlobConfig) -> rlnc::full::encoder::Encoder {
+        let mut padded_data = chunkset_data.to_vec();
+        padded_data.resize(config.get_chunkset_byte_length(), 0);
+
+        unsafe { rlnc::full::encoder::Encoder::new(padded_data, config.get_num_original_chunks()).unwrap_unchecked() }
+    }
+}
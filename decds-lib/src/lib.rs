@@ -15,6 +15,8 @@
 //! Merkle trees for both chunkset-level and blob-level integrity.
 //!
 //! ```rust
+//! # #[cfg(feature = "coding")]
+//! # {
 //! use decds_lib::Blob;
 //! use rand::Rng; // Assuming `rand` is available in your project for example data
 //!
@@ -22,8 +24,13 @@
 //! let original_data: Vec<u8> = (0..1024 * 1024 * 50).map(|_| rng.random()).collect(); // 50MB of random data
 //! let blob = Blob::new(original_data).expect("Failed to create blob");
 //! println!("Blob created with size: {} bytes", blob.get_blob_header().get_blob_size());
+//! # }
 //! ```
 //!
+//! Data too large to be held in memory, e.g. a huge file, can be streamed in with `Blob::from_reader` instead, which
+//! reads and erasure-codes it one chunkset at a time, or memory-mapped with `Blob::from_file`, which erasure-codes
+//! chunksets in parallel, straight from the mapping. The latter is `unsafe`, as the file must not be modified meanwhile.
+//!
 //! ### 2. Retrieve Erasure-Coded Shares (Proof-Carrying Chunks)
 //!
 //! Once a `Blob` is created, you can retrieve its erasure-coded shares. Each share is a `Vec<ProofCarryingChunk>`,
@@ -32,6 +39,8 @@
 //! (which is 10) are needed to reconstruct the original data of that chunkset.
 //!
 //! ```rust
+//! # #[cfg(feature = "coding")]
+//! # {
 //! use decds_lib::{Blob, DECDS_NUM_ERASURE_CODED_SHARES};
 //! use rand::Rng;
 //!
@@ -47,6 +56,7 @@
 //!     let share = blob.get_share(share_id).expect("Failed to get share");
 //!     // Do something with the share, e.g., send it to a storage node
 //! }
+//! # }
 //! ```
 //!
 //! ### 3. Repair/Reconstruct a Blob
@@ -57,6 +67,8 @@
 //! you can retrieve its repaired data.
 //!
 //! ```rust
+//! # #[cfg(feature = "coding")]
+//! # {
 //! use decds_lib::{Blob, BlobHeader, ProofCarryingChunk, RepairingBlob, DECDS_NUM_ERASURE_CODED_SHARES, DecdsError};
 //! use rand::{Rng, seq::SliceRandom};
 //!
@@ -101,8 +113,11 @@
 //!         Err(e) => {
 //!             // Handle cases where the chunk is not useful or chunkset is already repaired
 //!             match e {
-//!                 DecdsError::ChunksetReadyToRepair(_) | DecdsError::ChunksetAlreadyRepaired(_) | DecdsError::InvalidProofInChunk(_) => {
-//!                     // Chunk is redundant, already repaired, or invalid; simply skip it.
+//!                 DecdsError::ChunksetReadyToRepair(_)
+//!                 | DecdsError::ChunksetAlreadyRepaired(_)
+//!                 | DecdsError::NonInnovativeChunk(_, _)
+//!                 | DecdsError::InvalidProofInChunk(_, _) => {
+//!                     // Chunk is redundant, already repaired, not innovative, or invalid; simply skip it.
 //!                     // In a real system, invalid chunks would indicate a security issue.
 //!                 },
 //!                 _ => {
@@ -121,20 +136,308 @@
 //!
 //! assert_eq!(original_data_copy, final_repaired_data);
 //! println!("Blob successfully repaired and verified!");
+//! # }
+//! ```
+//!
+//! ### 4. Verify Merkle Proofs Independently
+//!
+//! Each `ProofCarryingChunk` exposes its leaf digest and Merkle inclusion proof, which can be checked using
+//! `MerkleTree::verify_proof` against the blob root commitment. See `MerkleTree` and `ProofCarryingChunk::get_chunk_digest`
+//! for the exact tree construction and leaf digest format, if you want to verify proofs in another implementation.
+//!
+//! ```rust
+//! # #[cfg(feature = "coding")]
+//! # {
+//! use decds_lib::{Blob, MerkleTree};
+//! use rand::Rng;
+//!
+//! let mut rng = rand::thread_rng();
+//! let original_data: Vec<u8> = (0..1024).map(|_| rng.random()).collect();
+//! let blob = Blob::new(original_data).expect("Failed to create blob");
+//! let blob_root = blob.get_blob_header().get_root_commitment();
+//!
+//! let chunk = &blob.get_share(0).expect("Failed to get share")[0];
+//! assert!(MerkleTree::verify_proof(chunk.get_global_chunk_id(), chunk.get_chunk_digest(), chunk.get_proof(), blob_root));
+//! # }
 //! ```
+//!
+//! ## Verification Only
+//!
+//! Erasure-coding and repairing of blobs sit behind the default `coding` feature. Gateways and auditors, which never
+//! encode or decode, can depend on this crate with `default-features = false`, which compiles just `BlobHeader` and
+//! `ProofCarryingChunk` (de)serialization, digests and Merkle proof verification, pulling neither `rlnc` nor `rayon`.
+//! Gateways handling untrusted bytes can call `verify_chunk_bytes`, which never panics and returns a `ChunkVerdict`.
+//!
+//! ## Fault Injection
+//!
+//! The opt-in `testing` feature exposes `ChunkFault`, for corrupting chunks in controlled ways, and `FaultScenario`, for
+//! running break → damage → repair scenarios end-to-end, so that storage stacks built on top of this crate can check they
+//! reject damaged chunks and still recover blobs.
+//!
+//! ## Anchoring
+//!
+//! A `BlobAnchor` is a compact, fixed-width record of a blob's ID, root commitment, size, digest and record offsets digest,
+//! meant to be published on a blockchain or transparency log. A header fetched later, from an untrusted source, can be
+//! checked against it with `BlobAnchor::verify_header`, before using it to validate chunks. When a blob is appended to,
+//! and re-encoded with the same seed, `BlobHeader::generate_consistency_proof` lets auditors check, with
+//! `MerkleTree::verify_consistency_proof`, that the anchored chunksets were left unchanged, without downloading any data.
+//!
+//! ## Chunk Availability
+//!
+//! A `ChunkInventory` records which storage node holds which share of which chunkset, as one small bitmap per chunkset
+//! and node. Inventories of the same blob, collected by different nodes, can be merged and exchanged in their compact
+//! serialized form, to plan fetching chunks for repair. `ChunkInventory::get_availability` condenses it into a
+//! `ShareAvailability`, one fixed-size bitset of available shares per chunkset, which tells repairable chunksets apart by
+//! popcount, quickly enough for stores of millions of chunksets.
+//!
+//! ## Importing Reed-Solomon Shards
+//!
+//! Archives erasure-coded by another `(10, 16)` linear code over GF(2^8), with reduction polynomial `0x11d`, like most
+//! Reed-Solomon implementations use, can be migrated without re-encoding, when stripes are laid out like decds lays out
+//! chunksets. `BlobPipeline::from_coded_shards` wraps each shard as a chunk, with its row of the generator matrix as
+//! coding vector, and resumes the pipeline at its encode stage.
+//!
+//! ## Erasure-Coding Geometry
+//!
+//! By default, blobs are split into 10MB chunksets, each erasure-coded into 16 shares, any 10 of which repair it. A
+//! `BlobConfig`, handed to `DecdsBuilder::config`, picks other chunkset sizes and `(k, n)` codes, with `n` at most 16. It's
+//! recorded in the `BlobHeader`, so repairing and verifying chunks works the same for all geometries.
+//!
+//! Chunksets are RLNC coded by default. The opt-in `reed-solomon` feature compiles in `Codec::ReedSolomon`, a systematic
+//! code, whose first `k` shares hold chunkset data as is, and any `k` shares of which are guaranteed to repair a chunkset.
+//! Both implement `ErasureCodec`, which chooses coding vectors of chunks, and the codec is recorded in the `BlobConfig`.
+//! Without the feature, headers and chunks of Reed-Solomon coded blobs are still verified, but erasure-coding or repairing
+//! them fails with `DecdsError::CodecNotCompiledIn`.
+//!
+//! ## Distributed Encoding
+//!
+//! Chunksets of a blob can be erasure-coded independently, e.g. by workers on different machines, each calling
+//! `DecdsBuilder::encode_chunkset`, which yields chunks proving inclusion in their chunkset only. A coordinator, collecting
+//! just chunkset commitments, builds a `MerkleTree` over them and hands it to `attach_blob_proofs`, which extends proofs
+//! of chunks to the blob root commitment. `BlobCoordinator` does the bookkeeping for the coordinator: it assigns disjoint
+//! chunkset ranges to workers, collects their commitments, assembles the `BlobHeader` and hands each worker blob-level
+//! proofs of its chunksets only, for `attach_blob_proof`.
+//!
+//! ```rust
+//! # #[cfg(feature = "coding")]
+//! # {
+//! use decds_lib::{DecdsBuilder, MerkleTree, attach_blob_proofs};
+//!
+//! let builder = DecdsBuilder::new().seed([7u8; 32]);
+//! let (commitments, mut chunks): (Vec<_>, Vec<_>) = (0..2)
+//!     .map(|chunkset_id| builder.encode_chunkset(chunkset_id, vec![0xde; 1024]).expect("Failed to encode chunkset"))
+//!     .unzip();
+//!
+//! let blob_tree = MerkleTree::new(commitments).expect("Failed to build blob tree");
+//! chunks.iter_mut().try_for_each(|chunks| attach_blob_proofs(chunks, &blob_tree)).expect("Failed to attach proofs");
+//!
+//! assert!(chunks.iter().flatten().all(|chunk| chunk.validate_inclusion_in_blob(blob_tree.get_root_commitment())));
+//! # }
+//! ```
+//!
+//! ## Header Registry
+//!
+//! A `HeaderRegistry` maps human-readable names, blob IDs and blob digests to blob headers, so that a header can be looked
+//! up by whichever of them is at hand, with `HeaderRegistry::resolve`. It's serialized as a whole, as `decds tag` and
+//! `decds resolve` do, keeping it in a single file.
+//!
+//! ## Audit Log
+//!
+//! `AuditLog` hands out hash-chained `AuditRecord`s of chunk puts, gets, deletes, verification results and repairs, to be
+//! appended to an append-only file. `AuditLog::from_bytes` checks integrity of the persisted log and resumes its chain.
+//! `decds relay`, `decds verify` and `decds repair` append to such a log when given one, and `decds audit` checks it.
+//!
+//! ## Sealing
+//!
+//! A `Blob` keeps track of which shares it hands out. `Blob::seal` makes it refuse to hand out any other share, and returns
+//! a `SealedBlobHeader`, optionally signed, recording issued shares, so that auditors can tell chunks of unexpected extra
+//! shares apart with `SealedBlobHeader::validate_chunk`.
+//!
+//! ## Time-Boxed Repair
+//!
+//! `RepairingBlob::repair_with_deadline` pulls chunks from a source until a time budget runs out, then repairs whatever
+//! chunksets it can and returns a `PartialRepair`, describing exactly which chunksets still lack how many chunks, so that
+//! batch jobs can make progress across runs.
+//!
+//! ## Checkpointing Repair
+//!
+//! A `RepairingBlob` built with `RepairBuilder::checkpointing` can be checkpointed with `RepairingBlob::to_bytes`, and
+//! resumed with `RepairingBlob::from_bytes`, so that a long repair survives process restarts, without fetching chunks
+//! received before the checkpoint again.
+//!
+//! ## Streaming Repair
+//!
+//! `RepairingBlob::write_repaired` writes repaired data to any `std::io::Write` sink, chunkset after chunkset, in order, as
+//! far as they are ready, freeing each one's decoder as it goes, so that huge blobs are repaired straight into a file,
+//! without concatenating them in memory. Consumers handling repaired chunksets themselves can drain them front-to-back
+//! with `RepairingBlob::drain_ready_in_order`, instead.
+//!
+//! ## Repair Progress
+//!
+//! `RepairingBlob::get_progress` returns a `RepairProgress`, counting chunksets ready to repair or repaired, and useful
+//! chunks received out of those required, which is kept up to date as chunks are added, instead of being recomputed over
+//! all chunksets. An `EventHandler` is also told of each chunkset becoming ready, of every change in progress, and of the
+//! blob needing no more chunks.
+//!
+//! ## Repair Pool
+//!
+//! `RepairPool` is a reusable repair engine for integrators receiving chunks of many chunksets at once. Submitted chunks
+//! are validated and fed to per-chunkset repair tasks, running on a work stealing thread pool, and repaired chunksets are
+//! emitted on a channel, in completion order. Repairing a single blob, `RepairingBlob::add_chunks` takes a batch of chunks,
+//! verifying their proofs in parallel and decoding chunks of different chunksets concurrently.
+//!
+//! ## Recoding
+//!
+//! Relay and storage nodes holding some chunks of a chunkset can hand out fresh random linear combinations of them, with
+//! `Recoder`, without repairing the chunkset. `RecodedChunk`s carry no Merkle proof, so they can't be validated against
+//! the header, and a forged one would silently corrupt the repaired chunkset. They are meant for forwarding between relays
+//! only, which may recode what they got recoded, and `RepairingBlob` doesn't accept them.
+//!
+//! ## Extra Shares
+//!
+//! `Blob::generate_extra_shares` tops up redundancy after the initial distribution, with shares beyond those committed to
+//! by the header. Each batch of extra shares is committed to by a `ShareExtension`, signed by the publisher and published
+//! alongside the header, against which `RepairingBlob::add_extra_chunk` validates their `ExtraChunk`s, after checking
+//! its signature.
+//!
+//! ## Huge Headers
+//!
+//! A blob of hundreds of thousands of chunksets has a multi-megabyte header. `BlobHeader::write_to` and
+//! `BlobHeader::from_reader` stream it, in the very format of `BlobHeader::to_bytes`, without an intermediate buffer, and
+//! `BlobHeaderView` validates chunks against a serialized header, reading only the chunkset commitments it needs.
+//!
+//! ## Delegated Verification
+//!
+//! `BlobHeader::subset` makes a `BlobHeaderSubset`, keeping only commitments of chunksets of interest, e.g. those covering
+//! a byte range, each with a Merkle proof of inclusion in the root commitment. A verifier of those chunksets' chunks needs
+//! only this subset, a few kilobytes, instead of the full header, and only has to trust its root commitment. A single
+//! chunkset commitment can be proven with `BlobHeader::get_chunkset_commitment_proof`. Full headers are checked to hash up
+//! to their root commitment whenever they're deserialized, so a tampered list of chunkset commitments is rejected.
+//!
+//! ## Content Flags
+//!
+//! `DecdsBuilder::content_flags` marks a blob as encrypted and/or compressed by the caller. The `ContentFlags` are recorded
+//! in the `BlobHeader`, and carried in the wire envelope of each `ProofCarryingChunk`, so that storage nodes and relays can
+//! reject chunks of plaintext blobs, without the header. Validating a chunk against the header also checks its flags.
+//!
+//! ## Records
+//!
+//! `DecdsBuilder::record_offsets` registers start offsets of application records, e.g. rows or messages, which are kept in
+//! the `BlobHeader`. `BlobHeader::get_chunkset_ids_for_record` then tells which chunksets hold a record, so readers fetch
+//! only those and read it with `RepairingBlob::read_record`, without an offset index of their own.
+//!
+//! ## Durability Modeling
+//!
+//! Before committing data, `DurabilitySimulation` Monte-Carlo estimates the probability of losing a blob over time, given
+//! its number of chunksets, per-node failure probabilities and placement of shares on nodes, so that geometry and
+//! placement can be compared without storing anything.
 
+mod anchor;
+mod audit;
+mod availability;
 mod blob;
+#[cfg(feature = "coding")]
+mod builder;
+mod cache;
+#[cfg(feature = "coding")]
+mod checkpoint;
 mod chunk;
 mod chunkset;
+mod codec;
+mod commitment;
+mod config;
 mod consts;
+mod coordinator;
+#[cfg(feature = "coding")]
+mod deadline;
 mod errors;
+#[cfg(feature = "coding")]
+mod events;
+#[cfg(feature = "coding")]
+mod extension;
+mod flags;
+mod inventory;
+mod layout;
+#[cfg(feature = "coding")]
+mod lazy;
 mod merkle_tree;
+#[cfg(feature = "coding")]
+mod mmap;
+#[cfg(feature = "coding")]
+mod pipeline;
+#[cfg(feature = "coding")]
+mod pool;
+#[cfg(feature = "coding")]
+mod progress;
+#[cfg(feature = "coding")]
+mod recoder;
+mod registry;
+mod seal;
+mod segment;
+#[cfg(feature = "coding")]
+mod simulation;
+mod subset;
+#[cfg(feature = "coding")]
+mod test_vectors;
+#[cfg(any(feature = "testing", all(test, feature = "coding")))]
+mod testing;
+mod verify;
+mod version;
+mod view;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "coding"))]
 mod tests;
 
-pub use blob::{Blob, BlobHeader, RepairingBlob};
+pub use anchor::BlobAnchor;
+pub use audit::{AuditLog, AuditOperation, AuditRecord};
+pub use availability::{ShareAvailability, ShareBitmap};
+pub use blob::BlobHeader;
+#[cfg(feature = "coding")]
+pub use blob::{AddOutcome, Blob, ChunkAcceptancePolicy, RepairingBlob};
+#[cfg(feature = "coding")]
+pub use builder::{DecdsBuilder, RepairBuilder};
+pub use cache::ChunkValidationCache;
 pub use chunk::ProofCarryingChunk;
+#[cfg(feature = "coding")]
 pub use chunkset::RepairingChunkSet;
+pub use codec::Codec;
+#[cfg(feature = "reed-solomon")]
+pub use codec::ReedSolomonCodec;
+#[cfg(feature = "coding")]
+pub use codec::{ErasureCodec, RlncCodec};
+pub use commitment::{attach_blob_proof, attach_blob_proofs, compute_blob_root_commitment, compute_chunkset_commitment};
+pub use config::BlobConfig;
 pub use consts::DECDS_NUM_ERASURE_CODED_SHARES;
-pub use errors::DecdsError;
+pub use coordinator::BlobCoordinator;
+#[cfg(feature = "coding")]
+pub use deadline::PartialRepair;
+pub use errors::{ChunksetValidationError, DecdsError};
+#[cfg(feature = "coding")]
+pub use events::EventHandler;
+#[cfg(feature = "coding")]
+pub use extension::{ExtraChunk, ShareExtension};
+pub use flags::ContentFlags;
+pub use inventory::ChunkInventory;
+pub use layout::BlobDirLayout;
+pub use merkle_tree::MerkleTree;
+#[cfg(feature = "coding")]
+pub use pipeline::{BlobPipeline, Committed, Encoded, Split};
+#[cfg(feature = "coding")]
+pub use pool::{RepairPool, RepairedChunkset};
+#[cfg(feature = "coding")]
+pub use progress::RepairProgress;
+#[cfg(feature = "coding")]
+pub use recoder::{RecodedChunk, Recoder};
+pub use registry::HeaderRegistry;
+pub use seal::SealedBlobHeader;
+pub use segment::{CHUNK_SEGMENT_HEADER_BYTE_LEN, ChunkReassembler};
+#[cfg(feature = "coding")]
+pub use simulation::{DurabilityEstimate, DurabilitySimulation, SharePlacement};
+pub use subset::BlobHeaderSubset;
+#[cfg(feature = "coding")]
+pub use test_vectors::TestVectors;
+#[cfg(any(feature = "testing", all(test, feature = "coding")))]
+pub use testing::{ChunkFault, FaultScenario, FaultScenarioReport};
+pub use verify::{ChunkVerdict, VerifiedChunk, verify_chunk_bytes};
+pub use version::{BlobVersion, VersionedBlob};
+pub use view::BlobHeaderView;
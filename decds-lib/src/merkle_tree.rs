@@ -4,6 +4,15 @@ use std::collections::VecDeque;
 
 /// Represents a Merkle Tree, providing functionalities to build a binary tree from digests of the leaf nodes,
 /// get the root commitment, generate inclusion proofs, and verify them.
+///
+/// The tree is built bottom-up. Each parent node is `BLAKE3(left || right)`, where `left` and `right` are 32-bytes
+/// child digests. If a level has an odd number of nodes, the last node is paired with the "zero hash" of that level.
+/// Zero hash of the leaf level is 32 zero bytes and the zero hash of each next level is `BLAKE3(zero || zero)`, where
+/// `zero` is the zero hash of the level below it. A tree with a single leaf has that leaf as its root.
+///
+/// An inclusion proof of a leaf is the list of sibling digests on its path to the root, ordered from the leaf level
+/// upwards. When verifying, the leaf index decides at each level whether the running digest is the left (even index)
+/// or the right (odd index) child, before the index is halved.
 pub struct MerkleTree {
     root: blake3::Hash,
     leaves: Vec<blake3::Hash>,
@@ -26,27 +35,21 @@ impl MerkleTree {
         }
 
         let mut zero_hash = blake3::Hash::from_bytes([0u8; 32]);
-        let mut current_level = VecDeque::from(leaf_nodes.clone());
+        let mut root = leaf_nodes[0];
 
-        while current_level.len() > 1 {
-            let mut parent_level = VecDeque::new();
-
-            while !current_level.is_empty() {
-                let left = unsafe { current_level.pop_front().unwrap_unchecked() };
-                let right = current_level.pop_front().unwrap_or(zero_hash);
+        if leaf_nodes.len() > 1 {
+            let mut current_level = Self::compute_parent_level(&leaf_nodes, zero_hash);
+            zero_hash = Self::parent_hash(zero_hash.as_bytes(), zero_hash.as_bytes());
 
-                let parent = Self::parent_hash(left.as_bytes(), right.as_bytes());
-                parent_level.push_back(parent);
+            while current_level.len() > 1 {
+                current_level = Self::compute_parent_level(&current_level, zero_hash);
+                zero_hash = Self::parent_hash(zero_hash.as_bytes(), zero_hash.as_bytes());
             }
 
-            zero_hash = blake3::Hasher::new().update(zero_hash.as_bytes()).update(zero_hash.as_bytes()).finalize();
-            current_level = parent_level;
+            root = current_level[0];
         }
 
-        Ok(MerkleTree {
-            root: unsafe { current_level.pop_front().unwrap_unchecked() },
-            leaves: leaf_nodes,
-        })
+        Ok(MerkleTree { root, leaves: leaf_nodes })
     }
 
     /// Returns the root commitment (hash) of the Merkle Tree.
@@ -58,6 +61,11 @@ impl MerkleTree {
         self.root
     }
 
+    /// Returns the leaf nodes of the Merkle Tree, ordered by leaf index.
+    pub(crate) fn get_leaf_nodes(&self) -> &[blake3::Hash] {
+        &self.leaves
+    }
+
     /// Generates a Merkle inclusion proof for a given leaf node at `leaf_index`.
     ///
     /// This proof consists of the sibling hashes required to reconstruct the path
@@ -115,6 +123,204 @@ impl MerkleTree {
         Ok(proof)
     }
 
+    /// Generates Merkle inclusion proofs for all leaf nodes at once, walking up the tree only once.
+    ///
+    /// This is equivalent to calling `Self::generate_proof` for each leaf node, but it takes O(n * log(n)) time,
+    /// instead of O(n^2), for a tree with n leaf nodes.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<Vec<blake3::Hash>>` - Merkle proofs of all leaf nodes, ordered by leaf index.
+    pub fn generate_all_proofs(&self) -> Vec<Vec<blake3::Hash>> {
+        let num_leaf_nodes = self.leaves.len();
+        let proof_size = num_leaf_nodes.next_power_of_two().ilog2() as usize;
+
+        let mut proofs = (0..num_leaf_nodes).map(|_| Vec::with_capacity(proof_size)).collect::<Vec<Vec<blake3::Hash>>>();
+
+        let mut current_level = self.leaves.clone();
+        let mut level = 0;
+
+        let mut zero_hash = blake3::Hash::from_bytes([0u8; 32]);
+
+        while current_level.len() > 1 {
+            proofs.iter_mut().enumerate().for_each(|(leaf_index, proof)| {
+                let sibling_index = (leaf_index >> level) ^ 1;
+                proof.push(*current_level.get(sibling_index).unwrap_or(&zero_hash));
+            });
+
+            current_level = Self::compute_parent_level(&current_level, zero_hash);
+            level += 1;
+
+            zero_hash = Self::parent_hash(zero_hash.as_bytes(), zero_hash.as_bytes());
+        }
+
+        proofs
+    }
+
+    /// Generates a Merkle consistency proof, showing that this tree extends the tree built over its first `old_num_leaves`
+    /// leaf nodes, i.e. that those leaf nodes are unchanged.
+    ///
+    /// The tree is treated as a perfect binary tree, padded with zero hashes (see `MerkleTree`). The proof consists of
+    /// digests of the maximal aligned subtrees covering leaf nodes `[0, old_num_leaves)`, ordered left to right, followed by
+    /// digests of the maximal aligned subtrees covering the remaining leaf positions `[old_num_leaves, 2^depth)`, also
+    /// ordered left to right, omitting those which lie entirely in the zero-padded part of the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `old_num_leaves` - Number of leaf nodes of the older tree.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<blake3::Hash>, DecdsError>` - Returns the consistency proof if successful. Returns
+    ///   `DecdsError::InvalidNumberOfOldLeafNodes` if `old_num_leaves` is zero or more than the number of leaf nodes.
+    pub fn generate_consistency_proof(&self, old_num_leaves: usize) -> Result<Vec<blake3::Hash>, DecdsError> {
+        let num_leaf_nodes = self.leaves.len();
+        if old_num_leaves == 0 || old_num_leaves > num_leaf_nodes {
+            return Err(DecdsError::InvalidNumberOfOldLeafNodes(old_num_leaves, num_leaf_nodes));
+        }
+
+        let mut levels = vec![self.leaves.clone()];
+        let mut zero_hashes = vec![blake3::Hash::from_bytes([0u8; 32])];
+
+        while levels[levels.len() - 1].len() > 1 {
+            let zero_hash = zero_hashes[zero_hashes.len() - 1];
+
+            levels.push(Self::compute_parent_level(&levels[levels.len() - 1], zero_hash));
+            zero_hashes.push(Self::parent_hash(zero_hash.as_bytes(), zero_hash.as_bytes()));
+        }
+
+        let depth = num_leaf_nodes.next_power_of_two().ilog2();
+        let node = |level: u32, index: usize| levels[level as usize].get(index).copied().unwrap_or(zero_hashes[level as usize]);
+
+        let proof = Self::subtrees_covering_prefix(old_num_leaves)
+            .chain(Self::subtrees_covering_suffix(old_num_leaves, depth).filter(|&(level, index)| (index << level) < num_leaf_nodes))
+            .map(|(level, index)| node(level, index))
+            .collect();
+
+        Ok(proof)
+    }
+
+    /// Verifies a Merkle consistency proof, as generated by `Self::generate_consistency_proof`, showing that the tree with
+    /// `num_leaves` leaf nodes and root `root_hash` extends the tree with `old_num_leaves` leaf nodes and root `old_root_hash`.
+    ///
+    /// # Arguments
+    ///
+    /// * `old_num_leaves` - Number of leaf nodes of the older tree.
+    /// * `old_root_hash` - Root hash of the older tree.
+    /// * `num_leaves` - Number of leaf nodes of the newer tree.
+    /// * `root_hash` - Root hash of the newer tree.
+    /// * `proof` - A slice of `blake3::Hash` representing the consistency proof.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - `true` if the proof is valid, i.e. the first `old_num_leaves` leaf nodes of both trees are same,
+    ///   `false` otherwise.
+    pub fn verify_consistency_proof(
+        old_num_leaves: usize,
+        old_root_hash: blake3::Hash,
+        num_leaves: usize,
+        root_hash: blake3::Hash,
+        proof: &[blake3::Hash],
+    ) -> bool {
+        if old_num_leaves == 0 || old_num_leaves > num_leaves {
+            return false;
+        }
+
+        let num_prefix_subtrees = old_num_leaves.count_ones() as usize;
+        if proof.len() < num_prefix_subtrees {
+            return false;
+        }
+
+        let Some(depth) = num_leaves.checked_next_power_of_two().map(|n| n.ilog2()) else {
+            return false;
+        };
+        let old_depth = old_num_leaves.next_power_of_two().ilog2();
+
+        let zero_hashes = (0..depth)
+            .scan(blake3::Hash::from_bytes([0u8; 32]), |zero_hash, _| {
+                let current = *zero_hash;
+                *zero_hash = Self::parent_hash(current.as_bytes(), current.as_bytes());
+                Some(current)
+            })
+            .collect::<Vec<blake3::Hash>>();
+
+        let (prefix_proof, suffix_proof) = proof.split_at(num_prefix_subtrees);
+        let prefix = Self::subtrees_covering_prefix(old_num_leaves)
+            .zip(prefix_proof.iter().copied())
+            .map(|((level, _), hash)| (level, hash))
+            .collect::<Vec<(u32, blake3::Hash)>>();
+
+        let old_suffix = Self::subtrees_covering_suffix(old_num_leaves, old_depth).map(|(level, _)| (level, zero_hashes[level as usize]));
+        if Self::fold_subtrees(prefix.iter().copied().chain(old_suffix)) != Some(old_root_hash) {
+            return false;
+        }
+
+        let mut suffix_proof = suffix_proof.iter().copied();
+        let mut suffix = Vec::new();
+
+        for (level, index) in Self::subtrees_covering_suffix(old_num_leaves, depth) {
+            if (index << level) < num_leaves {
+                match suffix_proof.next() {
+                    Some(hash) => suffix.push((level, hash)),
+                    None => return false,
+                }
+            } else {
+                suffix.push((level, zero_hashes[level as usize]));
+            }
+        }
+
+        suffix_proof.next().is_none() && Self::fold_subtrees(prefix.into_iter().chain(suffix)) == Some(root_hash)
+    }
+
+    /// Returns `(level, index)` of the maximal aligned subtrees covering leaf positions `[0, num_leaves)`, ordered left to right.
+    fn subtrees_covering_prefix(num_leaves: usize) -> impl Iterator<Item = (u32, usize)> {
+        (0..usize::BITS).rev().filter(move |&level| (num_leaves >> level) & 1 == 1).map(move |level| {
+            let start = num_leaves & !((1usize << level << 1).wrapping_sub(1));
+            (level, start >> level)
+        })
+    }
+
+    /// Returns `(level, index)` of the maximal aligned subtrees covering leaf positions `[from, 2^depth)`, ordered left to
+    /// right. Caller must ensure that `0 < from <= 2^depth`.
+    fn subtrees_covering_suffix(from: usize, depth: u32) -> impl Iterator<Item = (u32, usize)> {
+        let end = 1usize << depth;
+
+        std::iter::successors(Some(from), |&start| Some(start + (1usize << start.trailing_zeros())))
+            .take_while(move |&start| start < end)
+            .map(|start| {
+                let level = start.trailing_zeros();
+                (level, start >> level)
+            })
+    }
+
+    /// Folds digests of aligned subtrees, partitioning leaf positions of a perfect binary tree and ordered left to right,
+    /// into the root of the tree. Returns `None` if they don't fold into a single root.
+    fn fold_subtrees(subtrees: impl Iterator<Item = (u32, blake3::Hash)>) -> Option<blake3::Hash> {
+        let mut stack: Vec<(u32, blake3::Hash)> = Vec::new();
+
+        for subtree in subtrees {
+            stack.push(subtree);
+
+            while stack.len() > 1 && stack[stack.len() - 1].0 == stack[stack.len() - 2].0 {
+                let (level, right) = stack.pop()?;
+                let (_, left) = stack.pop()?;
+
+                stack.push((level + 1, Self::parent_hash(left.as_bytes(), right.as_bytes())));
+            }
+        }
+
+        match stack.as_slice() {
+            [(_, root)] => Some(*root),
+            _ => None,
+        }
+    }
+
+    /// Consumes the Merkle Tree, returning its leaf nodes.
+    #[cfg(feature = "coding")]
+    pub(crate) fn into_leaf_nodes(self) -> Vec<blake3::Hash> {
+        self.leaves
+    }
+
     /// Verifies a Merkle inclusion proof for a given leaf node against a provided Merkle root hash.
     ///
     /// # Arguments
@@ -145,6 +351,24 @@ impl MerkleTree {
         current_hash == root_hash
     }
 
+    /// Computes the parent level of a level of the tree, pairing the last node with `zero_hash`, if the level has
+    /// an odd number of nodes.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - Nodes of a level of the tree, having at least two nodes.
+    /// * `zero_hash` - The zero hash of that level.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<blake3::Hash>` - Nodes of the parent level.
+    fn compute_parent_level(level: &[blake3::Hash], zero_hash: blake3::Hash) -> Vec<blake3::Hash> {
+        level
+            .chunks(2)
+            .map(|pair| Self::parent_hash(pair[0].as_bytes(), pair.get(1).unwrap_or(&zero_hash).as_bytes()))
+            .collect()
+    }
+
     /// Computes the hash of a parent node from its two child hashes.
     ///
     /// # Arguments
@@ -230,6 +454,63 @@ pub mod tests {
         });
     }
 
+    #[test]
+    fn test_generate_all_proofs_matches_generate_proof() {
+        let mut rng = rand::rng();
+
+        [1, 2, 3, 16, 17, 1000].into_iter().for_each(|leaf_count| {
+            let leaf_nodes = generate_random_leaf_hashes(leaf_count, &mut rng);
+            let merkle_tree = MerkleTree::new(leaf_nodes.clone()).expect("Must be able to build Merkle Tree");
+
+            let proofs = merkle_tree.generate_all_proofs();
+            assert_eq!(proofs.len(), leaf_count);
+
+            proofs.iter().enumerate().for_each(|(leaf_index, proof)| {
+                assert_eq!(proof, &merkle_tree.generate_proof(leaf_index).expect("Must be able to generate Merkle Proof"));
+                assert!(MerkleTree::verify_proof(
+                    leaf_index,
+                    leaf_nodes[leaf_index],
+                    proof,
+                    merkle_tree.get_root_commitment()
+                ));
+            });
+        });
+    }
+
+    #[test]
+    fn test_nested_trees_with_non_power_of_two_leaf_count() {
+        let mut rng = rand::rng();
+
+        [16, 20, 24].into_iter().for_each(|inner_leaf_count: usize| {
+            let inner_proof_size = inner_leaf_count.next_power_of_two().ilog2() as usize;
+
+            let inner_leaf_nodes = (0..3)
+                .map(|_| generate_random_leaf_hashes(inner_leaf_count, &mut rng))
+                .collect::<Vec<Vec<blake3::Hash>>>();
+            let inner_trees = inner_leaf_nodes
+                .iter()
+                .map(|leaf_nodes| MerkleTree::new(leaf_nodes.clone()).expect("Must be able to build Merkle Tree"))
+                .collect::<Vec<MerkleTree>>();
+
+            let outer_tree = MerkleTree::new(inner_trees.iter().map(|tree| tree.get_root_commitment()).collect()).expect("Must be able to build Merkle Tree");
+            let outer_proofs = outer_tree.generate_all_proofs();
+
+            inner_trees.iter().enumerate().for_each(|(inner_tree_index, inner_tree)| {
+                inner_tree.generate_all_proofs().into_iter().enumerate().for_each(|(local_index, mut proof)| {
+                    assert_eq!(proof.len(), inner_proof_size);
+                    proof.extend_from_slice(&outer_proofs[inner_tree_index]);
+
+                    assert!(MerkleTree::verify_proof(
+                        (inner_tree_index << inner_proof_size) | local_index,
+                        inner_leaf_nodes[inner_tree_index][local_index],
+                        &proof,
+                        outer_tree.get_root_commitment()
+                    ));
+                });
+            });
+        });
+    }
+
     #[test]
     fn test_new_with_empty_leaf_nodes() {
         let leaf_nodes: Vec<blake3::Hash> = Vec::new();
@@ -322,4 +603,84 @@ pub mod tests {
         let tampered_leaf1 = blake3::hash(b"tampered_first");
         assert!(!MerkleTree::verify_proof(0, tampered_leaf1, &proof1, root_hash));
     }
+
+    #[test]
+    fn test_generate_and_verify_consistency_proof() {
+        let mut rng = rand::rng();
+        let leaf_nodes = generate_random_leaf_hashes(33, &mut rng);
+
+        (1..=leaf_nodes.len()).for_each(|num_leaves| {
+            let merkle_tree = MerkleTree::new(leaf_nodes[..num_leaves].to_vec()).expect("Must be able to build Merkle Tree");
+            let root_hash = merkle_tree.get_root_commitment();
+
+            (1..=num_leaves).for_each(|old_num_leaves| {
+                let old_root_hash = MerkleTree::new(leaf_nodes[..old_num_leaves].to_vec()).unwrap().get_root_commitment();
+                let proof = merkle_tree
+                    .generate_consistency_proof(old_num_leaves)
+                    .expect("Must be able to generate consistency proof");
+
+                assert!(MerkleTree::verify_consistency_proof(
+                    old_num_leaves,
+                    old_root_hash,
+                    num_leaves,
+                    root_hash,
+                    &proof
+                ));
+
+                // Negative test: Tamper proof and verify
+                if !proof.is_empty() {
+                    let tampered_proof = flip_a_single_bit_in_merkle_proof(proof.clone(), &mut rng);
+                    assert!(!MerkleTree::verify_consistency_proof(
+                        old_num_leaves,
+                        old_root_hash,
+                        num_leaves,
+                        root_hash,
+                        &tampered_proof
+                    ));
+                }
+
+                // Negative test: Proof with missing or extra digests
+                assert!(!MerkleTree::verify_consistency_proof(
+                    old_num_leaves,
+                    old_root_hash,
+                    num_leaves,
+                    root_hash,
+                    &proof[1..]
+                ));
+                assert!(!MerkleTree::verify_consistency_proof(
+                    old_num_leaves,
+                    old_root_hash,
+                    num_leaves,
+                    root_hash,
+                    &[proof.as_slice(), &[root_hash]].concat()
+                ));
+
+                // Negative test: Rewrite one of the older leaf nodes, in the newer tree
+                let mut rewritten_leaf_nodes = leaf_nodes[..num_leaves].to_vec();
+                rewritten_leaf_nodes[rng.random_range(0..old_num_leaves)] = blake3::hash(b"rewritten");
+                let rewritten_tree = MerkleTree::new(rewritten_leaf_nodes).unwrap();
+                let rewritten_proof = rewritten_tree.generate_consistency_proof(old_num_leaves).unwrap();
+                assert!(!MerkleTree::verify_consistency_proof(
+                    old_num_leaves,
+                    old_root_hash,
+                    num_leaves,
+                    rewritten_tree.get_root_commitment(),
+                    &rewritten_proof
+                ));
+            });
+        });
+    }
+
+    #[test]
+    fn test_generate_consistency_proof_invalid_old_num_leaves() {
+        let leaf_nodes = vec![blake3::hash(b"first"), blake3::hash(b"second")];
+        let merkle_tree = MerkleTree::new(leaf_nodes).expect("Must be able to build Merkle Tree");
+
+        assert_eq!(merkle_tree.generate_consistency_proof(0), Err(DecdsError::InvalidNumberOfOldLeafNodes(0, 2)));
+        assert_eq!(merkle_tree.generate_consistency_proof(3), Err(DecdsError::InvalidNumberOfOldLeafNodes(3, 2)));
+
+        let root_hash = merkle_tree.get_root_commitment();
+        assert!(!MerkleTree::verify_consistency_proof(0, root_hash, 2, root_hash, &[]));
+        assert!(!MerkleTree::verify_consistency_proof(3, root_hash, 2, root_hash, &[]));
+    }
 }
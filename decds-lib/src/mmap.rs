@@ -0,0 +1,100 @@
+use std::{fs::File, io, ops::Deref, path::Path};
+
+/// Read-only view of a whole file. On unix, the file is memory-mapped, so its pages are read in by the kernel as they're
+/// touched and can be dropped again under memory pressure, instead of the file being copied into memory as a whole.
+/// Elsewhere, the file is read into memory.
+///
+/// The file must not be truncated or modified while it's mapped, otherwise reads may observe changing data, or fail with
+/// SIGBUS, which is why opening one is `unsafe`.
+pub(crate) struct MappedFile {
+    #[cfg(unix)]
+    ptr: *mut libc::c_void,
+    #[cfg(unix)]
+    len: usize,
+    #[cfg(not(unix))]
+    data: Vec<u8>,
+}
+
+// Mapping is read-only and private, hence it's safe to share it across threads.
+unsafe impl Send for MappedFile {}
+unsafe impl Sync for MappedFile {}
+
+impl MappedFile {
+    /// Maps the whole file at `path` into memory, for reading.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be truncated or modified, by this or any other process, until the returned mapping is dropped.
+    #[cfg(unix)]
+    pub(crate) unsafe fn open(path: &Path) -> io::Result<Self> {
+        use std::os::fd::AsRawFd;
+
+        let file = File::open(path)?;
+        let len = usize::try_from(file.metadata()?.len()).map_err(|err| io::Error::new(io::ErrorKind::FileTooLarge, err))?;
+
+        // Zero-length mappings aren't allowed.
+        if len == 0 {
+            return Ok(MappedFile {
+                ptr: std::ptr::null_mut(),
+                len,
+            });
+        }
+
+        let ptr = unsafe { libc::mmap(std::ptr::null_mut(), len, libc::PROT_READ, libc::MAP_PRIVATE, file.as_raw_fd(), 0) };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(MappedFile { ptr, len })
+    }
+
+    /// Tells the kernel that the mapping is going to be read sequentially, once, so that it reads ahead aggressively and
+    /// drops pages soon after they're read, instead of churning the page cache. It's just advice, so failures are ignored.
+    #[cfg(unix)]
+    pub(crate) fn advise_sequential(&self) {
+        if self.len != 0 {
+            unsafe { libc::madvise(self.ptr, self.len, libc::MADV_SEQUENTIAL) };
+        }
+    }
+
+    /// Memory-mapping isn't supported on this platform, so the whole file at `path` is read into memory.
+    ///
+    /// # Safety
+    ///
+    /// Same contract as on unix, so that callers don't depend on the platform, though it isn't relied upon here.
+    #[cfg(not(unix))]
+    pub(crate) unsafe fn open(path: &Path) -> io::Result<Self> {
+        Ok(MappedFile { data: std::fs::read(path)? })
+    }
+
+    /// The file isn't mapped on this platform, so there's nothing to advise.
+    #[cfg(not(unix))]
+    pub(crate) fn advise_sequential(&self) {}
+}
+
+impl Deref for MappedFile {
+    type Target = [u8];
+
+    #[cfg(unix)]
+    fn deref(&self) -> &[u8] {
+        if self.len == 0 {
+            &[]
+        } else {
+            unsafe { std::slice::from_raw_parts(self.ptr as *const u8, self.len) }
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn deref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+#[cfg(unix)]
+impl Drop for MappedFile {
+    fn drop(&mut self) {
+        if self.len != 0 {
+            unsafe { libc::munmap(self.ptr, self.len) };
+        }
+    }
+}
@@ -0,0 +1,594 @@
+use crate::{
+    blob::{Blob, BlobHeader},
+    chunk::ProofCarryingChunk,
+    chunkset::ChunkSet,
+    config::BlobConfig,
+    consts::DECDS_NUM_ERASURE_CODED_SHARES,
+    errors::DecdsError,
+    events::{EventHandler, NoopEventHandler},
+    merkle_tree::MerkleTree,
+};
+use rayon::prelude::*;
+
+/// Staged construction of a `Blob`, doing exactly what `Blob::new` does, but one stage at a time.
+///
+/// The stages are: split (`BlobPipeline<Split>`) → encode (`BlobPipeline<Encoded>`) → commit (`BlobPipeline<Committed>`)
+/// → attach proofs (`Blob`). Each stage owns its intermediate output, which can be inspected, and the pipeline can be
+/// put on hold and resumed from any stage later. This lets one interleave custom steps, say uploading chunks of a
+/// chunkset as soon as it is encoded or publishing the `BlobHeader` before proofs are attached, without forking
+/// `Blob::new`. Transformations of the blob content itself (e.g. compression, encryption) belong before the split stage.
+pub struct BlobPipeline<Stage> {
+    byte_length: usize,
+    config: BlobConfig,
+    digest: blake3::Hash,
+    stage: Stage,
+}
+
+/// Blob content is zero-padded and split into chunksets, each of `BlobConfig::get_chunkset_byte_length` bytes.
+pub struct Split {
+    data: Vec<u8>,
+}
+
+/// Each chunkset is erasure-coded and committed to. Chunks carry Merkle proofs up to their chunkset root only.
+pub struct Encoded {
+    chunksets: Vec<ChunkSet>,
+}
+
+/// The blob root commitment is computed, so the `BlobHeader` is final, but chunks still lack blob-level proofs.
+pub struct Committed {
+    header: BlobHeader,
+    chunksets: Vec<ChunkSet>,
+    blob_proofs: Vec<Vec<blake3::Hash>>,
+}
+
+impl<Stage> BlobPipeline<Stage> {
+    /// Returns the original byte length of the blob data before padding.
+    pub fn get_blob_size(&self) -> usize {
+        self.byte_length
+    }
+
+    /// Returns the BLAKE3 digest of the original blob data.
+    pub fn get_blob_digest(&self) -> blake3::Hash {
+        self.digest
+    }
+
+    /// Returns the erasure-coding geometry, the blob is split and encoded with.
+    pub fn get_config(&self) -> BlobConfig {
+        self.config
+    }
+}
+
+impl BlobPipeline<Split> {
+    /// Starts a new pipeline by zero-padding the blob data to a multiple of `ChunkSet::BYTE_LENGTH` and splitting it
+    /// into chunksets.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The raw `Vec<u8>` representing the blob's content.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Self)` containing the pipeline at its split stage if successful.
+    /// - `Err(DecdsError::EmptyDataForBlob)` if the input `data` is empty.
+    pub fn new(data: Vec<u8>) -> Result<Self, DecdsError> {
+        Self::new_with_config(data, BlobConfig::default())
+    }
+
+    /// Same as `Self::new`, but splits and later encodes the blob with the geometry `config`, instead of the default one.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The raw `Vec<u8>` representing the blob's content.
+    /// * `config` - Erasure-coding geometry of the blob.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Self)` containing the pipeline at its split stage if successful.
+    /// - `Err(DecdsError::EmptyDataForBlob)` if the input `data` is empty.
+    /// - `Err(DecdsError::InvalidBlobConfig)` if `config` isn't valid.
+    /// - `Err(DecdsError::CodecNotCompiledIn)` if the codec of `config` is disabled by a feature flag.
+    pub fn new_with_config(mut data: Vec<u8>, config: BlobConfig) -> Result<Self, DecdsError> {
+        if data.is_empty() {
+            return Err(DecdsError::EmptyDataForBlob);
+        }
+        config.validate()?;
+        config.get_codec().check_compiled_in()?;
+
+        let digest = blake3::hash(&data);
+        let byte_length = data.len();
+
+        let num_chunksets = byte_length.div_ceil(config.get_chunkset_byte_length());
+        data.resize(num_chunksets * config.get_chunkset_byte_length(), 0);
+
+        Ok(BlobPipeline {
+            byte_length,
+            config,
+            digest,
+            stage: Split { data },
+        })
+    }
+
+    /// Returns the number of chunksets the blob is split into.
+    pub fn get_num_chunksets(&self) -> usize {
+        self.stage.data.len() / self.config.get_chunkset_byte_length()
+    }
+
+    /// Returns the zero-padded data of a chunkset, which is going to be erasure-coded.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(&[u8])` containing `BlobConfig::get_chunkset_byte_length` bytes of chunkset data if successful.
+    /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_id` is out of bounds.
+    pub fn get_chunkset_data(&self, chunkset_id: usize) -> Result<&[u8], DecdsError> {
+        let num_chunksets = self.get_num_chunksets();
+        if chunkset_id >= num_chunksets {
+            return Err(DecdsError::InvalidChunksetId(chunkset_id, num_chunksets));
+        }
+
+        let chunkset_byte_length = self.config.get_chunkset_byte_length();
+        let offset = chunkset_id * chunkset_byte_length;
+        Ok(&self.stage.data[offset..offset + chunkset_byte_length])
+    }
+
+    /// Erasure-codes all chunksets in parallel, using randomly sampled coding vectors.
+    pub fn encode(self) -> BlobPipeline<Encoded> {
+        self.encode_with_event_handler(&NoopEventHandler)
+    }
+
+    /// Same as `Self::encode`, but invokes `EventHandler::on_chunkset_encoded` of `handler`, as each chunkset is encoded.
+    pub fn encode_with_event_handler(self, handler: &dyn EventHandler) -> BlobPipeline<Encoded> {
+        let config = self.config;
+        self.encode_with(
+            |chunkset_id, chunkset_data| ChunkSet::new_with_config(chunkset_id, chunkset_data, &config, None),
+            handler,
+        )
+    }
+
+    /// Same as `Self::encode`, but coding vectors are deterministically derived from `seed`.
+    pub(crate) fn encode_with_seed(self, seed: &[u8; 32], handler: &dyn EventHandler) -> BlobPipeline<Encoded> {
+        let config = self.config;
+        self.encode_with(
+            |chunkset_id, chunkset_data| ChunkSet::new_with_config(chunkset_id, chunkset_data, &config, Some(seed)),
+            handler,
+        )
+    }
+
+    fn encode_with<F>(self, encode_chunkset: F, handler: &dyn EventHandler) -> BlobPipeline<Encoded>
+    where
+        F: Fn(usize, Vec<u8>) -> Result<ChunkSet, DecdsError> + Sync,
+    {
+        let chunksets = self
+            .stage
+            .data
+            .par_chunks_exact(self.config.get_chunkset_byte_length())
+            .enumerate()
+            .map(|(chunkset_id, chunkset_data)| {
+                let chunkset = unsafe { encode_chunkset(chunkset_id, chunkset_data.to_vec()).unwrap_unchecked() };
+                handler.on_chunkset_encoded(chunkset_id, chunkset.get_root_commitment());
+
+                chunkset
+            })
+            .collect::<Vec<ChunkSet>>();
+
+        BlobPipeline {
+            byte_length: self.byte_length,
+            config: self.config,
+            digest: self.digest,
+            stage: Encoded { chunksets },
+        }
+    }
+}
+
+impl BlobPipeline<Encoded> {
+    /// Imports a blob, already erasure-coded by another `(ChunkSet::NUM_ORIGINAL_CHUNKS, DECDS_NUM_ERASURE_CODED_SHARES)`
+    /// linear code over GF(2^8), with reduction polynomial `x^8 + x^4 + x^3 + x^2 + 1` (`0x11d`), e.g. a Reed-Solomon code
+    /// of another archiver, wrapping its shards into chunks as they are, without re-encoding. Row `i` of the code's
+    /// generator matrix becomes the coding vector of shard `i` of every stripe, so that the imported blob is repaired like
+    /// any other.
+    ///
+    /// Parameters must match the ones `BlobPipeline::new` splits blobs with, i.e. each stripe encodes `ChunkSet::BYTE_LENGTH`
+    /// bytes of blob data, zero-padded in the last stripe, followed by a single `0x81` end-of-data marker and zero-padded
+    /// into `ChunkSet::NUM_ORIGINAL_CHUNKS` pieces. Each stripe is decoded, for computing the blob digest, and its shards
+    /// are checked against their coding vectors.
+    ///
+    /// # Arguments
+    ///
+    /// * `byte_length` - Byte length of the original blob data.
+    /// * `coding_matrix` - Generator matrix of the code, `DECDS_NUM_ERASURE_CODED_SHARES` rows of
+    ///   `ChunkSet::NUM_ORIGINAL_CHUNKS` coefficients.
+    /// * `stripes` - Shards of each stripe, one stripe per chunkset, in order.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Self)` containing the pipeline at its encode stage if successful.
+    /// - `Err(DecdsError::EmptyDataForBlob)` if `byte_length` is zero.
+    /// - `Err(DecdsError::InvalidCodingMatrix)` if `coding_matrix` is of unexpected shape.
+    /// - `Err(DecdsError::InvalidNumberOfCodedStripes)` if there isn't exactly one stripe per chunkset of the blob.
+    /// - `Err(DecdsError::IncompatibleCodedShards)` if shards of a stripe can't be wrapped, see `ChunkSet::from_coded_shards`,
+    ///   or if the last stripe isn't zero-padded beyond `byte_length`.
+    pub fn from_coded_shards(byte_length: usize, coding_matrix: &[Vec<u8>], stripes: Vec<Vec<Vec<u8>>>) -> Result<Self, DecdsError> {
+        if byte_length == 0 {
+            return Err(DecdsError::EmptyDataForBlob);
+        }
+        if coding_matrix.len() != DECDS_NUM_ERASURE_CODED_SHARES {
+            return Err(DecdsError::InvalidCodingMatrix(format!(
+                "expected {} rows, got {}",
+                DECDS_NUM_ERASURE_CODED_SHARES,
+                coding_matrix.len()
+            )));
+        }
+        if let Some(row_idx) = coding_matrix.iter().position(|row| row.len() != ChunkSet::NUM_ORIGINAL_CHUNKS) {
+            return Err(DecdsError::InvalidCodingMatrix(format!(
+                "row {} has {} coefficients, expected {}",
+                row_idx,
+                coding_matrix[row_idx].len(),
+                ChunkSet::NUM_ORIGINAL_CHUNKS
+            )));
+        }
+
+        let num_chunksets = byte_length.div_ceil(ChunkSet::BYTE_LENGTH);
+        if stripes.len() != num_chunksets {
+            return Err(DecdsError::InvalidNumberOfCodedStripes(stripes.len(), num_chunksets));
+        }
+
+        let (chunksets, chunkset_data): (Vec<ChunkSet>, Vec<Vec<u8>>) = stripes
+            .into_par_iter()
+            .enumerate()
+            .map(|(chunkset_id, shards)| ChunkSet::from_coded_shards(chunkset_id, coding_matrix, shards))
+            .collect::<Result<Vec<(ChunkSet, Vec<u8>)>, DecdsError>>()?
+            .into_iter()
+            .unzip();
+
+        let num_padding_bytes = num_chunksets * ChunkSet::BYTE_LENGTH - byte_length;
+        if chunkset_data[num_chunksets - 1][ChunkSet::BYTE_LENGTH - num_padding_bytes..]
+            .iter()
+            .any(|&byte| byte != 0)
+        {
+            return Err(DecdsError::IncompatibleCodedShards(
+                num_chunksets - 1,
+                "blob data isn't zero-padded beyond its byte length".to_string(),
+            ));
+        }
+
+        let mut hasher = blake3::Hasher::new();
+        chunkset_data.iter().enumerate().for_each(|(chunkset_id, data)| {
+            let num_data_bytes = if chunkset_id + 1 == num_chunksets {
+                ChunkSet::BYTE_LENGTH - num_padding_bytes
+            } else {
+                ChunkSet::BYTE_LENGTH
+            };
+            hasher.update(&data[..num_data_bytes]);
+        });
+
+        Ok(BlobPipeline {
+            byte_length,
+            config: BlobConfig::default(),
+            digest: hasher.finalize(),
+            stage: Encoded { chunksets },
+        })
+    }
+
+    /// Resumes a pipeline at its encode stage, from chunksets erasure-coded elsewhere with the geometry `config`, e.g.
+    /// while transcoding.
+    pub(crate) fn from_chunksets(byte_length: usize, config: BlobConfig, digest: blake3::Hash, chunksets: Vec<ChunkSet>) -> Self {
+        BlobPipeline {
+            byte_length,
+            config,
+            digest,
+            stage: Encoded { chunksets },
+        }
+    }
+
+    /// Returns the number of erasure-coded chunksets.
+    pub fn get_num_chunksets(&self) -> usize {
+        self.stage.chunksets.len()
+    }
+
+    /// Returns the Merkle root commitment of an erasure-coded chunkset.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(blake3::Hash)` containing the chunkset root commitment if successful.
+    /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_id` is out of bounds.
+    pub fn get_chunkset_commitment(&self, chunkset_id: usize) -> Result<blake3::Hash, DecdsError> {
+        self.stage
+            .chunksets
+            .get(chunkset_id)
+            .map(|chunkset| chunkset.get_root_commitment())
+            .ok_or(DecdsError::InvalidChunksetId(chunkset_id, self.get_num_chunksets()))
+    }
+
+    /// Returns an erasure-coded chunk. At this stage, it only carries proof of inclusion in its chunkset, so it can be
+    /// validated with `ProofCarryingChunk::validate_inclusion_in_chunkset`, but not yet against the blob root commitment.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset.
+    /// * `local_chunk_id` - The local ID (`>= 0 && < DECDS_NUM_ERASURE_CODED_SHARES`) of the chunk within its chunkset.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(&ProofCarryingChunk)` containing a reference to the chunk if successful.
+    /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_id` is out of bounds.
+    /// - `Err(DecdsError::InvalidErasureCodedShareId)` if `local_chunk_id` is out of bounds.
+    pub fn get_chunk(&self, chunkset_id: usize, local_chunk_id: usize) -> Result<&ProofCarryingChunk, DecdsError> {
+        self.stage
+            .chunksets
+            .get(chunkset_id)
+            .ok_or(DecdsError::InvalidChunksetId(chunkset_id, self.get_num_chunksets()))?
+            .get_chunk(local_chunk_id)
+    }
+
+    /// Builds the blob-level Merkle tree over chunkset root commitments, finalizing the `BlobHeader`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(BlobPipeline<Committed>)` containing the pipeline at its commit stage if successful.
+    /// - `Err(DecdsError::NoLeafNodesToBuildMerkleTreeOn)` if there is no chunkset, which can't happen for a pipeline
+    ///   started with `BlobPipeline::new`.
+    pub fn commit(self) -> Result<BlobPipeline<Committed>, DecdsError> {
+        let merkle_leaves = self
+            .stage
+            .chunksets
+            .iter()
+            .map(|chunkset| chunkset.get_root_commitment())
+            .collect::<Vec<blake3::Hash>>();
+        let merkle_tree = MerkleTree::new(merkle_leaves)?;
+
+        let root_commitment = merkle_tree.get_root_commitment();
+        let blob_proofs = merkle_tree.generate_all_proofs();
+        let header = BlobHeader::new(self.byte_length, self.config, self.digest, root_commitment, merkle_tree.into_leaf_nodes());
+
+        Ok(BlobPipeline {
+            byte_length: self.byte_length,
+            config: self.config,
+            digest: self.digest,
+            stage: Committed {
+                header,
+                chunksets: self.stage.chunksets,
+                blob_proofs,
+            },
+        })
+    }
+}
+
+impl BlobPipeline<Committed> {
+    /// Returns the final `BlobHeader`, which can already be published, before proofs are attached to chunks.
+    pub fn get_blob_header(&self) -> &BlobHeader {
+        &self.stage.header
+    }
+
+    /// Returns the Merkle proof of inclusion of a chunkset root commitment in the blob root commitment.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(&[blake3::Hash])` containing the blob-level Merkle proof of the chunkset if successful.
+    /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_id` is out of bounds.
+    pub fn get_blob_inclusion_proof(&self, chunkset_id: usize) -> Result<&[blake3::Hash], DecdsError> {
+        self.stage
+            .blob_proofs
+            .get(chunkset_id)
+            .map(|blob_proof| blob_proof.as_slice())
+            .ok_or(DecdsError::InvalidChunksetId(chunkset_id, self.stage.blob_proofs.len()))
+    }
+
+    /// Extends Merkle proofs of all chunks up to the blob root commitment, finishing the pipeline.
+    pub fn attach_proofs(self) -> Blob {
+        let mut chunksets = self.stage.chunksets;
+
+        chunksets
+            .par_iter_mut()
+            .zip(self.stage.blob_proofs)
+            .for_each(|(chunkset, blob_proof)| chunkset.append_blob_inclusion_proof(&blob_proof));
+
+        Blob::from_parts(self.stage.header, chunksets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BlobPipeline, DECDS_NUM_ERASURE_CODED_SHARES, MerkleTree, RepairingBlob, chunkset::ChunkSet, errors::DecdsError};
+    use rand::Rng;
+
+    #[test]
+    fn test_blob_pipeline_stages() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..(ChunkSet::BYTE_LENGTH * 2 + 1)).map(|_| rng.random()).collect::<Vec<u8>>();
+        let blob_digest = blake3::hash(&blob_data);
+
+        let split = BlobPipeline::new(blob_data.clone()).expect("Must be able to split blob");
+        assert_eq!(split.get_blob_size(), blob_data.len());
+        assert_eq!(split.get_blob_digest(), blob_digest);
+        assert_eq!(split.get_num_chunksets(), 3);
+        assert_eq!(
+            split.get_chunkset_data(1).unwrap(),
+            &blob_data[ChunkSet::BYTE_LENGTH..ChunkSet::BYTE_LENGTH * 2]
+        );
+        assert_eq!(split.get_chunkset_data(3), Err(DecdsError::InvalidChunksetId(3, 3)));
+
+        let encoded = split.encode();
+        assert_eq!(encoded.get_num_chunksets(), 3);
+        (0..encoded.get_num_chunksets()).for_each(|chunkset_id| {
+            let chunkset_commitment = encoded.get_chunkset_commitment(chunkset_id).unwrap();
+
+            (0..DECDS_NUM_ERASURE_CODED_SHARES).for_each(|local_chunk_id| {
+                let chunk = encoded.get_chunk(chunkset_id, local_chunk_id).unwrap();
+                assert!(chunk.validate_inclusion_in_chunkset(chunkset_commitment));
+            });
+        });
+        assert_eq!(
+            encoded.get_chunk(0, DECDS_NUM_ERASURE_CODED_SHARES).err(),
+            Some(DecdsError::InvalidErasureCodedShareId(16))
+        );
+
+        let committed = encoded.commit().expect("Must be able to commit to blob");
+        let header = committed.get_blob_header().clone();
+        assert!(header.verify_root_commitment());
+        (0..header.get_num_chunksets()).for_each(|chunkset_id| {
+            assert!(MerkleTree::verify_proof(
+                chunkset_id,
+                header.get_chunkset_commitment(chunkset_id).unwrap(),
+                committed.get_blob_inclusion_proof(chunkset_id).unwrap(),
+                header.get_root_commitment()
+            ));
+        });
+
+        let blob = committed.attach_proofs();
+        assert_eq!(blob.get_blob_header(), &header);
+
+        let mut repairer = RepairingBlob::new(header.clone());
+        (0..DECDS_NUM_ERASURE_CODED_SHARES).for_each(|share_id| {
+            blob.get_share(share_id).unwrap().iter().for_each(|chunk| {
+                assert!(header.validate_chunk(chunk));
+                let _ = repairer.add_chunk(chunk);
+            });
+        });
+
+        let repaired = (0..header.get_num_chunksets())
+            .flat_map(|chunkset_id| repairer.get_repaired_chunkset(chunkset_id).expect("Must be able to repair chunkset"))
+            .collect::<Vec<u8>>();
+        assert_eq!(repaired, blob_data);
+    }
+
+    #[test]
+    fn test_blob_pipeline_new_empty_data() {
+        assert_eq!(BlobPipeline::new(Vec::new()).err(), Some(DecdsError::EmptyDataForBlob));
+    }
+
+    /// Erasure-codes blob data, the way `BlobPipeline::new` splits and pads it, with a systematic code, whose first
+    /// `NUM_ORIGINAL_CHUNKS` rows form the identity matrix, standing in for a Reed-Solomon archiver.
+    fn encode_with_systematic_code(blob_data: &[u8]) -> (Vec<Vec<u8>>, Vec<Vec<Vec<u8>>>) {
+        let mut rng = rand::rng();
+
+        let coding_matrix = (0..DECDS_NUM_ERASURE_CODED_SHARES)
+            .map(|row_idx| {
+                (0..ChunkSet::NUM_ORIGINAL_CHUNKS)
+                    .map(|col_idx| {
+                        if row_idx < ChunkSet::NUM_ORIGINAL_CHUNKS {
+                            (row_idx == col_idx) as u8
+                        } else {
+                            rng.random()
+                        }
+                    })
+                    .collect::<Vec<u8>>()
+            })
+            .collect::<Vec<Vec<u8>>>();
+
+        let mut padded_data = blob_data.to_vec();
+        padded_data.resize(blob_data.len().div_ceil(ChunkSet::BYTE_LENGTH) * ChunkSet::BYTE_LENGTH, 0);
+
+        let stripes = padded_data
+            .chunks_exact(ChunkSet::BYTE_LENGTH)
+            .map(|chunkset_data| {
+                let encoder = rlnc::full::encoder::Encoder::new(chunkset_data.to_vec(), ChunkSet::NUM_ORIGINAL_CHUNKS).unwrap();
+                coding_matrix
+                    .iter()
+                    .map(|coding_vector| encoder.code_with_coding_vector(coding_vector).unwrap()[ChunkSet::NUM_ORIGINAL_CHUNKS..].to_vec())
+                    .collect::<Vec<Vec<u8>>>()
+            })
+            .collect::<Vec<Vec<Vec<u8>>>>();
+
+        (coding_matrix, stripes)
+    }
+
+    #[test]
+    fn test_blob_pipeline_from_coded_shards() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..(ChunkSet::BYTE_LENGTH + 1)).map(|_| rng.random()).collect::<Vec<u8>>();
+        let (coding_matrix, stripes) = encode_with_systematic_code(&blob_data);
+
+        // First data shard is the first piece of blob data, as is
+        assert_eq!(&stripes[0][0][..1024], &blob_data[..1024]);
+
+        let encoded = BlobPipeline::from_coded_shards(blob_data.len(), &coding_matrix, stripes).expect("Must be able to import coded shards");
+        assert_eq!(encoded.get_blob_size(), blob_data.len());
+        assert_eq!(encoded.get_blob_digest(), blake3::hash(&blob_data));
+
+        let blob = encoded.commit().expect("Must be able to commit to blob").attach_proofs();
+        let header = blob.get_blob_header().clone();
+
+        // Repairing from parity shards and fewest data shards
+        let mut repairer = RepairingBlob::new(header.clone());
+        (DECDS_NUM_ERASURE_CODED_SHARES - ChunkSet::NUM_ORIGINAL_CHUNKS..DECDS_NUM_ERASURE_CODED_SHARES).for_each(|share_id| {
+            blob.get_share(share_id).unwrap().iter().for_each(|chunk| {
+                assert!(header.validate_chunk(chunk));
+                repairer.add_chunk(chunk).unwrap();
+            });
+        });
+
+        let repaired = (0..header.get_num_chunksets())
+            .flat_map(|chunkset_id| repairer.get_repaired_chunkset(chunkset_id).expect("Must be able to repair chunkset"))
+            .collect::<Vec<u8>>();
+        assert_eq!(repaired, blob_data);
+    }
+
+    #[test]
+    fn test_blob_pipeline_from_incompatible_coded_shards() {
+        let mut rng = rand::rng();
+
+        let mut blob_data = (0..ChunkSet::BYTE_LENGTH).map(|_| rng.random()).collect::<Vec<u8>>();
+        blob_data[ChunkSet::BYTE_LENGTH - 1] = 0xff;
+        let (coding_matrix, stripes) = encode_with_systematic_code(&blob_data);
+
+        assert_eq!(
+            BlobPipeline::from_coded_shards(0, &coding_matrix, stripes.clone()).err(),
+            Some(DecdsError::EmptyDataForBlob)
+        );
+        assert!(matches!(
+            BlobPipeline::from_coded_shards(blob_data.len(), &coding_matrix[1..], stripes.clone()),
+            Err(DecdsError::InvalidCodingMatrix(_))
+        ));
+        assert_eq!(
+            BlobPipeline::from_coded_shards(blob_data.len() + 1, &coding_matrix, stripes.clone()).err(),
+            Some(DecdsError::InvalidNumberOfCodedStripes(1, 2))
+        );
+
+        // Last byte of blob data would be padding
+        assert!(matches!(
+            BlobPipeline::from_coded_shards(blob_data.len() - 1, &coding_matrix, stripes.clone()),
+            Err(DecdsError::IncompatibleCodedShards(0, _))
+        ));
+
+        let mut tampered_stripes = stripes.clone();
+        tampered_stripes[0][DECDS_NUM_ERASURE_CODED_SHARES - 1][0] ^= 1;
+        assert!(matches!(
+            BlobPipeline::from_coded_shards(blob_data.len(), &coding_matrix, tampered_stripes),
+            Err(DecdsError::IncompatibleCodedShards(0, _))
+        ));
+
+        let mut truncated_stripes = stripes.clone();
+        truncated_stripes[0][3].pop();
+        assert!(matches!(
+            BlobPipeline::from_coded_shards(blob_data.len(), &coding_matrix, truncated_stripes),
+            Err(DecdsError::IncompatibleCodedShards(0, _))
+        ));
+
+        let rank_deficient_matrix = vec![coding_matrix[0].clone(); DECDS_NUM_ERASURE_CODED_SHARES];
+        assert!(matches!(
+            BlobPipeline::from_coded_shards(blob_data.len(), &rank_deficient_matrix, stripes),
+            Err(DecdsError::IncompatibleCodedShards(0, _))
+        ));
+    }
+}
@@ -0,0 +1,283 @@
+use crate::{blob::BlobHeader, chunk::ProofCarryingChunk, chunkset::RepairingChunkSet, errors::DecdsError};
+use std::{
+    mem,
+    sync::{
+        Arc, Mutex, MutexGuard,
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, Receiver, Sender},
+    },
+};
+
+/// A repaired chunkset, as emitted by `RepairPool`: its ID along with either its data, exactly
+/// `BlobHeader::get_chunkset_size` bytes, or the error which made its repair fail.
+pub type RepairedChunkset = (usize, Result<Vec<u8>, DecdsError>);
+
+/// Reusable repair engine, driven by chunk arrival, for integrators (e.g. CLI, server daemons) which receive chunks of
+/// many chunksets concurrently.
+///
+/// Each submitted chunk is queued on its chunkset, and a per-chunkset repair task is scheduled on a dedicated work
+/// stealing thread pool, unless one is already scheduled. A repair task owns the decoding state of its chunkset while
+/// it runs, validating and feeding it all queued chunks, so chunksets are repaired in parallel, without any two workers
+/// ever sharing the same decoder. As soon as a chunkset has enough useful chunks, it gets repaired and emitted on the
+/// channel returned by `Self::new`, in completion order. The channel gets disconnected once the pool is dropped and all
+/// its scheduled repair tasks have finished.
+pub struct RepairPool {
+    shared: Arc<SharedState>,
+    thread_pool: rayon::ThreadPool,
+}
+
+/// State of a `RepairPool`, shared by the pool and all its repair tasks.
+struct SharedState {
+    header: BlobHeader,
+    chunksets: Vec<Mutex<ChunksetSlot>>,
+    repaired_tx: Sender<RepairedChunkset>,
+    num_rejected_chunks: AtomicUsize,
+}
+
+/// Per-chunkset repair state, guarded by its own lock, so that chunk submission never contends across chunksets.
+struct ChunksetSlot {
+    /// Decoding state of the chunkset, taken out by the repair task while it runs, and dropped once repaired.
+    repairer: Option<RepairingChunkSet>,
+    /// Chunks submitted, but not yet consumed by a repair task.
+    pending: Vec<ProofCarryingChunk>,
+    is_scheduled: bool,
+    is_repaired: bool,
+}
+
+impl RepairPool {
+    /// Creates a new `RepairPool`, repairing chunksets of the blob described by `header` on `num_threads` worker threads.
+    ///
+    /// If the blob is stored inline in its header, there's nothing to repair, so its content is emitted right away, as
+    /// chunkset 0, and any chunk submitted later is rejected.
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - The `BlobHeader` of the blob to be repaired.
+    /// * `num_threads` - Number of worker threads of the pool. Zero lets `rayon` pick one, based on available CPUs.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok((RepairPool, Receiver<RepairedChunkset>))` containing the pool and the receiving end of the channel, on
+    ///   which repaired chunksets are emitted, if successful.
+    /// - `Err(DecdsError::ThreadPoolBuildFailed)` if the worker thread pool can't be built.
+    pub fn new(header: BlobHeader, num_threads: usize) -> Result<(Self, Receiver<RepairedChunkset>), DecdsError> {
+        let thread_pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .map_err(|err| DecdsError::ThreadPoolBuildFailed(err.to_string()))?;
+
+        let (repaired_tx, repaired_rx) = mpsc::channel();
+
+        let chunksets = if let Some(inline_data) = header.get_inline_data() {
+            let _ = repaired_tx.send((0, Ok(inline_data.to_vec())));
+
+            vec![Mutex::new(ChunksetSlot {
+                repairer: None,
+                pending: Vec::new(),
+                is_scheduled: false,
+                is_repaired: true,
+            })]
+        } else {
+            let config = header.get_config();
+
+            (0..header.get_num_chunksets())
+                .map(|chunkset_id| {
+                    Mutex::new(ChunksetSlot {
+                        repairer: Some(RepairingChunkSet::new_with_config(
+                            chunkset_id,
+                            unsafe { header.get_chunkset_commitment(chunkset_id).unwrap_unchecked() },
+                            &config,
+                        )),
+                        pending: Vec::new(),
+                        is_scheduled: false,
+                        is_repaired: false,
+                    })
+                })
+                .collect()
+        };
+
+        let shared = Arc::new(SharedState {
+            header,
+            chunksets,
+            repaired_tx,
+            num_rejected_chunks: AtomicUsize::new(0),
+        });
+
+        Ok((RepairPool { shared, thread_pool }, repaired_rx))
+    }
+
+    /// Returns a reference to the `BlobHeader` of the blob being repaired.
+    pub fn get_blob_header(&self) -> &BlobHeader {
+        &self.shared.header
+    }
+
+    /// Submits a chunk for repairing its chunkset. The chunk is queued and validated, against the `BlobHeader`, on a
+    /// worker thread, so this never blocks on decoding. Chunks failing validation are silently dropped, but counted, see
+    /// `Self::get_num_rejected_chunks`.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk` - The `ProofCarryingChunk` to be used in repairing its chunkset.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(())` if the chunk is queued.
+    /// - `Err(DecdsError::InvalidChunksetId)` if the chunk's chunkset ID is out of bounds.
+    /// - `Err(DecdsError::ChunksetAlreadyRepaired)` if the chunkset has already been repaired, or is repairing.
+    pub fn submit(&self, chunk: ProofCarryingChunk) -> Result<(), DecdsError> {
+        let chunkset_id = chunk.get_chunkset_id();
+        let num_chunksets = self.shared.chunksets.len();
+
+        let mut slot = self
+            .shared
+            .chunksets
+            .get(chunkset_id)
+            .map(lock_slot)
+            .ok_or(DecdsError::InvalidChunksetId(chunkset_id, num_chunksets))?;
+
+        if slot.is_repaired {
+            return Err(DecdsError::ChunksetAlreadyRepaired(chunkset_id));
+        }
+
+        slot.pending.push(chunk);
+        if !slot.is_scheduled {
+            slot.is_scheduled = true;
+            drop(slot);
+
+            let shared = Arc::clone(&self.shared);
+            self.thread_pool.spawn(move || shared.run_repair_task(chunkset_id));
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether the chunkset `chunkset_id` has already been repaired, or is repairing, i.e. it doesn't need any
+    /// more chunks.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(bool)` telling whether the chunkset is repaired.
+    /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_id` is out of bounds.
+    pub fn is_chunkset_repaired(&self, chunkset_id: usize) -> Result<bool, DecdsError> {
+        self.shared
+            .chunksets
+            .get(chunkset_id)
+            .map(|slot| lock_slot(slot).is_repaired)
+            .ok_or(DecdsError::InvalidChunksetId(chunkset_id, self.shared.chunksets.len()))
+    }
+
+    /// Returns the number of submitted chunks, which were dropped by repair tasks, as they either failed validation
+    /// against the `BlobHeader` or had an unexpected coding vector.
+    pub fn get_num_rejected_chunks(&self) -> usize {
+        self.shared.num_rejected_chunks.load(Ordering::Relaxed)
+    }
+}
+
+impl SharedState {
+    /// Repair task of chunkset `chunkset_id`. Keeps consuming chunks queued on the chunkset, until either there are no
+    /// more, in which case the decoding state is put back for the next task, or the chunkset gets repaired and emitted.
+    fn run_repair_task(&self, chunkset_id: usize) {
+        let slot = &self.chunksets[chunkset_id];
+
+        let mut repairer = match lock_slot(slot).repairer.take() {
+            Some(repairer) => repairer,
+            None => return,
+        };
+
+        loop {
+            let pending = {
+                let mut slot = lock_slot(slot);
+                if slot.pending.is_empty() {
+                    slot.repairer = Some(repairer);
+                    slot.is_scheduled = false;
+                    return;
+                }
+
+                mem::take(&mut slot.pending)
+            };
+
+            for chunk in pending.iter().filter(|chunk| {
+                let is_valid = self.header.validate_chunk(chunk);
+                if !is_valid {
+                    self.num_rejected_chunks.fetch_add(1, Ordering::Relaxed);
+                }
+
+                is_valid
+            }) {
+                if repairer.is_ready_to_repair() {
+                    break;
+                }
+
+                // Linearly dependent chunks are simply useless, only ones with a wrong coding vector are rejected.
+                if let Err(DecdsError::UnexpectedCodingVector(..)) = repairer.add_chunk_unvalidated(chunk) {
+                    self.num_rejected_chunks.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+
+            if repairer.is_ready_to_repair() {
+                {
+                    let mut slot = lock_slot(slot);
+                    slot.is_repaired = true;
+                    slot.is_scheduled = false;
+                    slot.pending.clear();
+                }
+
+                let result = repairer.repair().map(|mut repaired| {
+                    repaired.truncate(unsafe { self.header.get_chunkset_size(chunkset_id).unwrap_unchecked() });
+                    repaired
+                });
+
+                let _ = self.repaired_tx.send((chunkset_id, result));
+                return;
+            }
+        }
+    }
+}
+
+/// Locks a chunkset slot, recovering it even if some repair task panicked while holding the lock, as slot state is
+/// always left consistent.
+fn lock_slot(slot: &Mutex<ChunksetSlot>) -> MutexGuard<'_, ChunksetSlot> {
+    slot.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(all(test, feature = "coding"))]
+mod tests {
+    use crate::{ChunkFault, DecdsError, RepairPool, blob::Blob, consts::DECDS_NUM_ERASURE_CODED_SHARES};
+    use rand::Rng;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_repair_pool_emits_every_chunkset_once() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..rng.random_range(1usize << 20..=1usize << 22)).map(|_| rng.random()).collect::<Vec<u8>>();
+        let blob = Blob::new(blob_data.clone()).expect("Must be able to prepare blob");
+        let header = blob.get_blob_header().clone();
+
+        let (pool, repaired_rx) = RepairPool::new(header.clone(), 4).expect("Must be able to build repair pool");
+
+        // Submit shares in reverse, with a tampered copy of each chunk, so that chunks of many chunksets interleave.
+        for share_id in (0..DECDS_NUM_ERASURE_CODED_SHARES).rev() {
+            for chunk in blob.get_share(share_id).expect("Must be able to get erasure-coded share") {
+                for chunk in [ChunkFault::FlipPayloadBit(0).apply(&chunk), chunk] {
+                    match pool.submit(chunk) {
+                        Ok(()) | Err(DecdsError::ChunksetAlreadyRepaired(_)) => {}
+                        Err(err) => panic!("Unexpected error while submitting chunk: {}", err),
+                    }
+                }
+            }
+        }
+        drop(pool);
+
+        let repaired = repaired_rx
+            .into_iter()
+            .map(|(chunkset_id, result)| (chunkset_id, result.expect("Must be able to repair chunkset")))
+            .collect::<BTreeMap<usize, Vec<u8>>>();
+
+        assert_eq!(repaired.len(), header.get_num_chunksets());
+        assert_eq!(repaired.into_values().flatten().collect::<Vec<u8>>(), blob_data);
+    }
+}
@@ -0,0 +1,98 @@
+/// Snapshot of how far a `RepairingBlob` has come, kept up to date as chunks are added and chunksets are repaired, so that
+/// UIs and daemons can report repair progress, without iterating over all chunkset IDs. See `RepairingBlob::get_progress`
+/// and `EventHandler::on_progress`.
+///
+/// Only chunksets tracked by the repairer count, i.e. those overlapping its target byte range, if it's restricted to one.
+/// A chunkset stays ready to repair until it is retrieved, e.g. with `RepairingBlob::get_repaired_chunkset`, after which
+/// it counts as repaired.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RepairProgress {
+    num_chunksets: usize,
+    num_ready_chunksets: usize,
+    num_repaired_chunksets: usize,
+    num_useful_chunks: usize,
+    num_required_chunks: usize,
+}
+
+impl RepairProgress {
+    /// Creates progress of a repairer tracking `num_chunksets` chunksets, none of which has received any chunk yet, each
+    /// requiring `num_required_chunks_per_chunkset` useful chunks.
+    pub(crate) fn new(num_chunksets: usize, num_required_chunks_per_chunkset: usize) -> Self {
+        RepairProgress {
+            num_chunksets,
+            num_required_chunks: num_chunksets.saturating_mul(num_required_chunks_per_chunkset),
+            ..Default::default()
+        }
+    }
+
+    /// Creates progress of a repairer of an inline blob, whose only chunkset is ready to repair, without requiring chunks.
+    pub(crate) fn new_inline() -> Self {
+        RepairProgress {
+            num_chunksets: 1,
+            num_ready_chunksets: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Counts `num_useful_chunks` useful chunks, added to a chunkset, which became ready to repair with them, if
+    /// `is_chunkset_ready` is `true`.
+    pub(crate) fn record_useful_chunks(&mut self, num_useful_chunks: usize, is_chunkset_ready: bool) {
+        self.num_useful_chunks += num_useful_chunks;
+        if is_chunkset_ready {
+            self.num_ready_chunksets += 1;
+        }
+    }
+
+    /// Moves a chunkset, which was ready to repair, over to the repaired ones.
+    pub(crate) fn record_repaired_chunkset(&mut self) {
+        self.num_ready_chunksets = self.num_ready_chunksets.saturating_sub(1);
+        self.num_repaired_chunksets += 1;
+    }
+
+    /// Returns the number of chunksets tracked by the repairer.
+    pub fn get_num_chunksets(&self) -> usize {
+        self.num_chunksets
+    }
+
+    /// Returns the number of chunksets, which are ready to repair, but not yet repaired.
+    pub fn get_num_ready_chunksets(&self) -> usize {
+        self.num_ready_chunksets
+    }
+
+    /// Returns the number of chunksets, which are already repaired.
+    pub fn get_num_repaired_chunksets(&self) -> usize {
+        self.num_repaired_chunksets
+    }
+
+    /// Returns the number of useful, i.e. linearly independent, erasure-coded chunks received so far, over all tracked
+    /// chunksets, including those already repaired.
+    pub fn get_num_useful_chunks(&self) -> usize {
+        self.num_useful_chunks
+    }
+
+    /// Returns the number of useful erasure-coded chunks required to repair all tracked chunksets. It is zero for inline
+    /// blobs.
+    pub fn get_num_required_chunks(&self) -> usize {
+        self.num_required_chunks
+    }
+
+    /// Returns the fraction of required useful chunks received so far, in `[0, 1]`. It is `1.0` once every tracked
+    /// chunkset is ready to repair or repaired.
+    pub fn get_fraction(&self) -> f64 {
+        if self.num_required_chunks == 0 {
+            return 1.0;
+        }
+
+        self.num_useful_chunks as f64 / self.num_required_chunks as f64
+    }
+
+    /// Returns `true` if every tracked chunkset is ready to repair or repaired, i.e. no more chunks are required.
+    pub fn is_ready(&self) -> bool {
+        self.num_ready_chunksets + self.num_repaired_chunksets == self.num_chunksets
+    }
+
+    /// Returns `true` if every tracked chunkset is repaired.
+    pub fn is_complete(&self) -> bool {
+        self.num_repaired_chunksets == self.num_chunksets
+    }
+}
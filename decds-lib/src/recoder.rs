@@ -0,0 +1,224 @@
+use crate::{blob::BlobHeader, chunk::ProofCarryingChunk, codec::gf_mul, errors::DecdsError};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// A fresh random linear combination of erasure-coded chunks of a chunkset, produced by a `Recoder`.
+///
+/// Just like a chunk, it carries its coding vector, over the original pieces of the chunkset, followed by the coded piece.
+/// Unlike a chunk, it isn't part of the blob commitment, so it carries no Merkle proof and can't be validated against the
+/// `BlobHeader`. It's only as trustworthy as the node which recoded it, so it's only good for forwarding between relays,
+/// see `Recoder::add_recoded_chunk`; `RepairingBlob` doesn't accept it, as a forged one would corrupt the repaired
+/// chunkset.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct RecodedChunk {
+    chunkset_id: usize,
+    erasure_coded_data: Vec<u8>,
+}
+
+impl RecodedChunk {
+    /// Returns the ID of the chunkset this recoded chunk belongs to.
+    pub fn get_chunkset_id(&self) -> usize {
+        self.chunkset_id
+    }
+
+    /// Returns the erasure-coded data of this recoded chunk, i.e. its coding vector, followed by its coded piece.
+    pub fn get_erasure_coded_data(&self) -> &[u8] {
+        &self.erasure_coded_data
+    }
+
+    /// Checks that this recoded chunk belongs to chunkset `chunkset_id`, and its erasure-coded data is
+    /// `erasure_coded_data_byte_length` bytes long, as are erasure-coded data of chunks of that chunkset.
+    pub(crate) fn check_shape(&self, chunkset_id: usize, erasure_coded_data_byte_length: usize) -> Result<(), DecdsError> {
+        if self.chunkset_id != chunkset_id {
+            return Err(DecdsError::InvalidRecodedChunk(
+                self.chunkset_id,
+                format!("expected chunk of chunkset {}", chunkset_id),
+            ));
+        }
+        if self.erasure_coded_data.len() != erasure_coded_data_byte_length {
+            return Err(DecdsError::InvalidRecodedChunk(
+                self.chunkset_id,
+                format!(
+                    "erasure-coded data is {} bytes, expected {} bytes",
+                    self.erasure_coded_data.len(),
+                    erasure_coded_data_byte_length
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// RLNC recoder of a single chunkset, for relay and storage nodes, which hold some chunks of a chunkset and want to hand
+/// out new, random linear combinations of them, without repairing the chunkset first.
+///
+/// A recoded chunk is as useful as any chunk, as long as it's linearly independent of chunks the repairing side already
+/// has, which it is with high probability, whenever the recoder holds chunks the repairing side doesn't. So a node
+/// holding `k` chunks can serve any number of repairers, each needing a different subset of those `k` chunks, without
+/// knowing which. Chunks given to a recoder are validated against the `BlobHeader`, but recoded chunks can't be, see
+/// `RecodedChunk`.
+pub struct Recoder {
+    chunkset_id: usize,
+    commitment: blake3::Hash,
+    erasure_coded_data_byte_length: usize,
+    sources: Vec<Vec<u8>>,
+}
+
+impl Recoder {
+    /// Creates a new `Recoder` for chunkset `chunkset_id` of the blob described by `header`, holding no chunks yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - The `BlobHeader` of the blob, the chunkset belongs to.
+    /// * `chunkset_id` - The ID of the chunkset to recode chunks of.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Recoder)` containing the new recoder if successful.
+    /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_id` is out of bounds, or the blob is stored inline in its header.
+    pub fn new(header: &BlobHeader, chunkset_id: usize) -> Result<Self, DecdsError> {
+        Ok(Recoder {
+            chunkset_id,
+            commitment: header.get_chunkset_commitment(chunkset_id)?,
+            erasure_coded_data_byte_length: header.get_config().get_erasure_coded_data_byte_length(),
+            sources: Vec::new(),
+        })
+    }
+
+    /// Adds a `ProofCarryingChunk` of the chunkset to the recoder, after validating its Merkle proof of inclusion in the
+    /// chunkset.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk` - The `ProofCarryingChunk` to recode.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(())` if the chunk is successfully added.
+    /// - `Err(DecdsError::InvalidChunkMetadata)` if the chunk's `chunkset_id` does not match this `Recoder`.
+    /// - `Err(DecdsError::InvalidProofInChunk)` if the chunk's inclusion proof is invalid for this chunkset.
+    pub fn add_chunk(&mut self, chunk: &ProofCarryingChunk) -> Result<(), DecdsError> {
+        if chunk.get_chunkset_id() != self.chunkset_id {
+            return Err(DecdsError::InvalidChunkMetadata(chunk.get_chunkset_id(), chunk.get_local_chunk_id()));
+        }
+        if !chunk.validate_inclusion_in_chunkset(self.commitment) {
+            return Err(DecdsError::InvalidProofInChunk(chunk.get_chunkset_id(), chunk.get_local_chunk_id()));
+        }
+
+        self.sources.push(chunk.get_erasure_coded_data().to_vec());
+        Ok(())
+    }
+
+    /// Same as `Self::add_chunk`, but for chunks known to be valid, e.g. those held by the `Blob` itself.
+    pub(crate) fn add_chunk_unvalidated(&mut self, chunk: &ProofCarryingChunk) {
+        self.sources.push(chunk.get_erasure_coded_data().to_vec());
+    }
+
+    /// Adds a `RecodedChunk` of the chunkset to the recoder, so that nodes can recode what they got recoded, in turn. As
+    /// recoded chunks carry no proof, only their chunkset and length are checked.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk` - The `RecodedChunk` to recode.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(())` if the recoded chunk is successfully added.
+    /// - `Err(DecdsError::InvalidRecodedChunk)` if it doesn't belong to this chunkset, or is of unexpected length.
+    pub fn add_recoded_chunk(&mut self, chunk: &RecodedChunk) -> Result<(), DecdsError> {
+        chunk.check_shape(self.chunkset_id, self.erasure_coded_data_byte_length)?;
+
+        self.sources.push(chunk.erasure_coded_data.clone());
+        Ok(())
+    }
+
+    /// Returns the number of chunks, recoded or not, held by this recoder.
+    pub fn get_num_source_chunks(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Produces a new random linear combination of all chunks held by this recoder, each multiplied by a random non-zero
+    /// coefficient. Both coding vectors and coded pieces are combined alike, so the recoded chunk's coding vector is over
+    /// the original pieces of the chunkset, as any chunk's is.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(RecodedChunk)` containing the recoded chunk if successful.
+    /// - `Err(DecdsError::NoChunksToRecode)` if no chunk has been added to this recoder yet.
+    pub fn recode(&self) -> Result<RecodedChunk, DecdsError> {
+        if self.sources.is_empty() {
+            return Err(DecdsError::NoChunksToRecode(self.chunkset_id));
+        }
+
+        let mut rng = rand::rng();
+        let mut erasure_coded_data = vec![0u8; self.erasure_coded_data_byte_length];
+
+        self.sources.iter().for_each(|source| {
+            let coefficient = rng.random_range(1..=u8::MAX);
+            let products: [u8; 256] = std::array::from_fn(|byte| gf_mul(coefficient, byte as u8));
+
+            erasure_coded_data
+                .iter_mut()
+                .zip(source)
+                .for_each(|(acc, &byte)| *acc ^= products[byte as usize]);
+        });
+
+        Ok(RecodedChunk {
+            chunkset_id: self.chunkset_id,
+            erasure_coded_data,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "coding"))]
+mod tests {
+    use crate::{ChunkFault, DecdsError, Recoder, RepairingBlob, blob::Blob};
+    use rand::Rng;
+
+    #[test]
+    fn test_recoded_chunks_repair_chunkset_across_hops() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..rng.random_range(1usize << 20..=1usize << 22)).map(|_| rng.random()).collect::<Vec<u8>>();
+        let blob = Blob::new(blob_data.clone()).expect("Must be able to prepare blob");
+        let header = blob.get_blob_header().clone();
+        let num_original_chunks = header.get_config().get_num_original_chunks();
+
+        let mut relay = Recoder::new(&header, 0).expect("Must be able to create recoder");
+        assert_eq!(relay.recode(), Err(DecdsError::NoChunksToRecode(0)));
+
+        for share_id in 0..num_original_chunks {
+            let chunk = blob.get_chunk(0, share_id).expect("Must be able to get chunk");
+
+            assert!(matches!(
+                relay.add_chunk(&ChunkFault::FlipPayloadBit(0).apply(chunk)),
+                Err(DecdsError::InvalidProofInChunk(0, _))
+            ));
+            relay.add_chunk(chunk).expect("Must be able to add valid chunk");
+        }
+
+        // Second hop only ever sees recoded chunks of the first one.
+        let mut second_relay = Recoder::new(&header, 0).expect("Must be able to create recoder");
+        (0..num_original_chunks).for_each(|_| {
+            let recoded = relay.recode().expect("Must be able to recode");
+            second_relay.add_recoded_chunk(&recoded).expect("Must be able to add recoded chunk");
+        });
+
+        let mut repairer = RepairingBlob::new(header.clone());
+        while !repairer.is_chunkset_ready_to_repair(0).expect("Chunkset 0 must exist") {
+            let recoded = second_relay.recode().expect("Must be able to recode");
+            match repairer.add_recoded_chunk(&recoded) {
+                Ok(()) | Err(DecdsError::NonInnovativeRecodedChunk(0)) => {}
+                Err(err) => panic!("Unexpected error while adding recoded chunk: {}", err),
+            }
+        }
+
+        let repaired = repairer.get_repaired_chunkset(0).expect("Must be able to repair chunkset");
+        assert_eq!(repaired, blob_data[..repaired.len()]);
+    }
+}
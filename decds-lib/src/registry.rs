@@ -0,0 +1,254 @@
+use crate::{blob::BlobHeader, consts::DECDS_BINCODE_CONFIG, errors::DecdsError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Registry of blob headers, keyed by blob ID, along with human-readable names (tags) pointing to them, so that a header
+/// can be looked up by name, blob ID or blob digest, instead of shuttling `metadata.commit` files around. Registering
+/// another header of an already registered blob, e.g. encoded with another seed, replaces the previous one.
+///
+/// The registry is kept in memory and serialized as a whole, leaving where it is persisted to the caller.
+///
+/// ```rust
+/// use decds_lib::{Blob, HeaderRegistry};
+///
+/// let blob = Blob::new(b"hello, world".to_vec()).expect("Failed to create blob");
+/// let header = blob.get_blob_header();
+///
+/// let mut registry = HeaderRegistry::new();
+/// registry.tag("greeting", header.clone()).expect("Failed to tag blob header");
+///
+/// let (registry, _) = HeaderRegistry::from_bytes(&registry.to_bytes().expect("Failed to serialize registry")).expect("Failed to deserialize registry");
+/// assert_eq!(registry.resolve("greeting"), Some(header));
+/// assert_eq!(registry.resolve(&header.get_blob_digest().to_hex()), Some(header));
+/// ```
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct HeaderRegistry {
+    headers: BTreeMap<[u8; blake3::OUT_LEN], BlobHeader>,
+    names: BTreeMap<String, [u8; blake3::OUT_LEN]>,
+}
+
+impl HeaderRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of registered headers.
+    pub fn get_num_headers(&self) -> usize {
+        self.headers.len()
+    }
+
+    /// Registers a blob header, replacing the previously registered header of the same blob, if any.
+    ///
+    /// # Returns
+    ///
+    /// Returns the ID of the registered blob.
+    pub fn insert(&mut self, header: BlobHeader) -> blake3::Hash {
+        let blob_id = header.get_blob_id();
+        self.headers.insert(*blob_id.as_bytes(), header);
+
+        blob_id
+    }
+
+    /// Registers a blob header and points `name` to it, moving the name if it pointed to another blob before. Names must
+    /// be non-empty and free of whitespaces and control characters, so that they can be typed on a command line.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Human-readable name of the blob.
+    /// * `header` - The blob header to register.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Option<blake3::Hash>)` containing the ID of the blob `name` pointed to before, if successful.
+    /// - `Err(DecdsError::InvalidTagName)` if `name` is empty or has whitespaces or control characters.
+    pub fn tag(&mut self, name: &str, header: BlobHeader) -> Result<Option<blake3::Hash>, DecdsError> {
+        if name.is_empty() || name.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return Err(DecdsError::InvalidTagName(name.to_string()));
+        }
+
+        let blob_id = self.insert(header);
+        Ok(self.names.insert(name.to_string(), *blob_id.as_bytes()).map(blake3::Hash::from_bytes))
+    }
+
+    /// Removes the name `name`, leaving the header it pointed to registered.
+    ///
+    /// # Returns
+    ///
+    /// Returns the ID of the blob `name` pointed to, or `None` if there was no such name.
+    pub fn untag(&mut self, name: &str) -> Option<blake3::Hash> {
+        self.names.remove(name).map(blake3::Hash::from_bytes)
+    }
+
+    /// Unregisters the header of a blob, along with all names pointing to it.
+    ///
+    /// # Returns
+    ///
+    /// Returns the removed header, or `None` if the blob wasn't registered.
+    pub fn remove(&mut self, blob_id: &blake3::Hash) -> Option<BlobHeader> {
+        self.names.retain(|_, named_blob_id| named_blob_id != blob_id.as_bytes());
+        self.headers.remove(blob_id.as_bytes())
+    }
+
+    /// Returns the registered header of the blob with `blob_id`.
+    pub fn get_by_blob_id(&self, blob_id: &blake3::Hash) -> Option<&BlobHeader> {
+        self.headers.get(blob_id.as_bytes())
+    }
+
+    /// Returns the registered header of a blob, whose BLAKE3 digest is `blob_digest`. Blob ID is derived from the blob's
+    /// size and digest, so there's at most one match.
+    pub fn get_by_blob_digest(&self, blob_digest: &blake3::Hash) -> Option<&BlobHeader> {
+        self.headers.values().find(|header| header.get_blob_digest() == *blob_digest)
+    }
+
+    /// Returns the registered header `name` points to.
+    pub fn get_by_name(&self, name: &str) -> Option<&BlobHeader> {
+        self.names.get(name).and_then(|blob_id| self.headers.get(blob_id))
+    }
+
+    /// Returns all names, in lexicographic order, along with the ID of the blob each one points to.
+    pub fn names(&self) -> impl Iterator<Item = (&str, blake3::Hash)> + '_ {
+        self.names.iter().map(|(name, blob_id)| (name.as_str(), blake3::Hash::from_bytes(*blob_id)))
+    }
+
+    /// Looks a header up by name, falling back to interpreting `key` as hex-encoded blob ID and then as hex-encoded blob
+    /// digest.
+    pub fn resolve(&self, key: &str) -> Option<&BlobHeader> {
+        self.get_by_name(key).or_else(|| {
+            let hash = blake3::Hash::from_hex(key).ok()?;
+            self.get_by_blob_id(&hash).or_else(|| self.get_by_blob_digest(&hash))
+        })
+    }
+
+    /// Serializes the registry into a vector of bytes using `bincode`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Vec<u8>)` containing the serialized bytes if successful.
+    /// - `Err(DecdsError::HeaderRegistrySerializationFailed)` if serialization fails, containing the error message.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, DecdsError> {
+        bincode::serde::encode_to_vec(self, DECDS_BINCODE_CONFIG).map_err(|err| DecdsError::HeaderRegistrySerializationFailed(err.to_string()))
+    }
+
+    /// Deserializes a registry from a byte slice using `bincode`. Registered headers are checked just like
+    /// `BlobHeader::from_bytes` does, and must be keyed by their blob IDs, while names must point to registered headers.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The byte slice from which to deserialize the registry.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok((Self, usize))` containing the deserialized registry and the number of bytes read if successful.
+    /// - `Err(DecdsError::HeaderRegistryDeserializationFailed)` if `bincode` deserialization fails or the registry is
+    ///   inconsistent.
+    /// - `Err(DecdsError::BlobHeaderDeserializationFailed)` if a registered blob header is inconsistent.
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Self, usize), DecdsError> {
+        let (registry, n) = bincode::serde::decode_from_slice::<HeaderRegistry, bincode::config::Configuration>(bytes, DECDS_BINCODE_CONFIG)
+            .map_err(|err| DecdsError::HeaderRegistryDeserializationFailed(err.to_string()))?;
+
+        registry.headers.iter().try_for_each(|(blob_id, header)| {
+            header.validate_consistency()?;
+
+            if header.get_blob_id().as_bytes() != blob_id {
+                return Err(DecdsError::HeaderRegistryDeserializationFailed(format!(
+                    "header of blob {} is registered as {}",
+                    header.get_blob_id(),
+                    blake3::Hash::from_bytes(*blob_id)
+                )));
+            }
+
+            Ok(())
+        })?;
+
+        if let Some((name, _)) = registry.names.iter().find(|(_, blob_id)| !registry.headers.contains_key(*blob_id)) {
+            return Err(DecdsError::HeaderRegistryDeserializationFailed(format!(
+                "name {} points to an unregistered blob",
+                name
+            )));
+        }
+
+        Ok((registry, n))
+    }
+}
+
+#[cfg(all(test, feature = "coding"))]
+mod tests {
+    use crate::{DecdsBuilder, blob::Blob, errors::DecdsError, registry::HeaderRegistry};
+
+    #[test]
+    fn test_header_registry() {
+        let first_blob = Blob::new(vec![0xab; 1024]).expect("Failed to create blob");
+        let first_header = first_blob.get_blob_header();
+        let second_blob = Blob::new(vec![0xcd; 2048]).expect("Failed to create blob");
+        let second_header = second_blob.get_blob_header();
+
+        let mut registry = HeaderRegistry::new();
+        assert_eq!(registry.tag("first", first_header.clone()), Ok(None));
+        assert_eq!(registry.tag("latest", first_header.clone()), Ok(None));
+        assert_eq!(registry.insert(second_header.clone()), second_header.get_blob_id());
+        assert_eq!(registry.get_num_headers(), 2);
+
+        assert_eq!(registry.get_by_name("first"), Some(first_header));
+        assert_eq!(registry.get_by_blob_id(&second_header.get_blob_id()), Some(second_header));
+        assert_eq!(registry.get_by_blob_digest(&second_header.get_blob_digest()), Some(second_header));
+        assert_eq!(registry.resolve(&second_header.get_blob_id().to_hex()), Some(second_header));
+        assert_eq!(registry.resolve("second"), None);
+
+        // Moving a name reports where it pointed to
+        assert_eq!(registry.tag("latest", second_header.clone()), Ok(Some(first_header.get_blob_id())));
+        assert_eq!(
+            registry.names().collect::<Vec<_>>(),
+            vec![("first", first_header.get_blob_id()), ("latest", second_header.get_blob_id())]
+        );
+
+        assert_eq!(registry.tag("", first_header.clone()), Err(DecdsError::InvalidTagName(String::new())));
+        assert_eq!(
+            registry.tag("my blob", first_header.clone()),
+            Err(DecdsError::InvalidTagName("my blob".to_string()))
+        );
+
+        // Re-encoded blob replaces the registered header of the same blob
+        let reencoded_blob = DecdsBuilder::new().seed([7u8; 32]).build(vec![0xab; 1024]).expect("Failed to create blob");
+        registry.insert(reencoded_blob.get_blob_header().clone());
+        assert_eq!(registry.get_num_headers(), 2);
+        assert_eq!(registry.get_by_name("first"), Some(reencoded_blob.get_blob_header()));
+
+        let bytes = registry.to_bytes().unwrap();
+        assert_eq!(HeaderRegistry::from_bytes(&bytes), Ok((registry.clone(), bytes.len())));
+
+        assert_eq!(registry.untag("first"), Some(first_header.get_blob_id()));
+        assert_eq!(registry.untag("first"), None);
+        assert_eq!(registry.remove(&second_header.get_blob_id()), Some(second_header.clone()));
+        assert_eq!(registry.resolve("latest"), None);
+        assert_eq!(registry.get_num_headers(), 1);
+    }
+
+    #[test]
+    fn test_header_registry_rejects_inconsistent_bytes() {
+        let blob = Blob::new(vec![0xab; 1024]).expect("Failed to create blob");
+        let header = blob.get_blob_header();
+
+        let mut registry = HeaderRegistry::new();
+        registry.tag("blob", header.clone()).unwrap();
+
+        let mut misnamed = registry.clone();
+        misnamed.names.insert("dangling".to_string(), [0u8; 32]);
+        assert!(matches!(
+            HeaderRegistry::from_bytes(&misnamed.to_bytes().unwrap()),
+            Err(DecdsError::HeaderRegistryDeserializationFailed(_))
+        ));
+
+        let mut misplaced = registry.clone();
+        let header = misplaced.headers.pop_first().unwrap().1;
+        misplaced.headers.insert([0u8; 32], header);
+        misplaced.names.insert("blob".to_string(), [0u8; 32]);
+        assert!(matches!(
+            HeaderRegistry::from_bytes(&misplaced.to_bytes().unwrap()),
+            Err(DecdsError::HeaderRegistryDeserializationFailed(_))
+        ));
+    }
+}
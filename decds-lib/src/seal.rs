@@ -0,0 +1,225 @@
+use crate::{availability::ShareBitmap, blob::BlobHeader, chunk::ProofCarryingChunk, consts::DECDS_BINCODE_CONFIG, errors::DecdsError};
+use serde::{Deserialize, Serialize};
+
+/// Final header of a sealed `Blob`, i.e. one which refuses to hand out shares it didn't hand out before sealing, recording
+/// which share IDs were ever issued. Auditors can then tell chunks of issued shares from unexpected extra ones, e.g. minted
+/// from a leaked copy of the blob before it was sealed, which would otherwise validate against the blob header just fine.
+///
+/// The seal may be signed by whoever sealed the blob. Just like `VersionedBlob`, signing and verification are left to the
+/// caller, as closures over the 32-bytes seal ID.
+///
+/// ```rust
+/// use decds_lib::Blob;
+///
+/// // Stand-in for a real signature scheme.
+/// let sign = |seal_id: &blake3::Hash| blake3::keyed_hash(&[7u8; 32], seal_id.as_bytes()).as_bytes().to_vec();
+/// let verify = |seal_id: &blake3::Hash, signature: &[u8]| sign(seal_id) == signature;
+///
+/// let mut blob = Blob::new(vec![0xde; 1024]).expect("Failed to create blob");
+/// let chunk = blob.get_chunk(0, 3).expect("Failed to get chunk").clone();
+///
+/// let sealed = blob.seal_with_signature(sign);
+/// assert!(blob.get_share(4).is_err());
+/// assert!(sealed.verify_signature(verify).is_ok());
+/// assert!(sealed.validate_chunk(&chunk));
+/// assert!(sealed.is_share_issued(3) && !sealed.is_share_issued(4));
+/// ```
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct SealedBlobHeader {
+    header: BlobHeader,
+    issued_shares: ShareBitmap,
+    signature: Option<Vec<u8>>,
+}
+
+impl SealedBlobHeader {
+    /// BLAKE3 key derivation context, used for computing seal IDs.
+    const SEAL_ID_KDF_CONTEXT: &str = "decds 2025-07-01 blob seal id";
+
+    /// Seals `header`, recording `issued_shares`, without signing the seal.
+    #[cfg(feature = "coding")]
+    pub(crate) fn new(header: BlobHeader, issued_shares: ShareBitmap) -> Self {
+        SealedBlobHeader {
+            header,
+            issued_shares,
+            signature: None,
+        }
+    }
+
+    /// Signs the seal ID with `sign`, replacing the signature, if any.
+    #[cfg(feature = "coding")]
+    pub(crate) fn with_signature(mut self, sign: impl FnOnce(&blake3::Hash) -> Vec<u8>) -> Self {
+        self.signature = Some(sign(&self.get_seal_id()));
+        self
+    }
+
+    /// Computes the ID of a seal, which is what gets signed.
+    ///
+    /// The ID is computed as `BLAKE3(blob_id || root_commitment || records_digest || issued_shares)`, hashed in key
+    /// derivation mode with `SEAL_ID_KDF_CONTEXT`, where `issued_shares` is encoded as 2-bytes little-endian unsigned
+    /// integer. The records digest binds record offsets, see `BlobHeader::get_records_digest`.
+    fn compute_seal_id(header: &BlobHeader, issued_shares: ShareBitmap) -> blake3::Hash {
+        blake3::Hasher::new_derive_key(Self::SEAL_ID_KDF_CONTEXT)
+            .update(header.get_blob_id().as_bytes())
+            .update(header.get_root_commitment().as_bytes())
+            .update(header.get_records_digest().as_bytes())
+            .update(&issued_shares.to_le_bytes())
+            .finalize()
+    }
+
+    /// Returns the ID of this seal, committing to the blob, its root commitment, record offsets and issued share IDs.
+    pub fn get_seal_id(&self) -> blake3::Hash {
+        Self::compute_seal_id(&self.header, self.issued_shares)
+    }
+
+    /// Returns the header of the sealed blob.
+    pub fn get_blob_header(&self) -> &BlobHeader {
+        &self.header
+    }
+
+    /// Returns the bitmap of share IDs issued before the blob was sealed, where bit `share_id` is set if the share was issued.
+    pub fn get_issued_shares(&self) -> ShareBitmap {
+        self.issued_shares
+    }
+
+    /// Checks whether share `share_id` was issued before the blob was sealed.
+    pub fn is_share_issued(&self, share_id: usize) -> bool {
+        share_id < ShareBitmap::BITS as usize && (self.issued_shares >> share_id) & 1 == 1
+    }
+
+    /// Returns the signature over the seal ID, or `None` if the seal isn't signed.
+    pub fn get_signature(&self) -> Option<&[u8]> {
+        self.signature.as_deref()
+    }
+
+    /// Checks the signature over the seal ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `verify_signature` - Checks a signature over the seal ID, on behalf of whoever sealed the blob.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(())` if the seal is signed and its signature is valid.
+    /// - `Err(DecdsError::InvalidSealSignature)` if the seal isn't signed, or `verify_signature` rejects its signature.
+    pub fn verify_signature(&self, verify_signature: impl FnOnce(&blake3::Hash, &[u8]) -> bool) -> Result<(), DecdsError> {
+        match &self.signature {
+            Some(signature) if verify_signature(&self.get_seal_id(), signature) => Ok(()),
+            _ => Err(DecdsError::InvalidSealSignature),
+        }
+    }
+
+    /// Same as `BlobHeader::validate_chunk`, but also requires the chunk to belong to a share, which was issued before the
+    /// blob was sealed.
+    pub fn validate_chunk(&self, chunk: &ProofCarryingChunk) -> bool {
+        self.is_share_issued(chunk.get_local_chunk_id()) && self.header.validate_chunk(chunk)
+    }
+
+    /// Serializes the sealed header into a vector of bytes using `bincode`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Vec<u8>)` containing the serialized bytes if successful.
+    /// - `Err(DecdsError::SealedBlobHeaderSerializationFailed)` if serialization fails, containing the error message.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, DecdsError> {
+        bincode::serde::encode_to_vec(self, DECDS_BINCODE_CONFIG).map_err(|err| DecdsError::SealedBlobHeaderSerializationFailed(err.to_string()))
+    }
+
+    /// Deserializes a sealed header from a byte slice using `bincode`. The embedded blob header is checked just like
+    /// `BlobHeader::from_bytes` does, and only shares of the blob may be marked issued.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The byte slice from which to deserialize the sealed header.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok((Self, usize))` containing the deserialized sealed header and the number of bytes read if successful.
+    /// - `Err(DecdsError::SealedBlobHeaderDeserializationFailed)` if `bincode` deserialization fails, or a share, which the
+    ///   blob doesn't have, is marked issued.
+    /// - `Err(DecdsError::BlobHeaderDeserializationFailed)` if the embedded blob header is inconsistent.
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Self, usize), DecdsError> {
+        let (sealed, n) = bincode::serde::decode_from_slice::<SealedBlobHeader, bincode::config::Configuration>(bytes, DECDS_BINCODE_CONFIG)
+            .map_err(|err| DecdsError::SealedBlobHeaderDeserializationFailed(err.to_string()))?;
+
+        sealed.header.validate_consistency()?;
+        if (sealed.issued_shares as u32) >> sealed.header.get_config().get_num_shares() != 0 {
+            return Err(DecdsError::SealedBlobHeaderDeserializationFailed(
+                "shares, which the blob doesn't have, are marked issued".to_string(),
+            ));
+        }
+
+        Ok((sealed, n))
+    }
+}
+
+#[cfg(all(test, feature = "coding"))]
+mod tests {
+    use crate::{BlobConfig, DecdsBuilder, ProofCarryingChunk, blob::Blob, errors::DecdsError, seal::SealedBlobHeader};
+
+    fn sign_with(key: u8) -> impl Fn(&blake3::Hash) -> Vec<u8> {
+        move |seal_id| blake3::keyed_hash(&[key; 32], seal_id.as_bytes()).as_bytes().to_vec()
+    }
+
+    fn verify_with(key: u8) -> impl Fn(&blake3::Hash, &[u8]) -> bool {
+        move |seal_id, signature| sign_with(key)(seal_id) == signature
+    }
+
+    #[test]
+    fn test_sealing_blob() {
+        let config = BlobConfig::new().num_shares(12).chunkset_byte_length(1024);
+        let mut blob = DecdsBuilder::new().config(config).build(vec![0xab; 3000]).unwrap();
+        let other_blob = Blob::new(vec![0xab; 3000]).unwrap();
+
+        let share = blob.get_share(1).unwrap();
+        assert_eq!(blob.iter_share(5).unwrap().count(), blob.get_blob_header().get_num_chunksets());
+        assert_eq!(blob.share_stream(7).unwrap().count(), blob.get_blob_header().get_num_chunksets());
+        let chunk = blob.get_chunk(2, 11).unwrap().clone();
+        let unissued_chunk = blob.get_chunk(0, 0).unwrap().clone();
+        assert_eq!(blob.get_issued_shares(), 0b1000_1010_0011);
+        assert!(!blob.is_sealed());
+
+        let sealed = blob.seal_with_signature(sign_with(7));
+        assert!(blob.is_sealed());
+        assert_eq!(sealed.get_blob_header(), blob.get_blob_header());
+        assert_eq!(sealed.get_issued_shares(), 0b1000_1010_0011);
+
+        // Issued shares can still be handed out, new ones are refused
+        assert_eq!(blob.get_share(1).unwrap(), share);
+        assert_eq!(blob.get_chunk(2, 11), Ok(&chunk));
+        assert_eq!(blob.get_share(2), Err(DecdsError::BlobSealed(2)));
+        assert!(matches!(blob.iter_share(3), Err(DecdsError::BlobSealed(3))));
+        assert!(matches!(blob.share_stream(4), Err(DecdsError::BlobSealed(4))));
+        assert_eq!(blob.get_chunk(0, 6), Err(DecdsError::BlobSealed(6)));
+        assert_eq!(blob.get_issued_shares(), sealed.get_issued_shares());
+
+        // Chunks of unissued shares, or of other blobs, don't validate against the sealed header
+        assert!(share.iter().chain([&chunk]).all(|chunk| sealed.validate_chunk(chunk)));
+        assert!(blob.get_blob_header().validate_chunk(&unissued_chunk));
+        assert!(!sealed.validate_chunk(&unissued_chunk));
+        assert!(
+            !other_blob
+                .get_share(1)
+                .unwrap()
+                .iter()
+                .any(|chunk: &ProofCarryingChunk| sealed.validate_chunk(chunk))
+        );
+
+        assert_eq!(sealed.verify_signature(verify_with(7)), Ok(()));
+        assert_eq!(sealed.verify_signature(verify_with(8)), Err(DecdsError::InvalidSealSignature));
+        assert_eq!(blob.seal().verify_signature(verify_with(7)), Err(DecdsError::InvalidSealSignature));
+
+        let bytes = sealed.to_bytes().unwrap();
+        assert_eq!(SealedBlobHeader::from_bytes(&bytes), Ok((sealed.clone(), bytes.len())));
+
+        // Shares beyond the 12 of the blob can't be marked issued
+        let mut forged = sealed.clone();
+        forged.issued_shares |= 1 << 12;
+        assert!(matches!(
+            SealedBlobHeader::from_bytes(&forged.to_bytes().unwrap()),
+            Err(DecdsError::SealedBlobHeaderDeserializationFailed(_))
+        ));
+    }
+}
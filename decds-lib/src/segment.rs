@@ -0,0 +1,269 @@
+use crate::{chunk::ProofCarryingChunk, errors::DecdsError};
+use std::collections::{BTreeMap, HashMap};
+
+/// Byte length of the header, prepended to each segment, i.e. `transfer_digest || segment_index || num_segments`, where
+/// `transfer_digest` is the BLAKE3 digest of the serialized chunk and both counters are encoded as 4-bytes little-endian
+/// unsigned integers.
+pub const CHUNK_SEGMENT_HEADER_BYTE_LEN: usize = blake3::OUT_LEN + 2 * std::mem::size_of::<u32>();
+
+impl ProofCarryingChunk {
+    /// Splits the serialized chunk into framed segments, each at most `max_segment_byte_len` bytes, for transports with
+    /// small message limits. Each segment starts with a `CHUNK_SEGMENT_HEADER_BYTE_LEN` bytes header, identifying the
+    /// transfer by the digest of the serialized chunk, followed by a piece of it. Segments can be delivered out of order,
+    /// interleaved with segments of other chunks, and are put back together by a `ChunkReassembler`.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_segment_byte_len` - Maximum byte length of a segment, including its header.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Vec<Vec<u8>>)` containing the segments, ordered by their index, if successful.
+    /// - `Err(DecdsError::InvalidMaxSegmentByteLength)` if `max_segment_byte_len` can't fit a segment header and at least
+    ///   one byte of the chunk, or if the chunk would need more than `u32::MAX` segments.
+    /// - `Err(DecdsError::ProofCarryingChunkSerializationFailed)` if serialization fails.
+    pub fn to_segments(&self, max_segment_byte_len: usize) -> Result<Vec<Vec<u8>>, DecdsError> {
+        if max_segment_byte_len <= CHUNK_SEGMENT_HEADER_BYTE_LEN {
+            return Err(DecdsError::InvalidMaxSegmentByteLength(max_segment_byte_len));
+        }
+
+        let chunk_bytes = self.to_bytes()?;
+        let transfer_digest = blake3::hash(&chunk_bytes);
+
+        let payload_byte_len = max_segment_byte_len - CHUNK_SEGMENT_HEADER_BYTE_LEN;
+        let num_segments =
+            u32::try_from(chunk_bytes.len().div_ceil(payload_byte_len)).map_err(|_| DecdsError::InvalidMaxSegmentByteLength(max_segment_byte_len))?;
+
+        let segments = chunk_bytes
+            .chunks(payload_byte_len)
+            .enumerate()
+            .map(|(segment_index, payload)| {
+                let mut segment = Vec::with_capacity(CHUNK_SEGMENT_HEADER_BYTE_LEN + payload.len());
+
+                segment.extend_from_slice(transfer_digest.as_bytes());
+                segment.extend_from_slice(&(segment_index as u32).to_le_bytes());
+                segment.extend_from_slice(&num_segments.to_le_bytes());
+                segment.extend_from_slice(payload);
+
+                segment
+            })
+            .collect();
+
+        Ok(segments)
+    }
+}
+
+/// Segments of a chunk, received so far.
+struct PendingTransfer {
+    num_segments: u32,
+    payloads: BTreeMap<u32, Vec<u8>>,
+}
+
+/// Reassembles `ProofCarryingChunk`s from segments produced by `ProofCarryingChunk::to_segments`, possibly arriving out of
+/// order and interleaved with segments of other chunks. A chunk is returned once all of its segments arrived and the
+/// digest of the reassembled bytes matches the one its segments carry.
+///
+/// The reassembled chunk is only known to be the one the sender segmented. Its inclusion in a blob must still be checked,
+/// e.g. with `BlobHeader::validate_chunk`, or by handing it to a `RepairingBlob`.
+///
+/// ```rust
+/// use decds_lib::{Blob, ChunkReassembler};
+///
+/// let blob = Blob::new(vec![0xcd; 1024]).expect("Failed to create blob");
+/// let chunk = blob.get_chunk(0, 3).expect("Failed to get chunk");
+///
+/// let mut segments = chunk.to_segments(4096).expect("Failed to segment chunk");
+/// segments.reverse();
+///
+/// let mut reassembler = ChunkReassembler::new(8);
+/// let reassembled = segments
+///     .iter()
+///     .filter_map(|segment| reassembler.push(segment).expect("Failed to reassemble chunk"))
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(reassembled, vec![chunk.clone()]);
+/// assert_eq!(reassembler.get_num_pending_transfers(), 0);
+/// ```
+pub struct ChunkReassembler {
+    max_pending_transfers: usize,
+    pending: HashMap<blake3::Hash, PendingTransfer>,
+}
+
+impl ChunkReassembler {
+    /// Creates a reassembler, which keeps at most `max_pending_transfers` partially received chunks at a time, so that a
+    /// misbehaving sender, starting transfers it never completes, can't make it hold on to unbounded memory.
+    pub fn new(max_pending_transfers: usize) -> Self {
+        ChunkReassembler {
+            max_pending_transfers,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Returns the number of chunks, of which some, but not all, segments are received.
+    pub fn get_num_pending_transfers(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Drops already received segments of the chunk transfer identified by `transfer_digest`, e.g. once it timed out.
+    /// Returns `true` if such a transfer was pending.
+    pub fn discard(&mut self, transfer_digest: &blake3::Hash) -> bool {
+        self.pending.remove(transfer_digest).is_some()
+    }
+
+    /// Accepts a segment, returning the chunk it completes, if any. Repeated segments of a pending transfer are ignored, but
+    /// one arriving after its chunk was returned starts a new transfer, which is to be dropped with `Self::discard`.
+    ///
+    /// # Arguments
+    ///
+    /// * `segment` - A segment, as produced by `ProofCarryingChunk::to_segments`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Some(ProofCarryingChunk))` if the segment completes a chunk, whose digest matches.
+    /// - `Ok(None)` if more segments of the chunk are yet to arrive.
+    /// - `Err(DecdsError::InvalidChunkSegment)` if the segment is malformed, doesn't agree with earlier segments of the same
+    ///   transfer, or would start a new transfer while `max_pending_transfers` are already pending.
+    /// - `Err(DecdsError::ChunkSegmentsDigestMismatch)` if the reassembled bytes don't match the transfer digest. All
+    ///   segments of that transfer are dropped.
+    /// - `Err(DecdsError::ProofCarryingChunkDeserializationFailed)` if the reassembled bytes aren't exactly one chunk.
+    pub fn push(&mut self, segment: &[u8]) -> Result<Option<ProofCarryingChunk>, DecdsError> {
+        if segment.len() <= CHUNK_SEGMENT_HEADER_BYTE_LEN {
+            return Err(DecdsError::InvalidChunkSegment(format!("segment of {}B has no payload", segment.len())));
+        }
+
+        let (header, payload) = segment.split_at(CHUNK_SEGMENT_HEADER_BYTE_LEN);
+        let (digest_bytes, counters) = header.split_at(blake3::OUT_LEN);
+
+        let transfer_digest = blake3::Hash::from_bytes(unsafe { digest_bytes.try_into().unwrap_unchecked() });
+        let segment_index = u32::from_le_bytes(unsafe { counters[..4].try_into().unwrap_unchecked() });
+        let num_segments = u32::from_le_bytes(unsafe { counters[4..].try_into().unwrap_unchecked() });
+
+        if segment_index >= num_segments {
+            return Err(DecdsError::InvalidChunkSegment(format!(
+                "segment index {} of {} segments",
+                segment_index, num_segments
+            )));
+        }
+
+        if !self.pending.contains_key(&transfer_digest) && self.pending.len() >= self.max_pending_transfers {
+            return Err(DecdsError::InvalidChunkSegment(format!(
+                "too many pending transfers, at most {} allowed",
+                self.max_pending_transfers
+            )));
+        }
+
+        let transfer = self.pending.entry(transfer_digest).or_insert_with(|| PendingTransfer {
+            num_segments,
+            payloads: BTreeMap::new(),
+        });
+        if transfer.num_segments != num_segments {
+            return Err(DecdsError::InvalidChunkSegment(format!(
+                "transfer has {} segments, but segment claims {}",
+                transfer.num_segments, num_segments
+            )));
+        }
+
+        transfer.payloads.entry(segment_index).or_insert_with(|| payload.to_vec());
+        if transfer.payloads.len() < num_segments as usize {
+            return Ok(None);
+        }
+
+        let chunk_bytes = unsafe { self.pending.remove(&transfer_digest).unwrap_unchecked() }
+            .payloads
+            .into_values()
+            .collect::<Vec<Vec<u8>>>()
+            .concat();
+        if blake3::hash(&chunk_bytes) != transfer_digest {
+            return Err(DecdsError::ChunkSegmentsDigestMismatch);
+        }
+
+        match ProofCarryingChunk::from_bytes(&chunk_bytes)? {
+            (chunk, n) if n == chunk_bytes.len() => Ok(Some(chunk)),
+            (_, n) => Err(DecdsError::ProofCarryingChunkDeserializationFailed(format!(
+                "{} trailing bytes after proof carrying chunk",
+                chunk_bytes.len() - n
+            ))),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "coding"))]
+mod tests {
+    use crate::{ChunkReassembler, ProofCarryingChunk, blob::Blob, errors::DecdsError, segment::CHUNK_SEGMENT_HEADER_BYTE_LEN};
+    use rand::seq::SliceRandom;
+
+    #[test]
+    fn test_chunk_segmentation_and_reassembly() {
+        let mut rng = rand::rng();
+
+        let blob = Blob::new(vec![0xcd; 1024]).expect("Failed to create blob");
+        let chunks = (0..4)
+            .map(|share_id| blob.get_chunk(0, share_id).unwrap().clone())
+            .collect::<Vec<ProofCarryingChunk>>();
+
+        let max_segment_byte_len = 64 * 1024 + CHUNK_SEGMENT_HEADER_BYTE_LEN;
+        let mut segments = chunks
+            .iter()
+            .flat_map(|chunk| chunk.to_segments(max_segment_byte_len).unwrap())
+            .collect::<Vec<Vec<u8>>>();
+        assert!(segments.iter().all(|segment| segment.len() <= max_segment_byte_len));
+
+        segments.shuffle(&mut rng);
+        segments.insert(1, segments[0].clone());
+
+        let mut reassembler = ChunkReassembler::new(chunks.len());
+        let mut reassembled = segments
+            .iter()
+            .filter_map(|segment| reassembler.push(segment).unwrap())
+            .collect::<Vec<ProofCarryingChunk>>();
+        reassembled.sort_by_key(|chunk| chunk.get_local_chunk_id());
+
+        assert_eq!(reassembled, chunks);
+        assert_eq!(reassembler.get_num_pending_transfers(), 0);
+    }
+
+    #[test]
+    fn test_chunk_reassembly_rejects_bad_segments() {
+        let blob = Blob::new(vec![0xcd; 1024]).expect("Failed to create blob");
+        let chunk = blob.get_chunk(0, 0).unwrap();
+
+        assert_eq!(
+            chunk.to_segments(CHUNK_SEGMENT_HEADER_BYTE_LEN),
+            Err(DecdsError::InvalidMaxSegmentByteLength(CHUNK_SEGMENT_HEADER_BYTE_LEN))
+        );
+
+        let segments = chunk.to_segments(1 << 18).unwrap();
+        let mut reassembler = ChunkReassembler::new(1);
+
+        assert!(matches!(
+            reassembler.push(&segments[0][..CHUNK_SEGMENT_HEADER_BYTE_LEN]),
+            Err(DecdsError::InvalidChunkSegment(_))
+        ));
+
+        // Tampered payload is caught once all segments arrive, and the transfer is dropped
+        let mut tampered_segment = segments[1].clone();
+        tampered_segment[CHUNK_SEGMENT_HEADER_BYTE_LEN] ^= 1;
+
+        segments[2..].iter().for_each(|segment| assert_eq!(reassembler.push(segment), Ok(None)));
+        assert_eq!(reassembler.push(&tampered_segment), Ok(None));
+        assert_eq!(reassembler.push(&segments[0]), Err(DecdsError::ChunkSegmentsDigestMismatch));
+        assert_eq!(reassembler.get_num_pending_transfers(), 0);
+
+        // Segment disagreeing with earlier ones, on the number of segments
+        let mut inconsistent_segment = segments[1].clone();
+        inconsistent_segment[blake3::OUT_LEN + 4] += 1;
+
+        assert_eq!(reassembler.push(&segments[0]), Ok(None));
+        assert!(matches!(reassembler.push(&inconsistent_segment), Err(DecdsError::InvalidChunkSegment(_))));
+
+        // At most one transfer may be pending
+        let other_segments = blob.get_chunk(0, 1).unwrap().to_segments(1 << 18).unwrap();
+        assert!(matches!(reassembler.push(&other_segments[0]), Err(DecdsError::InvalidChunkSegment(_))));
+
+        let transfer_digest = blake3::Hash::from_bytes(segments[0][..blake3::OUT_LEN].try_into().unwrap());
+        assert!(reassembler.discard(&transfer_digest));
+        assert_eq!(reassembler.push(&other_segments[0]), Ok(None));
+    }
+}
@@ -0,0 +1,316 @@
+use crate::{blob::BlobHeader, chunkset::ChunkSet, consts::DECDS_NUM_ERASURE_CODED_SHARES, errors::DecdsError};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use rayon::prelude::*;
+
+/// Assigns the erasure-coded share `share_id` of chunkset `chunkset_id` to a storage node, returning the node's index.
+pub type SharePlacement = Box<dyn Fn(usize, usize) -> usize + Send + Sync>;
+
+/// Monte-Carlo model of the durability of a blob, spread across storage nodes, which fail independently, each with its own
+/// probability per period, e.g. per day. Failed nodes never come back and lost shares are never repaired, so that the
+/// estimate is of the worst case, before committing data to a geometry and placement.
+///
+/// A chunkset is lost once fewer than `num_original_chunks` of its shares survive, and the blob is lost with its first
+/// lost chunkset. Geometry defaults to the one `Blob` uses, but can be changed for comparing alternatives.
+///
+/// ```rust
+/// use decds_lib::DurabilitySimulation;
+///
+/// // 4 chunksets, each share `i` on node `i`, every node failing with 1% probability per period
+/// let simulation = DurabilitySimulation::new(4, vec![0.01; 16]);
+/// let estimate = simulation.simulate(365, 1_000, 42).expect("Failed to simulate");
+///
+/// // Loss probability never decreases over time
+/// assert!(estimate.get_loss_probability(0) <= estimate.get_loss_probability(364));
+/// ```
+pub struct DurabilitySimulation {
+    num_chunksets: usize,
+    num_original_chunks: usize,
+    num_shares: usize,
+    node_failure_probabilities: Vec<f64>,
+    placement: SharePlacement,
+}
+
+impl DurabilitySimulation {
+    /// Creates a simulation of a blob of `num_chunksets` chunksets, whose share `share_id` of every chunkset is stored on
+    /// node `share_id % node_failure_probabilities.len()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_chunksets` - Number of chunksets of the blob.
+    /// * `node_failure_probabilities` - Probability of each storage node failing during a period, indexed by node.
+    pub fn new(num_chunksets: usize, node_failure_probabilities: Vec<f64>) -> Self {
+        let num_nodes = node_failure_probabilities.len().max(1);
+
+        DurabilitySimulation {
+            num_chunksets,
+            num_original_chunks: ChunkSet::NUM_ORIGINAL_CHUNKS,
+            num_shares: DECDS_NUM_ERASURE_CODED_SHARES,
+            node_failure_probabilities,
+            placement: Box::new(move |_, share_id| share_id % num_nodes),
+        }
+    }
+
+    /// Same as `Self::new`, taking the number of chunksets and their geometry from the header of a blob. An inline blob
+    /// is simulated as having no chunksets, hence it is never lost.
+    pub fn from_header(header: &BlobHeader, node_failure_probabilities: Vec<f64>) -> Self {
+        let config = header.get_config();
+        Self::new(header.get_num_chunksets(), node_failure_probabilities).geometry(config.get_num_original_chunks(), config.get_num_shares())
+    }
+
+    /// Sets geometry of chunksets, i.e. each one is erasure-coded into `num_shares` shares, any `num_original_chunks` of
+    /// which are enough for repairing it.
+    pub fn geometry(mut self, num_original_chunks: usize, num_shares: usize) -> Self {
+        self.num_original_chunks = num_original_chunks;
+        self.num_shares = num_shares;
+        self
+    }
+
+    /// Sets where shares are stored, e.g. rotating shares of consecutive chunksets across nodes. Shares placed on the same
+    /// node fail together.
+    pub fn placement(mut self, placement: SharePlacement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    /// Estimates probability of losing the blob, by the end of each of `num_periods` periods, over `num_trials` random
+    /// trials, run in parallel. Same seed always gives same estimate.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_periods` - Number of periods to simulate.
+    /// * `num_trials` - Number of Monte-Carlo trials, more trials give more precise estimates.
+    /// * `seed` - Seed of the random failures.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(DurabilityEstimate)` if successful.
+    /// - `Err(DecdsError::InvalidSimulationParameter)` if there are no trials or nodes, if a failure probability isn't
+    ///   within `[0, 1]`, if geometry is impossible, or if a share is placed on a non-existent node.
+    pub fn simulate(&self, num_periods: usize, num_trials: usize, seed: u64) -> Result<DurabilityEstimate, DecdsError> {
+        self.validate(num_trials)?;
+
+        let mut placement = vec![0usize; self.num_chunksets * self.num_shares];
+        for chunkset_id in 0..self.num_chunksets {
+            for share_id in 0..self.num_shares {
+                let node_idx = (self.placement)(chunkset_id, share_id);
+                if node_idx >= self.node_failure_probabilities.len() {
+                    return Err(DecdsError::InvalidSimulationParameter(format!(
+                        "share {} of chunkset {} is placed on node {}, but there are {} nodes",
+                        share_id,
+                        chunkset_id,
+                        node_idx,
+                        self.node_failure_probabilities.len()
+                    )));
+                }
+
+                placement[chunkset_id * self.num_shares + share_id] = node_idx;
+            }
+        }
+
+        let num_losses_per_period = (0..num_trials)
+            .into_par_iter()
+            .map(|trial_idx| self.simulate_trial(&placement, num_periods, seed, trial_idx))
+            .fold(
+                || vec![0usize; num_periods],
+                |mut num_losses_per_period, lost_in_period| {
+                    if let Some(period) = lost_in_period {
+                        num_losses_per_period[period] += 1;
+                    }
+                    num_losses_per_period
+                },
+            )
+            .reduce(
+                || vec![0usize; num_periods],
+                |mut lhs, rhs| {
+                    lhs.iter_mut().zip(rhs).for_each(|(lhs, rhs)| *lhs += rhs);
+                    lhs
+                },
+            );
+
+        let loss_probabilities = num_losses_per_period
+            .iter()
+            .scan(0usize, |num_lost_trials, &num_losses| {
+                *num_lost_trials += num_losses;
+                Some(*num_lost_trials as f64 / num_trials as f64)
+            })
+            .collect();
+
+        Ok(DurabilityEstimate {
+            num_trials,
+            loss_probabilities,
+        })
+    }
+
+    fn validate(&self, num_trials: usize) -> Result<(), DecdsError> {
+        if num_trials == 0 {
+            return Err(DecdsError::InvalidSimulationParameter("number of trials must be non-zero".to_string()));
+        }
+        if self.node_failure_probabilities.is_empty() {
+            return Err(DecdsError::InvalidSimulationParameter("number of nodes must be non-zero".to_string()));
+        }
+        if let Some(node_idx) = self.node_failure_probabilities.iter().position(|p| !(0.0..=1.0).contains(p)) {
+            return Err(DecdsError::InvalidSimulationParameter(format!(
+                "failure probability of node {} must be within [0, 1]",
+                node_idx
+            )));
+        }
+        if self.num_original_chunks == 0 || self.num_original_chunks > self.num_shares {
+            return Err(DecdsError::InvalidSimulationParameter(format!(
+                "{} original chunks can't be erasure-coded into {} shares",
+                self.num_original_chunks, self.num_shares
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the period, during which the blob is lost in this trial, or `None` if it survives all periods.
+    fn simulate_trial(&self, placement: &[usize], num_periods: usize, seed: u64, trial_idx: usize) -> Option<usize> {
+        let trial_seed = blake3::hash(&[seed.to_le_bytes(), (trial_idx as u64).to_le_bytes()].concat());
+        let mut rng = StdRng::from_seed(*trial_seed.as_bytes());
+
+        // Period each node fails in, sampled from geometric distribution, `usize::MAX` if it never fails
+        let node_failed_in = self
+            .node_failure_probabilities
+            .iter()
+            .map(|&p| {
+                if p <= 0.0 {
+                    usize::MAX
+                } else if p >= 1.0 {
+                    0
+                } else {
+                    let u = rng.random::<f64>();
+                    ((1.0 - u).ln() / (1.0 - p).ln()).floor().min(usize::MAX as f64) as usize
+                }
+            })
+            .collect::<Vec<usize>>();
+
+        // A chunkset is lost, when its `num_shares - num_original_chunks + 1`-th share fails
+        let num_tolerated_failures = self.num_shares - self.num_original_chunks;
+        let mut share_failed_in = vec![0usize; self.num_shares];
+
+        placement
+            .chunks_exact(self.num_shares)
+            .map(|chunkset_placement| {
+                share_failed_in
+                    .iter_mut()
+                    .zip(chunkset_placement)
+                    .for_each(|(failed_in, &node_idx)| *failed_in = node_failed_in[node_idx]);
+
+                *share_failed_in.select_nth_unstable(num_tolerated_failures).1
+            })
+            .min()
+            .filter(|&lost_in| lost_in < num_periods)
+    }
+}
+
+/// Result of a `DurabilitySimulation`, i.e. the estimated probability of a blob being lost by the end of each period.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DurabilityEstimate {
+    num_trials: usize,
+    loss_probabilities: Vec<f64>,
+}
+
+impl DurabilityEstimate {
+    /// Returns the number of Monte-Carlo trials, the estimate is based on.
+    pub fn get_num_trials(&self) -> usize {
+        self.num_trials
+    }
+
+    /// Returns the estimated probability of the blob being lost by the end of each period, indexed by period.
+    pub fn get_loss_probabilities(&self) -> &[f64] {
+        &self.loss_probabilities
+    }
+
+    /// Returns the estimated probability of the blob being lost by the end of period `period`. Periods beyond the
+    /// simulated ones report the probability of the last simulated one.
+    pub fn get_loss_probability(&self, period: usize) -> f64 {
+        self.loss_probabilities
+            .get(period)
+            .or(self.loss_probabilities.last())
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DECDS_NUM_ERASURE_CODED_SHARES, DecdsError, DurabilitySimulation, chunkset::ChunkSet};
+
+    #[test]
+    fn test_durability_simulation() {
+        const NUM_PERIODS: usize = 100;
+        const NUM_TRIALS: usize = 2_000;
+
+        // Nodes never failing never lose the blob, nodes always failing lose it in first period
+        let estimate = DurabilitySimulation::new(8, vec![0.0; DECDS_NUM_ERASURE_CODED_SHARES])
+            .simulate(NUM_PERIODS, NUM_TRIALS, 0)
+            .unwrap();
+        assert_eq!(estimate.get_num_trials(), NUM_TRIALS);
+        assert!(estimate.get_loss_probabilities().iter().all(|&p| p == 0.0));
+
+        let estimate = DurabilitySimulation::new(8, vec![1.0; DECDS_NUM_ERASURE_CODED_SHARES])
+            .simulate(NUM_PERIODS, NUM_TRIALS, 0)
+            .unwrap();
+        assert_eq!(estimate.get_loss_probability(0), 1.0);
+
+        // Losing all but `NUM_ORIGINAL_CHUNKS` shares keeps the blob, losing one more loses it
+        let mut node_failure_probabilities = vec![0.0; DECDS_NUM_ERASURE_CODED_SHARES];
+        node_failure_probabilities[..DECDS_NUM_ERASURE_CODED_SHARES - ChunkSet::NUM_ORIGINAL_CHUNKS].fill(1.0);
+        let simulation = DurabilitySimulation::new(8, node_failure_probabilities.clone());
+        assert_eq!(simulation.simulate(NUM_PERIODS, NUM_TRIALS, 0).unwrap().get_loss_probability(NUM_PERIODS), 0.0);
+
+        node_failure_probabilities[DECDS_NUM_ERASURE_CODED_SHARES - ChunkSet::NUM_ORIGINAL_CHUNKS] = 0.1;
+        let simulation = DurabilitySimulation::new(8, node_failure_probabilities);
+        let estimate = simulation.simulate(NUM_PERIODS, NUM_TRIALS, 7).unwrap();
+
+        assert_eq!(estimate, simulation.simulate(NUM_PERIODS, NUM_TRIALS, 7).unwrap());
+        assert!(estimate.get_loss_probabilities().windows(2).all(|w| w[0] <= w[1]));
+        assert!((estimate.get_loss_probability(0) - 0.1).abs() < 0.05);
+
+        // Concentrating all shares on a single node makes the blob as durable as that node
+        let estimate = DurabilitySimulation::new(8, vec![0.1, 0.0])
+            .placement(Box::new(|_, _| 0))
+            .simulate(NUM_PERIODS, NUM_TRIALS, 7)
+            .unwrap();
+        assert!((estimate.get_loss_probability(0) - 0.1).abs() < 0.05);
+
+        // More redundancy is more durable
+        let less_redundant = DurabilitySimulation::new(8, vec![0.01; DECDS_NUM_ERASURE_CODED_SHARES])
+            .geometry(14, 16)
+            .simulate(NUM_PERIODS, NUM_TRIALS, 7)
+            .unwrap();
+        let more_redundant = DurabilitySimulation::new(8, vec![0.01; DECDS_NUM_ERASURE_CODED_SHARES])
+            .geometry(8, 16)
+            .simulate(NUM_PERIODS, NUM_TRIALS, 7)
+            .unwrap();
+        assert!(less_redundant.get_loss_probability(NUM_PERIODS) > more_redundant.get_loss_probability(NUM_PERIODS));
+    }
+
+    #[test]
+    fn test_durability_simulation_with_invalid_parameters() {
+        let simulation = DurabilitySimulation::new(8, vec![0.1; 4]);
+        assert!(simulation.simulate(10, 100, 0).is_ok());
+
+        assert!(matches!(simulation.simulate(10, 0, 0), Err(DecdsError::InvalidSimulationParameter(_))));
+        assert!(matches!(
+            DurabilitySimulation::new(8, Vec::new()).simulate(10, 100, 0),
+            Err(DecdsError::InvalidSimulationParameter(_))
+        ));
+        assert!(matches!(
+            DurabilitySimulation::new(8, vec![1.5; 4]).simulate(10, 100, 0),
+            Err(DecdsError::InvalidSimulationParameter(_))
+        ));
+        assert!(matches!(
+            DurabilitySimulation::new(8, vec![0.1; 4]).geometry(17, 16).simulate(10, 100, 0),
+            Err(DecdsError::InvalidSimulationParameter(_))
+        ));
+        assert!(matches!(
+            DurabilitySimulation::new(8, vec![0.1; 4])
+                .placement(Box::new(|_, share_id| share_id))
+                .simulate(10, 100, 0),
+            Err(DecdsError::InvalidSimulationParameter(_))
+        ));
+    }
+}
@@ -0,0 +1,313 @@
+use crate::{
+    blob::{BlobHeader, deserialize_addressable_usize},
+    chunk::ProofCarryingChunk,
+    config::BlobConfig,
+    consts::DECDS_BINCODE_CONFIG,
+    errors::DecdsError,
+    flags::ContentFlags,
+    merkle_tree::MerkleTree,
+};
+use serde::{Deserialize, Serialize};
+
+/// Reduced `BlobHeader`, for delegating verification of chunks of a few chunksets, e.g. those covering a byte range, to a
+/// verifier, which can't afford fetching the whole header of a blob of hundreds of thousands of chunksets.
+///
+/// It keeps all fields of the header, but only commitments of chunksets it was made for, each carrying a Merkle proof of
+/// its inclusion in the blob root commitment, so that it's a few kilobytes, instead of megabytes. Chunks validated against
+/// a subset are bound to its root commitment, just as those validated against the full header, so a verifier needs to
+/// trust only the root commitment, e.g. by checking it against a `BlobAnchor`.
+///
+/// ```rust
+/// use decds_lib::{Blob, BlobHeaderSubset};
+///
+/// let blob = Blob::new(vec![0xde; 1 << 20]).expect("Failed to create blob");
+/// let subset = blob.get_blob_header().subset(&[0]).expect("Failed to make header subset");
+/// let subset_bytes = subset.to_bytes().expect("Failed to serialize header subset");
+///
+/// let (subset, _) = BlobHeaderSubset::from_bytes(&subset_bytes).expect("Failed to deserialize header subset");
+/// assert!(subset.validate_chunk(blob.get_chunk(0, 0).expect("Failed to get chunk")));
+/// ```
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct BlobHeaderSubset {
+    #[serde(deserialize_with = "deserialize_addressable_usize")]
+    byte_length: usize,
+    #[serde(deserialize_with = "deserialize_addressable_usize")]
+    num_chunksets: usize,
+    config: BlobConfig,
+    content_flags: ContentFlags,
+    digest: blake3::Hash,
+    root_commitment: blake3::Hash,
+    is_inline: bool,
+    chunksets: Vec<IncludedChunkset>,
+}
+
+/// Commitment of a chunkset, included in a `BlobHeaderSubset`, along with its Merkle proof of inclusion in the blob root
+/// commitment.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+struct IncludedChunkset {
+    #[serde(deserialize_with = "deserialize_addressable_usize")]
+    chunkset_id: usize,
+    commitment: blake3::Hash,
+    proof: Vec<blake3::Hash>,
+}
+
+impl BlobHeader {
+    /// Makes a `BlobHeaderSubset` of this header, keeping only commitments of chunksets `chunkset_ids`, e.g. as returned by
+    /// `Self::get_chunkset_ids_for_byte_range`, each along with its Merkle proof of inclusion in the root commitment.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_ids` - IDs of chunksets to include, in any order. Repeated IDs are included only once.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(BlobHeaderSubset)` if successful.
+    /// - `Err(DecdsError::InvalidChunksetId)` if any of `chunkset_ids` is out of bounds.
+    pub fn subset(&self, chunkset_ids: &[usize]) -> Result<BlobHeaderSubset, DecdsError> {
+        let mut chunkset_ids = chunkset_ids.to_vec();
+        chunkset_ids.sort_unstable();
+        chunkset_ids.dedup();
+
+        if let Some(&chunkset_id) = chunkset_ids.last().filter(|&&chunkset_id| chunkset_id >= self.get_num_chunksets()) {
+            return Err(DecdsError::InvalidChunksetId(chunkset_id, self.get_num_chunksets()));
+        }
+
+        let blob_tree = self.build_commitment_tree();
+        let chunksets = chunkset_ids
+            .into_iter()
+            .map(|chunkset_id| IncludedChunkset {
+                chunkset_id,
+                commitment: self.get_chunkset_commitments()[chunkset_id],
+                proof: unsafe { blob_tree.generate_proof(chunkset_id).unwrap_unchecked() },
+            })
+            .collect();
+
+        Ok(BlobHeaderSubset {
+            byte_length: self.get_blob_size(),
+            num_chunksets: self.get_num_chunksets(),
+            config: self.get_config(),
+            content_flags: self.get_content_flags(),
+            digest: self.get_blob_digest(),
+            root_commitment: self.get_root_commitment(),
+            is_inline: self.is_inline(),
+            chunksets,
+        })
+    }
+}
+
+impl BlobHeaderSubset {
+    /// Returns the original byte length of the blob data before padding.
+    pub fn get_blob_size(&self) -> usize {
+        self.byte_length
+    }
+
+    /// Returns the total number of chunksets that comprise the blob, not only those included in this subset.
+    pub fn get_num_chunksets(&self) -> usize {
+        self.num_chunksets
+    }
+
+    /// Returns the erasure-coding geometry, the blob is split and encoded with.
+    pub fn get_config(&self) -> BlobConfig {
+        self.config
+    }
+
+    /// Returns content flags of the blob, see `ContentFlags`.
+    pub fn get_content_flags(&self) -> ContentFlags {
+        self.content_flags
+    }
+
+    /// Returns `true` if the blob data is stored inline in its header, which a subset doesn't carry.
+    pub fn is_inline(&self) -> bool {
+        self.is_inline
+    }
+
+    /// Returns the BLAKE3 digest of the original, unpadded blob data.
+    pub fn get_blob_digest(&self) -> blake3::Hash {
+        self.digest
+    }
+
+    /// Returns the blob ID, see `BlobHeader::get_blob_id`.
+    pub fn get_blob_id(&self) -> blake3::Hash {
+        BlobHeader::compute_blob_id(self.byte_length as u64, self.digest)
+    }
+
+    /// Returns the Merkle root commitment of the entire blob.
+    pub fn get_root_commitment(&self) -> blake3::Hash {
+        self.root_commitment
+    }
+
+    /// Returns IDs of chunksets included in this subset, in ascending order.
+    pub fn get_chunkset_ids(&self) -> impl Iterator<Item = usize> + '_ {
+        self.chunksets.iter().map(|chunkset| chunkset.chunkset_id)
+    }
+
+    /// Returns the Merkle root commitment of a specific chunkset within the blob, if it's included in this subset.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset whose commitment is to be retrieved.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(blake3::Hash)` containing the root commitment of the specified chunkset if successful.
+    /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_id` is out of bounds.
+    /// - `Err(DecdsError::ChunksetNotInHeaderSubset)` if the chunkset exists, but isn't included in this subset.
+    pub fn get_chunkset_commitment(&self, chunkset_id: usize) -> Result<blake3::Hash, DecdsError> {
+        if chunkset_id >= self.num_chunksets {
+            return Err(DecdsError::InvalidChunksetId(chunkset_id, self.num_chunksets));
+        }
+
+        self.chunksets
+            .binary_search_by_key(&chunkset_id, |chunkset| chunkset.chunkset_id)
+            .map(|idx| self.chunksets[idx].commitment)
+            .map_err(|_| DecdsError::ChunksetNotInHeaderSubset(chunkset_id))
+    }
+
+    /// Validates a `ProofCarryingChunk` against this subset, as `BlobHeader::validate_chunk` does against the full header.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk` - A reference to the `ProofCarryingChunk` to validate.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the chunk belongs to a chunkset included in this subset, and its proofs are consistent with it,
+    /// `false` otherwise. No chunk is valid for an inline blob.
+    pub fn validate_chunk(&self, chunk: &ProofCarryingChunk) -> bool {
+        let chunk_digest = chunk.get_chunk_digest();
+
+        !self.is_inline
+            && (chunk.get_local_chunk_id() < self.config.get_num_shares())
+            && (chunk.get_content_flags() == self.content_flags)
+            && chunk.validate_inclusion_in_blob_with_digest(chunk_digest, self.root_commitment)
+            && self
+                .get_chunkset_commitment(chunk.get_chunkset_id())
+                .is_ok_and(|commitment| chunk.validate_inclusion_in_chunkset_with_digest(chunk_digest, commitment))
+    }
+
+    /// Serializes the header subset into a vector of bytes using `bincode`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Vec<u8>)` containing the serialized bytes if successful.
+    /// - `Err(DecdsError::BlobHeaderSubsetSerializationFailed)` if serialization fails, containing the error message.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, DecdsError> {
+        bincode::serde::encode_to_vec(self, DECDS_BINCODE_CONFIG).map_err(|err| DecdsError::BlobHeaderSubsetSerializationFailed(err.to_string()))
+    }
+
+    /// Deserializes a header subset from a byte slice using `bincode`. Included chunkset commitments are checked against
+    /// the root commitment, so that a subset can be fetched from an untrusted source, as long as its root commitment is
+    /// trusted.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The byte slice from which to deserialize the header subset.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok((Self, usize))` containing the deserialized header subset and the number of bytes read if successful.
+    /// - `Err(DecdsError::BlobHeaderSubsetDeserializationFailed)` if `bincode` deserialization fails, if the recorded
+    ///   `BlobConfig` or `ContentFlags` aren't valid, if the number of chunksets does not match the blob size, if included chunksets are out
+    ///   of bounds or not in ascending order, or if a Merkle proof of an included chunkset commitment is invalid.
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Self, usize), DecdsError> {
+        let (subset, n) = bincode::serde::decode_from_slice::<BlobHeaderSubset, bincode::config::Configuration>(bytes, DECDS_BINCODE_CONFIG)
+            .map_err(|err| DecdsError::BlobHeaderSubsetDeserializationFailed(err.to_string()))?;
+
+        subset
+            .config
+            .validate()
+            .map_err(|err| DecdsError::BlobHeaderSubsetDeserializationFailed(err.to_string()))?;
+        subset
+            .content_flags
+            .validate()
+            .map_err(|err| DecdsError::BlobHeaderSubsetDeserializationFailed(err.to_string()))?;
+
+        let expected_num_chunksets = if subset.is_inline {
+            1
+        } else {
+            subset.byte_length.div_ceil(subset.config.get_chunkset_byte_length())
+        };
+        if subset.byte_length == 0 || subset.num_chunksets != expected_num_chunksets {
+            return Err(DecdsError::BlobHeaderSubsetDeserializationFailed(
+                "number of chunksets does not match blob size".to_string(),
+            ));
+        }
+
+        let is_ascending = subset.chunksets.windows(2).all(|pair| pair[0].chunkset_id < pair[1].chunkset_id);
+        if !is_ascending || subset.chunksets.last().is_some_and(|chunkset| chunkset.chunkset_id >= subset.num_chunksets) {
+            return Err(DecdsError::BlobHeaderSubsetDeserializationFailed(
+                "included chunksets are out of bounds or not in ascending order".to_string(),
+            ));
+        }
+
+        if let Some(chunkset) = subset
+            .chunksets
+            .iter()
+            .find(|chunkset| !MerkleTree::verify_proof(chunkset.chunkset_id, chunkset.commitment, &chunkset.proof, subset.root_commitment))
+        {
+            return Err(DecdsError::BlobHeaderSubsetDeserializationFailed(format!(
+                "invalid proof of commitment of chunkset {}",
+                chunkset.chunkset_id
+            )));
+        }
+
+        Ok((subset, n))
+    }
+}
+
+#[cfg(all(test, feature = "coding"))]
+mod tests {
+    use crate::{BlobHeaderSubset, ChunkFault, DecdsError, blob::Blob, consts::DECDS_NUM_ERASURE_CODED_SHARES};
+    use rand::Rng;
+
+    #[test]
+    fn test_header_subset_validates_chunks_of_included_chunksets_only() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..rng.random_range(24usize << 20..=32usize << 20)).map(|_| rng.random()).collect::<Vec<u8>>();
+        let blob = Blob::new(blob_data).expect("Must be able to prepare blob");
+        let header = blob.get_blob_header();
+        let num_chunksets = header.get_num_chunksets();
+
+        assert_eq!(
+            header.subset(&[0, num_chunksets]),
+            Err(DecdsError::InvalidChunksetId(num_chunksets, num_chunksets))
+        );
+
+        let chunkset_ids = header
+            .get_chunkset_ids_for_byte_range(1..=header.get_config().get_chunkset_byte_length())
+            .expect("Must be able to find chunksets of byte range");
+        assert_eq!(chunkset_ids, vec![0, 1]);
+
+        let subset = header.subset(&[1, 0, 1]).expect("Must be able to make header subset");
+        let subset_bytes = subset.to_bytes().expect("Must be able to serialize header subset");
+        assert!(subset_bytes.len() < header.to_bytes().expect("Must be able to serialize header").len());
+
+        let (subset, _) = BlobHeaderSubset::from_bytes(&subset_bytes).expect("Must be able to deserialize header subset");
+        assert_eq!(subset.get_chunkset_ids().collect::<Vec<usize>>(), chunkset_ids);
+        assert_eq!(subset.get_blob_id(), header.get_blob_id());
+        assert_eq!(subset.get_chunkset_commitment(1), header.get_chunkset_commitment(1));
+        assert_eq!(subset.get_chunkset_commitment(2), Err(DecdsError::ChunksetNotInHeaderSubset(2)));
+
+        for share_id in 0..DECDS_NUM_ERASURE_CODED_SHARES {
+            for chunk in blob.iter_share(share_id).expect("Must be able to get erasure-coded share") {
+                assert_eq!(subset.validate_chunk(chunk), chunkset_ids.contains(&chunk.get_chunkset_id()));
+                assert!(!subset.validate_chunk(&ChunkFault::FlipPayloadBit(0).apply(chunk)));
+            }
+        }
+
+        // A tampered commitment doesn't make it past deserialization.
+        let mut tampered = subset.clone();
+        tampered.chunksets[1].commitment = tampered.chunksets[0].commitment;
+        assert_eq!(
+            BlobHeaderSubset::from_bytes(&tampered.to_bytes().expect("Must be able to serialize header subset")).map(|_| ()),
+            Err(DecdsError::BlobHeaderSubsetDeserializationFailed(
+                "invalid proof of commitment of chunkset 1".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use crate::{
+    blob::{Blob, BlobHeader},
+    chunk::ProofCarryingChunk,
+    chunkset::ChunkSet,
+    consts::DECDS_NUM_ERASURE_CODED_SHARES,
+    errors::DecdsError,
+};
+use std::fmt::{Display, Formatter, Write};
+
+/// Canonical test vectors for the `decds` wire format, deterministically generated from a 32-bytes seed.
+///
+/// Both the blob content and RLNC coding vectors of all erasure-coded chunks are derived from the seed, so that
+/// alternative implementations can reproduce expected chunk digests, Merkle proofs and serialized blob header bytes,
+/// given nothing but the seed and the blob size. Use `Display` to render them in a line oriented `key = value` format,
+/// where all byte strings and digests are lowercase hex encoded.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestVectors {
+    seed: [u8; 32],
+    blob_data: Vec<u8>,
+    header: BlobHeader,
+    header_bytes: Vec<u8>,
+    chunks: Vec<ProofCarryingChunk>,
+    serialized_chunk_digests: Vec<blake3::Hash>,
+}
+
+impl TestVectors {
+    /// BLAKE3 key derivation context, used for deriving blob content from the seed.
+    const BLOB_DATA_KDF_CONTEXT: &str = "decds 2025-07-01 test vector blob data";
+
+    /// Generates test vectors for a blob of `blob_size` bytes. The blob content is the first `blob_size` bytes of
+    /// BLAKE3 extendable output of the seed, hashed in key derivation mode with `BLOB_DATA_KDF_CONTEXT`. Coding vectors
+    /// are derived from the same seed, see `ChunkSet::derive_coding_vector`.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - 32-bytes seed, which fixes everything about the generated test vectors.
+    /// * `blob_size` - Byte length of the blob to generate test vectors for.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(TestVectors)` containing the generated test vectors if successful.
+    /// - `Err(DecdsError::EmptyDataForBlob)` if `blob_size` is zero.
+    /// - `Err(DecdsError)` if serialization of the blob header or any chunk fails.
+    pub fn generate(seed: [u8; 32], blob_size: usize) -> Result<Self, DecdsError> {
+        let mut blob_data = vec![0u8; blob_size];
+        blake3::Hasher::new_derive_key(Self::BLOB_DATA_KDF_CONTEXT)
+            .update(&seed)
+            .finalize_xof()
+            .fill(&mut blob_data);
+
+        let blob = Blob::new_with_seed(blob_data.clone(), &seed)?;
+        let header = blob.get_blob_header().clone();
+        let header_bytes = header.to_bytes()?;
+
+        let shares = (0..DECDS_NUM_ERASURE_CODED_SHARES)
+            .map(|share_id| blob.get_share(share_id))
+            .collect::<Result<Vec<Vec<ProofCarryingChunk>>, DecdsError>>()?;
+
+        let chunks = (0..header.get_num_chunksets())
+            .flat_map(|chunkset_id| shares.iter().map(move |share| share[chunkset_id].clone()))
+            .collect::<Vec<ProofCarryingChunk>>();
+
+        let serialized_chunk_digests = chunks
+            .iter()
+            .map(|chunk| chunk.to_bytes().map(|bytes| blake3::hash(&bytes)))
+            .collect::<Result<Vec<blake3::Hash>, DecdsError>>()?;
+
+        Ok(TestVectors {
+            seed,
+            blob_data,
+            header,
+            header_bytes,
+            chunks,
+            serialized_chunk_digests,
+        })
+    }
+
+    /// Returns the seed, these test vectors were generated from.
+    pub fn get_seed(&self) -> [u8; 32] {
+        self.seed
+    }
+
+    /// Returns the blob content, derived from the seed.
+    pub fn get_blob_data(&self) -> &[u8] {
+        &self.blob_data
+    }
+
+    /// Returns a reference to the `BlobHeader` of the generated blob.
+    pub fn get_blob_header(&self) -> &BlobHeader {
+        &self.header
+    }
+
+    /// Returns the serialized bytes of the `BlobHeader` of the generated blob.
+    pub fn get_blob_header_bytes(&self) -> &[u8] {
+        &self.header_bytes
+    }
+
+    /// Returns all proof-carrying chunks of the generated blob, ordered by their global chunk ID.
+    pub fn get_chunks(&self) -> &[ProofCarryingChunk] {
+        &self.chunks
+    }
+
+    /// Returns BLAKE3 digests of serialized bytes of all proof-carrying chunks, ordered by their global chunk ID.
+    pub fn get_serialized_chunk_digests(&self) -> &[blake3::Hash] {
+        &self.serialized_chunk_digests
+    }
+}
+
+/// Lowercase hex encodes `bytes`.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::with_capacity(bytes.len() * 2), |mut hex, byte| {
+        let _ = write!(hex, "{:02x}", byte);
+        hex
+    })
+}
+
+impl Display for TestVectors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "seed = {}", to_hex(&self.seed))?;
+        writeln!(f, "blob_size = {}", self.header.get_blob_size())?;
+        writeln!(f, "blob = {}", to_hex(&self.blob_data))?;
+        writeln!(f, "blob_digest = {}", self.header.get_blob_digest())?;
+        writeln!(f, "root_commitment = {}", self.header.get_root_commitment())?;
+        writeln!(f, "header = {}", to_hex(&self.header_bytes))?;
+
+        for chunkset_id in 0..self.header.get_num_chunksets() {
+            writeln!(f)?;
+            writeln!(f, "chunkset_id = {}", chunkset_id)?;
+            writeln!(
+                f,
+                "chunkset_commitment = {}",
+                self.header.get_chunkset_commitment(chunkset_id).map_err(|_| std::fmt::Error)?
+            )?;
+
+            let from = chunkset_id * ChunkSet::NUM_ERASURE_CODED_CHUNKS;
+            let till = from + ChunkSet::NUM_ERASURE_CODED_CHUNKS;
+
+            for (chunk, serialized_chunk_digest) in self.chunks[from..till].iter().zip(&self.serialized_chunk_digests[from..till]) {
+                let proof = chunk.get_proof().iter().map(|node| node.to_string()).collect::<Vec<String>>().join(",");
+
+                writeln!(f)?;
+                writeln!(f, "chunk_id = {}", chunk.get_global_chunk_id())?;
+                writeln!(
+                    f,
+                    "coding_vector = {}",
+                    to_hex(&chunk.get_erasure_coded_data()[..ChunkSet::NUM_ORIGINAL_CHUNKS])
+                )?;
+                writeln!(f, "chunk_digest = {}", chunk.get_chunk_digest())?;
+                writeln!(f, "proof = {}", proof)?;
+                writeln!(f, "serialized_chunk_digest = {}", serialized_chunk_digest)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MerkleTree, RepairingBlob, chunkset::ChunkSet, errors::DecdsError, test_vectors::TestVectors};
+
+    #[test]
+    fn test_generated_test_vectors_are_deterministic() {
+        let seed = [7u8; 32];
+
+        let vectors_a = TestVectors::generate(seed, 1024).expect("Must be able to generate test vectors");
+        let vectors_b = TestVectors::generate(seed, 1024).expect("Must be able to generate test vectors");
+
+        assert_eq!(vectors_a, vectors_b);
+        assert_eq!(vectors_a.to_string(), vectors_b.to_string());
+
+        let vectors_c = TestVectors::generate([8u8; 32], 1024).expect("Must be able to generate test vectors");
+        assert_ne!(vectors_a.get_blob_data(), vectors_c.get_blob_data());
+        assert_ne!(
+            vectors_a.get_blob_header().get_root_commitment(),
+            vectors_c.get_blob_header().get_root_commitment()
+        );
+    }
+
+    #[test]
+    fn test_generated_test_vectors_verify_and_repair() {
+        let seed = [42u8; 32];
+        let vectors = TestVectors::generate(seed, ChunkSet::BYTE_LENGTH + 1).expect("Must be able to generate test vectors");
+        let header = vectors.get_blob_header();
+
+        assert_eq!(vectors.get_chunks().len(), header.get_num_chunks());
+        assert!(header.verify_root_commitment());
+
+        vectors.get_chunks().iter().enumerate().for_each(|(chunk_id, chunk)| {
+            assert_eq!(chunk.get_global_chunk_id(), chunk_id);
+            assert_eq!(
+                &chunk.get_erasure_coded_data()[..ChunkSet::NUM_ORIGINAL_CHUNKS],
+                ChunkSet::derive_coding_vector(&seed, chunk.get_chunkset_id(), chunk.get_local_chunk_id(), ChunkSet::NUM_ORIGINAL_CHUNKS).as_slice()
+            );
+            assert!(MerkleTree::verify_proof(
+                chunk_id,
+                chunk.get_chunk_digest(),
+                chunk.get_proof(),
+                header.get_root_commitment()
+            ));
+        });
+
+        let mut repairer = RepairingBlob::new(header.clone());
+        vectors.get_chunks().iter().for_each(|chunk| match repairer.add_chunk(chunk) {
+            Ok(()) | Err(DecdsError::ChunksetReadyToRepair(_)) => {}
+            Err(e) => panic!("Unexpected error: {}", e),
+        });
+
+        let repaired = (0..header.get_num_chunksets())
+            .flat_map(|chunkset_id| repairer.get_repaired_chunkset(chunkset_id).expect("Must be able to repair chunkset"))
+            .collect::<Vec<u8>>();
+        assert_eq!(repaired, vectors.get_blob_data());
+    }
+
+    #[test]
+    fn test_generate_test_vectors_with_empty_blob() {
+        assert_eq!(TestVectors::generate([0u8; 32], 0), Err(DecdsError::EmptyDataForBlob));
+    }
+}
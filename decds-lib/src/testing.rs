@@ -0,0 +1,322 @@
+use crate::{
+    blob::{Blob, RepairingBlob},
+    chunk::{Chunk, ProofCarryingChunk},
+    consts::DECDS_NUM_ERASURE_CODED_SHARES,
+    errors::DecdsError,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A controlled way of corrupting a `ProofCarryingChunk`, for validating that a storage stack, built on top of `decds`,
+/// detects damaged chunks and still repairs the blob. Bit indices wrap around the length of the targeted field, so any
+/// index is valid for any chunk.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChunkFault {
+    /// Flips a bit of the erasure-coded data. Contains the bit index.
+    FlipPayloadBit(usize),
+    /// Flips a bit of the Merkle inclusion proof, seen as a concatenation of its hashes. Contains the bit index.
+    FlipProofBit(usize),
+    /// Flips a bit of the chunkset ID, the chunk claims. Contains the bit index.
+    FlipChunksetIdBit(u32),
+    /// Flips a bit of the global chunk ID, the chunk claims. Contains the bit index.
+    FlipChunkIdBit(u32),
+}
+
+impl ChunkFault {
+    /// Returns a copy of `chunk`, corrupted with this fault. The original chunk is left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk` - The `ProofCarryingChunk` to corrupt.
+    ///
+    /// # Returns
+    ///
+    /// Returns the corrupted `ProofCarryingChunk`.
+    pub fn apply(&self, chunk: &ProofCarryingChunk) -> ProofCarryingChunk {
+        let mut chunkset_id = chunk.get_chunkset_id();
+        let mut chunk_id = chunk.get_global_chunk_id();
+        let mut erasure_coded_data = chunk.get_erasure_coded_data().to_vec();
+        let mut proof = chunk.get_proof().to_vec();
+
+        match *self {
+            ChunkFault::FlipPayloadBit(bit_idx) => flip_bit(&mut erasure_coded_data, bit_idx),
+            ChunkFault::FlipProofBit(bit_idx) => {
+                let mut proof_bytes = proof.iter().flat_map(|hash| *hash.as_bytes()).collect::<Vec<u8>>();
+                flip_bit(&mut proof_bytes, bit_idx);
+
+                proof = proof_bytes
+                    .chunks_exact(blake3::OUT_LEN)
+                    .map(|hash_bytes| blake3::Hash::from_bytes(unsafe { hash_bytes.try_into().unwrap_unchecked() }))
+                    .collect();
+            }
+            ChunkFault::FlipChunksetIdBit(bit_idx) => chunkset_id ^= 1usize << (bit_idx % usize::BITS),
+            ChunkFault::FlipChunkIdBit(bit_idx) => chunk_id ^= 1usize << (bit_idx % usize::BITS),
+        }
+
+        ProofCarryingChunk::new(Chunk::new(chunkset_id, chunk_id, erasure_coded_data), proof).with_content_flags(chunk.get_content_flags())
+    }
+}
+
+/// Flips the bit at index `bit_idx`, modulo the bit length of `bytes`. Empty `bytes` are left as is.
+fn flip_bit(bytes: &mut [u8], bit_idx: usize) {
+    if bytes.is_empty() {
+        return;
+    }
+
+    let bit_idx = bit_idx % (bytes.len() * 8);
+    bytes[bit_idx / 8] ^= 1u8 << (bit_idx % 8);
+}
+
+/// An end-to-end break → damage → repair scenario. Shares can be dropped from chunksets, simulating lost chunk files or
+/// unreachable storage nodes, and chunks can be corrupted with `ChunkFault`s, simulating bit rot or malicious nodes.
+///
+/// ```rust
+/// use decds_lib::{ChunkFault, FaultScenario};
+///
+/// let report = FaultScenario::new()
+///     .drop_shares(0, &[0, 1, 2, 3])
+///     .inject_fault(0, 4, ChunkFault::FlipPayloadBit(42))
+///     .run(vec![0xde; 1024])
+///     .expect("Failed to run fault scenario");
+///
+/// assert_eq!(report.get_num_rejected_chunks(), 1);
+/// assert!(report.is_blob_recovered());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FaultScenario {
+    dropped_shares: BTreeMap<usize, BTreeSet<usize>>,
+    faults: BTreeMap<(usize, usize), Vec<ChunkFault>>,
+}
+
+impl FaultScenario {
+    /// Creates a scenario, which neither drops nor corrupts any chunk.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drops given shares of a chunkset, so that they never reach the repairer.
+    pub fn drop_shares(mut self, chunkset_id: usize, share_ids: &[usize]) -> Self {
+        self.dropped_shares.entry(chunkset_id).or_default().extend(share_ids);
+        self
+    }
+
+    /// Corrupts the chunk of a share in a chunkset with `fault`. Multiple faults, injected into the same chunk, are applied
+    /// in order of injection.
+    pub fn inject_fault(mut self, chunkset_id: usize, share_id: usize, fault: ChunkFault) -> Self {
+        self.faults.entry((chunkset_id, share_id)).or_default().push(fault);
+        self
+    }
+
+    /// Applies this scenario on an erasure-coded blob, returning surviving chunks, some of them corrupted, along with the
+    /// chunkset ID and share ID they are stored at. These can be handed to a storage stack under test.
+    ///
+    /// # Arguments
+    ///
+    /// * `blob` - The erasure-coded `Blob` to damage.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of `(chunkset_id, share_id, chunk)` tuples, in order of chunkset ID and then share ID. It is
+    /// empty for an inline blob, as it has no chunks.
+    pub fn damage(&self, blob: &Blob) -> Vec<(usize, usize, ProofCarryingChunk)> {
+        let header = blob.get_blob_header();
+        let num_chunksets = if header.is_inline() { 0 } else { header.get_num_chunksets() };
+
+        (0..num_chunksets)
+            .flat_map(|chunkset_id| (0..DECDS_NUM_ERASURE_CODED_SHARES).map(move |share_id| (chunkset_id, share_id)))
+            .filter(|(chunkset_id, share_id)| {
+                !self
+                    .dropped_shares
+                    .get(chunkset_id)
+                    .is_some_and(|dropped_share_ids| dropped_share_ids.contains(share_id))
+            })
+            .map(|(chunkset_id, share_id)| {
+                let chunk = unsafe { blob.get_chunk(chunkset_id, share_id).unwrap_unchecked() };
+                let damaged_chunk = self
+                    .faults
+                    .get(&(chunkset_id, share_id))
+                    .map_or_else(|| chunk.clone(), |faults| faults.iter().fold(chunk.clone(), |chunk, fault| fault.apply(&chunk)));
+
+                (chunkset_id, share_id, damaged_chunk)
+            })
+            .collect()
+    }
+
+    /// Erasure-codes `data` into a blob, damages it following this scenario and repairs it again, checking that every
+    /// repaired chunkset matches the original data.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The original blob data.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(FaultScenarioReport)` describing how the repairer coped with the damage.
+    /// - `Err(DecdsError)` if `data` can't be erasure-coded, or if repairing fails in a way, no damage can explain.
+    pub fn run(&self, data: Vec<u8>) -> Result<FaultScenarioReport, DecdsError> {
+        let blob = Blob::new(data.clone())?;
+        let header = blob.get_blob_header();
+        let mut repairer = RepairingBlob::new(header.clone());
+
+        let damaged_chunks = self.damage(&blob);
+        let num_rejected_chunks = damaged_chunks.iter().try_fold(0, |num_rejected_chunks, (_, share_id, chunk)| {
+            match repairer.add_chunk_from_source(chunk, &format!("share{:02}", share_id)) {
+                Ok(())
+                | Err(DecdsError::ChunksetReadyToRepair(_))
+                | Err(DecdsError::ChunksetAlreadyRepaired(_))
+                | Err(DecdsError::NonInnovativeChunk(_, _)) => Ok(num_rejected_chunks),
+                Err(DecdsError::InvalidProofInChunk(_, _)) | Err(DecdsError::InvalidChunkMetadata(_, _)) | Err(DecdsError::InvalidChunksetId(_, _)) => {
+                    Ok(num_rejected_chunks + 1)
+                }
+                Err(e) => Err(e),
+            }
+        })?;
+
+        let num_chunksets = if header.is_inline() { 0 } else { header.get_num_chunksets() };
+        let mut unrepaired_chunksets = Vec::new();
+        let mut corrupted_chunksets = Vec::new();
+
+        for chunkset_id in 0..num_chunksets {
+            if !repairer.is_chunkset_ready_to_repair(chunkset_id)? {
+                unrepaired_chunksets.push(chunkset_id);
+                continue;
+            }
+
+            let (from, to) = header.get_byte_range_for_chunkset(chunkset_id)?;
+            if repairer.get_repaired_chunkset(chunkset_id)? != data[from..to] {
+                corrupted_chunksets.push(chunkset_id);
+            }
+        }
+
+        Ok(FaultScenarioReport {
+            num_offered_chunks: damaged_chunks.len(),
+            num_rejected_chunks,
+            unrepaired_chunksets,
+            corrupted_chunksets,
+            suspect_sources: repairer
+                .get_suspect_sources()
+                .into_iter()
+                .map(|(source, num_invalid_chunks)| (source.to_string(), num_invalid_chunks))
+                .collect(),
+        })
+    }
+}
+
+/// Outcome of `FaultScenario::run`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FaultScenarioReport {
+    num_offered_chunks: usize,
+    num_rejected_chunks: usize,
+    unrepaired_chunksets: Vec<usize>,
+    corrupted_chunksets: Vec<usize>,
+    suspect_sources: Vec<(String, usize)>,
+}
+
+impl FaultScenarioReport {
+    /// Returns the number of chunks, which survived dropping and were offered to the repairer.
+    pub fn get_num_offered_chunks(&self) -> usize {
+        self.num_offered_chunks
+    }
+
+    /// Returns the number of offered chunks, which the repairer rejected as invalid.
+    pub fn get_num_rejected_chunks(&self) -> usize {
+        self.num_rejected_chunks
+    }
+
+    /// Returns IDs of chunksets, which didn't receive enough valid chunks to be repaired.
+    pub fn get_unrepaired_chunksets(&self) -> &[usize] {
+        &self.unrepaired_chunksets
+    }
+
+    /// Returns IDs of chunksets, which were repaired, but don't match the original data. This must always be empty, as
+    /// corrupted chunks are supposed to be rejected before they reach the decoder.
+    pub fn get_corrupted_chunksets(&self) -> &[usize] {
+        &self.corrupted_chunksets
+    }
+
+    /// Returns sources of rejected chunks, i.e. `shareXX`, along with the number of chunks rejected from each of them.
+    pub fn get_suspect_sources(&self) -> &[(String, usize)] {
+        &self.suspect_sources
+    }
+
+    /// Returns `true` if every chunkset was repaired and matches the original data.
+    pub fn is_blob_recovered(&self) -> bool {
+        self.unrepaired_chunksets.is_empty() && self.corrupted_chunksets.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        blob::Blob,
+        chunkset::ChunkSet,
+        consts::DECDS_NUM_ERASURE_CODED_SHARES,
+        testing::{ChunkFault, FaultScenario},
+    };
+
+    #[test]
+    fn test_chunk_fault_invalidates_chunk() {
+        let blob = Blob::new(vec![0xde; 1024]).expect("Failed to create blob");
+        let header = blob.get_blob_header();
+        let chunk = blob.get_chunk(0, 3).unwrap();
+
+        [
+            ChunkFault::FlipPayloadBit(0),
+            ChunkFault::FlipPayloadBit(usize::MAX),
+            ChunkFault::FlipProofBit(7),
+            ChunkFault::FlipChunksetIdBit(0),
+            ChunkFault::FlipChunkIdBit(1),
+        ]
+        .iter()
+        .for_each(|fault| {
+            let damaged_chunk = fault.apply(chunk);
+
+            assert_ne!(&damaged_chunk, chunk, "{:?}", fault);
+            assert!(!header.validate_chunk(&damaged_chunk), "{:?}", fault);
+            assert_eq!(&fault.apply(&damaged_chunk), chunk, "{:?}", fault);
+        });
+    }
+
+    #[test]
+    fn test_fault_scenario_run() {
+        const BLOB_SIZE: usize = ChunkSet::BYTE_LENGTH + 1;
+
+        let report = FaultScenario::new().run(vec![0xab; BLOB_SIZE]).unwrap();
+        assert_eq!(report.get_num_offered_chunks(), 2 * DECDS_NUM_ERASURE_CODED_SHARES);
+        assert_eq!(report.get_num_rejected_chunks(), 0);
+        assert!(report.is_blob_recovered());
+
+        let report = FaultScenario::new()
+            .drop_shares(0, &[0, 1])
+            .inject_fault(0, 4, ChunkFault::FlipProofBit(100))
+            .inject_fault(0, 5, ChunkFault::FlipChunkIdBit(0))
+            .inject_fault(1, 0, ChunkFault::FlipChunksetIdBit(0))
+            .inject_fault(1, 1, ChunkFault::FlipPayloadBit(3))
+            .run(vec![0xab; BLOB_SIZE])
+            .unwrap();
+        assert_eq!(report.get_num_offered_chunks(), 2 * DECDS_NUM_ERASURE_CODED_SHARES - 2);
+        assert_eq!(report.get_num_rejected_chunks(), 4);
+        assert_eq!(
+            report.get_suspect_sources(),
+            &[
+                ("share00".to_string(), 1),
+                ("share01".to_string(), 1),
+                ("share04".to_string(), 1),
+                ("share05".to_string(), 1)
+            ]
+        );
+        assert!(report.is_blob_recovered());
+
+        let report = FaultScenario::new()
+            .drop_shares(1, &(0..DECDS_NUM_ERASURE_CODED_SHARES - 1).collect::<Vec<usize>>())
+            .run(vec![0xab; BLOB_SIZE])
+            .unwrap();
+        assert_eq!(report.get_unrepaired_chunksets(), &[1]);
+        assert!(report.get_corrupted_chunksets().is_empty());
+        assert!(!report.is_blob_recovered());
+
+        let report = FaultScenario::new().drop_shares(0, &[0]).run(vec![0xab; 10]).unwrap();
+        assert_eq!(report.get_num_offered_chunks(), DECDS_NUM_ERASURE_CODED_SHARES - 1);
+        assert!(report.is_blob_recovered());
+    }
+}
@@ -30,7 +30,7 @@ fn prop_test_blob_building_and_repairing_works() {
                 match repairer.add_chunk(share) {
                     Ok(()) => { /* Found a useful chunk */ }
                     Err(e) => match e {
-                        DecdsError::ChunkDecodingFailed(id, _) => unsafe {
+                        DecdsError::NonInnovativeChunk(id, _) => unsafe {
                             assert!(!repairer.is_chunkset_ready_to_repair(id).unwrap_unchecked());
                             assert!(!repairer.is_chunkset_already_repaired(id).unwrap_unchecked());
                         },
@@ -0,0 +1,435 @@
+#[cfg(feature = "coding")]
+use crate::mmap::MappedFile;
+use crate::{blob::BlobHeader, chunk::ProofCarryingChunk, errors::DecdsError};
+use std::io::Read;
+#[cfg(feature = "coding")]
+use std::path::Path;
+
+/// Outcome of `verify_chunk_bytes`. Coordinates of the chunk are filled in as far as parsing got, so that even a rejected
+/// chunk can be attributed to a blob, chunkset and share, whenever its bytes allow.
+#[derive(Debug, PartialEq)]
+pub struct ChunkVerdict {
+    blob_id: Option<blake3::Hash>,
+    chunkset_id: Option<usize>,
+    share_id: Option<usize>,
+    chunk_digest: Option<blake3::Hash>,
+    error: Option<DecdsError>,
+}
+
+impl ChunkVerdict {
+    /// Returns `true` if the chunk belongs to the blob, i.e. both of its Merkle inclusion proofs are valid.
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Returns the ID of the blob, if the blob header could be deserialized.
+    pub fn get_blob_id(&self) -> Option<blake3::Hash> {
+        self.blob_id
+    }
+
+    /// Returns the chunkset ID, the chunk claims, if the chunk could be deserialized.
+    pub fn get_chunkset_id(&self) -> Option<usize> {
+        self.chunkset_id
+    }
+
+    /// Returns the share ID, the chunk claims, if the chunk could be deserialized.
+    pub fn get_share_id(&self) -> Option<usize> {
+        self.share_id
+    }
+
+    /// Returns the BLAKE3 digest of the chunk, if the chunk could be deserialized.
+    pub fn get_chunk_digest(&self) -> Option<blake3::Hash> {
+        self.chunk_digest
+    }
+
+    /// Returns why the chunk was rejected, if it was.
+    pub fn get_error(&self) -> Option<&DecdsError> {
+        self.error.as_ref()
+    }
+}
+
+/// A `ProofCarryingChunk`, whose Merkle inclusion proofs are verified against a blob header, by `BlobHeader::verify_chunk`.
+/// A `RepairingBlob` of the same blob accepts it without verifying it again (see `RepairingBlob::add_verified_chunk`), so
+/// that verification and decoding can run as separate stages, e.g. on different threads.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VerifiedChunk {
+    chunk: ProofCarryingChunk,
+    root_commitment: blake3::Hash,
+}
+
+impl VerifiedChunk {
+    /// Returns the verified chunk.
+    pub fn get_chunk(&self) -> &ProofCarryingChunk {
+        &self.chunk
+    }
+
+    /// Returns the root commitment of the blob, the chunk is verified against.
+    pub fn get_root_commitment(&self) -> blake3::Hash {
+        self.root_commitment
+    }
+
+    /// Consumes the verified chunk, returning the underlying chunk.
+    pub fn into_chunk(self) -> ProofCarryingChunk {
+        self.chunk
+    }
+}
+
+impl BlobHeader {
+    /// Verifies that a chunk belongs to the blob described by this header, i.e. that both of its Merkle inclusion proofs
+    /// are valid, wrapping it up as a `VerifiedChunk`.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk` - The `ProofCarryingChunk` to verify.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(VerifiedChunk)` if the chunk belongs to the blob.
+    /// - `Err(DecdsError::InvalidChunksetId)` if the chunk claims a chunkset, the blob doesn't have. An inline blob has none.
+    /// - `Err(DecdsError::InvalidProofInChunk)` if a Merkle inclusion proof of the chunk is invalid.
+    pub fn verify_chunk(&self, chunk: ProofCarryingChunk) -> Result<VerifiedChunk, DecdsError> {
+        let chunk_digest = chunk.get_chunk_digest();
+        self.verify_chunk_with_digest(chunk, chunk_digest)
+    }
+
+    /// Same as `Self::verify_chunk`, but reads the serialized chunk from `reader` (see `ProofCarryingChunk::from_reader`),
+    /// so that the chunk is hashed while being read and never hashed again.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader from which to deserialize the chunk.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Err(DecdsError::ProofCarryingChunkDeserializationFailed)` if a chunk can't be deserialized from `reader`.
+    /// - Otherwise, exactly what `Self::verify_chunk` returns.
+    pub fn read_verified_chunk<R: Read>(&self, reader: &mut R) -> Result<VerifiedChunk, DecdsError> {
+        ProofCarryingChunk::from_reader(reader).and_then(|(chunk, chunk_digest)| self.verify_chunk_with_digest(chunk, chunk_digest))
+    }
+
+    /// Verifies a chunk file, as written by `ProofCarryingChunk::to_bytes`, against this header, in place. The file is
+    /// memory-mapped, with the kernel advised of sequential access, and its erasure-coded data is hashed right off the
+    /// mapping, so that verifying huge stores neither allocates a chunk sized buffer per file nor churns the page cache.
+    /// Trailing bytes after the chunk are rejected. Use `Self::read_verified_chunk` for chunk files, which may be rewritten
+    /// meanwhile, e.g. by a relay.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be truncated or modified, by this or any other process, until this function returns. Truncating it
+    /// makes reads off the mapping fail with SIGBUS, while modifying it may make the chunk, read off the mapping in pieces,
+    /// inconsistent.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the chunk file.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `ChunkVerdict`, with the blob ID always filled in, which is valid if the chunk belongs to the blob.
+    /// Otherwise its error is one of:
+    /// - `DecdsError::ChunkFileReadFailed` if the file can't be opened or mapped.
+    /// - `DecdsError::ProofCarryingChunkDeserializationFailed` if the file isn't exactly one proof-carrying chunk.
+    /// - `DecdsError::InvalidChunksetId` if the chunk claims a chunkset, the blob doesn't have. An inline blob has none.
+    /// - `DecdsError::InvalidProofInChunk` if a Merkle inclusion proof of the chunk is invalid.
+    #[cfg(feature = "coding")]
+    pub unsafe fn verify_chunk_file<P: AsRef<Path>>(&self, path: P) -> ChunkVerdict {
+        let mut verdict = ChunkVerdict {
+            blob_id: Some(self.get_blob_id()),
+            chunkset_id: None,
+            share_id: None,
+            chunk_digest: None,
+            error: None,
+        };
+
+        let mapped_file = match unsafe { MappedFile::open(path.as_ref()) } {
+            Ok(mapped_file) => mapped_file,
+            Err(err) => {
+                verdict.error = Some(DecdsError::ChunkFileReadFailed(err.to_string()));
+                return verdict;
+            }
+        };
+        mapped_file.advise_sequential();
+
+        let (chunk, chunk_digest) = match ProofCarryingChunk::from_bytes_without_data(&mapped_file) {
+            Ok((chunk, chunk_digest, n)) if n == mapped_file.len() => (chunk, chunk_digest),
+            Ok((_, _, n)) => {
+                verdict.error = Some(DecdsError::ProofCarryingChunkDeserializationFailed(format!(
+                    "{} trailing bytes after proof carrying chunk",
+                    mapped_file.len() - n
+                )));
+                return verdict;
+            }
+            Err(e) => {
+                verdict.error = Some(e);
+                return verdict;
+            }
+        };
+
+        verdict.chunkset_id = Some(chunk.get_chunkset_id());
+        verdict.share_id = Some(chunk.get_local_chunk_id());
+        verdict.chunk_digest = Some(chunk_digest);
+
+        let num_chunksets = if self.is_inline() { 0 } else { self.get_num_chunksets() };
+        if chunk.get_chunkset_id() >= num_chunksets {
+            verdict.error = Some(DecdsError::InvalidChunksetId(chunk.get_chunkset_id(), num_chunksets));
+        } else if !self.validate_chunk_with_digest(&chunk, chunk_digest) {
+            verdict.error = Some(DecdsError::InvalidProofInChunk(chunk.get_chunkset_id(), chunk.get_local_chunk_id()));
+        }
+
+        verdict
+    }
+
+    fn verify_chunk_with_digest(&self, chunk: ProofCarryingChunk, chunk_digest: blake3::Hash) -> Result<VerifiedChunk, DecdsError> {
+        let num_chunksets = if self.is_inline() { 0 } else { self.get_num_chunksets() };
+
+        if chunk.get_chunkset_id() >= num_chunksets {
+            Err(DecdsError::InvalidChunksetId(chunk.get_chunkset_id(), num_chunksets))
+        } else if !self.validate_chunk_with_digest(&chunk, chunk_digest) {
+            Err(DecdsError::InvalidProofInChunk(chunk.get_chunkset_id(), chunk.get_local_chunk_id()))
+        } else {
+            Ok(VerifiedChunk {
+                chunk,
+                root_commitment: self.get_root_commitment(),
+            })
+        }
+    }
+}
+
+/// Verifies a serialized `ProofCarryingChunk` against a serialized `BlobHeader`, without keeping any state between calls.
+///
+/// Meant for FFI, WASM or HTTP gateways, which receive both as untrusted bytes. Both are parsed and bounds-checked, and
+/// trailing bytes after either of them are rejected. The chunk's proofs are then verified against the header's
+/// commitments. It never panics or exits, every failure is reported in the returned verdict. This is available without
+/// the `coding` feature.
+///
+/// # Arguments
+///
+/// * `header_bytes` - Serialized `BlobHeader`, as returned by `BlobHeader::to_bytes`.
+/// * `chunk_bytes` - Serialized `ProofCarryingChunk`, as returned by `ProofCarryingChunk::to_bytes`.
+///
+/// # Returns
+///
+/// Returns a `ChunkVerdict`, which is valid if the chunk belongs to the blob. Otherwise its error is one of:
+/// - `DecdsError::BlobHeaderDeserializationFailed` if `header_bytes` isn't exactly one valid blob header.
+/// - `DecdsError::ProofCarryingChunkDeserializationFailed` if `chunk_bytes` isn't exactly one proof-carrying chunk.
+/// - `DecdsError::InvalidChunksetId` if the chunk claims a chunkset, the blob doesn't have. An inline blob has none.
+/// - `DecdsError::InvalidProofInChunk` if a Merkle inclusion proof of the chunk is invalid.
+pub fn verify_chunk_bytes(header_bytes: &[u8], chunk_bytes: &[u8]) -> ChunkVerdict {
+    let mut verdict = ChunkVerdict {
+        blob_id: None,
+        chunkset_id: None,
+        share_id: None,
+        chunk_digest: None,
+        error: None,
+    };
+
+    let header = match BlobHeader::from_bytes(header_bytes) {
+        Ok((header, n)) if n == header_bytes.len() => header,
+        Ok((_, n)) => {
+            verdict.error = Some(DecdsError::BlobHeaderDeserializationFailed(format!(
+                "{} trailing bytes after blob header",
+                header_bytes.len() - n
+            )));
+            return verdict;
+        }
+        Err(e) => {
+            verdict.error = Some(e);
+            return verdict;
+        }
+    };
+    verdict.blob_id = Some(header.get_blob_id());
+
+    let chunk = match ProofCarryingChunk::from_bytes(chunk_bytes) {
+        Ok((chunk, n)) if n == chunk_bytes.len() => chunk,
+        Ok((_, n)) => {
+            verdict.error = Some(DecdsError::ProofCarryingChunkDeserializationFailed(format!(
+                "{} trailing bytes after proof carrying chunk",
+                chunk_bytes.len() - n
+            )));
+            return verdict;
+        }
+        Err(e) => {
+            verdict.error = Some(e);
+            return verdict;
+        }
+    };
+
+    let chunk_digest = chunk.get_chunk_digest();
+    verdict.chunkset_id = Some(chunk.get_chunkset_id());
+    verdict.share_id = Some(chunk.get_local_chunk_id());
+    verdict.chunk_digest = Some(chunk_digest);
+
+    let num_chunksets = if header.is_inline() { 0 } else { header.get_num_chunksets() };
+    if chunk.get_chunkset_id() >= num_chunksets {
+        verdict.error = Some(DecdsError::InvalidChunksetId(chunk.get_chunkset_id(), num_chunksets));
+    } else if !header.validate_chunk_with_digest(&chunk, chunk_digest) {
+        verdict.error = Some(DecdsError::InvalidProofInChunk(chunk.get_chunkset_id(), chunk.get_local_chunk_id()));
+    }
+
+    verdict
+}
+
+#[cfg(all(test, feature = "coding"))]
+mod tests {
+    use crate::{RepairingBlob, blob::Blob, consts, errors::DecdsError, verify::verify_chunk_bytes};
+    use rand::Rng;
+
+    #[test]
+    fn test_verify_chunk_bytes() {
+        let blob = Blob::new(vec![0xcd; 1024]).expect("Failed to create blob");
+        let header = blob.get_blob_header();
+        let header_bytes = header.to_bytes().unwrap();
+
+        let chunk = blob.get_chunk(0, 5).unwrap();
+        let chunk_bytes = chunk.to_bytes().unwrap();
+
+        let verdict = verify_chunk_bytes(&header_bytes, &chunk_bytes);
+        assert!(verdict.is_valid());
+        assert_eq!(verdict.get_blob_id(), Some(header.get_blob_id()));
+        assert_eq!(verdict.get_chunkset_id(), Some(0));
+        assert_eq!(verdict.get_share_id(), Some(5));
+        assert_eq!(verdict.get_chunk_digest(), Some(chunk.get_chunk_digest()));
+        assert_eq!(verdict.get_error(), None);
+
+        let mut tampered_chunk_bytes = chunk_bytes.clone();
+        // Last byte of the proof, which precedes the content flags byte.
+        let last_proof_byte_idx = tampered_chunk_bytes.len() - 2;
+        tampered_chunk_bytes[last_proof_byte_idx] ^= 1;
+
+        let verdict = verify_chunk_bytes(&header_bytes, &tampered_chunk_bytes);
+        assert!(!verdict.is_valid());
+        assert_eq!(verdict.get_share_id(), Some(5));
+        assert_eq!(verdict.get_error(), Some(&DecdsError::InvalidProofInChunk(0, 5)));
+
+        let other_blob = Blob::new(vec![0xcd; 2048]).expect("Failed to create blob");
+        let verdict = verify_chunk_bytes(&other_blob.get_blob_header().to_bytes().unwrap(), &chunk_bytes);
+        assert_eq!(verdict.get_blob_id(), Some(other_blob.get_blob_header().get_blob_id()));
+        assert_eq!(verdict.get_error(), Some(&DecdsError::InvalidProofInChunk(0, 5)));
+
+        let inline_blob = Blob::new_with_inline_threshold(vec![0xcd; 1024], 1024).expect("Failed to create blob");
+        let verdict = verify_chunk_bytes(&inline_blob.get_blob_header().to_bytes().unwrap(), &chunk_bytes);
+        assert_eq!(verdict.get_error(), Some(&DecdsError::InvalidChunksetId(0, 0)));
+
+        let verdict = verify_chunk_bytes(&[header_bytes.as_slice(), &[0]].concat(), &chunk_bytes);
+        assert!(matches!(verdict.get_error(), Some(DecdsError::BlobHeaderDeserializationFailed(_))));
+        assert_eq!(verdict.get_blob_id(), None);
+
+        let verdict = verify_chunk_bytes(&header_bytes, &[chunk_bytes.as_slice(), &[0]].concat());
+        assert!(matches!(verdict.get_error(), Some(DecdsError::ProofCarryingChunkDeserializationFailed(_))));
+        assert_eq!(verdict.get_chunkset_id(), None);
+    }
+
+    #[test]
+    fn test_verify_chunk_file() {
+        let blob = Blob::new(vec![0xcd; 1024]).expect("Failed to create blob");
+        let header = blob.get_blob_header();
+
+        let chunk = blob.get_chunk(0, 5).unwrap();
+        let chunk_bytes = chunk.to_bytes().unwrap();
+
+        let chunk_path = std::env::temp_dir().join(format!("decds-test-verify-chunk-file-{}", std::process::id()));
+        std::fs::write(&chunk_path, &chunk_bytes).unwrap();
+
+        // SAFETY: The file is private to this test, and is only rewritten in between verifications.
+        let verdict = unsafe { header.verify_chunk_file(&chunk_path) };
+        assert!(verdict.is_valid());
+        assert_eq!(verdict.get_blob_id(), Some(header.get_blob_id()));
+        assert_eq!(verdict.get_chunkset_id(), Some(0));
+        assert_eq!(verdict.get_share_id(), Some(5));
+        assert_eq!(verdict.get_chunk_digest(), Some(chunk.get_chunk_digest()));
+
+        let mut tampered_chunk_bytes = chunk_bytes.clone();
+        tampered_chunk_bytes[chunk_bytes.len() / 2] ^= 1;
+        std::fs::write(&chunk_path, &tampered_chunk_bytes).unwrap();
+        assert_eq!(
+            unsafe { header.verify_chunk_file(&chunk_path) }.get_error(),
+            Some(&DecdsError::InvalidProofInChunk(0, 5))
+        );
+
+        std::fs::write(&chunk_path, [chunk_bytes.as_slice(), &[0]].concat()).unwrap();
+        assert!(matches!(
+            unsafe { header.verify_chunk_file(&chunk_path) }.get_error(),
+            Some(DecdsError::ProofCarryingChunkDeserializationFailed(_))
+        ));
+
+        std::fs::write(&chunk_path, &chunk_bytes[..chunk_bytes.len() - 1]).unwrap();
+        assert!(matches!(
+            unsafe { header.verify_chunk_file(&chunk_path) }.get_error(),
+            Some(DecdsError::ProofCarryingChunkDeserializationFailed(_))
+        ));
+
+        std::fs::write(&chunk_path, []).unwrap();
+        assert!(!unsafe { header.verify_chunk_file(&chunk_path) }.is_valid());
+
+        std::fs::remove_file(&chunk_path).unwrap();
+        assert!(matches!(
+            unsafe { header.verify_chunk_file(&chunk_path) }.get_error(),
+            Some(DecdsError::ChunkFileReadFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_chunk_bytes_never_panics_on_garbage() {
+        let mut rng = rand::rng();
+
+        let blob = Blob::new(vec![0xcd; 1024]).expect("Failed to create blob");
+        let header_bytes = blob.get_blob_header().to_bytes().unwrap();
+        let chunk_bytes = blob.get_chunk(0, 0).unwrap().to_bytes().unwrap();
+
+        (0..=header_bytes.len()).for_each(|len| {
+            assert!(!verify_chunk_bytes(&header_bytes[..len], &chunk_bytes).is_valid() || len == header_bytes.len());
+        });
+        (0..chunk_bytes.len()).step_by(4099).for_each(|len| {
+            assert!(!verify_chunk_bytes(&header_bytes, &chunk_bytes[..len]).is_valid());
+        });
+
+        (0..64).for_each(|_| {
+            let garbage = (0..rng.random_range(0..256)).map(|_| rng.random()).collect::<Vec<u8>>();
+
+            assert!(!verify_chunk_bytes(&garbage, &chunk_bytes).is_valid());
+            assert!(!verify_chunk_bytes(&header_bytes, &garbage).is_valid());
+        });
+
+        let mut huge_chunk_bytes = chunk_bytes.clone();
+        huge_chunk_bytes[2..11].copy_from_slice(&[0xfd, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f]);
+        assert!(!verify_chunk_bytes(&header_bytes, &huge_chunk_bytes).is_valid());
+    }
+
+    #[test]
+    fn test_verified_chunks_repair_blob() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..4096).map(|_| rng.random()).collect::<Vec<u8>>();
+        let blob = Blob::new(blob_data.clone()).expect("Failed to create blob");
+        let header = blob.get_blob_header();
+
+        let other_blob = Blob::new(vec![0xcd; 4096]).expect("Failed to create blob");
+        let other_chunk = other_blob.get_chunk(0, 0).unwrap().clone();
+
+        assert_eq!(header.verify_chunk(other_chunk.clone()), Err(DecdsError::InvalidProofInChunk(0, 0)));
+        assert!(matches!(
+            header.read_verified_chunk(&mut [0u8; 2].as_slice()),
+            Err(DecdsError::ProofCarryingChunkDeserializationFailed(_))
+        ));
+
+        let mut other_repairer = RepairingBlob::new(other_blob.get_blob_header().clone());
+        let verified_chunk = header.verify_chunk(blob.get_chunk(0, 0).unwrap().clone()).unwrap();
+        assert_eq!(other_repairer.add_verified_chunk(&verified_chunk), Err(DecdsError::InvalidProofInChunk(0, 0)));
+
+        let mut repairer = RepairingBlob::new(header.clone());
+        for share_id in 0..consts::DECDS_NUM_ERASURE_CODED_SHARES {
+            let chunk_bytes = blob.get_chunk(0, share_id).unwrap().to_bytes().unwrap();
+            let verified_chunk = header.read_verified_chunk(&mut chunk_bytes.as_slice()).unwrap();
+            assert_eq!(verified_chunk.get_chunk(), blob.get_chunk(0, share_id).unwrap());
+
+            let _ = repairer.add_verified_chunk(&verified_chunk);
+            if repairer.is_chunkset_ready_to_repair(0).unwrap() {
+                break;
+            }
+        }
+
+        assert_eq!(repairer.get_repaired_chunkset(0).unwrap(), blob_data);
+    }
+}
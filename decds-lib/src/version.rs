@@ -0,0 +1,327 @@
+use crate::{blob::BlobHeader, consts::DECDS_BINCODE_CONFIG, errors::DecdsError};
+use serde::{Deserialize, Serialize};
+
+/// One version of a mutable logical object: the header of an immutable blob, holding the object's content at that point in
+/// time, linked to the version it supersedes and signed by its author.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct BlobVersion {
+    header: BlobHeader,
+    parent_version_id: Option<blake3::Hash>,
+    timestamp: u64,
+    signature: Vec<u8>,
+}
+
+impl BlobVersion {
+    /// BLAKE3 key derivation context, used for computing version IDs.
+    const VERSION_ID_KDF_CONTEXT: &str = "decds 2025-07-01 blob version id";
+
+    /// Computes the ID of a version, which is what its author signs.
+    ///
+    /// The ID is computed as `BLAKE3(header || has_parent || parent_version_id || timestamp)`, hashed in key derivation mode
+    /// with `VERSION_ID_KDF_CONTEXT`, where `header` is the serialized blob header, as `BlobHeader::to_bytes` returns it,
+    /// `has_parent` is a single byte, `parent_version_id` is all zeros for the first version and `timestamp` is encoded as
+    /// 8-bytes little-endian unsigned integer. Committing to the whole header, not just the blob ID, binds the signature to
+    /// the chunk commitments, so that a header of the same content, encoded differently, can't be swapped in.
+    fn compute_version_id(header: &BlobHeader, parent_version_id: Option<blake3::Hash>, timestamp: u64) -> blake3::Hash {
+        let mut hasher = blake3::Hasher::new_derive_key(Self::VERSION_ID_KDF_CONTEXT);
+
+        // Writing into a hasher never fails, neither does encoding the header fields, which are integers and byte strings.
+        let _ = header.write_to(&mut hasher);
+
+        hasher
+            .update(&[parent_version_id.is_some() as u8])
+            .update(parent_version_id.as_ref().map_or(&[0u8; blake3::OUT_LEN], |id| id.as_bytes()))
+            .update(&timestamp.to_le_bytes())
+            .finalize()
+    }
+
+    /// Returns the ID of this version, committing to its blob header, parent version and timestamp.
+    pub fn get_version_id(&self) -> blake3::Hash {
+        Self::compute_version_id(&self.header, self.parent_version_id, self.timestamp)
+    }
+
+    /// Returns the header of the blob, holding content of the object at this version.
+    pub fn get_blob_header(&self) -> &BlobHeader {
+        &self.header
+    }
+
+    /// Returns the ID of the version this one supersedes, or `None` for the first version.
+    pub fn get_parent_version_id(&self) -> Option<blake3::Hash> {
+        self.parent_version_id
+    }
+
+    /// Returns the timestamp of this version, as chosen by its author, e.g. seconds since the UNIX epoch.
+    pub fn get_timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// Returns the author's signature over the version ID.
+    pub fn get_signature(&self) -> &[u8] {
+        &self.signature
+    }
+}
+
+/// Version history manifest of a mutable logical object, chaining headers of successive immutable blobs. Each version
+/// names its parent by version ID, carries a non-decreasing timestamp and a signature of its author over its version ID.
+///
+/// Signing and verification are left to the caller, so that any signature scheme can be plugged in, as closures over the
+/// 32-bytes version ID. The manifest is meant to be stored and replicated next to the blobs it refers to.
+///
+/// ```rust
+/// use decds_lib::{Blob, VersionedBlob};
+///
+/// // Stand-in for a real signature scheme.
+/// let sign = |version_id: &blake3::Hash| blake3::keyed_hash(&[7u8; 32], version_id.as_bytes()).as_bytes().to_vec();
+/// let verify = |version_id: &blake3::Hash, signature: &[u8]| sign(version_id) == signature;
+///
+/// let v1 = Blob::new(b"hello".to_vec()).expect("Failed to create blob");
+/// let v2 = Blob::new(b"hello, world".to_vec()).expect("Failed to create blob");
+///
+/// let mut manifest = VersionedBlob::new(v1.get_blob_header().clone(), 1_700_000_000, sign);
+/// manifest.commit(v2.get_blob_header().clone(), 1_700_000_600, sign).expect("Failed to commit version");
+///
+/// assert_eq!(manifest.get_head().get_blob_header(), v2.get_blob_header());
+/// assert_eq!(manifest.history().count(), 2);
+/// assert!(manifest.verify(verify).is_ok());
+/// ```
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct VersionedBlob {
+    versions: Vec<BlobVersion>,
+}
+
+impl VersionedBlob {
+    /// Starts the version history of an object, with `header` as its first version.
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - Header of the blob, holding the first version of the object.
+    /// * `timestamp` - Timestamp of the first version.
+    /// * `sign` - Signs the version ID on behalf of the author.
+    ///
+    /// # Returns
+    ///
+    /// Returns a manifest holding a single version.
+    pub fn new(header: BlobHeader, timestamp: u64, sign: impl FnOnce(&blake3::Hash) -> Vec<u8>) -> Self {
+        let signature = sign(&BlobVersion::compute_version_id(&header, None, timestamp));
+
+        VersionedBlob {
+            versions: vec![BlobVersion {
+                header,
+                parent_version_id: None,
+                timestamp,
+                signature,
+            }],
+        }
+    }
+
+    /// Appends a new version on top of the current head.
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - Header of the blob, holding the new version of the object.
+    /// * `timestamp` - Timestamp of the new version, which must not be older than the current head.
+    /// * `sign` - Signs the version ID on behalf of the author.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(())` if the version is appended.
+    /// - `Err(DecdsError::InvalidVersionChain)` if `timestamp` is older than the one of the current head.
+    pub fn commit(&mut self, header: BlobHeader, timestamp: u64, sign: impl FnOnce(&blake3::Hash) -> Vec<u8>) -> Result<(), DecdsError> {
+        let head = self.get_head();
+        if timestamp < head.timestamp {
+            return Err(DecdsError::InvalidVersionChain(self.versions.len()));
+        }
+
+        let parent_version_id = Some(head.get_version_id());
+        let signature = sign(&BlobVersion::compute_version_id(&header, parent_version_id, timestamp));
+
+        self.versions.push(BlobVersion {
+            header,
+            parent_version_id,
+            timestamp,
+            signature,
+        });
+        Ok(())
+    }
+
+    /// Returns the latest version of the object.
+    pub fn get_head(&self) -> &BlobVersion {
+        unsafe { self.versions.last().unwrap_unchecked() }
+    }
+
+    /// Returns the number of versions in the history.
+    pub fn get_num_versions(&self) -> usize {
+        self.versions.len()
+    }
+
+    /// Walks the version history, from the head back to the first version.
+    pub fn history(&self) -> impl Iterator<Item = &BlobVersion> + '_ {
+        self.versions.iter().rev()
+    }
+
+    /// Verifies the whole version history: the first version has no parent, every other version names its predecessor as
+    /// parent, timestamps never decrease, and every version is signed.
+    ///
+    /// # Arguments
+    ///
+    /// * `verify_signature` - Checks a signature over a version ID, returning `true` if it was made by a trusted author.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(())` if the history is intact.
+    /// - `Err(DecdsError::InvalidVersionChain)` if a version doesn't extend its predecessor, with its index from the first version.
+    /// - `Err(DecdsError::InvalidVersionSignature)` if a version isn't properly signed, with its index from the first version.
+    pub fn verify(&self, verify_signature: impl Fn(&blake3::Hash, &[u8]) -> bool) -> Result<(), DecdsError> {
+        let mut parent: Option<&BlobVersion> = None;
+
+        for (version_idx, version) in self.versions.iter().enumerate() {
+            let is_linked = match parent {
+                Some(parent) => version.parent_version_id == Some(parent.get_version_id()) && version.timestamp >= parent.timestamp,
+                None => version.parent_version_id.is_none(),
+            };
+            if !is_linked {
+                return Err(DecdsError::InvalidVersionChain(version_idx));
+            }
+
+            if !verify_signature(&version.get_version_id(), &version.signature) {
+                return Err(DecdsError::InvalidVersionSignature(version_idx));
+            }
+
+            parent = Some(version);
+        }
+
+        Ok(())
+    }
+
+    /// Serializes the manifest into a vector of bytes using `bincode`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(Vec<u8>)` containing the serialized bytes if successful.
+    /// - `Err(DecdsError::VersionedBlobSerializationFailed)` if serialization fails, containing the error message.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, DecdsError> {
+        bincode::serde::encode_to_vec(self, DECDS_BINCODE_CONFIG).map_err(|err| DecdsError::VersionedBlobSerializationFailed(err.to_string()))
+    }
+
+    /// Deserializes a manifest from a byte slice using `bincode`. Embedded blob headers are checked just like
+    /// `BlobHeader::from_bytes` does, but signatures are not, see `Self::verify`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The byte slice from which to deserialize the manifest.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok((Self, usize))` containing the deserialized manifest and the number of bytes read if successful.
+    /// - `Err(DecdsError::VersionedBlobDeserializationFailed)` if `bincode` deserialization fails or the manifest has no version.
+    /// - `Err(DecdsError::BlobHeaderDeserializationFailed)` if an embedded blob header is inconsistent.
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Self, usize), DecdsError> {
+        let (manifest, n) = bincode::serde::decode_from_slice::<VersionedBlob, bincode::config::Configuration>(bytes, DECDS_BINCODE_CONFIG)
+            .map_err(|err| DecdsError::VersionedBlobDeserializationFailed(err.to_string()))?;
+
+        if manifest.versions.is_empty() {
+            return Err(DecdsError::VersionedBlobDeserializationFailed("manifest has no version".to_string()));
+        }
+
+        manifest.versions.iter().try_for_each(|version| version.header.validate_consistency())?;
+        Ok((manifest, n))
+    }
+}
+
+#[cfg(all(test, feature = "coding"))]
+mod tests {
+    use crate::{DecdsBuilder, blob::Blob, errors::DecdsError, version::VersionedBlob};
+
+    fn sign_with(key: u8) -> impl Fn(&blake3::Hash) -> Vec<u8> {
+        move |version_id| blake3::keyed_hash(&[key; 32], version_id.as_bytes()).as_bytes().to_vec()
+    }
+
+    fn verify_with(key: u8) -> impl Fn(&blake3::Hash, &[u8]) -> bool {
+        move |version_id, signature| sign_with(key)(version_id) == signature
+    }
+
+    #[test]
+    fn test_versioned_blob_commit_verify_and_walk_history() {
+        let headers = (1..=3)
+            .map(|version| Blob::new(vec![version as u8; 1024 * version]).unwrap().get_blob_header().clone())
+            .collect::<Vec<_>>();
+
+        let mut manifest = VersionedBlob::new(headers[0].clone(), 100, sign_with(1));
+        manifest.commit(headers[1].clone(), 100, sign_with(1)).unwrap();
+        manifest.commit(headers[2].clone(), 200, sign_with(1)).unwrap();
+
+        assert_eq!(manifest.get_num_versions(), 3);
+        assert_eq!(manifest.get_head().get_blob_header(), &headers[2]);
+        assert_eq!(manifest.get_head().get_timestamp(), 200);
+        assert!(manifest.history().map(|version| version.get_blob_header()).eq(headers.iter().rev()));
+        assert!(
+            manifest
+                .history()
+                .zip(manifest.history().skip(1))
+                .all(|(version, parent)| version.get_parent_version_id() == Some(parent.get_version_id()))
+        );
+        assert_eq!(manifest.history().last().unwrap().get_parent_version_id(), None);
+
+        assert_eq!(manifest.verify(verify_with(1)), Ok(()));
+        assert_eq!(manifest.verify(verify_with(2)), Err(DecdsError::InvalidVersionSignature(0)));
+
+        assert_eq!(manifest.commit(headers[0].clone(), 199, sign_with(1)), Err(DecdsError::InvalidVersionChain(3)));
+        assert_eq!(manifest.get_num_versions(), 3);
+
+        let (deserialized_manifest, n) = VersionedBlob::from_bytes(&manifest.to_bytes().unwrap()).unwrap();
+        assert_eq!(deserialized_manifest, manifest);
+        assert_eq!(n, manifest.to_bytes().unwrap().len());
+    }
+
+    #[test]
+    fn test_versioned_blob_detects_tampering() {
+        let header_a = Blob::new(vec![0xaa; 1024]).unwrap().get_blob_header().clone();
+        let header_b = Blob::new(vec![0xbb; 1024]).unwrap().get_blob_header().clone();
+
+        let mut manifest = VersionedBlob::new(header_a.clone(), 100, sign_with(1));
+        manifest.commit(header_b.clone(), 200, sign_with(1)).unwrap();
+        manifest.commit(header_a.clone(), 300, sign_with(1)).unwrap();
+
+        let mut reordered_manifest = manifest.clone();
+        reordered_manifest.versions.swap(1, 2);
+        assert_eq!(reordered_manifest.verify(verify_with(1)), Err(DecdsError::InvalidVersionChain(1)));
+
+        let mut dropped_manifest = manifest.clone();
+        dropped_manifest.versions.remove(1);
+        assert_eq!(dropped_manifest.verify(verify_with(1)), Err(DecdsError::InvalidVersionChain(1)));
+
+        let mut backdated_manifest = manifest.clone();
+        backdated_manifest.versions[2].timestamp = 50;
+        assert_eq!(backdated_manifest.verify(verify_with(1)), Err(DecdsError::InvalidVersionChain(2)));
+
+        let mut swapped_blob_manifest = manifest.clone();
+        swapped_blob_manifest.versions[1].header = header_a;
+        assert_eq!(swapped_blob_manifest.verify(verify_with(1)), Err(DecdsError::InvalidVersionSignature(1)));
+
+        let reencoded_header = DecdsBuilder::new().seed([7u8; 32]).build(vec![0xbb; 1024]).unwrap().get_blob_header().clone();
+        assert_eq!(reencoded_header.get_blob_id(), header_b.get_blob_id());
+        assert_ne!(reencoded_header.get_root_commitment(), header_b.get_root_commitment());
+
+        let mut reencoded_blob_manifest = manifest.clone();
+        reencoded_blob_manifest.versions[1].header = reencoded_header;
+        assert_eq!(reencoded_blob_manifest.verify(verify_with(1)), Err(DecdsError::InvalidVersionSignature(1)));
+
+        let mut forged_manifest = manifest.clone();
+        forged_manifest.versions[2].signature = sign_with(2)(&forged_manifest.versions[2].get_version_id());
+        assert_eq!(forged_manifest.verify(verify_with(1)), Err(DecdsError::InvalidVersionSignature(2)));
+
+        let empty_manifest = VersionedBlob { versions: Vec::new() };
+        assert!(matches!(
+            VersionedBlob::from_bytes(&empty_manifest.to_bytes().unwrap()),
+            Err(DecdsError::VersionedBlobDeserializationFailed(_))
+        ));
+        assert!(matches!(
+            VersionedBlob::from_bytes(&[0xff; 8]),
+            Err(DecdsError::VersionedBlobDeserializationFailed(_))
+        ));
+    }
+}
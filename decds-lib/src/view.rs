@@ -0,0 +1,243 @@
+use crate::{
+    blob::{BlobHeader, read_hash},
+    chunk::ProofCarryingChunk,
+    config::BlobConfig,
+    errors::DecdsError,
+    flags::ContentFlags,
+};
+use std::io::{Read, Seek, SeekFrom};
+
+/// Lazy view of a serialized `BlobHeader`, e.g. a header file, for gateways which only ever need a few chunkset
+/// commitments of a blob of hundreds of thousands of chunksets, and so don't want to materialize its multi-megabyte header.
+///
+/// Opening the view reads all fields but the chunkset commitments, which are looked up on demand, by seeking straight to
+/// them, as each of them is a fixed-width run of `blake3::OUT_LEN` bytes on the wire. Chunks validated against the view
+/// are bound to its root commitment, just as those validated against a fully deserialized header. Unlike
+/// `BlobHeader::from_bytes`, the view can't check that chunkset commitments hash up to the root commitment, without reading
+/// all of them, but a tampered commitment only makes chunks of its chunkset fail validation, as their proofs run through
+/// the genuine commitment up to the root commitment.
+///
+/// ```rust
+/// # #[cfg(feature = "coding")]
+/// # {
+/// use decds_lib::{Blob, BlobHeaderView};
+/// use std::io::Cursor;
+///
+/// let blob = Blob::new(vec![0xde; 1 << 20]).expect("Failed to create blob");
+/// let header_bytes = blob.get_blob_header().to_bytes().expect("Failed to serialize blob header");
+///
+/// let mut view = BlobHeaderView::open(Cursor::new(header_bytes)).expect("Failed to open blob header view");
+/// let chunk = blob.get_chunk(0, 0).expect("Failed to get chunk");
+/// assert!(view.validate_chunk(chunk));
+/// # }
+/// ```
+pub struct BlobHeaderView<R> {
+    reader: R,
+    header: BlobHeader,
+    commitments_offset: u64,
+}
+
+impl<R: Read + Seek> BlobHeaderView<R> {
+    /// Opens a view of the serialized `BlobHeader`, starting at the current position of `reader`, as written by
+    /// `BlobHeader::to_bytes` or `BlobHeader::write_to`. Chunkset commitments are skipped over, not read, but the header
+    /// is checked for consistency, just as `BlobHeader::from_bytes` does, and for being long enough to hold all of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Seekable reader of the serialized header, e.g. a `BufReader` over a header file.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(BlobHeaderView)` if successful.
+    /// - `Err(DecdsError::BlobHeaderDeserializationFailed)` if reading or seeking fails, or for any reason
+    ///   `BlobHeader::from_bytes` fails.
+    pub fn open(mut reader: R) -> Result<Self, DecdsError> {
+        let map_seek_err = |err: std::io::Error| DecdsError::BlobHeaderDeserializationFailed(err.to_string());
+
+        let mut header = BlobHeader::read_leading_fields(&mut reader)?;
+        let commitments_offset = reader.stream_position().map_err(map_seek_err)?;
+
+        let commitments_byte_len = (header.get_num_chunksets() as u64)
+            .checked_mul(blake3::OUT_LEN as u64)
+            .and_then(|byte_len| i64::try_from(byte_len).ok())
+            .ok_or(DecdsError::BlobHeaderDeserializationFailed("too many chunkset commitments".to_string()))?;
+
+        // Seeking past the end doesn't fail, but reading the inline data tag, which follows the commitments, does.
+        reader.seek(SeekFrom::Current(commitments_byte_len)).map_err(map_seek_err)?;
+        header.read_trailing_fields(&mut reader)?;
+        header.validate_consistency_with(header.get_num_chunksets())?;
+
+        Ok(BlobHeaderView {
+            reader,
+            header,
+            commitments_offset,
+        })
+    }
+
+    /// Returns the original byte length of the blob data before padding.
+    pub fn get_blob_size(&self) -> usize {
+        self.header.get_blob_size()
+    }
+
+    /// Returns the total number of chunksets that comprise the blob.
+    pub fn get_num_chunksets(&self) -> usize {
+        self.header.get_num_chunksets()
+    }
+
+    /// Returns the erasure-coding geometry, the blob is split and encoded with.
+    pub fn get_config(&self) -> BlobConfig {
+        self.header.get_config()
+    }
+
+    /// Returns `true` if the blob data is stored inline in the header, instead of being erasure-coded into chunks.
+    pub fn is_inline(&self) -> bool {
+        self.header.is_inline()
+    }
+
+    /// Returns content flags of the blob, see `ContentFlags`.
+    pub fn get_content_flags(&self) -> ContentFlags {
+        self.header.get_content_flags()
+    }
+
+    /// Returns the BLAKE3 digest of the original, unpadded blob data.
+    pub fn get_blob_digest(&self) -> blake3::Hash {
+        self.header.get_blob_digest()
+    }
+
+    /// Returns the byte range of an application record within the blob, see `BlobHeader::get_byte_range_for_record`.
+    pub fn get_byte_range_for_record(&self, record_id: usize) -> Result<(usize, usize), DecdsError> {
+        self.header.get_byte_range_for_record(record_id)
+    }
+
+    /// Returns the blob ID, see `BlobHeader::get_blob_id`.
+    pub fn get_blob_id(&self) -> blake3::Hash {
+        self.header.get_blob_id()
+    }
+
+    /// Returns the Merkle root commitment of the entire blob.
+    pub fn get_root_commitment(&self) -> blake3::Hash {
+        self.header.get_root_commitment()
+    }
+
+    /// Reads the Merkle root commitment of a specific chunkset within the blob, seeking straight to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunkset_id` - The ID of the chunkset whose commitment is to be retrieved.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Result` which is:
+    /// - `Ok(blake3::Hash)` containing the root commitment of the specified chunkset if successful.
+    /// - `Err(DecdsError::InvalidChunksetId)` if `chunkset_id` is out of bounds.
+    /// - `Err(DecdsError::BlobHeaderDeserializationFailed)` if seeking or reading fails.
+    pub fn get_chunkset_commitment(&mut self, chunkset_id: usize) -> Result<blake3::Hash, DecdsError> {
+        if chunkset_id >= self.get_num_chunksets() {
+            return Err(DecdsError::InvalidChunksetId(chunkset_id, self.get_num_chunksets()));
+        }
+
+        // Can't overflow, as the view was opened only if all commitments are addressable.
+        let offset = self.commitments_offset + chunkset_id as u64 * blake3::OUT_LEN as u64;
+        self.reader
+            .seek(SeekFrom::Start(offset))
+            .map_err(|err| DecdsError::BlobHeaderDeserializationFailed(err.to_string()))?;
+
+        read_hash(&mut self.reader)
+    }
+
+    /// Validates a `ProofCarryingChunk` against the header, as `BlobHeader::validate_chunk` does, reading only the
+    /// commitment of the chunk's chunkset.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk` - A reference to the `ProofCarryingChunk` to validate.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the chunk is valid and its proofs are consistent with the blob header, `false` otherwise, also if
+    /// its chunkset commitment can't be read. No chunk is valid for an inline blob.
+    pub fn validate_chunk(&mut self, chunk: &ProofCarryingChunk) -> bool {
+        let chunk_digest = chunk.get_chunk_digest();
+
+        !self.is_inline()
+            && (chunk.get_local_chunk_id() < self.header.get_config().get_num_shares())
+            && (chunk.get_content_flags() == self.header.get_content_flags())
+            && chunk.validate_inclusion_in_blob_with_digest(chunk_digest, self.get_root_commitment())
+            && self
+                .get_chunkset_commitment(chunk.get_chunkset_id())
+                .is_ok_and(|commitment| chunk.validate_inclusion_in_chunkset_with_digest(chunk_digest, commitment))
+    }
+
+    /// Consumes the view, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+#[cfg(all(test, feature = "coding"))]
+mod tests {
+    use crate::{BlobHeader, BlobHeaderView, ChunkFault, DecdsError, blob::Blob};
+    use rand::Rng;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_streamed_header_round_trips_and_serves_lazy_commitments() {
+        let mut rng = rand::rng();
+
+        let blob_data = (0..rng.random_range(4usize << 20..=8usize << 20)).map(|_| rng.random()).collect::<Vec<u8>>();
+        let blob = Blob::new(blob_data).expect("Must be able to prepare blob");
+        let header = blob.get_blob_header();
+
+        let mut streamed_bytes = Vec::new();
+        let num_written_bytes = header.write_to(&mut streamed_bytes).expect("Must be able to stream header");
+        assert_eq!(num_written_bytes, streamed_bytes.len());
+        assert_eq!(streamed_bytes, header.to_bytes().expect("Must be able to serialize header"));
+
+        // Trailing bytes are left unread.
+        streamed_bytes.extend_from_slice(b"trailer");
+        let mut reader = &streamed_bytes[..];
+        assert_eq!(&BlobHeader::from_reader(&mut reader).expect("Must be able to read header"), header);
+        assert_eq!(reader, b"trailer");
+
+        let mut view = BlobHeaderView::open(Cursor::new(&streamed_bytes)).expect("Must be able to open header view");
+        assert_eq!(view.get_num_chunksets(), header.get_num_chunksets());
+        assert_eq!(view.get_root_commitment(), header.get_root_commitment());
+        assert_eq!(view.get_blob_id(), header.get_blob_id());
+
+        for chunkset_id in (0..header.get_num_chunksets()).rev() {
+            assert_eq!(view.get_chunkset_commitment(chunkset_id), header.get_chunkset_commitment(chunkset_id));
+
+            let chunk = blob.get_chunk(chunkset_id, 0).expect("Must be able to get chunk");
+            assert!(view.validate_chunk(chunk));
+            assert!(!view.validate_chunk(&ChunkFault::FlipPayloadBit(0).apply(chunk)));
+        }
+        assert_eq!(
+            view.get_chunkset_commitment(header.get_num_chunksets()),
+            Err(DecdsError::InvalidChunksetId(header.get_num_chunksets(), header.get_num_chunksets()))
+        );
+
+        // Truncated anywhere, the header is rejected, either way it's read.
+        for truncated_len in [1, streamed_bytes.len() / 2, num_written_bytes - 1] {
+            let truncated = &streamed_bytes[..truncated_len];
+            assert!(BlobHeader::from_reader(&mut &truncated[..]).is_err());
+            assert!(BlobHeaderView::open(Cursor::new(truncated)).is_err());
+        }
+
+        let inline_blob = Blob::new_with_inline_threshold(b"hello, world".to_vec(), 64).expect("Must be able to prepare inline blob");
+        let inline_header = inline_blob.get_blob_header();
+
+        let mut inline_bytes = Vec::new();
+        inline_header.write_to(&mut inline_bytes).expect("Must be able to stream header");
+        assert_eq!(inline_bytes, inline_header.to_bytes().expect("Must be able to serialize header"));
+        assert_eq!(
+            &BlobHeader::from_reader(&mut &inline_bytes[..]).expect("Must be able to read header"),
+            inline_header
+        );
+        assert!(
+            BlobHeaderView::open(Cursor::new(inline_bytes))
+                .expect("Must be able to open header view")
+                .is_inline()
+        );
+    }
+}